@@ -0,0 +1,357 @@
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex, OnceLock};
+use std::time::Duration;
+
+use cynic::{GraphQlErrorPathSegment, GraphQlResponse, QueryBuilder};
+use serde_json::{json, Map, Value};
+use tokio::sync::oneshot;
+
+use super::{error_messages, ErrorExtensions, Transport};
+use crate::api::error::{ApiError, ApiResult};
+use crate::api::model::{IssueDetail, IssueRef};
+use crate::api::queries::issue::{Issue, IssueQuery, IssueVariables};
+
+/// How long the first lookup waits for others to join it.
+const BATCH_WINDOW: Duration = Duration::from_millis(5);
+
+/// Most aliases per document, to stay well inside Linear's complexity limit.
+const BATCH_LIMIT: usize = 10;
+
+type Lookup = ApiResult<Option<IssueDetail>>;
+
+struct Waiting {
+    target: IssueRef,
+    replies: Vec<oneshot::Sender<Lookup>>,
+}
+
+/// Collects `issue_detail` lookups made close together and sends them as one
+/// aliased document, so a burst of lookups costs one round trip.
+#[derive(Default)]
+pub(super) struct Batcher {
+    queue: Mutex<Vec<(IssueRef, oneshot::Sender<Lookup>)>>,
+}
+
+impl Batcher {
+    pub(super) async fn lookup(
+        self: &Arc<Self>,
+        transport: &Transport,
+        target: IssueRef,
+    ) -> Lookup {
+        let (reply, answer) = oneshot::channel();
+
+        let opens_batch = {
+            let mut queue = self.queue.lock().unwrap_or_else(|e| e.into_inner());
+            queue.push((target, reply));
+            queue.len() == 1
+        };
+
+        if opens_batch {
+            let batcher = Arc::clone(self);
+            let transport = transport.clone();
+
+            tokio::spawn(async move {
+                tokio::time::sleep(BATCH_WINDOW).await;
+                batcher.flush(&transport).await;
+            });
+        }
+
+        answer.await.unwrap_or(Err(ApiError::Empty))
+    }
+
+    async fn flush(&self, transport: &Transport) {
+        let queued = std::mem::take(&mut *self.queue.lock().unwrap_or_else(|e| e.into_inner()));
+        let mut waiting = coalesce(queued);
+
+        let mut chunks = Vec::new();
+        while !waiting.is_empty() {
+            let rest = waiting.split_off(waiting.len().min(BATCH_LIMIT));
+            chunks.push(std::mem::replace(&mut waiting, rest));
+        }
+
+        futures::future::join_all(chunks.into_iter().map(|chunk| send(transport, chunk))).await;
+    }
+}
+
+fn coalesce(queued: Vec<(IssueRef, oneshot::Sender<Lookup>)>) -> Vec<Waiting> {
+    let mut waiting: Vec<Waiting> = Vec::new();
+
+    for (target, reply) in queued {
+        match waiting.iter_mut().find(|entry| entry.target == target) {
+            Some(entry) => entry.replies.push(reply),
+            None => waiting.push(Waiting {
+                target,
+                replies: vec![reply],
+            }),
+        }
+    }
+
+    waiting
+}
+
+async fn send(transport: &Transport, chunk: Vec<Waiting>) {
+    let targets: Vec<&IssueRef> = chunk.iter().map(|entry| &entry.target).collect();
+
+    let results = match targets.as_slice() {
+        [target] => vec![single(transport, target).await],
+        _ => match transport.post(&aliased_document(&targets)).await {
+            Ok(response) => split(response, targets.len()),
+            Err(error) => targets.iter().map(|_| Err(error.replicate())).collect(),
+        },
+    };
+
+    for (entry, result) in chunk.into_iter().zip(results) {
+        answer(entry.replies, result);
+    }
+}
+
+pub(super) async fn single(transport: &Transport, target: &IssueRef) -> Lookup {
+    let operation = IssueQuery::build(IssueVariables {
+        id: target.to_string(),
+    });
+    let result = transport.fetch_json(operation).await?;
+
+    Ok(result.issue.map(IssueDetail::from))
+}
+
+fn answer(replies: Vec<oneshot::Sender<Lookup>>, result: Lookup) {
+    let mut replies = replies.into_iter();
+    let Some(first) = replies.next() else {
+        return;
+    };
+
+    for reply in replies {
+        let copy = match &result {
+            Ok(detail) => Ok(detail.clone()),
+            Err(error) => Err(error.replicate()),
+        };
+        let _ = reply.send(copy);
+    }
+
+    let _ = first.send(result);
+}
+
+fn alias(index: usize) -> String {
+    format!("i{index}")
+}
+
+/// The `Issue` selection set cynic generates for `IssueQuery`, so the batched
+/// document asks for exactly the fields a single lookup does.
+fn issue_selection() -> &'static str {
+    static SELECTION: OnceLock<String> = OnceLock::new();
+
+    SELECTION.get_or_init(|| {
+        let query = IssueQuery::build(IssueVariables { id: String::new() }).query;
+        let open = query
+            .find("issue(id: $id)")
+            .and_then(|at| query[at..].find('{').map(|brace| at + brace))
+            .expect("IssueQuery selects issue(id: $id)");
+
+        let mut depth = 0;
+        for (offset, c) in query[open..].char_indices() {
+            match c {
+                '{' => depth += 1,
+                '}' => depth -= 1,
+                _ => continue,
+            }
+
+            if depth == 0 {
+                return query[open..=open + offset].to_string();
+            }
+        }
+
+        unreachable!("cynic emits balanced selection sets")
+    })
+}
+
+fn aliased_document(targets: &[&IssueRef]) -> Value {
+    let selection = issue_selection();
+
+    let parameters: Vec<String> = (0..targets.len())
+        .map(|index| format!("${}: String!", alias(index)))
+        .collect();
+
+    let fields: String = (0..targets.len())
+        .map(|index| {
+            let name = alias(index);
+            format!("  {name}: issue(id: ${name}) {selection}\n")
+        })
+        .collect();
+
+    let variables: Map<String, Value> = targets
+        .iter()
+        .enumerate()
+        .map(|(index, target)| (alias(index), Value::String(target.to_string())))
+        .collect();
+
+    json!({
+        "query": format!("query IssueBatch({}) {{\n{fields}}}\n", parameters.join(", ")),
+        "variables": variables,
+        "operationName": "IssueBatch",
+    })
+}
+
+/// Hands each alias its own issue or its own errors. Errors that name no
+/// alias fail the whole batch.
+fn split(
+    response: GraphQlResponse<HashMap<String, Option<Issue>>, ErrorExtensions>,
+    len: usize,
+) -> Vec<Lookup> {
+    let mut per_alias: HashMap<String, Vec<String>> = HashMap::new();
+    let mut unattributed = Vec::new();
+
+    for error in response.errors.unwrap_or_default() {
+        let owner = match error.path.as_deref() {
+            Some([GraphQlErrorPathSegment::Field(field), ..]) => Some(field.clone()),
+            _ => None,
+        };
+        let message = error_messages(vec![error]);
+
+        match owner {
+            Some(field) => per_alias.entry(field).or_default().extend(message),
+            None => unattributed.extend(message),
+        }
+    }
+
+    if !unattributed.is_empty() {
+        return (0..len)
+            .map(|_| Err(ApiError::GraphQl(unattributed.clone())))
+            .collect();
+    }
+
+    let mut data = response.data.unwrap_or_default();
+
+    (0..len)
+        .map(|index| {
+            let name = alias(index);
+
+            match per_alias.remove(&name) {
+                Some(messages) => Err(ApiError::GraphQl(messages)),
+                None => Ok(data.remove(&name).flatten().map(IssueDetail::from)),
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn response(body: Value) -> GraphQlResponse<HashMap<String, Option<Issue>>, ErrorExtensions> {
+        serde_json::from_value(body).expect("a GraphQL response")
+    }
+
+    fn issue(id: &str) -> Value {
+        json!({
+            "id": id,
+            "identifier": id.to_uppercase(),
+            "title": null,
+            "description": null,
+            "url": "",
+            "branchName": "",
+            "priority": 0.0,
+            "updatedAt": "2026-07-16T18:40:00Z",
+            "state": { "name": "Todo", "type": "unstarted" },
+            "team": { "id": "t" },
+            "assignee": null,
+            "labels": { "nodes": [] },
+            "comments": { "nodes": [] },
+            "reactions": []
+        })
+    }
+
+    #[test]
+    fn the_selection_is_the_single_lookup_selection() {
+        let selection = issue_selection();
+
+        assert!(selection.starts_with('{'));
+        assert!(selection.ends_with('}'));
+        assert!(selection.contains("updatedAt"));
+        assert!(selection.contains("comments"));
+    }
+
+    #[test]
+    fn each_target_gets_its_own_alias_and_variable() {
+        let first = IssueRef::Identifier("DAN2-7".into());
+        let second = IssueRef::Identifier("DAN2-8".into());
+
+        let document = aliased_document(&[&first, &second]);
+        let query = document["query"].as_str().expect("a query string");
+
+        assert!(query.starts_with("query IssueBatch($i0: String!, $i1: String!)"));
+        assert!(query.contains("i0: issue(id: $i0) {"));
+        assert!(query.contains("i1: issue(id: $i1) {"));
+        assert_eq!(
+            document["variables"],
+            json!({"i0": "DAN2-7", "i1": "DAN2-8"})
+        );
+    }
+
+    #[test]
+    fn results_split_back_in_order() {
+        let results = split(
+            response(json!({ "data": { "i1": issue("b"), "i0": issue("a") } })),
+            2,
+        );
+
+        let identifiers: Vec<String> = results
+            .into_iter()
+            .map(|result| result.expect("a lookup").expect("an issue").identifier)
+            .collect();
+        assert_eq!(identifiers, vec!["A", "B"]);
+    }
+
+    #[test]
+    fn an_error_on_one_alias_fails_only_that_lookup() {
+        let results = split(
+            response(json!({
+                "data": { "i0": issue("a"), "i1": null },
+                "errors": [{ "message": "Entity not found", "path": ["i1"] }]
+            })),
+            2,
+        );
+
+        assert!(matches!(results[0], Ok(Some(_))));
+        assert!(
+            matches!(&results[1], Err(ApiError::GraphQl(messages)) if messages[0] == "Entity not found")
+        );
+    }
+
+    #[test]
+    fn an_error_without_a_path_fails_every_lookup() {
+        let results = split(
+            response(json!({
+                "data": null,
+                "errors": [{ "message": "Rate limit exceeded" }]
+            })),
+            2,
+        );
+
+        assert!(results
+            .iter()
+            .all(|result| matches!(result, Err(error) if error.is_rate_limited())));
+    }
+
+    #[test]
+    fn a_missing_alias_is_not_found() {
+        let results = split(response(json!({ "data": { "i0": null } })), 1);
+
+        assert!(matches!(results[0], Ok(None)));
+    }
+
+    #[test]
+    fn duplicate_targets_share_one_alias() {
+        let (first, _) = oneshot::channel();
+        let (second, _) = oneshot::channel();
+        let (third, _) = oneshot::channel();
+        let target = IssueRef::Identifier("DAN2-7".into());
+
+        let waiting = coalesce(vec![
+            (target.clone(), first),
+            (IssueRef::Identifier("DAN2-8".into()), second),
+            (target, third),
+        ]);
+
+        assert_eq!(waiting.len(), 2);
+        assert_eq!(waiting[0].replies.len(), 2);
+    }
+}
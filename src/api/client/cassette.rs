@@ -0,0 +1,418 @@
+use std::collections::{BTreeMap, HashMap};
+use std::fs::File;
+use std::io::{Seek, SeekFrom, Write};
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::api::error::{ApiError, ApiResult};
+
+const REDACTED: &str = "[redacted]";
+
+/// Whether a `Client` writes its traffic to a cassette or answers from one.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CassetteMode {
+    /// Fields named in `redact` are blanked wherever they appear, in
+    /// variables and responses alike.
+    Record {
+        path: PathBuf,
+        redact: Vec<String>,
+    },
+    Replay(PathBuf),
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub(super) struct Cassette {
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    redacted: Vec<String>,
+    interactions: Vec<Interaction>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct Interaction {
+    request: Value,
+    #[serde(default)]
+    headers: BTreeMap<String, String>,
+    status: u16,
+    response: Value,
+}
+
+/// What a replayed request gets back: the status and the raw body.
+pub(super) struct Recorded {
+    pub(super) status: u16,
+    pub(super) body: String,
+}
+
+/// An open cassette. One tape serves every client a session builds, so
+/// reconnecting or switching workspace keeps recording onto it.
+pub struct Tape(Deck);
+
+enum Deck {
+    Recording {
+        path: PathBuf,
+        redacted: Vec<String>,
+        reel: Arc<Mutex<Reel>>,
+    },
+    Replaying {
+        cassette: Cassette,
+        served: Mutex<HashMap<String, usize>>,
+    },
+}
+
+/// The cassette file being recorded, opened at the first exchange. Each
+/// exchange is added in place of the closing brackets, which are written
+/// again after it, so the file is whole between exchanges without being
+/// rewritten.
+#[derive(Default)]
+struct Reel {
+    file: Option<File>,
+}
+
+/// What closes the interactions list and the cassette.
+const TAIL: &str = "\n]}\n";
+
+impl Reel {
+    fn add(&mut self, path: &Path, redacted: &[String], interaction: &str) -> std::io::Result<()> {
+        let file = match &mut self.file {
+            Some(file) => {
+                file.seek(SeekFrom::End(-(TAIL.len() as i64)))?;
+                file.write_all(b",")?;
+                file
+            }
+            None => {
+                let mut file = File::create(path)?;
+                let head = Cassette {
+                    redacted: redacted.to_vec(),
+                    interactions: Vec::new(),
+                };
+                let head = serde_json::to_vec(&head)?;
+                // Everything but the empty list's closing brackets.
+                file.write_all(&head[..head.len() - "]}".len()])?;
+                self.file.insert(file)
+            }
+        };
+
+        write!(file, "\n{interaction}{TAIL}")?;
+        file.flush()
+    }
+}
+
+impl Tape {
+    pub fn open(mode: &CassetteMode) -> ApiResult<Self> {
+        match mode {
+            CassetteMode::Record { path, redact } => Ok(Tape(Deck::Recording {
+                path: path.clone(),
+                redacted: redact.clone(),
+                reel: Arc::default(),
+            })),
+            CassetteMode::Replay(path) => Ok(Tape(Deck::Replaying {
+                cassette: load(path)?,
+                served: Mutex::default(),
+            })),
+        }
+    }
+
+    /// The recorded answer to `request`, when replaying.
+    pub(super) fn replay(&self, request: &Value) -> Option<ApiResult<Recorded>> {
+        let Deck::Replaying { cassette, served } = &self.0 else {
+            return None;
+        };
+
+        let request = redact(request.clone(), &cassette.redacted);
+        let key = match_key(&request);
+        let matching: Vec<&Interaction> = cassette
+            .interactions
+            .iter()
+            .filter(|interaction| match_key(&interaction.request) == key)
+            .collect();
+
+        // Repeats of one request replay in recorded order, then stick at the last.
+        let Some(last) = matching.last() else {
+            return Some(Err(ApiError::Cassette(format!(
+                "no recorded response for {key}"
+            ))));
+        };
+
+        let mut served = served.lock().unwrap_or_else(|e| e.into_inner());
+        let count = served.entry(key).or_default();
+        let interaction = matching.get(*count).unwrap_or(last);
+        *count += 1;
+
+        Some(Ok(Recorded {
+            status: interaction.status,
+            body: match &interaction.response {
+                Value::String(raw) => raw.clone(),
+                json => json.to_string(),
+            },
+        }))
+    }
+
+    /// Appends one exchange to the file as it happens, so an interrupted
+    /// session still leaves a usable cassette.
+    pub(super) async fn record(
+        &self,
+        request: &Value,
+        authorization: &str,
+        status: u16,
+        body: &str,
+    ) -> ApiResult<()> {
+        let Deck::Recording {
+            path,
+            redacted,
+            reel,
+        } = &self.0
+        else {
+            return Ok(());
+        };
+
+        let response = serde_json::from_str(body).unwrap_or_else(|_| Value::String(body.into()));
+        let interaction = Interaction {
+            request: redact(request.clone(), redacted),
+            headers: BTreeMap::from([(
+                "authorization".into(),
+                redact_authorization(authorization),
+            )]),
+            status,
+            response: redact(response, redacted),
+        };
+        let unwritable = |error: &dyn std::fmt::Display| {
+            ApiError::Cassette(format!("{}: {error}", path.display()))
+        };
+        let interaction =
+            serde_json::to_string_pretty(&interaction).map_err(|error| unwritable(&error))?;
+
+        let target = path.clone();
+        let redacted = redacted.clone();
+        let reel = Arc::clone(reel);
+        tokio::task::spawn_blocking(move || {
+            reel.lock()
+                .unwrap_or_else(|e| e.into_inner())
+                .add(&target, &redacted, &interaction)
+        })
+        .await
+        .map_err(|error| unwritable(&error))?
+        .map_err(|error| unwritable(&error))
+    }
+}
+
+fn load(path: &Path) -> ApiResult<Cassette> {
+    let unreadable =
+        |error: &dyn std::fmt::Display| ApiError::Cassette(format!("{}: {error}", path.display()));
+
+    let raw = std::fs::read_to_string(path).map_err(|error| unreadable(&error))?;
+
+    serde_json::from_str(&raw).map_err(|error| unreadable(&error))
+}
+
+fn match_key(request: &Value) -> String {
+    let operation = request["operationName"].as_str().unwrap_or("anonymous");
+
+    format!("{operation} {}", request["variables"])
+}
+
+fn redact_authorization(value: &str) -> String {
+    match value.split_once(' ') {
+        Some((scheme, _)) => format!("{scheme} {REDACTED}"),
+        None => REDACTED.to_string(),
+    }
+}
+
+fn redact(value: Value, fields: &[String]) -> Value {
+    match value {
+        Value::Object(map) => Value::Object(
+            map.into_iter()
+                .map(|(key, value)| {
+                    let value = if fields.contains(&key) && !value.is_null() {
+                        Value::String(REDACTED.into())
+                    } else {
+                        redact(value, fields)
+                    };
+                    (key, value)
+                })
+                .collect(),
+        ),
+        Value::Array(items) => {
+            Value::Array(items.into_iter().map(|item| redact(item, fields)).collect())
+        }
+        other => other,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn request(operation: &str, variables: Value) -> Value {
+        json!({ "query": "", "operationName": operation, "variables": variables })
+    }
+
+    fn replaying(interactions: Vec<(Value, &str)>, redacted: &[&str]) -> Tape {
+        Tape(Deck::Replaying {
+            cassette: Cassette {
+                redacted: redacted.iter().map(|field| field.to_string()).collect(),
+                interactions: interactions
+                    .into_iter()
+                    .map(|(request, body)| Interaction {
+                        request,
+                        headers: BTreeMap::new(),
+                        status: 200,
+                        response: Value::String(body.into()),
+                    })
+                    .collect(),
+            },
+            served: Mutex::default(),
+        })
+    }
+
+    fn body(tape: &Tape, request: &Value) -> String {
+        tape.replay(request)
+            .expect("a replaying tape")
+            .expect("a recorded response")
+            .body
+    }
+
+    #[test]
+    fn named_fields_are_redacted_at_any_depth() {
+        let redacted = redact(
+            json!({
+                "term": "oven",
+                "nodes": [{ "email": "dan@example.com", "name": "Dan" }],
+                "assignee": { "email": null },
+            }),
+            &["email".into(), "term".into()],
+        );
+
+        assert_eq!(
+            redacted,
+            json!({
+                "term": REDACTED,
+                "nodes": [{ "email": REDACTED, "name": "Dan" }],
+                "assignee": { "email": null },
+            })
+        );
+    }
+
+    #[test]
+    fn the_authorization_scheme_survives_redaction() {
+        assert_eq!(redact_authorization("Bearer abc123"), "Bearer [redacted]");
+        assert_eq!(redact_authorization("lin_api_abc123"), REDACTED);
+    }
+
+    #[test]
+    fn replay_matches_on_operation_and_variables() {
+        let tape = replaying(
+            vec![
+                (request("IssueQuery", json!({ "id": "DAN-1" })), "one"),
+                (request("IssueQuery", json!({ "id": "DAN-2" })), "two"),
+            ],
+            &[],
+        );
+
+        assert_eq!(
+            body(&tape, &request("IssueQuery", json!({ "id": "DAN-2" }))),
+            "two"
+        );
+        assert!(matches!(
+            tape.replay(&request("IssueQuery", json!({ "id": "DAN-3" }))),
+            Some(Err(ApiError::Cassette(_)))
+        ));
+    }
+
+    #[test]
+    fn repeated_requests_replay_in_order_then_repeat_the_last() {
+        let asked = request("NotificationsQuery", json!({ "first": 100 }));
+        let tape = replaying(
+            vec![(asked.clone(), "first"), (asked.clone(), "second")],
+            &[],
+        );
+
+        let bodies: Vec<String> = (0..3).map(|_| body(&tape, &asked)).collect();
+
+        assert_eq!(bodies, vec!["first", "second", "second"]);
+    }
+
+    #[test]
+    fn redacted_variables_still_match() {
+        let tape = replaying(
+            vec![(
+                request("SearchIssuesQuery", json!({ "term": REDACTED })),
+                "hits",
+            )],
+            &["term"],
+        );
+
+        assert_eq!(
+            body(
+                &tape,
+                &request("SearchIssuesQuery", json!({ "term": "oven" }))
+            ),
+            "hits"
+        );
+    }
+
+    #[test]
+    fn a_recording_tape_answers_nothing() {
+        let tape = Tape::open(&CassetteMode::Record {
+            path: PathBuf::from("unused.json"),
+            redact: Vec::new(),
+        })
+        .expect("a recording tape");
+
+        assert!(tape.replay(&request("ViewerQuery", json!(null))).is_none());
+    }
+
+    #[tokio::test]
+    async fn every_exchange_on_a_shared_tape_reaches_the_file() {
+        let dir = tempfile::tempdir().expect("a temp dir");
+        let path = dir.path().join("session.json");
+        let tape = Tape::open(&CassetteMode::Record {
+            path: path.clone(),
+            redact: Vec::new(),
+        })
+        .expect("a recording tape");
+
+        for operation in ["ViewerQuery", "IssuesQuery"] {
+            tape.record(&request(operation, json!(null)), "Bearer abc", 200, "{}")
+                .await
+                .expect("recorded");
+        }
+
+        let saved = load(&path).expect("a saved cassette");
+        let operations: Vec<_> = saved
+            .interactions
+            .iter()
+            .map(|interaction| interaction.request["operationName"].clone())
+            .collect();
+        assert_eq!(operations, vec![json!("ViewerQuery"), json!("IssuesQuery")]);
+    }
+
+    #[tokio::test]
+    async fn an_exchange_is_added_without_rewriting_the_ones_before() {
+        let dir = tempfile::tempdir().expect("a temp dir");
+        let path = dir.path().join("session.json");
+        let tape = Tape::open(&CassetteMode::Record {
+            path: path.clone(),
+            redact: vec!["email".into()],
+        })
+        .expect("a recording tape");
+
+        tape.record(&request("ViewerQuery", json!(null)), "", 200, "{}")
+            .await
+            .expect("recorded");
+        let first = std::fs::read_to_string(&path).expect("the cassette");
+        assert_eq!(load(&path).expect("a whole cassette").interactions.len(), 1);
+
+        tape.record(&request("IssuesQuery", json!(null)), "", 200, "{}")
+            .await
+            .expect("recorded");
+        let second = std::fs::read_to_string(&path).expect("the cassette");
+
+        assert!(second.starts_with(first.trim_end_matches(TAIL)));
+        let saved = load(&path).expect("a whole cassette");
+        assert_eq!(saved.interactions.len(), 2);
+        assert_eq!(saved.redacted, vec!["email".to_string()]);
+    }
+}
@@ -1,4 +1,9 @@
+mod batch;
+mod cassette;
 mod map;
+mod settings;
+
+use std::sync::Arc;
 
 use cynic::{GraphQlError, GraphQlResponse, MutationBuilder, QueryBuilder};
 use reqwest::Client as HttpClient;
@@ -13,16 +18,15 @@ use crate::api::model::{
 use crate::api::queries::actions::{
     AssigneeInput, AssigneeMutation, AssigneeVariables, CommentCreateInput, CommentCreateMutation,
     CommentCreateVariables, CommentDeleteMutation, CommentDeleteVariables, CommentUpdateInput,
-    CommentUpdateMutation, CommentUpdateVariables, LabelsInput, LabelsMutation, LabelsVariables,
-    PriorityInput, PriorityMutation, PriorityVariables, ReactionCreateInput,
-    ReactionCreateMutation, ReactionCreateVariables, ReactionDeleteMutation,
-    ReactionDeleteVariables, StatusInput, StatusMutation, StatusVariables, TeamMembersQuery,
-    TeamStatesQuery, TeamVariables,
+    CommentUpdateMutation, CommentUpdateVariables, DescriptionInput, DescriptionMutation,
+    DescriptionVariables, LabelsInput, LabelsMutation, LabelsVariables, PriorityInput,
+    PriorityMutation, PriorityVariables, ReactionCreateInput, ReactionCreateMutation,
+    ReactionCreateVariables, ReactionDeleteMutation, ReactionDeleteVariables, StatusInput,
+    StatusMutation, StatusVariables, TeamMembersQuery, TeamStatesQuery, TeamVariables,
 };
 use crate::api::queries::custom_views::{
     CustomViewIssuesQuery, CustomViewIssuesVariables, CustomViewsQuery, CustomViewsVariables,
 };
-use crate::api::queries::issue::{IssueQuery, IssueVariables};
 use crate::api::queries::labels::{
     IssueLabelFilter, LabelSearchQuery, LabelSearchVariables, StringComparator,
 };
@@ -34,24 +38,58 @@ use crate::api::queries::users::{UserFilter, UserSearchQuery, UserSearchVariable
 use crate::api::queries::viewer::ViewerQuery;
 use crate::api::LinearApi;
 
+use batch::Batcher;
+pub use cassette::{CassetteMode, Tape};
 use map::build_cynic_filter;
-
-const API_ENDPOINT: &str = "https://api.linear.app/graphql";
+pub use settings::{ConnectionSettings, DEFAULT_ENDPOINT};
 
 const PAGE_SIZE: i32 = 100;
 
 const USER_SEARCH_LIMIT: i32 = 25;
 
 pub struct Client {
-    http_client: HttpClient,
-    credential: Credential,
+    transport: Transport,
+    batcher: Arc<Batcher>,
 }
 
 impl Client {
     pub fn new(credential: Credential) -> Self {
-        Self {
+        Self::with_transport(Transport {
             http_client: HttpClient::new(),
+            endpoint: DEFAULT_ENDPOINT.to_string(),
             credential,
+            tape: None,
+        })
+    }
+
+    pub fn configured(credential: Credential, settings: &ConnectionSettings) -> ApiResult<Self> {
+        let tape = settings.cassette.as_ref().map(Tape::open).transpose()?;
+
+        Self::taped(credential, settings, tape.map(Arc::new))
+    }
+
+    /// Like `configured`, but onto a tape already open, so clients built
+    /// one after another in a session share one cassette.
+    pub fn taped(
+        credential: Credential,
+        settings: &ConnectionSettings,
+        tape: Option<Arc<Tape>>,
+    ) -> ApiResult<Self> {
+        Ok(Self::with_transport(Transport {
+            http_client: settings.http_client()?,
+            endpoint: settings.endpoint().to_string(),
+            credential,
+            tape,
+        }))
+    }
+
+    fn with_transport(transport: Transport) -> Self {
+        crate::debug_log::redact(&transport.credential.header_value());
+        crate::debug_log::redact(&transport.credential.secret());
+
+        Self {
+            transport,
+            batcher: Arc::default(),
         }
     }
 
@@ -59,41 +97,122 @@ impl Client {
     where
         T: for<'de> serde::Deserialize<'de>,
         V: serde::Serialize,
+    {
+        self.transport.fetch_json(operation).await
+    }
+
+    async fn run_mutation<T, V>(&self, operation: cynic::Operation<T, V>) -> ApiResult<()>
+    where
+        T: for<'de> serde::Deserialize<'de>,
+        V: serde::Serialize,
+    {
+        self.fetch_json(operation).await.map(|_| ())
+    }
+}
+
+#[derive(Clone)]
+struct Transport {
+    http_client: HttpClient,
+    endpoint: String,
+    credential: Credential,
+    tape: Option<Arc<Tape>>,
+}
+
+impl Transport {
+    async fn post<T, B>(&self, body: &B) -> ApiResult<GraphQlResponse<T, ErrorExtensions>>
+    where
+        T: for<'de> serde::Deserialize<'de>,
+        B: serde::Serialize + ?Sized,
+    {
+        if !crate::debug_log::enabled() {
+            return self.exchange(body).await;
+        }
+
+        let request = serde_json::to_value(body).unwrap_or_default();
+        let operation = request["operationName"].as_str().unwrap_or("anonymous");
+        let started = std::time::Instant::now();
+        let result = self.exchange(body).await;
+        let elapsed = started.elapsed().as_millis();
+
+        crate::debug_log::record("api", || {
+            let outcome = match &result {
+                Ok(response) => match &response.errors {
+                    Some(errors) => format!("errors: {}", error_summary(errors)),
+                    None => "ok".to_string(),
+                },
+                Err(error) => format!("failed: {error}"),
+            };
+            format!(
+                "{operation} {} in {elapsed}ms, {outcome}",
+                redacted(&request["variables"])
+            )
+        });
+
+        result
+    }
+
+    async fn exchange<T, B>(&self, body: &B) -> ApiResult<GraphQlResponse<T, ErrorExtensions>>
+    where
+        T: for<'de> serde::Deserialize<'de>,
+        B: serde::Serialize + ?Sized,
+    {
+        let Some(tape) = &self.tape else {
+            let (status, text) = self.send(body).await?;
+            return decode(status, &text);
+        };
+
+        let request = serde_json::to_value(body)
+            .map_err(|error| ApiError::Cassette(format!("encoding request: {error}")))?;
+
+        if let Some(recorded) = tape.replay(&request) {
+            let recorded = recorded?;
+            return decode(recorded.status, &recorded.body);
+        }
+
+        let (status, text) = self.send(&request).await?;
+        tape.record(&request, &self.credential.header_value(), status, &text)
+            .await?;
+
+        decode(status, &text)
+    }
+
+    async fn send<B>(&self, body: &B) -> ApiResult<(u16, String)>
+    where
+        B: serde::Serialize + ?Sized,
     {
         let response = self
             .http_client
-            .post(API_ENDPOINT)
+            .post(&self.endpoint)
             .header("Content-Type", "application/json")
             .header("Authorization", self.credential.header_value())
-            .json(&operation)
+            .json(body)
             .send()
             .await?;
 
         let status = response.status();
+        crate::debug_log::record("http", || format!("{} {status}", self.endpoint));
 
-        if !status.is_success() {
-            let body = response.text().await.unwrap_or_default();
-            return Err(ApiError::Status {
-                status: status.as_u16(),
-                body,
-            });
-        }
-
-        let result: GraphQlResponse<T, ErrorExtensions> = response.json().await?;
-
-        if let Some(errors) = result.errors {
-            return Err(ApiError::GraphQl(error_messages(errors)));
-        }
+        let text = if status.is_success() {
+            response.text().await?
+        } else {
+            response.text().await.unwrap_or_default()
+        };
 
-        result.data.ok_or(ApiError::Empty)
+        Ok((status.as_u16(), text))
     }
 
-    async fn run_mutation<T, V>(&self, operation: cynic::Operation<T, V>) -> ApiResult<()>
+    async fn fetch_json<T, V>(&self, operation: cynic::Operation<T, V>) -> ApiResult<T>
     where
         T: for<'de> serde::Deserialize<'de>,
         V: serde::Serialize,
     {
-        self.fetch_json(operation).await.map(|_| ())
+        let result: GraphQlResponse<T, ErrorExtensions> = self.post(&operation).await?;
+
+        if let Some(errors) = result.errors {
+            return Err(ApiError::GraphQl(error_messages(errors)));
+        }
+
+        result.data.ok_or(ApiError::Empty)
     }
 }
 
@@ -202,12 +321,12 @@ impl LinearApi for Client {
     }
 
     async fn issue_detail(&self, target: &IssueRef) -> ApiResult<Option<IssueDetail>> {
-        let operation = IssueQuery::build(IssueVariables {
-            id: target.to_string(),
-        });
-        let result = self.fetch_json(operation).await?;
+        // Batches depend on timing, so a cassette sees every lookup on its own.
+        if self.transport.tape.is_some() {
+            return batch::single(&self.transport, target).await;
+        }
 
-        Ok(result.issue.map(IssueDetail::from))
+        self.batcher.lookup(&self.transport, target.clone()).await
     }
 
     async fn notifications(&self, after: Option<&Cursor>) -> ApiResult<Page<NotificationItem>> {
@@ -282,7 +401,7 @@ impl LinearApi for Client {
         Ok(result.users.nodes.into_iter().map(User::from).collect())
     }
 
-    async fn search_labels(&self, term: &str) -> ApiResult<Vec<Label>> {
+    async fn search_labels(&self, term: &str, after: Option<&Cursor>) -> ApiResult<Page<Label>> {
         let filter = (!term.is_empty()).then(|| IssueLabelFilter {
             name: Some(StringComparator {
                 contains_ignore_case: Some(term.to_string()),
@@ -292,15 +411,18 @@ impl LinearApi for Client {
         let operation = LabelSearchQuery::build(LabelSearchVariables {
             filter,
             first: PAGE_SIZE,
+            after: after.map(|cursor| cursor.0.clone()),
         });
-
         let result = self.fetch_json(operation).await?;
-        Ok(result
-            .issue_labels
-            .nodes
-            .into_iter()
-            .map(Label::from)
-            .collect())
+        let connection = result.issue_labels;
+
+        Ok(Page {
+            next: next_cursor(
+                connection.page_info.has_next_page,
+                connection.page_info.end_cursor,
+            ),
+            items: connection.nodes.into_iter().map(Label::from).collect(),
+        })
     }
 
     async fn update_issue(&self, id: &IssueId, update: IssueUpdate) -> ApiResult<()> {
@@ -342,6 +464,15 @@ impl LinearApi for Client {
                 }))
                 .await
             }
+            IssueUpdate::Description(description) => {
+                self.run_mutation(DescriptionMutation::build(DescriptionVariables {
+                    id,
+                    input: DescriptionInput {
+                        description: Some(description),
+                    },
+                }))
+                .await
+            }
         }
     }
 
@@ -413,6 +544,21 @@ struct ErrorExtensions {
     user_presentable_message: Option<String>,
 }
 
+fn decode<T>(status: u16, body: &str) -> ApiResult<GraphQlResponse<T, ErrorExtensions>>
+where
+    T: for<'de> serde::Deserialize<'de>,
+{
+    if !(200..300).contains(&status) {
+        return Err(ApiError::Status {
+            status,
+            body: body.to_string(),
+        });
+    }
+
+    serde_json::from_str(body)
+        .map_err(|error| ApiError::Network(format!("decoding the response: {error}")))
+}
+
 fn next_cursor(has_next_page: bool, end_cursor: Option<String>) -> Option<Cursor> {
     has_next_page.then_some(end_cursor).flatten().map(Cursor)
 }
@@ -429,10 +575,50 @@ fn error_messages(errors: Vec<GraphQlError<ErrorExtensions>>) -> Vec<String> {
         .collect()
 }
 
+fn error_summary(errors: &[GraphQlError<ErrorExtensions>]) -> String {
+    errors
+        .iter()
+        .map(|error| error.message.as_str())
+        .collect::<Vec<_>>()
+        .join("; ")
+}
+
+/// `variables` with its keys kept and its text replaced by its length:
+/// they carry comment bodies, search terms and ids the log has no need for.
+fn redacted(variables: &serde_json::Value) -> serde_json::Value {
+    use serde_json::Value;
+
+    match variables {
+        Value::String(text) => Value::String(format!("<{} chars>", text.chars().count())),
+        Value::Array(items) => Value::String(format!("<{} items>", items.len())),
+        Value::Object(fields) => Value::Object(
+            fields
+                .iter()
+                .map(|(key, value)| (key.clone(), redacted(value)))
+                .collect(),
+        ),
+        Value::Null | Value::Bool(_) | Value::Number(_) => variables.clone(),
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    #[test]
+    fn logged_variables_keep_their_keys_but_not_their_text() {
+        let variables = serde_json::json!({
+            "id": "issue-1",
+            "input": { "body": "my secret plans", "labelIds": ["a", "b"] },
+            "first": 50,
+        });
+
+        assert_eq!(
+            redacted(&variables).to_string(),
+            r#"{"first":50,"id":"<7 chars>","input":{"body":"<15 chars>","labelIds":"<2 items>"}}"#
+        );
+    }
+
     #[test]
     fn error_messages_prefer_the_user_presentable_message() {
         let errors = vec![
@@ -0,0 +1,276 @@
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+use reqwest::{Certificate, Client as HttpClient, Proxy};
+use serde::{Deserialize, Serialize};
+
+use super::cassette::CassetteMode;
+use crate::api::error::{ApiError, ApiResult};
+
+pub const DEFAULT_ENDPOINT: &str = "https://api.linear.app/graphql";
+
+const ENDPOINT_VAR: &str = "LINEAR_TUI_ENDPOINT";
+const PROXY_VAR: &str = "LINEAR_TUI_PROXY";
+const CA_CERTS_VAR: &str = "LINEAR_TUI_CA_CERTS";
+const TIMEOUT_VAR: &str = "LINEAR_TUI_TIMEOUT";
+const CONNECT_TIMEOUT_VAR: &str = "LINEAR_TUI_CONNECT_TIMEOUT";
+const RECORD_VAR: &str = "LINEAR_TUI_RECORD";
+const REPLAY_VAR: &str = "LINEAR_TUI_REPLAY";
+const REDACT_VAR: &str = "LINEAR_TUI_REDACT";
+
+/// How to reach the GraphQL API. Every field is optional: unset fields fall
+/// back to reqwest's defaults and the public Linear endpoint.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq, Eq)]
+pub struct ConnectionSettings {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub endpoint: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub proxy: Option<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub ca_certs: Vec<PathBuf>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub timeout_secs: Option<u64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub connect_timeout_secs: Option<u64>,
+    /// Per session only, so a cassette never sticks to a saved account.
+    #[serde(skip)]
+    pub cassette: Option<CassetteMode>,
+}
+
+impl ConnectionSettings {
+    pub fn from_env() -> ApiResult<Self> {
+        Self::from_vars(|name| std::env::var(name).ok())
+    }
+
+    fn from_vars(var: impl Fn(&str) -> Option<String>) -> ApiResult<Self> {
+        let var = |name: &str| var(name).filter(|value| !value.trim().is_empty());
+
+        Ok(Self {
+            endpoint: var(ENDPOINT_VAR),
+            proxy: var(PROXY_VAR),
+            ca_certs: var(CA_CERTS_VAR)
+                .map(|paths| std::env::split_paths(&paths).collect())
+                .unwrap_or_default(),
+            timeout_secs: var(TIMEOUT_VAR)
+                .map(|value| seconds(TIMEOUT_VAR, &value))
+                .transpose()?,
+            connect_timeout_secs: var(CONNECT_TIMEOUT_VAR)
+                .map(|value| seconds(CONNECT_TIMEOUT_VAR, &value))
+                .transpose()?,
+            cassette: match (var(RECORD_VAR), var(REPLAY_VAR)) {
+                (Some(_), Some(_)) => {
+                    return Err(ApiError::Config(format!(
+                        "set {RECORD_VAR} or {REPLAY_VAR}, not both"
+                    )))
+                }
+                (Some(path), None) => Some(CassetteMode::Record {
+                    path: PathBuf::from(path),
+                    redact: var(REDACT_VAR)
+                        .map(|fields| {
+                            fields
+                                .split(',')
+                                .map(str::trim)
+                                .filter(|field| !field.is_empty())
+                                .map(str::to_string)
+                                .collect()
+                        })
+                        .unwrap_or_default(),
+                }),
+                (None, Some(path)) => Some(CassetteMode::Replay(PathBuf::from(path))),
+                (None, None) => None,
+            },
+        })
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self == &Self::default()
+    }
+
+    /// Field by field, `self` wins wherever it is set.
+    pub fn over(self, base: &ConnectionSettings) -> ConnectionSettings {
+        ConnectionSettings {
+            endpoint: self.endpoint.or_else(|| base.endpoint.clone()),
+            proxy: self.proxy.or_else(|| base.proxy.clone()),
+            ca_certs: if self.ca_certs.is_empty() {
+                base.ca_certs.clone()
+            } else {
+                self.ca_certs
+            },
+            timeout_secs: self.timeout_secs.or(base.timeout_secs),
+            connect_timeout_secs: self.connect_timeout_secs.or(base.connect_timeout_secs),
+            cassette: self.cassette.or_else(|| base.cassette.clone()),
+        }
+    }
+
+    pub fn endpoint(&self) -> &str {
+        self.endpoint.as_deref().unwrap_or(DEFAULT_ENDPOINT)
+    }
+
+    pub fn http_client(&self) -> ApiResult<HttpClient> {
+        let mut builder = HttpClient::builder();
+
+        if let Some(proxy) = &self.proxy {
+            let proxy = Proxy::all(proxy)
+                .map_err(|error| ApiError::Config(format!("proxy {proxy}: {error}")))?;
+            builder = builder.proxy(proxy);
+        }
+
+        for path in &self.ca_certs {
+            builder = builder.add_root_certificate(certificate(path)?);
+        }
+
+        if let Some(secs) = self.timeout_secs {
+            builder = builder.timeout(Duration::from_secs(secs));
+        }
+
+        if let Some(secs) = self.connect_timeout_secs {
+            builder = builder.connect_timeout(Duration::from_secs(secs));
+        }
+
+        builder
+            .build()
+            .map_err(|error| ApiError::Config(error.to_string()))
+    }
+}
+
+fn seconds(name: &str, value: &str) -> ApiResult<u64> {
+    value
+        .trim()
+        .parse()
+        .map_err(|_| ApiError::Config(format!("{name} must be a whole number of seconds")))
+}
+
+fn certificate(path: &Path) -> ApiResult<Certificate> {
+    let unreadable = |error: &dyn std::fmt::Display| {
+        ApiError::Config(format!("CA certificate {}: {error}", path.display()))
+    };
+
+    let pem = std::fs::read(path).map_err(|error| unreadable(&error))?;
+
+    Certificate::from_pem(&pem).map_err(|error| unreadable(&error))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn vars(pairs: &[(&str, &str)]) -> impl Fn(&str) -> Option<String> {
+        let pairs: Vec<(String, String)> = pairs
+            .iter()
+            .map(|(name, value)| (name.to_string(), value.to_string()))
+            .collect();
+
+        move |name| {
+            pairs
+                .iter()
+                .find(|(key, _)| key == name)
+                .map(|(_, value)| value.clone())
+        }
+    }
+
+    #[test]
+    fn unset_settings_use_the_public_endpoint() {
+        let settings = ConnectionSettings::from_vars(vars(&[])).unwrap();
+
+        assert!(settings.is_empty());
+        assert_eq!(settings.endpoint(), DEFAULT_ENDPOINT);
+        assert!(settings.http_client().is_ok());
+    }
+
+    #[test]
+    fn environment_variables_fill_every_field() {
+        let settings = ConnectionSettings::from_vars(vars(&[
+            (ENDPOINT_VAR, "http://127.0.0.1:4000/graphql"),
+            (PROXY_VAR, "http://proxy.corp:3128"),
+            (CA_CERTS_VAR, "/etc/ssl/corp.pem"),
+            (TIMEOUT_VAR, "30"),
+            (CONNECT_TIMEOUT_VAR, " 5 "),
+        ]))
+        .unwrap();
+
+        assert_eq!(settings.endpoint(), "http://127.0.0.1:4000/graphql");
+        assert_eq!(settings.proxy.as_deref(), Some("http://proxy.corp:3128"));
+        assert_eq!(settings.ca_certs, vec![PathBuf::from("/etc/ssl/corp.pem")]);
+        assert_eq!(settings.timeout_secs, Some(30));
+        assert_eq!(settings.connect_timeout_secs, Some(5));
+    }
+
+    #[test]
+    fn a_malformed_timeout_is_a_config_error() {
+        let result = ConnectionSettings::from_vars(vars(&[(TIMEOUT_VAR, "soon")]));
+
+        assert!(matches!(result, Err(ApiError::Config(message)) if message.contains(TIMEOUT_VAR)));
+    }
+
+    #[test]
+    fn set_fields_win_over_the_base() {
+        let account = ConnectionSettings {
+            endpoint: Some("http://stand-in/graphql".into()),
+            timeout_secs: Some(10),
+            ..ConnectionSettings::default()
+        };
+        let env = ConnectionSettings {
+            proxy: Some("http://proxy.corp:3128".into()),
+            timeout_secs: Some(60),
+            ..ConnectionSettings::default()
+        };
+
+        let merged = env.over(&account);
+
+        assert_eq!(merged.endpoint(), "http://stand-in/graphql");
+        assert_eq!(merged.proxy.as_deref(), Some("http://proxy.corp:3128"));
+        assert_eq!(merged.timeout_secs, Some(60));
+    }
+
+    #[test]
+    fn a_missing_ca_certificate_fails_to_build() {
+        let settings = ConnectionSettings {
+            ca_certs: vec![PathBuf::from("/nonexistent/corp.pem")],
+            ..ConnectionSettings::default()
+        };
+
+        assert!(matches!(
+            settings.http_client(),
+            Err(ApiError::Config(message)) if message.contains("corp.pem")
+        ));
+    }
+
+    #[test]
+    fn recording_names_the_fields_to_redact() {
+        let settings = ConnectionSettings::from_vars(vars(&[
+            (RECORD_VAR, "bug.cassette.json"),
+            (REDACT_VAR, "email, description,"),
+        ]))
+        .unwrap();
+
+        assert_eq!(
+            settings.cassette,
+            Some(CassetteMode::Record {
+                path: PathBuf::from("bug.cassette.json"),
+                redact: vec!["email".into(), "description".into()],
+            })
+        );
+    }
+
+    #[test]
+    fn recording_and_replaying_at_once_is_a_config_error() {
+        let result =
+            ConnectionSettings::from_vars(vars(&[(RECORD_VAR, "a.json"), (REPLAY_VAR, "b.json")]));
+
+        assert!(matches!(result, Err(ApiError::Config(_))));
+    }
+
+    #[test]
+    fn only_set_fields_are_serialised() {
+        let settings = ConnectionSettings {
+            proxy: Some("http://proxy.corp:3128".into()),
+            cassette: Some(CassetteMode::Replay(PathBuf::from("bug.cassette.json"))),
+            ..ConnectionSettings::default()
+        };
+
+        assert_eq!(
+            serde_json::to_string(&settings).unwrap(),
+            r#"{"proxy":"http://proxy.corp:3128"}"#
+        );
+    }
+}
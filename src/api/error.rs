@@ -8,6 +8,8 @@ const ERROR_BODY_SNIPPET: usize = 200;
 pub enum ApiError {
     #[error("network request to Linear failed: {0}")]
     Http(#[from] reqwest::Error),
+    #[error("network request to Linear failed: {0}")]
+    Network(String),
     #[error("Linear returned HTTP {status}{}", detail(.body))]
     Status { status: u16, body: String },
     #[error("{0}")]
@@ -16,6 +18,12 @@ pub enum ApiError {
     GraphQl(Vec<String>),
     #[error("Linear returned an empty response")]
     Empty,
+    #[error("invalid connection settings: {0}")]
+    Config(String),
+    #[error("cassette: {0}")]
+    Cassette(String),
+    #[error("fixture: {0}")]
+    Fixture(String),
     #[error("{resource} {id} was not found")]
     NotFound { resource: &'static str, id: String },
 }
@@ -28,7 +36,54 @@ impl ApiError {
             ApiError::GraphQl(messages) => messages
                 .iter()
                 .any(|message| message.to_lowercase().contains("authenticat")),
-            ApiError::Http(_) | ApiError::Empty | ApiError::NotFound { .. } => false,
+            ApiError::Http(_)
+            | ApiError::Network(_)
+            | ApiError::Config(_)
+            | ApiError::Cassette(_)
+            | ApiError::Fixture(_)
+            | ApiError::Empty
+            | ApiError::NotFound { .. } => false,
+        }
+    }
+
+    pub fn is_rate_limited(&self) -> bool {
+        match self {
+            ApiError::Status { status, .. } => *status == 429,
+            ApiError::GraphQl(messages) => messages.iter().any(|message| {
+                let message = message.to_lowercase();
+                message.contains("rate limit") || message.contains("ratelimit")
+            }),
+            ApiError::Http(_)
+            | ApiError::Network(_)
+            | ApiError::Auth(_)
+            | ApiError::Config(_)
+            | ApiError::Cassette(_)
+            | ApiError::Fixture(_)
+            | ApiError::Empty
+            | ApiError::NotFound { .. } => false,
+        }
+    }
+
+    /// A copy for handing the same failure to several callers. Transport
+    /// errors are not `Clone`, so they carry over as their message.
+    pub fn replicate(&self) -> ApiError {
+        match self {
+            ApiError::Http(error) => ApiError::Network(error.to_string()),
+            ApiError::Network(message) => ApiError::Network(message.clone()),
+            ApiError::Status { status, body } => ApiError::Status {
+                status: *status,
+                body: body.clone(),
+            },
+            ApiError::Auth(message) => ApiError::Auth(message.clone()),
+            ApiError::GraphQl(messages) => ApiError::GraphQl(messages.clone()),
+            ApiError::Config(message) => ApiError::Config(message.clone()),
+            ApiError::Cassette(message) => ApiError::Cassette(message.clone()),
+            ApiError::Fixture(message) => ApiError::Fixture(message.clone()),
+            ApiError::Empty => ApiError::Empty,
+            ApiError::NotFound { resource, id } => ApiError::NotFound {
+                resource,
+                id: id.clone(),
+            },
         }
     }
 }
@@ -75,4 +130,20 @@ mod tests {
         assert!(!ApiError::Empty.is_auth());
         assert!(!ApiError::GraphQl(vec!["rate limited".into()]).is_auth());
     }
+
+    #[test]
+    fn rate_limits_are_recognised_by_status_or_message() {
+        assert!(ApiError::Status {
+            status: 429,
+            body: String::new()
+        }
+        .is_rate_limited());
+        assert!(ApiError::GraphQl(vec!["Rate limit exceeded".into()]).is_rate_limited());
+        assert!(ApiError::GraphQl(vec!["RATELIMITED".into()]).is_rate_limited());
+        assert!(!ApiError::Status {
+            status: 500,
+            body: String::new()
+        }
+        .is_rate_limited());
+    }
 }
@@ -1,18 +1,26 @@
-use std::path::Path;
+use std::collections::{HashMap, HashSet};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Mutex, MutexGuard};
 
-use anyhow::{Context, Result};
+use anyhow::{bail, Context, Result};
 use serde::{Deserialize, Serialize};
 
 use crate::api::model::{
     Comment, CommentId, Cursor, IssueDetail, IssueFilter, IssueId, IssueRef, IssueSummary,
     IssueUpdate, Label, LabelId, NotificationItem, Page, Priority, Reaction, ReactionId,
-    ReactionTarget, Rgb, SavedView, Session, StateId, StateOption, StateType, Team, TeamId, User,
-    UserId, ViewId, WorkflowState,
+    ReactionTarget, Rgb, SavedView, Session, StateId, StateOption, StateType, Team, TeamId,
+    Timestamp, User, UserId, ViewId, WorkflowState,
 };
-use crate::api::{ApiResult, LinearApi};
+use crate::api::{ApiError, ApiResult, LinearApi};
+
+/// Version 2 added teams, workflow states, members and labels.
+pub const FIXTURE_VERSION: u32 = 2;
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Fixture {
+    #[serde(default = "legacy_version")]
+    pub version: u32,
     pub viewer: User,
     #[serde(default)]
     pub org_name: String,
@@ -25,31 +33,200 @@ pub struct Fixture {
     #[serde(default)]
     pub issues: Vec<IssueSummary>,
     #[serde(default)]
-    pub saved_view_issues: std::collections::HashMap<ViewId, Vec<IssueSummary>>,
+    pub saved_view_issues: HashMap<ViewId, Vec<IssueSummary>>,
     #[serde(default)]
     pub details: Vec<IssueDetail>,
+    #[serde(default)]
+    pub teams: Vec<Team>,
+    #[serde(default)]
+    pub workflow_states: HashMap<TeamId, Vec<StateOption>>,
+    #[serde(default)]
+    pub team_members: HashMap<TeamId, Vec<User>>,
+    #[serde(default)]
+    pub users: Vec<User>,
+    #[serde(default)]
+    pub labels: Vec<Label>,
 }
 
-pub struct FixtureClient {
-    fixture: Fixture,
+fn legacy_version() -> u32 {
+    1
 }
 
-impl FixtureClient {
-    pub fn new(fixture: Fixture) -> Self {
-        Self { fixture }
+impl Fixture {
+    pub fn sample() -> Self {
+        sample_fixture()
     }
 
-    pub fn from_path(path: impl AsRef<Path>) -> Result<Self> {
+    pub fn load(path: impl AsRef<Path>) -> Result<Self> {
         let path = path.as_ref();
         let raw = std::fs::read_to_string(path)
             .with_context(|| format!("reading fixture {}", path.display()))?;
         let fixture: Fixture = serde_json::from_str(&raw)
             .with_context(|| format!("parsing fixture {}", path.display()))?;
-        Ok(Self::new(fixture))
+
+        if fixture.version > FIXTURE_VERSION {
+            bail!(
+                "fixture {} is version {}, newer than the supported {FIXTURE_VERSION}",
+                path.display(),
+                fixture.version
+            );
+        }
+
+        Ok(fixture.upgraded())
+    }
+
+    /// Fixtures from before version 2 carry no workspace metadata, so they
+    /// borrow the sample's for every team they mention.
+    pub fn upgraded(mut self) -> Self {
+        if self.version < 2 {
+            let sample = sample_fixture();
+            let states = sample_states();
+            let members = sample_members();
+
+            let mentioned = self
+                .issues
+                .iter()
+                .map(|issue| &issue.team_id)
+                .chain(self.details.iter().map(|detail| &detail.team_id))
+                .chain(sample.teams.iter().map(|team| &team.id));
+
+            for team_id in mentioned {
+                self.workflow_states
+                    .entry(team_id.clone())
+                    .or_insert_with(|| states.clone());
+                self.team_members
+                    .entry(team_id.clone())
+                    .or_insert_with(|| members.clone());
+            }
+
+            self.teams = sample.teams;
+            self.users = sample.users;
+            self.labels = sample.labels;
+        }
+
+        self.version = FIXTURE_VERSION;
+        self
+    }
+}
+
+/// Serves a `Fixture` as if it were a workspace. Mutations apply to the
+/// held fixture, so edits show up in later reads; a client built with
+/// `writing_back` also saves the result after every change.
+pub struct FixtureClient {
+    fixture: Mutex<Fixture>,
+    /// Held through a whole change, so the fixture lock need not be held
+    /// while the file is written.
+    saving: Mutex<()>,
+    write_back: Option<PathBuf>,
+    next_id: AtomicU64,
+    clock: fn() -> Timestamp,
+}
+
+impl FixtureClient {
+    pub fn new(fixture: Fixture) -> Self {
+        Self {
+            fixture: Mutex::new(fixture),
+            saving: Mutex::new(()),
+            write_back: None,
+            next_id: AtomicU64::new(1),
+            clock: Timestamp::now,
+        }
+    }
+
+    /// Stamps changes with `clock` rather than the wall clock.
+    pub fn with_clock(self, clock: fn() -> Timestamp) -> Self {
+        Self { clock, ..self }
+    }
+
+    pub fn from_path(path: impl AsRef<Path>) -> Result<Self> {
+        Ok(Self::new(Fixture::load(path)?))
+    }
+
+    /// Like `from_path`, but every mutation rewrites the file.
+    pub fn writing_back(path: impl AsRef<Path>) -> Result<Self> {
+        let path = path.as_ref();
+
+        Ok(Self {
+            write_back: Some(path.to_path_buf()),
+            ..Self::from_path(path)?
+        })
     }
 
     pub fn sample() -> Self {
-        Self::new(sample_fixture())
+        Self::new(Fixture::sample())
+    }
+
+    /// The fixture as it stands after any mutations.
+    pub fn snapshot(&self) -> Fixture {
+        self.read().clone()
+    }
+
+    fn read(&self) -> MutexGuard<'_, Fixture> {
+        self.fixture.lock().unwrap_or_else(|e| e.into_inner())
+    }
+
+    /// Applies `change` to a copy, which replaces the fixture only once
+    /// it is saved: a change that fails, or cannot be written, leaves no
+    /// trace.
+    fn mutate<T>(&self, change: impl FnOnce(&mut Fixture) -> ApiResult<T>) -> ApiResult<T> {
+        let _saving = self.saving.lock().unwrap_or_else(|e| e.into_inner());
+        let mut changed = self.read().clone();
+        let result = change(&mut changed)?;
+
+        if let Some(path) = &self.write_back {
+            save(path, &changed)?;
+        }
+
+        *self.read() = changed;
+        Ok(result)
+    }
+
+    /// An id no comment or reaction in the fixture is using yet.
+    fn fresh_id(&self, fixture: &Fixture, prefix: &str) -> String {
+        let taken: HashSet<&str> = fixture
+            .details
+            .iter()
+            .flat_map(|detail| {
+                detail
+                    .reactions
+                    .iter()
+                    .map(|reaction| reaction.id.as_str())
+                    .chain(detail.comments.iter().flat_map(|comment| {
+                        std::iter::once(comment.id.as_str())
+                            .chain(comment.reactions.iter().map(|r| r.id.as_str()))
+                    }))
+            })
+            .collect();
+
+        loop {
+            let id = format!("{prefix}_{}", self.next_id.fetch_add(1, Ordering::Relaxed));
+            if !taken.contains(id.as_str()) {
+                return id;
+            }
+        }
+    }
+}
+
+fn save(path: &Path, fixture: &Fixture) -> ApiResult<()> {
+    let unwritable =
+        |error: &dyn std::fmt::Display| ApiError::Fixture(format!("{}: {error}", path.display()));
+
+    let json = serde_json::to_string_pretty(fixture).map_err(|error| unwritable(&error))?;
+
+    std::fs::write(path, json).map_err(|error| unwritable(&error))
+}
+
+fn unknown_team(team_id: &TeamId) -> ApiError {
+    ApiError::NotFound {
+        resource: "team",
+        id: team_id.to_string(),
+    }
+}
+
+fn not_found(resource: &'static str, id: impl std::fmt::Display) -> ApiError {
+    ApiError::NotFound {
+        resource,
+        id: id.to_string(),
     }
 }
 
@@ -84,18 +261,117 @@ fn matches(issue: &IssueSummary, filter: &IssueFilter) -> bool {
     true
 }
 
+/// One issue update, resolved against the fixture's metadata so it can be
+/// applied to every copy of the issue alike.
+enum Change {
+    State(WorkflowState),
+    Assignee(Option<User>),
+    Priority(Priority),
+    Labels(Vec<Label>),
+    Description(String),
+}
+
+impl Change {
+    fn resolve(fixture: &Fixture, team_id: &TeamId, update: IssueUpdate) -> ApiResult<Self> {
+        match update {
+            IssueUpdate::Status(state_id) => fixture
+                .workflow_states
+                .get(team_id)
+                .into_iter()
+                .chain(fixture.workflow_states.values())
+                .flatten()
+                .find(|option| option.id == state_id)
+                .map(|option| {
+                    Change::State(WorkflowState {
+                        name: option.name.clone(),
+                        state_type: option.state_type,
+                    })
+                })
+                .ok_or_else(|| not_found("state", state_id)),
+            IssueUpdate::Assignee(None) => Ok(Change::Assignee(None)),
+            IssueUpdate::Assignee(Some(user_id)) => std::iter::once(&fixture.viewer)
+                .chain(&fixture.users)
+                .chain(fixture.team_members.values().flatten())
+                .find(|user| user.id == user_id)
+                .map(|user| Change::Assignee(Some(user.clone())))
+                .ok_or_else(|| not_found("user", user_id)),
+            IssueUpdate::Priority(priority) => Ok(Change::Priority(priority)),
+            IssueUpdate::Labels(label_ids) => label_ids
+                .iter()
+                .map(|label_id| {
+                    fixture
+                        .labels
+                        .iter()
+                        .chain(fixture.issues.iter().flat_map(|issue| &issue.labels))
+                        .find(|label| &label.id == label_id)
+                        .cloned()
+                        .ok_or_else(|| not_found("label", label_id))
+                })
+                .collect::<ApiResult<_>>()
+                .map(Change::Labels),
+            IssueUpdate::Description(description) => Ok(Change::Description(description)),
+        }
+    }
+
+    fn apply_to_summary(&self, issue: &mut IssueSummary, now: Timestamp) {
+        match self {
+            Change::State(state) => issue.state = state.clone(),
+            Change::Assignee(assignee) => issue.assignee = assignee.clone(),
+            Change::Priority(priority) => issue.priority = *priority,
+            Change::Labels(labels) => issue.labels = labels.clone(),
+            Change::Description(_) => {}
+        }
+        issue.updated_at = now;
+    }
+
+    fn apply_to_detail(&self, detail: &mut IssueDetail, now: Timestamp) {
+        match self {
+            Change::State(state) => detail.state = state.clone(),
+            Change::Assignee(assignee) => detail.assignee = assignee.clone(),
+            Change::Priority(priority) => detail.priority = *priority,
+            Change::Labels(labels) => detail.labels = labels.clone(),
+            Change::Description(description) => detail.description = Some(description.clone()),
+        }
+        detail.updated_at = now;
+    }
+}
+
+impl Fixture {
+    fn team_of(&self, id: &IssueId) -> Option<TeamId> {
+        self.issues
+            .iter()
+            .chain(self.saved_view_issues.values().flatten())
+            .map(|issue| (&issue.id, &issue.team_id))
+            .chain(
+                self.details
+                    .iter()
+                    .map(|detail| (&detail.id, &detail.team_id)),
+            )
+            .find(|(issue_id, _)| *issue_id == id)
+            .map(|(_, team_id)| team_id.clone())
+    }
+
+    fn comments_mut(&mut self) -> impl Iterator<Item = &mut Comment> {
+        self.details
+            .iter_mut()
+            .flat_map(|detail| detail.comments.iter_mut())
+    }
+}
+
 #[async_trait::async_trait]
 impl LinearApi for FixtureClient {
     async fn session(&self) -> ApiResult<Session> {
+        let fixture = self.read();
+
         Ok(Session {
-            user: self.fixture.viewer.clone(),
-            org_name: self.fixture.org_name.clone(),
-            org_url_key: self.fixture.org_url_key.clone(),
+            user: fixture.viewer.clone(),
+            org_name: fixture.org_name.clone(),
+            org_url_key: fixture.org_url_key.clone(),
         })
     }
 
     async fn custom_views(&self) -> ApiResult<Vec<SavedView>> {
-        Ok(self.fixture.saved_views.clone())
+        Ok(self.read().saved_views.clone())
     }
 
     async fn custom_view_issues(
@@ -103,12 +379,12 @@ impl LinearApi for FixtureClient {
         id: &ViewId,
         _after: Option<&Cursor>,
     ) -> ApiResult<Page<IssueSummary>> {
-        let issues = self
-            .fixture
+        let fixture = self.read();
+        let issues = fixture
             .saved_view_issues
             .get(id)
-            .cloned()
-            .unwrap_or_else(|| self.fixture.issues.clone());
+            .unwrap_or(&fixture.issues)
+            .clone();
 
         Ok(Page::single(issues))
     }
@@ -119,7 +395,7 @@ impl LinearApi for FixtureClient {
         _after: Option<&Cursor>,
     ) -> ApiResult<Page<IssueSummary>> {
         Ok(Page::single(
-            self.fixture
+            self.read()
                 .issues
                 .iter()
                 .filter(|issue| matches(issue, filter))
@@ -135,7 +411,7 @@ impl LinearApi for FixtureClient {
     ) -> ApiResult<Page<IssueSummary>> {
         let needle = term.to_lowercase();
         Ok(Page::single(
-            self.fixture
+            self.read()
                 .issues
                 .iter()
                 .filter(|issue| {
@@ -152,7 +428,7 @@ impl LinearApi for FixtureClient {
 
     async fn issue_detail(&self, target: &IssueRef) -> ApiResult<Option<IssueDetail>> {
         Ok(self
-            .fixture
+            .read()
             .details
             .iter()
             .find(|detail| target.matches_detail(detail))
@@ -160,120 +436,178 @@ impl LinearApi for FixtureClient {
     }
 
     async fn notifications(&self, _after: Option<&Cursor>) -> ApiResult<Page<NotificationItem>> {
-        Ok(Page::single(self.fixture.notifications.clone()))
+        Ok(Page::single(self.read().notifications.clone()))
     }
 
-    async fn workflow_states(&self, _team_id: &TeamId) -> ApiResult<Vec<StateOption>> {
-        Ok(vec![
-            StateOption {
-                id: StateId::from_raw("s_backlog"),
-                name: "Backlog".into(),
-                state_type: StateType::Backlog,
-            },
-            StateOption {
-                id: StateId::from_raw("s_todo"),
-                name: "Todo".into(),
-                state_type: StateType::Unstarted,
-            },
-            StateOption {
-                id: StateId::from_raw("s_started"),
-                name: "In Progress".into(),
-                state_type: StateType::Started,
-            },
-            StateOption {
-                id: StateId::from_raw("s_done"),
-                name: "Done".into(),
-                state_type: StateType::Completed,
-            },
-            StateOption {
-                id: StateId::from_raw("s_canceled"),
-                name: "Cancelled".into(),
-                state_type: StateType::Cancelled,
-            },
-        ])
+    async fn workflow_states(&self, team_id: &TeamId) -> ApiResult<Vec<StateOption>> {
+        self.read()
+            .workflow_states
+            .get(team_id)
+            .cloned()
+            .ok_or_else(|| unknown_team(team_id))
     }
 
     async fn teams(&self) -> ApiResult<Vec<Team>> {
-        Ok(vec![
-            Team {
-                id: TeamId::from_raw("t_donut"),
-                name: "Donuts".into(),
-                key: "DAN".into(),
-                triage_enabled: false,
-            },
-            Team {
-                id: TeamId::from_raw("t_pizza"),
-                name: "Pizza".into(),
-                key: "DAN2".into(),
-                triage_enabled: true,
-            },
-        ])
+        Ok(self.read().teams.clone())
     }
 
-    async fn team_members(&self, _team_id: &TeamId) -> ApiResult<Vec<User>> {
-        Ok(vec![
-            person("dan", true),
-            person("sam", false),
-            person("alex", false),
-        ])
+    async fn team_members(&self, team_id: &TeamId) -> ApiResult<Vec<User>> {
+        self.read()
+            .team_members
+            .get(team_id)
+            .cloned()
+            .ok_or_else(|| unknown_team(team_id))
     }
 
     async fn search_users(&self, term: &str) -> ApiResult<Vec<User>> {
         let needle = term.to_lowercase();
 
-        Ok(["dan", "sam", "alex", "danniiee", "charlieh"]
-            .into_iter()
-            .filter(|name| name.contains(&needle))
-            .map(|name| person(name, name == "dan"))
+        Ok(self
+            .read()
+            .users
+            .iter()
+            .filter(|user| {
+                user.display_name.to_lowercase().contains(&needle)
+                    || user.name.to_lowercase().contains(&needle)
+            })
+            .cloned()
             .collect())
     }
 
-    async fn search_labels(&self, term: &str) -> ApiResult<Vec<Label>> {
+    async fn search_labels(&self, term: &str, _after: Option<&Cursor>) -> ApiResult<Page<Label>> {
         let needle = term.to_lowercase();
 
-        Ok([
-            ("lbl_oven", "oven", "#eb5757"),
-            ("lbl_upsell", "upsell", "#f2c94c"),
-            ("lbl_bug", "bug", "#9b51e0"),
-            ("lbl_chore", "chore", "#4f4f4f"),
-        ]
-        .into_iter()
-        .filter(|(_, name, _)| name.contains(&needle))
-        .map(|(id, name, colour)| Label {
-            id: LabelId::from_raw(id),
-            name: name.into(),
-            colour: Rgb::parse_hex(colour),
-        })
-        .collect())
+        Ok(Page::single(
+            self.read()
+                .labels
+                .iter()
+                .filter(|label| label.name.to_lowercase().contains(&needle))
+                .cloned()
+                .collect(),
+        ))
     }
 
-    async fn update_issue(&self, _id: &IssueId, _update: IssueUpdate) -> ApiResult<()> {
-        Ok(())
+    async fn update_issue(&self, id: &IssueId, update: IssueUpdate) -> ApiResult<()> {
+        self.mutate(|fixture| {
+            let team_id = fixture.team_of(id).ok_or_else(|| not_found("issue", id))?;
+            let change = Change::resolve(fixture, &team_id, update)?;
+            let now = (self.clock)();
+
+            fixture
+                .issues
+                .iter_mut()
+                .chain(fixture.saved_view_issues.values_mut().flatten())
+                .filter(|issue| &issue.id == id)
+                .for_each(|issue| change.apply_to_summary(issue, now));
+            fixture
+                .details
+                .iter_mut()
+                .filter(|detail| &detail.id == id)
+                .for_each(|detail| change.apply_to_detail(detail, now));
+
+            Ok(())
+        })
     }
 
     async fn create_comment(
         &self,
-        _issue_id: &IssueId,
-        _body: &str,
-        _parent_id: Option<&CommentId>,
+        issue_id: &IssueId,
+        body: &str,
+        parent_id: Option<&CommentId>,
     ) -> ApiResult<()> {
-        Ok(())
+        self.mutate(|fixture| {
+            let comment = Comment {
+                id: CommentId::from_raw(self.fresh_id(fixture, "c")),
+                parent_id: parent_id.cloned(),
+                author: Some(fixture.viewer.display_name.clone()),
+                is_mine: true,
+                body: body.into(),
+                created_at: (self.clock)(),
+                reactions: Vec::new(),
+            };
+
+            let detail = fixture
+                .details
+                .iter_mut()
+                .find(|detail| &detail.id == issue_id)
+                .ok_or_else(|| not_found("issue", issue_id))?;
+            detail.comments.push(comment);
+
+            Ok(())
+        })
     }
 
-    async fn update_comment(&self, _comment_id: &CommentId, _body: &str) -> ApiResult<()> {
-        Ok(())
+    async fn update_comment(&self, comment_id: &CommentId, body: &str) -> ApiResult<()> {
+        self.mutate(|fixture| {
+            let comment = fixture
+                .comments_mut()
+                .find(|comment| &comment.id == comment_id)
+                .ok_or_else(|| not_found("comment", comment_id))?;
+            comment.body = body.into();
+
+            Ok(())
+        })
     }
 
-    async fn delete_comment(&self, _comment_id: &CommentId) -> ApiResult<()> {
-        Ok(())
+    async fn delete_comment(&self, comment_id: &CommentId) -> ApiResult<()> {
+        self.mutate(|fixture| {
+            let detail = fixture
+                .details
+                .iter_mut()
+                .find(|detail| detail.comments.iter().any(|c| &c.id == comment_id))
+                .ok_or_else(|| not_found("comment", comment_id))?;
+            detail.comments.retain(|comment| &comment.id != comment_id);
+
+            Ok(())
+        })
     }
 
-    async fn create_reaction(&self, _target: &ReactionTarget, _emoji: &str) -> ApiResult<()> {
-        Ok(())
+    async fn create_reaction(&self, target: &ReactionTarget, emoji: &str) -> ApiResult<()> {
+        self.mutate(|fixture| {
+            let reaction = Reaction {
+                id: ReactionId::from_raw(self.fresh_id(fixture, "r")),
+                emoji: emoji.into(),
+                mine: true,
+            };
+
+            let reactions = match target {
+                ReactionTarget::Issue(issue_id) => fixture
+                    .details
+                    .iter_mut()
+                    .find(|detail| &detail.id == issue_id)
+                    .map(|detail| &mut detail.reactions)
+                    .ok_or_else(|| not_found("issue", issue_id))?,
+                ReactionTarget::Comment(comment_id) => fixture
+                    .comments_mut()
+                    .find(|comment| &comment.id == comment_id)
+                    .map(|comment| &mut comment.reactions)
+                    .ok_or_else(|| not_found("comment", comment_id))?,
+            };
+            reactions.push(reaction);
+
+            Ok(())
+        })
     }
 
-    async fn delete_reaction(&self, _reaction_id: &ReactionId) -> ApiResult<()> {
-        Ok(())
+    async fn delete_reaction(&self, reaction_id: &ReactionId) -> ApiResult<()> {
+        self.mutate(|fixture| {
+            let reactions = fixture
+                .details
+                .iter_mut()
+                .flat_map(|detail| {
+                    std::iter::once(&mut detail.reactions).chain(
+                        detail
+                            .comments
+                            .iter_mut()
+                            .map(|comment| &mut comment.reactions),
+                    )
+                })
+                .find(|reactions| reactions.iter().any(|r| &r.id == reaction_id))
+                .ok_or_else(|| not_found("reaction", reaction_id))?;
+            reactions.retain(|reaction| &reaction.id != reaction_id);
+
+            Ok(())
+        })
     }
 }
 
@@ -341,6 +675,31 @@ fn team_for(identifier: &str) -> TeamId {
     }
 }
 
+fn sample_states() -> Vec<StateOption> {
+    [
+        ("s_backlog", "Backlog", StateType::Backlog),
+        ("s_todo", "Todo", StateType::Unstarted),
+        ("s_started", "In Progress", StateType::Started),
+        ("s_done", "Done", StateType::Completed),
+        ("s_canceled", "Cancelled", StateType::Cancelled),
+    ]
+    .into_iter()
+    .map(|(id, name, state_type)| StateOption {
+        id: StateId::from_raw(id),
+        name: name.into(),
+        state_type,
+    })
+    .collect()
+}
+
+fn sample_members() -> Vec<User> {
+    vec![
+        person("dan", true),
+        person("sam", false),
+        person("alex", false),
+    ]
+}
+
 fn sample_fixture() -> Fixture {
     let issues = vec![
         summary(
@@ -560,7 +919,7 @@ oven-ctl --set-target 430
             })
             .collect()
     };
-    let saved_view_issues = std::collections::HashMap::from([
+    let saved_view_issues = HashMap::from([
         (
             ViewId::from_raw("v_urgent"),
             pick(&["i1", "i2", "i3", "i4", "i5", "i6"]),
@@ -569,7 +928,37 @@ oven-ctl --set-target 430
         (ViewId::from_raw("v_menu"), pick(&["i4", "i5", "i7"])),
     ]);
 
+    let teams = vec![
+        Team {
+            id: TeamId::from_raw("t_donut"),
+            name: "Donuts".into(),
+            key: "DAN".into(),
+            triage_enabled: false,
+        },
+        Team {
+            id: TeamId::from_raw("t_pizza"),
+            name: "Pizza".into(),
+            key: "DAN2".into(),
+            triage_enabled: true,
+        },
+    ];
+
+    let labels = [
+        ("lbl_oven", "oven", "#eb5757"),
+        ("lbl_upsell", "upsell", "#f2c94c"),
+        ("lbl_bug", "bug", "#9b51e0"),
+        ("lbl_chore", "chore", "#4f4f4f"),
+    ]
+    .into_iter()
+    .map(|(id, name, colour)| Label {
+        id: LabelId::from_raw(id),
+        name: name.into(),
+        colour: Rgb::parse_hex(colour),
+    })
+    .collect();
+
     Fixture {
+        version: FIXTURE_VERSION,
         viewer: person("dan", true),
         org_name: "Dan's Donuts".into(),
         org_url_key: "dans-donuts".into(),
@@ -578,5 +967,304 @@ oven-ctl --set-target 430
         issues,
         saved_view_issues,
         details,
+        workflow_states: teams
+            .iter()
+            .map(|team| (team.id.clone(), sample_states()))
+            .collect(),
+        team_members: teams
+            .iter()
+            .map(|team| (team.id.clone(), sample_members()))
+            .collect(),
+        teams,
+        users: ["dan", "sam", "alex", "danniiee", "charlieh"]
+            .into_iter()
+            .map(|name| person(name, name == "dan"))
+            .collect(),
+        labels,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn a_legacy_fixture_borrows_the_sample_metadata() {
+        let legacy: Fixture = serde_json::from_str(
+            r#"{"viewer": {"id": "u", "name": "n", "display_name": "n", "url": "", "is_me": true},
+                "issues": [{"id": "i", "identifier": "OPS-1",
+                            "state": {"name": "Todo", "type": "unstarted"},
+                            "team_id": "t_ops"}]}"#,
+        )
+        .unwrap();
+        assert_eq!(legacy.version, 1);
+
+        let client = FixtureClient::new(legacy.upgraded());
+
+        assert_eq!(client.snapshot().version, FIXTURE_VERSION);
+        assert_eq!(
+            client
+                .workflow_states(&TeamId::from_raw("t_ops"))
+                .await
+                .unwrap(),
+            sample_states()
+        );
+        assert_eq!(client.teams().await.unwrap().len(), 2);
+        assert!(!client
+            .search_labels("", None)
+            .await
+            .unwrap()
+            .items
+            .is_empty());
+    }
+
+    #[tokio::test]
+    async fn a_recorded_fixture_answers_from_its_own_metadata() {
+        let fixture = Fixture {
+            teams: Vec::new(),
+            workflow_states: HashMap::new(),
+            team_members: HashMap::new(),
+            users: vec![person("robin", true)],
+            labels: Vec::new(),
+            ..sample_fixture()
+        }
+        .upgraded();
+        let client = FixtureClient::new(fixture);
+
+        assert!(client.teams().await.unwrap().is_empty());
+        assert!(client
+            .search_labels("", None)
+            .await
+            .unwrap()
+            .items
+            .is_empty());
+        assert_eq!(client.search_users("ROB").await.unwrap().len(), 1);
+        assert!(matches!(
+            client.workflow_states(&TeamId::from_raw("t_pizza")).await,
+            Err(ApiError::NotFound {
+                resource: "team",
+                ..
+            })
+        ));
+    }
+
+    #[test]
+    fn a_fixture_from_a_newer_build_is_refused() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("future.json");
+        let mut fixture = serde_json::to_value(sample_fixture()).unwrap();
+        fixture["version"] = (FIXTURE_VERSION + 1).into();
+        std::fs::write(&path, fixture.to_string()).unwrap();
+
+        let error = FixtureClient::from_path(&path).err().expect("a refusal");
+
+        assert!(error.to_string().contains("newer"));
+    }
+
+    fn identifiers(page: Page<IssueSummary>) -> Vec<String> {
+        page.items
+            .into_iter()
+            .map(|issue| issue.identifier)
+            .collect()
+    }
+
+    async fn dan2_7(client: &FixtureClient) -> IssueDetail {
+        client
+            .issue_detail(&IssueRef::parse("DAN2-7"))
+            .await
+            .unwrap()
+            .expect("the sample detail")
+    }
+
+    #[tokio::test]
+    async fn a_status_change_moves_the_issue_between_feeds() {
+        let client = FixtureClient::sample();
+        let done = IssueFilter {
+            state_types_in: vec![StateType::Completed],
+            ..IssueFilter::default()
+        };
+        let open = IssueFilter {
+            state_types_not_in: vec![StateType::Completed, StateType::Cancelled],
+            ..IssueFilter::default()
+        };
+        assert!(!identifiers(client.issues(&done, None).await.unwrap()).contains(&"DAN2-7".into()));
+
+        client
+            .update_issue(
+                &IssueId::from_raw("i1"),
+                IssueUpdate::Status(StateId::from_raw("s_done")),
+            )
+            .await
+            .unwrap();
+
+        assert!(identifiers(client.issues(&done, None).await.unwrap()).contains(&"DAN2-7".into()));
+        assert!(!identifiers(client.issues(&open, None).await.unwrap()).contains(&"DAN2-7".into()));
+        assert_eq!(dan2_7(&client).await.state.name, "Done");
+    }
+
+    #[tokio::test]
+    async fn updates_resolve_against_the_workspace_metadata() {
+        let client = FixtureClient::sample();
+        let id = IssueId::from_raw("i1");
+
+        client
+            .update_issue(&id, IssueUpdate::Assignee(Some(UserId::from_raw("u_sam"))))
+            .await
+            .unwrap();
+        assert_eq!(
+            dan2_7(&client).await.assignee.map(|user| user.display_name),
+            Some("sam".into())
+        );
+
+        client
+            .update_issue(&id, IssueUpdate::Assignee(None))
+            .await
+            .unwrap();
+        assert!(dan2_7(&client).await.assignee.is_none());
+
+        assert!(matches!(
+            client
+                .update_issue(&id, IssueUpdate::Status(StateId::from_raw("s_nowhere")))
+                .await,
+            Err(ApiError::NotFound {
+                resource: "state",
+                ..
+            })
+        ));
+    }
+
+    #[tokio::test]
+    async fn comments_appear_change_and_disappear() {
+        let client = FixtureClient::sample();
+        let issue_id = IssueId::from_raw("i1");
+        let before = dan2_7(&client).await.comments.len();
+
+        client
+            .create_comment(&issue_id, "Thermostat swapped", None)
+            .await
+            .unwrap();
+        let added = dan2_7(&client).await.comments.pop().unwrap();
+        assert!(added.is_mine);
+        assert_eq!(added.body, "Thermostat swapped");
+
+        client
+            .update_comment(&added.id, "Thermostat swapped again")
+            .await
+            .unwrap();
+        assert_eq!(
+            dan2_7(&client).await.comments.last().unwrap().body,
+            "Thermostat swapped again"
+        );
+
+        client.delete_comment(&added.id).await.unwrap();
+        assert_eq!(dan2_7(&client).await.comments.len(), before);
+    }
+
+    #[tokio::test]
+    async fn reactions_toggle() {
+        let client = FixtureClient::sample();
+        let target = ReactionTarget::Comment(CommentId::from_raw("c2"));
+
+        client.create_reaction(&target, "fire").await.unwrap();
+        let reaction = dan2_7(&client)
+            .await
+            .comments
+            .into_iter()
+            .find(|comment| comment.id.as_str() == "c2")
+            .and_then(|comment| comment.reactions.into_iter().last())
+            .expect("the new reaction");
+        assert!(reaction.mine);
+        assert_eq!(reaction.emoji, "fire");
+
+        client.delete_reaction(&reaction.id).await.unwrap();
+        assert!(dan2_7(&client)
+            .await
+            .comments
+            .iter()
+            .flat_map(|comment| &comment.reactions)
+            .all(|r| r.id != reaction.id));
+    }
+
+    #[tokio::test]
+    async fn changes_are_stamped_by_the_client_clock() {
+        let client = FixtureClient::sample().with_clock(|| Timestamp::from_epoch(1_000));
+        let issue_id = IssueId::from_raw("i1");
+
+        client
+            .update_issue(&issue_id, IssueUpdate::Priority(Priority::Low))
+            .await
+            .unwrap();
+        client
+            .create_comment(&issue_id, "Stamped", None)
+            .await
+            .unwrap();
+
+        let detail = dan2_7(&client).await;
+        assert_eq!(detail.updated_at, Timestamp::from_epoch(1_000));
+        assert_eq!(
+            detail.comments.last().unwrap().created_at,
+            Timestamp::from_epoch(1_000)
+        );
+    }
+
+    #[tokio::test]
+    async fn a_change_that_cannot_be_saved_is_not_kept() {
+        let dir = tempfile::tempdir().unwrap();
+        let client = FixtureClient {
+            write_back: Some(dir.path().join("missing").join("demo.json")),
+            ..FixtureClient::sample()
+        };
+        let before = dan2_7(&client).await.comments.len();
+
+        let saved = client
+            .create_comment(&IssueId::from_raw("i1"), "Lost", None)
+            .await;
+
+        assert!(matches!(saved, Err(ApiError::Fixture(_))));
+        assert_eq!(dan2_7(&client).await.comments.len(), before);
+    }
+
+    #[tokio::test]
+    async fn a_writing_back_client_saves_every_change() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("demo.json");
+        std::fs::write(&path, serde_json::to_string(&sample_fixture()).unwrap()).unwrap();
+
+        let client = FixtureClient::writing_back(&path).unwrap();
+        client
+            .create_comment(&IssueId::from_raw("i1"), "Saved to disk", None)
+            .await
+            .unwrap();
+
+        let reloaded = FixtureClient::from_path(&path).unwrap();
+        assert_eq!(
+            dan2_7(&reloaded).await.comments.last().unwrap().body,
+            "Saved to disk"
+        );
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn changes_saved_at_once_are_all_kept() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("demo.json");
+        std::fs::write(&path, serde_json::to_string(&sample_fixture()).unwrap()).unwrap();
+
+        let client = std::sync::Arc::new(FixtureClient::writing_back(&path).unwrap());
+        let before = dan2_7(&client).await.comments.len();
+
+        let posts = (0..8).map(|n| {
+            let client = client.clone();
+            tokio::spawn(async move {
+                client
+                    .create_comment(&IssueId::from_raw("i1"), &format!("Post {n}"), None)
+                    .await
+            })
+        });
+        for post in futures::future::join_all(posts).await {
+            post.unwrap().unwrap();
+        }
+
+        let reloaded = FixtureClient::from_path(&path).unwrap();
+        assert_eq!(dan2_7(&reloaded).await.comments.len(), before + 8);
     }
 }
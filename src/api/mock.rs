@@ -0,0 +1,644 @@
+use std::collections::VecDeque;
+use std::io;
+use std::net::SocketAddr;
+use std::sync::{Arc, Mutex};
+
+use serde_json::{json, Map, Value};
+use tokio::io::{AsyncBufReadExt, AsyncReadExt, AsyncWriteExt, BufReader};
+use tokio::net::{TcpListener, TcpStream};
+use tokio::task::JoinHandle;
+
+use crate::api::client::ConnectionSettings;
+use crate::api::error::{ApiError, ApiResult};
+use crate::api::model::{
+    CommentId, IssueDetail, IssueFilter, IssueId, IssueRef, IssueSummary, IssueUpdate, Label,
+    LabelId, NotificationItem, Page, Priority, ReactionId, ReactionTarget, StateId, StateType,
+    TeamId, User, UserId, ViewId,
+};
+use crate::api::LinearApi;
+
+/// A failure the server returns instead of the next answer.
+#[derive(Debug, Clone)]
+pub enum Fault {
+    Status { status: u16, body: String },
+    GraphQl(String),
+}
+
+impl Fault {
+    pub fn unauthorized() -> Self {
+        Fault::Status {
+            status: 401,
+            body: errors_body("Authentication required, not authenticated"),
+        }
+    }
+
+    pub fn rate_limited() -> Self {
+        Fault::Status {
+            status: 429,
+            body: errors_body("Rate limit exceeded"),
+        }
+    }
+}
+
+/// One request as the server received it.
+#[derive(Debug, Clone)]
+pub struct Recorded {
+    pub operation: String,
+    pub variables: Value,
+    pub authorization: Option<String>,
+}
+
+struct State {
+    page_size: usize,
+    token: Option<String>,
+    faults: VecDeque<Fault>,
+    requests: Vec<Recorded>,
+}
+
+/// An in-process stand-in for Linear's GraphQL endpoint. It answers the
+/// operations `Client` sends from any `LinearApi`, in Linear's wire shape,
+/// so the query building and response mapping run for real.
+pub struct MockServer {
+    addr: SocketAddr,
+    state: Arc<Mutex<State>>,
+    task: JoinHandle<()>,
+}
+
+impl MockServer {
+    pub async fn start(api: impl LinearApi + 'static) -> io::Result<Self> {
+        let listener = TcpListener::bind(("127.0.0.1", 0)).await?;
+        let addr = listener.local_addr()?;
+        let api: Arc<dyn LinearApi> = Arc::new(api);
+        let state = Arc::new(Mutex::new(State {
+            page_size: usize::MAX,
+            token: None,
+            faults: VecDeque::new(),
+            requests: Vec::new(),
+        }));
+
+        let task = tokio::spawn({
+            let state = Arc::clone(&state);
+            async move {
+                while let Ok((stream, _)) = listener.accept().await {
+                    tokio::spawn(serve(stream, Arc::clone(&api), Arc::clone(&state)));
+                }
+            }
+        });
+
+        Ok(Self { addr, state, task })
+    }
+
+    pub fn endpoint(&self) -> String {
+        format!("http://{}/graphql", self.addr)
+    }
+
+    pub fn settings(&self) -> ConnectionSettings {
+        ConnectionSettings {
+            endpoint: Some(self.endpoint()),
+            ..ConnectionSettings::default()
+        }
+    }
+
+    /// Caps every connection at `size` nodes, whatever `first` asks for.
+    pub fn set_page_size(&self, size: usize) {
+        self.lock().page_size = size.max(1);
+    }
+
+    /// Answers 401 to any request whose `Authorization` header differs.
+    pub fn require_authorization(&self, header: impl Into<String>) {
+        self.lock().token = Some(header.into());
+    }
+
+    /// Queues a failure; each request takes the oldest one left.
+    pub fn fail_next(&self, fault: Fault) {
+        self.lock().faults.push_back(fault);
+    }
+
+    pub fn requests(&self) -> Vec<Recorded> {
+        self.lock().requests.clone()
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, State> {
+        self.state.lock().unwrap_or_else(|e| e.into_inner())
+    }
+}
+
+impl Drop for MockServer {
+    fn drop(&mut self) {
+        self.task.abort();
+    }
+}
+
+struct Response {
+    status: u16,
+    body: String,
+}
+
+async fn serve(stream: TcpStream, api: Arc<dyn LinearApi>, state: Arc<Mutex<State>>) {
+    let (reader, mut writer) = stream.into_split();
+    let mut reader = BufReader::new(reader);
+
+    while let Ok(Some((authorization, body))) = read_request(&mut reader).await {
+        let response = respond(api.as_ref(), &state, authorization, &body).await;
+        let reason = match response.status {
+            200 => "OK",
+            400 => "Bad Request",
+            401 => "Unauthorized",
+            429 => "Too Many Requests",
+            _ => "Error",
+        };
+        let head = format!(
+            "HTTP/1.1 {} {reason}\r\nContent-Type: application/json\r\nContent-Length: {}\r\n\r\n",
+            response.status,
+            response.body.len()
+        );
+
+        if writer.write_all(head.as_bytes()).await.is_err()
+            || writer.write_all(response.body.as_bytes()).await.is_err()
+        {
+            return;
+        }
+    }
+}
+
+/// Reads one HTTP/1.1 request, returning its `Authorization` header and body,
+/// or `None` once the client closes the connection.
+async fn read_request<R>(reader: &mut R) -> io::Result<Option<(Option<String>, Vec<u8>)>>
+where
+    R: AsyncBufReadExt + Unpin,
+{
+    let mut line = String::new();
+    if reader.read_line(&mut line).await? == 0 {
+        return Ok(None);
+    }
+
+    let mut length = 0;
+    let mut authorization = None;
+
+    loop {
+        line.clear();
+        if reader.read_line(&mut line).await? == 0 {
+            return Ok(None);
+        }
+
+        let header = line.trim_end();
+        if header.is_empty() {
+            break;
+        }
+
+        if let Some((name, value)) = header.split_once(':') {
+            let value = value.trim();
+            if name.eq_ignore_ascii_case("content-length") {
+                length = value.parse().unwrap_or(0);
+            } else if name.eq_ignore_ascii_case("authorization") {
+                authorization = Some(value.to_string());
+            }
+        }
+    }
+
+    let mut body = vec![0; length];
+    reader.read_exact(&mut body).await?;
+
+    Ok(Some((authorization, body)))
+}
+
+async fn respond(
+    api: &dyn LinearApi,
+    state: &Mutex<State>,
+    authorization: Option<String>,
+    body: &[u8],
+) -> Response {
+    let Ok(request) = serde_json::from_slice::<Value>(body) else {
+        return Response {
+            status: 400,
+            body: errors_body("Request body is not JSON"),
+        };
+    };
+
+    let operation = request["operationName"]
+        .as_str()
+        .unwrap_or_default()
+        .to_string();
+    let variables = request["variables"].clone();
+
+    let (fault, page_size) = {
+        let mut state = state.lock().unwrap_or_else(|e| e.into_inner());
+        state.requests.push(Recorded {
+            operation: operation.clone(),
+            variables: variables.clone(),
+            authorization: authorization.clone(),
+        });
+
+        let rejected = state
+            .token
+            .as_ref()
+            .is_some_and(|token| authorization.as_ref() != Some(token));
+
+        let fault = if rejected {
+            Some(Fault::unauthorized())
+        } else {
+            state.faults.pop_front()
+        };
+
+        (fault, state.page_size)
+    };
+
+    match fault {
+        Some(Fault::Status { status, body }) => Response { status, body },
+        Some(Fault::GraphQl(message)) => Response {
+            status: 200,
+            body: errors_body(&message),
+        },
+        None => {
+            let body = match answer(api, &operation, &variables, page_size).await {
+                Ok(data) => json!({ "data": data }).to_string(),
+                Err(error) => errors_body(&error.to_string()),
+            };
+
+            Response { status: 200, body }
+        }
+    }
+}
+
+fn errors_body(message: &str) -> String {
+    json!({ "data": null, "errors": [{ "message": message }] }).to_string()
+}
+
+fn invalid(message: impl Into<String>) -> ApiError {
+    ApiError::GraphQl(vec![message.into()])
+}
+
+async fn answer(
+    api: &dyn LinearApi,
+    operation: &str,
+    variables: &Value,
+    page_size: usize,
+) -> ApiResult<Value> {
+    let string = |name: &str| -> ApiResult<String> {
+        variables[name]
+            .as_str()
+            .map(str::to_string)
+            .ok_or_else(|| invalid(format!("Variable ${name} is required")))
+    };
+    let window = || Window::new(variables, page_size);
+    let input = &variables["input"];
+
+    Ok(match operation {
+        "ViewerQuery" => {
+            let session = api.session().await?;
+            json!({
+                "viewer": user(&session.user),
+                "organization": { "name": session.org_name, "urlKey": session.org_url_key },
+            })
+        }
+        "CustomViewsQuery" => {
+            let views: Vec<Value> = api
+                .custom_views()
+                .await?
+                .iter()
+                .map(|view| json!({ "id": view.id.to_string(), "name": view.name }))
+                .collect();
+            json!({ "customViews": { "nodes": views } })
+        }
+        "CustomViewIssuesQuery" => {
+            let id = ViewId::from_raw(string("id")?);
+            let page = api.custom_view_issues(&id, None).await?;
+            json!({ "customView": { "issues": window()?.connection(page, summary) } })
+        }
+        "IssuesQuery" => {
+            let filter = issue_filter(&variables["filter"]);
+            let page = api.issues(&filter, None).await?;
+            json!({ "issues": window()?.connection(page, summary) })
+        }
+        "SearchIssuesQuery" => {
+            let page = api.search_issues(&string("term")?, None).await?;
+            json!({ "searchIssues": window()?.connection(page, summary) })
+        }
+        "IssueQuery" => {
+            let target = IssueRef::parse(&string("id")?);
+            json!({ "issue": api.issue_detail(&target).await?.as_ref().map(detail) })
+        }
+        "IssueBatch" => {
+            let mut data = Map::new();
+            for (alias, id) in variables.as_object().into_iter().flatten() {
+                let target = IssueRef::parse(id.as_str().unwrap_or_default());
+                let issue = api.issue_detail(&target).await?;
+                data.insert(alias.clone(), json!(issue.as_ref().map(detail)));
+            }
+            Value::Object(data)
+        }
+        "NotificationsQuery" => {
+            let page = api.notifications(None).await?;
+            json!({ "notifications": window()?.connection(page, notification) })
+        }
+        "TeamsQuery" => {
+            let teams: Vec<Value> = api
+                .teams()
+                .await?
+                .iter()
+                .map(|team| {
+                    json!({
+                        "id": team.id.to_string(),
+                        "name": team.name,
+                        "key": team.key,
+                        "triageEnabled": team.triage_enabled,
+                    })
+                })
+                .collect();
+            json!({ "teams": { "nodes": teams } })
+        }
+        "TeamStatesQuery" => {
+            let states = api
+                .workflow_states(&TeamId::from_raw(string("id")?))
+                .await?;
+            // Linear lists states last position first; `Client` reverses them.
+            let nodes: Vec<Value> = states
+                .iter()
+                .enumerate()
+                .rev()
+                .map(|(position, state)| {
+                    json!({
+                        "id": state.id.to_string(),
+                        "name": state.name,
+                        "type": state.state_type.as_api(),
+                        "position": position as f64,
+                    })
+                })
+                .collect();
+            json!({ "team": { "states": { "nodes": nodes } } })
+        }
+        "TeamMembersQuery" => {
+            let members = api.team_members(&TeamId::from_raw(string("id")?)).await?;
+            let nodes: Vec<Value> = members.iter().map(user).collect();
+            json!({ "team": { "members": { "nodes": nodes } } })
+        }
+        "UserSearchQuery" => {
+            let term = variables["filter"]["or"][0]["displayName"]["containsIgnoreCase"]
+                .as_str()
+                .unwrap_or_default();
+            let nodes: Vec<Value> = api.search_users(term).await?.iter().map(user).collect();
+            json!({ "users": { "nodes": nodes } })
+        }
+        "LabelSearchQuery" => {
+            let term = variables["filter"]["name"]["containsIgnoreCase"]
+                .as_str()
+                .unwrap_or_default();
+            let page = api.search_labels(term, None).await?;
+            json!({ "issueLabels": window()?.connection(page, label) })
+        }
+        "StatusMutation"
+        | "AssigneeMutation"
+        | "PriorityMutation"
+        | "LabelsMutation"
+        | "DescriptionMutation" => {
+            let update = issue_update(operation, input)?;
+            api.update_issue(&IssueId::from_raw(string("id")?), update)
+                .await?;
+            json!({ "issueUpdate": { "success": true } })
+        }
+        "CommentCreateMutation" => {
+            let issue_id = IssueId::from_raw(input["issueId"].as_str().unwrap_or_default());
+            let parent_id = input["parentId"].as_str().map(CommentId::from_raw);
+            api.create_comment(
+                &issue_id,
+                input["body"].as_str().unwrap_or_default(),
+                parent_id.as_ref(),
+            )
+            .await?;
+            json!({ "commentCreate": { "success": true } })
+        }
+        "CommentUpdateMutation" => {
+            api.update_comment(
+                &CommentId::from_raw(string("id")?),
+                input["body"].as_str().unwrap_or_default(),
+            )
+            .await?;
+            json!({ "commentUpdate": { "success": true } })
+        }
+        "CommentDeleteMutation" => {
+            api.delete_comment(&CommentId::from_raw(string("id")?))
+                .await?;
+            json!({ "commentDelete": { "success": true } })
+        }
+        "ReactionCreateMutation" => {
+            let target = match (input["commentId"].as_str(), input["issueId"].as_str()) {
+                (Some(id), _) => ReactionTarget::Comment(CommentId::from_raw(id)),
+                (None, Some(id)) => ReactionTarget::Issue(IssueId::from_raw(id)),
+                (None, None) => return Err(invalid("A reaction needs a comment or an issue")),
+            };
+            api.create_reaction(&target, input["emoji"].as_str().unwrap_or_default())
+                .await?;
+            json!({ "reactionCreate": { "success": true } })
+        }
+        "ReactionDeleteMutation" => {
+            api.delete_reaction(&ReactionId::from_raw(string("id")?))
+                .await?;
+            json!({ "reactionDelete": { "success": true } })
+        }
+        other => return Err(invalid(format!("Unknown operation {other:?}"))),
+    })
+}
+
+/// The slice of a connection one request asks for. Cursors are offsets.
+struct Window {
+    start: usize,
+    len: usize,
+}
+
+impl Window {
+    fn new(variables: &Value, page_size: usize) -> ApiResult<Self> {
+        let start = match variables["after"].as_str() {
+            Some(cursor) => cursor
+                .parse()
+                .map_err(|_| invalid(format!("Invalid cursor {cursor:?}")))?,
+            None => 0,
+        };
+        let first = variables["first"]
+            .as_u64()
+            .map_or(usize::MAX, |first| first as usize);
+
+        Ok(Self {
+            start,
+            len: first.min(page_size),
+        })
+    }
+
+    fn connection<T>(self, page: Page<T>, node: impl Fn(&T) -> Value) -> Value {
+        let items = page.items;
+        let start = self.start.min(items.len());
+        let end = start.saturating_add(self.len).min(items.len());
+        let nodes: Vec<Value> = items[start..end].iter().map(node).collect();
+
+        json!({
+            "nodes": nodes,
+            "pageInfo": {
+                "hasNextPage": end < items.len(),
+                "endCursor": (end > start).then(|| end.to_string()),
+            },
+        })
+    }
+}
+
+/// Reads back the filter `build_cynic_filter` produced.
+fn issue_filter(filter: &Value) -> IssueFilter {
+    let is_me = |field: &str| filter[field]["isMe"]["eq"].as_bool() == Some(true);
+    let types = |comparator: &str| -> Vec<StateType> {
+        filter["state"]["type"][comparator]
+            .as_array()
+            .into_iter()
+            .flatten()
+            .filter_map(Value::as_str)
+            .map(StateType::from_api)
+            .collect()
+    };
+
+    IssueFilter {
+        assigned_to_me: is_me("assignee"),
+        created_by_me: is_me("creator"),
+        state_types_in: types("in"),
+        state_types_not_in: types("nin"),
+        team: filter["team"]["id"]["eq"].as_str().map(TeamId::from_raw),
+        ..IssueFilter::default()
+    }
+}
+
+fn issue_update(operation: &str, input: &Value) -> ApiResult<IssueUpdate> {
+    let missing = |field: &str| invalid(format!("Field {field:?} is required"));
+
+    Ok(match operation {
+        "StatusMutation" => IssueUpdate::Status(StateId::from_raw(
+            input["stateId"]
+                .as_str()
+                .ok_or_else(|| missing("stateId"))?,
+        )),
+        "AssigneeMutation" => {
+            IssueUpdate::Assignee(input["assigneeId"].as_str().map(UserId::from_raw))
+        }
+        "PriorityMutation" => {
+            let priority = input["priority"]
+                .as_u64()
+                .ok_or_else(|| missing("priority"))?;
+            IssueUpdate::Priority(Priority::from(priority as u8))
+        }
+        "LabelsMutation" => IssueUpdate::Labels(
+            input["labelIds"]
+                .as_array()
+                .ok_or_else(|| missing("labelIds"))?
+                .iter()
+                .filter_map(Value::as_str)
+                .map(LabelId::from_raw)
+                .collect(),
+        ),
+        _ => IssueUpdate::Description(
+            input["description"]
+                .as_str()
+                .ok_or_else(|| missing("description"))?
+                .to_string(),
+        ),
+    })
+}
+
+fn user(user: &User) -> Value {
+    json!({
+        "id": user.id.to_string(),
+        "name": user.name,
+        "displayName": user.display_name,
+        "url": user.url,
+        "isMe": user.is_me,
+    })
+}
+
+fn label(label: &Label) -> Value {
+    json!({
+        "id": label.id.to_string(),
+        "name": label.name,
+        "color": String::from(label.colour),
+    })
+}
+
+fn summary(issue: &IssueSummary) -> Value {
+    json!({
+        "id": issue.id.to_string(),
+        "identifier": issue.identifier,
+        "title": issue.title.clone().unwrap_or_default(),
+        "priority": u8::from(issue.priority) as f64,
+        "url": issue.url,
+        "branchName": issue.branch_name,
+        "updatedAt": String::from(issue.updated_at),
+        "state": { "name": issue.state.name, "type": issue.state.state_type.as_api() },
+        "team": { "id": issue.team_id.to_string() },
+        "assignee": issue.assignee.as_ref().map(|assignee| json!({ "displayName": assignee.display_name })),
+        "labels": { "nodes": issue.labels.iter().map(label).collect::<Vec<_>>() },
+    })
+}
+
+fn detail(issue: &IssueDetail) -> Value {
+    let reactions = |reactions: &[crate::api::model::Reaction]| -> Vec<Value> {
+        reactions
+            .iter()
+            .map(|reaction| {
+                json!({
+                    "id": reaction.id.to_string(),
+                    "emoji": reaction.emoji,
+                    "user": reaction.mine.then(|| json!({ "isMe": true })),
+                })
+            })
+            .collect()
+    };
+
+    let comments: Vec<Value> = issue
+        .comments
+        .iter()
+        .map(|comment| {
+            json!({
+                "id": comment.id.to_string(),
+                "body": comment.body,
+                "createdAt": String::from(comment.created_at),
+                "user": comment.author.as_ref().map(|author| {
+                    json!({ "displayName": author, "isMe": comment.is_mine })
+                }),
+                "parent": comment.parent_id.as_ref().map(|id| json!({ "id": id.to_string() })),
+                "reactions": reactions(&comment.reactions),
+            })
+        })
+        .collect();
+
+    json!({
+        "id": issue.id.to_string(),
+        "identifier": issue.identifier,
+        "title": issue.title,
+        "description": issue.description,
+        "url": issue.url,
+        "branchName": issue.branch_name,
+        "priority": u8::from(issue.priority) as f64,
+        "updatedAt": String::from(issue.updated_at),
+        "state": { "name": issue.state.name, "type": issue.state.state_type.as_api() },
+        "team": { "id": issue.team_id.to_string() },
+        "assignee": issue.assignee.as_ref().map(|assignee| {
+            json!({ "displayName": assignee.display_name, "isMe": assignee.is_me })
+        }),
+        "labels": { "nodes": issue.labels.iter().map(label).collect::<Vec<_>>() },
+        "comments": { "nodes": comments },
+        "reactions": reactions(&issue.reactions),
+    })
+}
+
+fn notification(item: &NotificationItem) -> Value {
+    let read_at = item.is_read.then_some("2026-01-01T00:00:00+00:00");
+
+    match &item.issue_id {
+        Some(issue_id) => json!({
+            "__typename": "IssueNotification",
+            "title": item.title,
+            "issue": { "id": issue_id.to_string() },
+            "readAt": read_at,
+            "groupingKey": item.grouping_key,
+        }),
+        None => json!({
+            "__typename": "ProjectNotification",
+            "title": item.title,
+            "readAt": read_at,
+            "groupingKey": item.grouping_key,
+        }),
+    }
+}
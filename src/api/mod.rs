@@ -1,10 +1,13 @@
 pub mod client;
 pub mod error;
 pub mod fixture;
+#[cfg(any(test, feature = "mock"))]
+pub mod mock;
 pub mod model;
 pub mod queries;
+pub mod scrub;
 
-pub use client::Client;
+pub use client::{CassetteMode, Client, ConnectionSettings, Tape};
 pub use error::{ApiError, ApiResult};
 pub use fixture::FixtureClient;
 pub use model::*;
@@ -34,7 +37,7 @@ pub trait LinearApi: Send + Sync {
     async fn teams(&self) -> ApiResult<Vec<Team>>;
     async fn team_members(&self, team_id: &TeamId) -> ApiResult<Vec<User>>;
     async fn search_users(&self, term: &str) -> ApiResult<Vec<User>>;
-    async fn search_labels(&self, term: &str) -> ApiResult<Vec<Label>>;
+    async fn search_labels(&self, term: &str, after: Option<&Cursor>) -> ApiResult<Page<Label>>;
     async fn update_issue(&self, id: &IssueId, update: IssueUpdate) -> ApiResult<()>;
     async fn create_comment(
         &self,
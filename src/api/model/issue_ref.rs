@@ -21,6 +21,16 @@ impl IssueRef {
         }
     }
 
+    /// The issue a Linear issue URL points at. Other URLs are not issues.
+    pub fn from_linear_url(url: &str) -> Option<Self> {
+        if !url.starts_with("https://linear.app/") {
+            return None;
+        }
+
+        let candidate = identifier_from_url(url)?;
+        looks_like_identifier(candidate).then(|| IssueRef::Identifier(candidate.to_uppercase()))
+    }
+
     pub fn matches_detail(&self, detail: &IssueDetail) -> bool {
         match self {
             IssueRef::Id(id) => detail.id == *id,
@@ -106,6 +116,22 @@ mod tests {
         );
     }
 
+    #[test]
+    fn only_linear_issue_urls_name_an_issue() {
+        assert_eq!(
+            IssueRef::from_linear_url("https://linear.app/dans-donuts/issue/DAN2-7/wood-fired"),
+            Some(identifier("DAN2-7"))
+        );
+        assert_eq!(
+            IssueRef::from_linear_url("https://linear.app/dans-donuts/profiles/dan"),
+            None
+        );
+        assert_eq!(
+            IssueRef::from_linear_url("https://example.com/issue/DAN2-7"),
+            None
+        );
+    }
+
     #[test]
     fn anything_that_is_not_an_identifier_is_taken_as_an_id() {
         assert_eq!(
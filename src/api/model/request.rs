@@ -9,6 +9,7 @@ pub enum IssueUpdate {
     Assignee(Option<UserId>),
     Priority(Priority),
     Labels(Vec<LabelId>),
+    Description(String),
 }
 
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -178,6 +178,17 @@ impl Timestamp {
         Some(Timestamp(self.0 + boundary))
     }
 
+    /// The calendar day where the user is, as `2026-07-16`.
+    pub fn date(self) -> String {
+        chrono::DateTime::from_timestamp(self.0, 0)
+            .map(|dt| {
+                dt.with_timezone(&chrono::Local)
+                    .format("%Y-%m-%d")
+                    .to_string()
+            })
+            .unwrap_or_default()
+    }
+
     pub fn humanise(self, now: Timestamp) -> String {
         match self.age(now) {
             Age::JustNow => "just now".into(),
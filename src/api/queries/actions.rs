@@ -92,6 +92,13 @@ pub struct LabelsInput {
     pub label_ids: Vec<String>,
 }
 
+#[derive(Debug, Clone, InputObject)]
+#[cynic(schema_path = "schema.graphql", graphql_type = "IssueUpdateInput")]
+pub struct DescriptionInput {
+    #[cynic(skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+}
+
 // No `skip_serializing_if`: `None` serialises as explicit `null`, which unassigns.
 // TODO: fold back into one input via MaybeUndefined once https://codeberg.org/obmarg/cynic/issues/125 lands.
 #[derive(Debug, Clone, InputObject)]
@@ -118,6 +125,12 @@ pub struct PriorityVariables {
     pub input: PriorityInput,
 }
 
+#[derive(Debug, QueryVariables)]
+pub struct DescriptionVariables {
+    pub id: String,
+    pub input: DescriptionInput,
+}
+
 #[derive(Debug, QueryVariables)]
 pub struct LabelsVariables {
     pub id: String,
@@ -163,6 +176,17 @@ pub struct LabelsMutation {
     pub issue_update: IssuePayload,
 }
 
+#[derive(Debug, QueryFragment)]
+#[cynic(
+    schema_path = "schema.graphql",
+    graphql_type = "Mutation",
+    variables = "DescriptionVariables"
+)]
+pub struct DescriptionMutation {
+    #[arguments(id: $id, input: $input)]
+    pub issue_update: IssuePayload,
+}
+
 #[derive(Debug, QueryFragment)]
 #[cynic(
     schema_path = "schema.graphql",
@@ -11,10 +11,18 @@ pub struct IssueLabel {
     pub colour: String,
 }
 
+#[derive(Debug, Clone, QueryFragment)]
+#[cynic(schema_path = "schema.graphql")]
+pub struct PageInfo {
+    pub has_next_page: bool,
+    pub end_cursor: Option<String>,
+}
+
 #[derive(Debug, Clone, QueryFragment)]
 #[cynic(schema_path = "schema.graphql")]
 pub struct IssueLabelConnection {
     pub nodes: Vec<IssueLabel>,
+    pub page_info: PageInfo,
 }
 
 #[derive(Debug, Clone, InputObject)]
@@ -35,6 +43,7 @@ pub struct IssueLabelFilter {
 pub struct LabelSearchVariables {
     pub filter: Option<IssueLabelFilter>,
     pub first: i32,
+    pub after: Option<String>,
 }
 
 #[derive(Debug, QueryFragment)]
@@ -44,6 +53,6 @@ pub struct LabelSearchVariables {
     variables = "LabelSearchVariables"
 )]
 pub struct LabelSearchQuery {
-    #[arguments(filter: $filter, first: $first)]
+    #[arguments(filter: $filter, first: $first, after: $after)]
     pub issue_labels: IssueLabelConnection,
 }
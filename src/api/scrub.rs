@@ -0,0 +1,652 @@
+use std::collections::{BTreeSet, HashMap};
+
+use sha2::{Digest, Sha256};
+
+use crate::api::fixture::Fixture;
+use crate::api::model::{
+    Comment, CommentId, IssueDetail, IssueId, IssueSummary, Label, LabelId, NotificationItem,
+    Reaction, ReactionId, SavedView, StateId, StateOption, Team, TeamId, User, UserId, ViewId,
+    WorkflowState,
+};
+
+const ORG_NAME: &str = "Example Org";
+const ORG_URL_KEY: &str = "example-org";
+const LINK: &str = "https://example.com";
+
+const PSEUDONYMS: &[&str] = &[
+    "ash", "birch", "cedar", "elm", "fern", "hazel", "ivy", "juniper", "larch", "maple", "oak",
+    "pine", "rowan", "sage", "willow", "yew",
+];
+
+const TEAM_KEYS: &[&str] = &[
+    "ALPHA", "BRAVO", "CHARLIE", "DELTA", "ECHO", "FOXTROT", "GOLF", "HOTEL", "INDIA", "JULIET",
+    "KILO", "LIMA", "MIKE", "NOVEMBER", "OSCAR", "PAPA",
+];
+
+const LOREM: &[&str] = &[
+    "lorem",
+    "ipsum",
+    "dolor",
+    "sit",
+    "amet",
+    "consectetur",
+    "adipiscing",
+    "elit",
+    "sed",
+    "do",
+    "eiusmod",
+    "tempor",
+    "incididunt",
+    "ut",
+    "labore",
+    "et",
+    "dolore",
+    "magna",
+    "aliqua",
+    "enim",
+    "ad",
+    "minim",
+    "veniam",
+    "quis",
+    "nostrud",
+    "exercitation",
+    "ullamco",
+    "laboris",
+    "nisi",
+    "aliquip",
+    "ex",
+    "ea",
+    "commodo",
+    "consequat",
+    "duis",
+    "aute",
+    "irure",
+    "in",
+    "reprehenderit",
+    "voluptate",
+    "velit",
+    "esse",
+    "cillum",
+    "fugiat",
+    "nulla",
+    "pariatur",
+    "excepteur",
+    "sint",
+    "occaecat",
+    "cupidatat",
+    "non",
+    "proident",
+    "sunt",
+    "culpa",
+    "qui",
+    "officia",
+    "deserunt",
+    "mollit",
+    "anim",
+    "id",
+    "est",
+    "laborum",
+];
+
+/// Rewrites a fixture so it can be shared: people become pseudonyms, prose
+/// becomes lorem ipsum with its markdown intact, and ids, identifiers and
+/// URLs are remapped consistently so every cross-reference still resolves.
+/// The same input always scrubs to the same output.
+pub fn scrub(fixture: Fixture) -> Fixture {
+    Scrubber::new(&fixture).fixture(fixture)
+}
+
+struct Scrubber {
+    people: HashMap<String, String>,
+    team_keys: HashMap<String, String>,
+}
+
+impl Scrubber {
+    fn new(fixture: &Fixture) -> Self {
+        let mut people = BTreeSet::new();
+        let mut full_names = Vec::new();
+        let mut keys = BTreeSet::new();
+
+        let users = std::iter::once(&fixture.viewer)
+            .chain(&fixture.users)
+            .chain(fixture.team_members.values().flatten())
+            .chain(summaries(fixture).filter_map(|issue| issue.assignee.as_ref()))
+            .chain(
+                fixture
+                    .details
+                    .iter()
+                    .filter_map(|detail| detail.assignee.as_ref()),
+            );
+        for user in users {
+            people.insert(user.display_name.to_lowercase());
+            full_names.push((user.name.to_lowercase(), user.display_name.to_lowercase()));
+        }
+        for comment in fixture.details.iter().flat_map(|detail| &detail.comments) {
+            if let Some(author) = &comment.author {
+                people.insert(author.to_lowercase());
+            }
+        }
+        people.remove("");
+
+        let mut people = assign(people, PSEUDONYMS);
+        for (name, display_name) in full_names {
+            if let Some(pseudonym) = people.get(&display_name).cloned() {
+                people.entry(name).or_insert(pseudonym);
+            }
+        }
+
+        keys.extend(fixture.teams.iter().map(|team| team.key.clone()));
+        let identifiers = summaries(fixture)
+            .map(|issue| &issue.identifier)
+            .chain(fixture.details.iter().map(|detail| &detail.identifier));
+        for identifier in identifiers {
+            if let Some((key, _)) = identifier.split_once('-') {
+                keys.insert(key.to_string());
+            }
+        }
+
+        Self {
+            people,
+            team_keys: assign(keys, TEAM_KEYS),
+        }
+    }
+
+    fn fixture(&self, fixture: Fixture) -> Fixture {
+        Fixture {
+            version: fixture.version,
+            viewer: self.user(fixture.viewer),
+            org_name: ORG_NAME.into(),
+            org_url_key: ORG_URL_KEY.into(),
+            notifications: fixture
+                .notifications
+                .into_iter()
+                .map(|item| self.notification(item))
+                .collect(),
+            saved_views: fixture
+                .saved_views
+                .into_iter()
+                .map(|view| SavedView {
+                    id: remap(&view.id),
+                    name: self.text(&view.name),
+                })
+                .collect(),
+            issues: self.summaries(fixture.issues),
+            saved_view_issues: fixture
+                .saved_view_issues
+                .into_iter()
+                .map(|(id, issues)| (remap(&id), self.summaries(issues)))
+                .collect(),
+            details: fixture
+                .details
+                .into_iter()
+                .map(|detail| self.detail(detail))
+                .collect(),
+            teams: fixture
+                .teams
+                .into_iter()
+                .map(|team| Team {
+                    id: remap(&team.id),
+                    name: capitalised(&self.team_key(&team.key).to_lowercase()),
+                    key: self.team_key(&team.key),
+                    triage_enabled: team.triage_enabled,
+                })
+                .collect(),
+            workflow_states: fixture
+                .workflow_states
+                .into_iter()
+                .map(|(id, states)| {
+                    let states = states
+                        .into_iter()
+                        .map(|state| StateOption {
+                            id: remap(&state.id),
+                            name: self.text(&state.name),
+                            ..state
+                        })
+                        .collect();
+                    (remap(&id), states)
+                })
+                .collect(),
+            team_members: fixture
+                .team_members
+                .into_iter()
+                .map(|(id, members)| (remap(&id), self.users(members)))
+                .collect(),
+            users: self.users(fixture.users),
+            labels: fixture
+                .labels
+                .into_iter()
+                .map(|label| self.label(label))
+                .collect(),
+        }
+    }
+
+    fn person(&self, name: &str) -> String {
+        self.people
+            .get(&name.to_lowercase())
+            .cloned()
+            .unwrap_or_else(|| name.to_string())
+    }
+
+    fn team_key(&self, key: &str) -> String {
+        self.team_keys
+            .get(key)
+            .cloned()
+            .unwrap_or_else(|| key.to_string())
+    }
+
+    fn identifier(&self, identifier: &str) -> String {
+        match identifier.split_once('-') {
+            Some((key, number)) => format!("{}-{number}", self.team_key(key)),
+            None => identifier.to_string(),
+        }
+    }
+
+    fn user(&self, user: User) -> User {
+        let pseudonym = self.person(&user.display_name);
+
+        User {
+            id: remap(&user.id),
+            name: self.person(&user.name),
+            url: format!("https://linear.app/{ORG_URL_KEY}/profiles/{pseudonym}"),
+            display_name: pseudonym,
+            is_me: user.is_me,
+        }
+    }
+
+    fn users(&self, users: Vec<User>) -> Vec<User> {
+        users.into_iter().map(|user| self.user(user)).collect()
+    }
+
+    fn label(&self, label: Label) -> Label {
+        Label {
+            id: remap(&label.id),
+            name: self.text(&label.name),
+            colour: label.colour,
+        }
+    }
+
+    /// Teams name their own states, so the name goes like any other text;
+    /// the type, which drives grouping and icons, stays.
+    fn state(&self, state: WorkflowState) -> WorkflowState {
+        WorkflowState {
+            name: self.text(&state.name),
+            ..state
+        }
+    }
+
+    fn issue_url(&self, identifier: &str) -> String {
+        format!("https://linear.app/{ORG_URL_KEY}/issue/{identifier}")
+    }
+
+    fn branch_name(&self, identifier: &str) -> String {
+        format!("{}/{}", PSEUDONYMS[0], identifier.to_lowercase())
+    }
+
+    fn summaries(&self, issues: Vec<IssueSummary>) -> Vec<IssueSummary> {
+        issues
+            .into_iter()
+            .map(|issue| {
+                let identifier = self.identifier(&issue.identifier);
+
+                IssueSummary {
+                    id: remap(&issue.id),
+                    title: issue.title.as_deref().map(|title| self.text(title)),
+                    state: self.state(issue.state),
+                    priority: issue.priority,
+                    assignee: issue.assignee.map(|user| self.user(user)),
+                    labels: issue
+                        .labels
+                        .into_iter()
+                        .map(|label| self.label(label))
+                        .collect(),
+                    url: self.issue_url(&identifier),
+                    branch_name: self.branch_name(&identifier),
+                    team_id: remap(&issue.team_id),
+                    updated_at: issue.updated_at,
+                    identifier,
+                }
+            })
+            .collect()
+    }
+
+    fn detail(&self, detail: IssueDetail) -> IssueDetail {
+        let identifier = self.identifier(&detail.identifier);
+
+        IssueDetail {
+            id: remap(&detail.id),
+            title: detail.title.as_deref().map(|title| self.text(title)),
+            description: detail
+                .description
+                .as_deref()
+                .map(|description| self.text(description)),
+            url: self.issue_url(&identifier),
+            state: self.state(detail.state),
+            priority: detail.priority,
+            assignee: detail.assignee.map(|user| self.user(user)),
+            labels: detail
+                .labels
+                .into_iter()
+                .map(|label| self.label(label))
+                .collect(),
+            comments: detail
+                .comments
+                .into_iter()
+                .map(|comment| Comment {
+                    id: remap(&comment.id),
+                    parent_id: comment.parent_id.as_ref().map(remap),
+                    author: comment.author.as_deref().map(|author| self.person(author)),
+                    is_mine: comment.is_mine,
+                    body: self.text(&comment.body),
+                    created_at: comment.created_at,
+                    reactions: reactions(comment.reactions),
+                })
+                .collect(),
+            reactions: reactions(detail.reactions),
+            branch_name: self.branch_name(&identifier),
+            team_id: remap(&detail.team_id),
+            updated_at: detail.updated_at,
+            identifier,
+        }
+    }
+
+    fn notification(&self, item: NotificationItem) -> NotificationItem {
+        NotificationItem {
+            title: self.text(&item.title),
+            issue_id: item.issue_id.as_ref().map(remap),
+            is_read: item.is_read,
+            grouping_key: remap(&item.grouping_key),
+        }
+    }
+
+    /// Replaces every word while leaving punctuation, whitespace, numbers
+    /// and code fences alone, which keeps markdown structure as it was.
+    fn text(&self, text: &str) -> String {
+        text.split_inclusive('\n')
+            .map(|line| {
+                if line.trim_start().starts_with("```") {
+                    line.to_string()
+                } else {
+                    self.line(line)
+                }
+            })
+            .collect()
+    }
+
+    fn line(&self, line: &str) -> String {
+        let mut out = String::with_capacity(line.len());
+        let mut rest = line;
+
+        while let Some(c) = rest.chars().next() {
+            if rest.starts_with("http://") || rest.starts_with("https://") {
+                let end = rest
+                    .find(|c: char| c.is_whitespace() || matches!(c, ')' | '>' | ']'))
+                    .unwrap_or(rest.len());
+                out.push_str(LINK);
+                rest = &rest[end..];
+            } else if c.is_alphanumeric() {
+                let end = rest
+                    .find(|c: char| !c.is_alphanumeric())
+                    .unwrap_or(rest.len());
+                out.push_str(&self.word(&rest[..end]));
+                rest = &rest[end..];
+            } else {
+                out.push(c);
+                rest = &rest[c.len_utf8()..];
+            }
+        }
+
+        out
+    }
+
+    fn word(&self, word: &str) -> String {
+        if word.chars().count() == 1 || word.chars().all(|c| c.is_ascii_digit()) {
+            return word.to_string();
+        }
+
+        if let Some(key) = self.team_keys.get(word) {
+            return key.clone();
+        }
+
+        if let Some(pseudonym) = self.people.get(&word.to_lowercase()) {
+            return pseudonym.clone();
+        }
+
+        let lorem = LOREM[pick(&word.to_lowercase(), LOREM.len())];
+
+        if word.chars().next().is_some_and(char::is_uppercase) {
+            capitalised(lorem)
+        } else {
+            lorem.to_string()
+        }
+    }
+}
+
+fn summaries(fixture: &Fixture) -> impl Iterator<Item = &IssueSummary> {
+    fixture
+        .issues
+        .iter()
+        .chain(fixture.saved_view_issues.values().flatten())
+}
+
+fn reactions(reactions: Vec<Reaction>) -> Vec<Reaction> {
+    reactions
+        .into_iter()
+        .map(|reaction| Reaction {
+            id: remap(&reaction.id),
+            ..reaction
+        })
+        .collect()
+}
+
+/// Hands out `pool` in order over the sorted originals, numbering any
+/// overflow, so the mapping depends only on which names occur.
+fn assign(originals: BTreeSet<String>, pool: &[&str]) -> HashMap<String, String> {
+    originals
+        .into_iter()
+        .enumerate()
+        .map(|(index, original)| {
+            let base = pool[index % pool.len()];
+            let replacement = match index / pool.len() {
+                0 => base.to_string(),
+                round => format!("{base}{}", round + 1),
+            };
+            (original, replacement)
+        })
+        .collect()
+}
+
+fn digest(raw: &str) -> [u8; 32] {
+    Sha256::digest(raw.as_bytes()).into()
+}
+
+fn pick(raw: &str, len: usize) -> usize {
+    let bytes = digest(raw);
+    u32::from_be_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]) as usize % len
+}
+
+trait Opaque {
+    fn raw(&self) -> &str;
+    fn stand_in(raw: String) -> Self;
+}
+
+macro_rules! opaque {
+    ($($name:ident),*) => {
+        $(impl Opaque for $name {
+            fn raw(&self) -> &str {
+                self.as_str()
+            }
+
+            fn stand_in(raw: String) -> Self {
+                $name::from_raw(raw)
+            }
+        })*
+    };
+}
+
+opaque!(IssueId, CommentId, TeamId, UserId, ViewId, StateId, ReactionId, LabelId);
+
+impl Opaque for String {
+    fn raw(&self) -> &str {
+        self
+    }
+
+    fn stand_in(raw: String) -> Self {
+        raw
+    }
+}
+
+/// A stand-in id derived from the original, so references stay joined
+/// without keeping the original value.
+fn remap<T: Opaque>(id: &T) -> T {
+    if id.raw().is_empty() {
+        return T::stand_in(String::new());
+    }
+
+    let hex: String = digest(id.raw())[..8]
+        .iter()
+        .map(|byte| format!("{byte:02x}"))
+        .collect();
+
+    T::stand_in(hex)
+}
+
+fn capitalised(word: &str) -> String {
+    let mut chars = word.chars();
+    match chars.next() {
+        Some(first) => first.to_uppercase().chain(chars).collect(),
+        None => String::new(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::api::model::IssueRef;
+
+    fn markdown_shape(text: &str) -> Vec<String> {
+        text.lines()
+            .map(|line| line.chars().take_while(|c| !c.is_alphabetic()).collect())
+            .collect()
+    }
+
+    #[test]
+    fn nothing_identifying_survives() {
+        let json = serde_json::to_string(&scrub(Fixture::sample())).unwrap();
+
+        for original in [
+            "Dan's Donuts",
+            "dans-donuts",
+            "danniiee",
+            "thermocouple",
+            "DAN2-7",
+            "Wood-fired",
+            "example.com/runbook",
+        ] {
+            assert!(!json.contains(original), "{original} leaked");
+        }
+    }
+
+    #[test]
+    fn relationships_survive() {
+        let scrubbed = scrub(Fixture::sample());
+        let detail = &scrubbed.details[0];
+
+        let comment_ids: Vec<_> = detail.comments.iter().map(|c| &c.id).collect();
+        assert!(detail
+            .comments
+            .iter()
+            .filter_map(|comment| comment.parent_id.as_ref())
+            .all(|parent| comment_ids.contains(&parent)));
+
+        let summary = scrubbed
+            .issues
+            .iter()
+            .find(|issue| issue.id == detail.id)
+            .expect("the detail's summary");
+        assert_eq!(summary.identifier, detail.identifier);
+        assert_eq!(summary.url, detail.url);
+        assert!(matches!(
+            IssueRef::parse(&detail.identifier),
+            IssueRef::Identifier(_)
+        ));
+
+        assert!(scrubbed
+            .notifications
+            .iter()
+            .filter_map(|item| item.issue_id.as_ref())
+            .all(|id| scrubbed.issues.iter().any(|issue| &issue.id == id)));
+        assert!(scrubbed
+            .saved_view_issues
+            .keys()
+            .all(|id| scrubbed.saved_views.iter().any(|view| &view.id == id)));
+        assert!(scrubbed.issues.iter().all(|issue| {
+            scrubbed.workflow_states.contains_key(&issue.team_id)
+                && scrubbed.team_members.contains_key(&issue.team_id)
+        }));
+    }
+
+    #[test]
+    fn state_names_are_scrubbed_alike_everywhere() {
+        let original = Fixture::sample();
+        let scrubbed = scrub(original.clone());
+
+        let names = |fixture: &Fixture| -> BTreeSet<String> {
+            fixture
+                .workflow_states
+                .values()
+                .flatten()
+                .map(|state| state.name.clone())
+                .collect()
+        };
+        assert!(names(&original).is_disjoint(&names(&scrubbed)));
+
+        for issue in &scrubbed.issues {
+            assert!(
+                scrubbed.workflow_states[&issue.team_id]
+                    .iter()
+                    .any(|state| state.name == issue.state.name),
+                "{} is not one of its team's states",
+                issue.state.name
+            );
+        }
+        assert_eq!(scrubbed.details[0].state, scrubbed.issues[0].state);
+    }
+
+    #[test]
+    fn people_keep_one_pseudonym_everywhere() {
+        let scrubbed = scrub(Fixture::sample());
+        let me = &scrubbed.viewer.display_name;
+
+        assert!(PSEUDONYMS.contains(&me.as_str()));
+        assert_eq!(
+            scrubbed.issues[0]
+                .assignee
+                .as_ref()
+                .map(|a| &a.display_name),
+            Some(me)
+        );
+        assert_eq!(scrubbed.details[0].comments[0].author.as_ref(), Some(me));
+        assert!(scrubbed.users.iter().any(|user| &user.display_name == me));
+    }
+
+    #[test]
+    fn markdown_structure_is_kept() {
+        let original = Fixture::sample();
+        let scrubbed = scrub(original.clone());
+
+        let before = original.details[0].description.as_deref().unwrap();
+        let after = scrubbed.details[0].description.as_deref().unwrap();
+
+        assert_eq!(markdown_shape(after), markdown_shape(before));
+        assert!(after.contains("](https://example.com)"));
+        assert!(after.contains("- [x] "));
+    }
+
+    #[test]
+    fn scrubbing_is_deterministic() {
+        let once = serde_json::to_value(scrub(Fixture::sample())).unwrap();
+        let twice = serde_json::to_value(scrub(Fixture::sample())).unwrap();
+
+        assert_eq!(once, twice);
+    }
+}
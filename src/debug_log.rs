@@ -0,0 +1,197 @@
+//! An opt-in debug log. Nothing is written unless `init` ran, which the
+//! binary does when `LINEAR_TUI_DEBUG` is set.
+
+use std::fs::{File, OpenOptions};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::sync::{Mutex, OnceLock};
+
+use crate::api::Timestamp;
+
+pub const FILE_NAME: &str = "debug.log";
+
+const MAX_BYTES: u64 = 1024 * 1024;
+const KEEP: usize = 3;
+const REDACTED: &str = "[redacted]";
+
+/// Secrets shorter than this are too likely to match ordinary text.
+const MIN_SECRET: usize = 8;
+
+static LOGGER: OnceLock<Mutex<Logger>> = OnceLock::new();
+
+/// Starts logging to `path`. Later calls keep the first file.
+pub fn init(path: PathBuf) -> std::io::Result<()> {
+    let logger = Logger::open(path, MAX_BYTES, KEEP)?;
+    let _ = LOGGER.set(Mutex::new(logger));
+
+    Ok(())
+}
+
+pub fn enabled() -> bool {
+    LOGGER.get().is_some()
+}
+
+/// Writes one line under `area`. The message is only built when logging
+/// is on, so callers can format freely.
+pub fn record(area: &str, message: impl FnOnce() -> String) {
+    if let Some(logger) = LOGGER.get() {
+        let line = message();
+        logger
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .write(area, &line);
+    }
+}
+
+/// Scrubs `secret` from every line written from now on.
+pub fn redact(secret: &str) {
+    if let Some(logger) = LOGGER.get() {
+        logger
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .redact(secret);
+    }
+}
+
+struct Logger {
+    path: PathBuf,
+    file: Option<File>,
+    written: u64,
+    max_bytes: u64,
+    keep: usize,
+    secrets: Vec<String>,
+}
+
+impl Logger {
+    fn open(path: PathBuf, max_bytes: u64, keep: usize) -> std::io::Result<Self> {
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+
+        let file = append(&path)?;
+        let written = file.metadata()?.len();
+
+        Ok(Self {
+            path,
+            file: Some(file),
+            written,
+            max_bytes,
+            keep,
+            secrets: Vec::new(),
+        })
+    }
+
+    fn redact(&mut self, secret: &str) {
+        if secret.len() >= MIN_SECRET && !self.secrets.iter().any(|known| known == secret) {
+            self.secrets.push(secret.to_string());
+        }
+    }
+
+    fn write(&mut self, area: &str, message: &str) {
+        let mut line = format!("{} {area} {message}\n", String::from(Timestamp::now()));
+        for secret in &self.secrets {
+            line = line.replace(secret.as_str(), REDACTED);
+        }
+
+        if self.written + line.len() as u64 > self.max_bytes {
+            self.rotate();
+        }
+
+        // A log that cannot be written is dropped rather than interrupting
+        // the session it is meant to describe.
+        let Some(file) = &mut self.file else {
+            return;
+        };
+        match file.write_all(line.as_bytes()) {
+            Ok(()) => self.written += line.len() as u64,
+            Err(_) => self.file = None,
+        }
+    }
+
+    /// Shifts `debug.log` to `debug.log.1`, `.1` to `.2` and so on, dropping
+    /// whatever falls past `keep`.
+    fn rotate(&mut self) {
+        self.file = None;
+
+        for index in (1..self.keep).rev() {
+            let _ = std::fs::rename(rotated(&self.path, index), rotated(&self.path, index + 1));
+        }
+        if self.keep > 0 {
+            let _ = std::fs::rename(&self.path, rotated(&self.path, 1));
+        } else {
+            let _ = std::fs::remove_file(&self.path);
+        }
+
+        self.file = std::fs::File::create(&self.path).ok();
+        self.written = 0;
+    }
+}
+
+fn append(path: &Path) -> std::io::Result<File> {
+    OpenOptions::new().create(true).append(true).open(path)
+}
+
+fn rotated(path: &Path, index: usize) -> PathBuf {
+    let mut name = path.as_os_str().to_owned();
+    name.push(format!(".{index}"));
+    PathBuf::from(name)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn read(path: &Path) -> String {
+        std::fs::read_to_string(path).unwrap_or_default()
+    }
+
+    #[test]
+    fn registered_secrets_never_reach_the_file() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join(FILE_NAME);
+        let mut logger = Logger::open(path.clone(), MAX_BYTES, KEEP).unwrap();
+
+        logger.redact("lin_api_0123456789");
+        logger.redact("short");
+        logger.write("api", "Authorization: lin_api_0123456789 short");
+
+        let written = read(&path);
+        assert!(
+            written.contains("api Authorization: [redacted] short"),
+            "{written}"
+        );
+        assert!(!written.contains("0123456789"));
+    }
+
+    #[test]
+    fn a_full_log_rotates_and_keeps_only_the_newest_files() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join(FILE_NAME);
+        let mut logger = Logger::open(path.clone(), 64, 2).unwrap();
+
+        for index in 0..8 {
+            logger.write("cache", &format!("line {index} {}", "x".repeat(20)));
+        }
+
+        assert!(read(&path).contains("line 7"));
+        assert!(read(&rotated(&path, 1)).contains("line 6"));
+        assert!(read(&rotated(&path, 2)).contains("line 5"));
+        assert!(!rotated(&path, 3).exists());
+    }
+
+    #[test]
+    fn reopening_appends_to_the_existing_log() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join(FILE_NAME);
+
+        Logger::open(path.clone(), MAX_BYTES, KEEP)
+            .unwrap()
+            .write("run", "first");
+        Logger::open(path.clone(), MAX_BYTES, KEEP)
+            .unwrap()
+            .write("run", "second");
+
+        let written = read(&path);
+        assert!(written.contains("first") && written.contains("second"));
+    }
+}
@@ -1,4 +1,5 @@
 pub mod api;
+pub mod debug_log;
 pub mod oauth;
 pub mod store;
 pub mod tui;
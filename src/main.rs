@@ -1,10 +1,12 @@
 use std::path::PathBuf;
 use std::sync::Arc;
 
-use anyhow::{anyhow, Result};
+use anyhow::{anyhow, Context, Result};
 use clap::{Parser, Subcommand};
 
-use linear_tui::api::{self, fixture::FixtureClient, Client, Credential, IssueRef, LinearApi};
+use linear_tui::api::{
+    self, fixture::FixtureClient, Client, ConnectionSettings, Credential, IssueRef, LinearApi, Tape,
+};
 use linear_tui::store::StateDir;
 use linear_tui::tui::render::theme::ColourMode;
 use linear_tui::tui::{
@@ -12,6 +14,9 @@ use linear_tui::tui::{
     app::App,
     feed::{Feed, FeedKey},
     focus::{DetailFocus, LeftPanel, Origin},
+    journal::{self, Journal, Recorded, Replay},
+    overlay::EditorKeys,
+    script::Step,
     view::ViewKind,
 };
 
@@ -29,6 +34,9 @@ struct Args {
 enum Command {
     Render(RenderArgs),
     Record(RecordArgs),
+    Scrub(ScrubArgs),
+    Demo(DemoArgs),
+    Replay(ReplayArgs),
 }
 
 #[derive(Parser)]
@@ -47,12 +55,72 @@ struct RenderArgs {
 
     #[arg(long, default_value_t = 32)]
     height: u16,
+
+    /// Keys to press before rendering, such as "j j enter e s". `snap`
+    /// prints the frame so far, followed by a form feed line.
+    #[arg(long, conflicts_with = "script")]
+    keys: Option<String>,
+
+    /// A file holding a key script, one or more keys per line.
+    #[arg(long)]
+    script: Option<PathBuf>,
+
+    /// Print frames with their styles rather than as plain text.
+    #[arg(long)]
+    styled: bool,
 }
 
 #[derive(Parser)]
 struct RecordArgs {
     #[arg(long, default_value = "fixtures/recorded.json")]
     out: PathBuf,
+
+    /// How many assigned issues to capture in full, comments included.
+    #[arg(long, default_value_t = 5)]
+    details: usize,
+
+    /// Anonymise the fixture before writing it, as `scrub` does.
+    #[arg(long)]
+    scrub: bool,
+}
+
+/// Anonymises a recorded fixture so it can be attached to a public report.
+#[derive(Parser)]
+struct ScrubArgs {
+    fixture: PathBuf,
+
+    /// Where to write the scrubbed copy; defaults to `<name>.scrubbed.json`
+    /// beside the input, which is never overwritten.
+    #[arg(long)]
+    out: Option<PathBuf>,
+}
+
+/// Runs the full TUI against a fixture, with no account or token needed.
+#[derive(Parser)]
+struct DemoArgs {
+    /// The fixture to serve; defaults to the built-in sample.
+    fixture: Option<PathBuf>,
+
+    /// Save edits back to the fixture file as they are made.
+    #[arg(long, requires = "fixture")]
+    write_back: bool,
+}
+
+/// Replays a journal written under LINEAR_TUI_JOURNAL into a fresh app.
+#[derive(Parser)]
+struct ReplayArgs {
+    journal: PathBuf,
+
+    /// Print a frame after every keypress and message, not just the last.
+    #[arg(long)]
+    step: bool,
+
+    /// Stop after this many entries.
+    #[arg(long)]
+    until: Option<usize>,
+
+    #[arg(long)]
+    styled: bool,
 }
 
 #[tokio::main]
@@ -64,6 +132,9 @@ async fn main() -> Result<()> {
         Some(Command::Record(record_args)) => {
             record(&resolve_api_key(&args.api_key)?, record_args).await
         }
+        Some(Command::Scrub(scrub_args)) => scrub(scrub_args),
+        Some(Command::Demo(demo_args)) => run_demo(demo_args).await,
+        Some(Command::Replay(replay_args)) => replay(replay_args),
         None => run_tui(bootstrap_credential(&args.api_key)).await,
     }
 }
@@ -81,8 +152,19 @@ fn bootstrap_credential(flag: &Option<String>) -> Option<Credential> {
 }
 
 async fn run_tui(bootstrap: Option<Credential>) -> Result<()> {
-    let make_client: tui::run::ClientFactory =
-        Arc::new(|credential| Arc::new(Client::new(credential)) as Arc<dyn LinearApi>);
+    let env = ConnectionSettings::from_env()?;
+    // Opened once: every client the session builds records onto this tape.
+    let tape = env
+        .cassette
+        .as_ref()
+        .map(Tape::open)
+        .transpose()?
+        .map(Arc::new);
+    let make_client: tui::run::ClientFactory = Arc::new(move |credential, account| {
+        let settings = env.clone().over(account);
+        let client = Client::taped(credential, &settings, tape.clone())?;
+        Ok(Arc::new(client) as Arc<dyn LinearApi>)
+    });
 
     tui::render::theme::init(colour_mode());
 
@@ -92,22 +174,99 @@ async fn run_tui(bootstrap: Option<Credential>) -> Result<()> {
 
     let path = host_state_dir().ok_or_else(|| anyhow!("Could not resolve a state directory"))?;
     migrate_legacy_state_dir(&path);
+    debug_log(&path)?;
 
-    let mut terminal = ratatui::init();
+    let journal = journal()?;
+    let mut terminal = tui::terminal::init();
+    tui::crash::install(path.clone());
     let mut app = App::new();
+    app.ui.editor_keys = editor_keys();
     let result = tui::run(
         &mut terminal,
         &mut app,
         bootstrap,
         make_client,
         StateDir::at(path),
+        journal,
     )
     .await;
 
-    ratatui::restore();
+    tui::terminal::restore();
     result
 }
 
+async fn run_demo(args: DemoArgs) -> Result<()> {
+    let api: Arc<dyn LinearApi> = match &args.fixture {
+        Some(path) if args.write_back => Arc::new(FixtureClient::writing_back(path)?),
+        Some(path) => Arc::new(FixtureClient::from_path(path)?),
+        None => Arc::new(FixtureClient::sample()),
+    };
+
+    tui::render::theme::init(colour_mode());
+
+    if let Some(overrides) = theme_overrides()? {
+        tui::render::theme::init_overrides(overrides);
+    }
+
+    // Kept apart from the real state so a demo never touches saved accounts.
+    let path = host_state_dir()
+        .ok_or_else(|| anyhow!("Could not resolve a state directory"))?
+        .join("demo");
+    debug_log(&path)?;
+
+    let journal = journal()?;
+    let mut terminal = tui::terminal::init();
+    tui::crash::install(path.clone());
+    let mut app = App::new();
+    app.ui.editor_keys = editor_keys();
+    let result = tui::run_fixture(&mut terminal, &mut app, api, StateDir::at(path), journal).await;
+
+    tui::terminal::restore();
+    result
+}
+
+/// Turned on by `LINEAR_TUI_DEBUG`; the log sits beside the rest of the
+/// state so it is easy to find and attach to a bug report.
+fn debug_log(state: &std::path::Path) -> Result<()> {
+    if std::env::var_os("LINEAR_TUI_DEBUG").is_none_or(|value| value.is_empty()) {
+        return Ok(());
+    }
+
+    let path = state.join(linear_tui::debug_log::FILE_NAME);
+    linear_tui::debug_log::init(path.clone())
+        .with_context(|| format!("opening debug log {}", path.display()))
+}
+
+fn journal() -> Result<Option<Journal>> {
+    std::env::var_os("LINEAR_TUI_JOURNAL")
+        .map(|path| Journal::create(&PathBuf::from(path)))
+        .transpose()
+}
+
+fn replay(args: ReplayArgs) -> Result<()> {
+    tui::render::theme::init(ColourMode::Ansi);
+
+    let mut replay = Replay::default();
+
+    for (index, entry) in journal::load(&args.journal)?.into_iter().enumerate() {
+        if args.until.is_some_and(|until| index >= until) {
+            break;
+        }
+
+        let shown = !matches!(entry.event, Recorded::Tick { .. } | Recorded::Idle { .. });
+        let summary = entry.event.summary();
+        let redrew = replay.apply(entry.event);
+
+        if args.step && shown && redrew {
+            println!("── {} {summary}", index + 1);
+            println!("{}", replay.frame(args.styled));
+        }
+    }
+
+    print!("{}", replay.frame(args.styled));
+    Ok(())
+}
+
 fn host_state_dir() -> Option<PathBuf> {
     let home = std::env::var_os("HOME").map(PathBuf::from)?;
 
@@ -156,9 +315,15 @@ fn colour_mode() -> ColourMode {
     }
 }
 
-fn theme_overrides() -> Result<Option<linear_tui::tui::render::theme::Overrides>> {
-    use anyhow::Context;
+/// `LINEAR_TUI_EDITOR_KEYS=vi` gives the comment editor vi's modes.
+fn editor_keys() -> EditorKeys {
+    match std::env::var("LINEAR_TUI_EDITOR_KEYS").as_deref() {
+        Ok("vi") => EditorKeys::Vi,
+        _ => EditorKeys::Plain,
+    }
+}
 
+fn theme_overrides() -> Result<Option<linear_tui::tui::render::theme::Overrides>> {
     let Some(path) = std::env::var_os("LINEAR_TUI_THEME").map(PathBuf::from) else {
         return Ok(None);
     };
@@ -181,13 +346,20 @@ async fn headless_render(args: RenderArgs) -> Result<()> {
     };
 
     let mut app = App::new();
+    app.ui.editor_keys = editor_keys();
     if let Ok(session) = api.session().await {
+        app.session
+            .set_accounts(vec![tui::run::fixture_account(session.clone())]);
+        let _ = app.session.activate(&session.org_url_key);
         app.workspace.session.set(session, app.now);
     }
 
     let index = view_index(&args.view);
     app.ui.view_state.select(Some(index));
 
+    let startup = tui::update::initial_commands(&mut app);
+    tui::script::settle(&mut app, api.as_ref(), startup.into()).await;
+
     match &app.active_view().kind {
         ViewKind::Issues(filter) => {
             let key = FeedKey::Issues(filter.clone());
@@ -211,11 +383,43 @@ async fn headless_render(args: RenderArgs) -> Result<()> {
         }
     }
 
-    let output = tui::render_to_string(&mut app, args.width, args.height);
-    print!("{output}");
+    for step in key_script(&args)? {
+        match step {
+            Step::Key(key) => {
+                let commands = tui::update::handle_key(&mut app, key);
+                tui::script::settle(&mut app, api.as_ref(), commands).await;
+            }
+            Step::Snap => println!("{}{}", frame(&mut app, &args), tui::script::FRAME_SEPARATOR),
+        }
+
+        if app.should_quit {
+            return Ok(());
+        }
+    }
+
+    print!("{}", frame(&mut app, &args));
     Ok(())
 }
 
+fn key_script(args: &RenderArgs) -> Result<Vec<Step>> {
+    let script = match (&args.keys, &args.script) {
+        (Some(keys), _) => keys.clone(),
+        (None, Some(path)) => std::fs::read_to_string(path)
+            .map_err(|error| anyhow!("Could not read key script {}: {error}", path.display()))?,
+        (None, None) => return Ok(Vec::new()),
+    };
+
+    tui::script::parse(&script).map_err(|error| anyhow!("Invalid key script: {error}"))
+}
+
+fn frame(app: &mut App, args: &RenderArgs) -> String {
+    if args.styled {
+        tui::render_styled_to_string(app, args.width, args.height)
+    } else {
+        tui::render_to_string(app, args.width, args.height)
+    }
+}
+
 fn view_index(name: &str) -> usize {
     match name {
         "progress" | "in-progress" => 1,
@@ -224,26 +428,55 @@ fn view_index(name: &str) -> usize {
     }
 }
 
+/// How many issue details `record` looks up at once.
+const RECORD_LOOKUPS: usize = 4;
+
 async fn record(api_key: &str, args: RecordArgs) -> Result<()> {
-    use api::fixture::Fixture;
+    use api::fixture::{Fixture, FIXTURE_VERSION};
     use api::IssueFilter;
+    use futures::{StreamExt, TryStreamExt};
 
-    let client = Client::new(Credential::PersonalKey(api_key.to_string()));
+    let client = Client::configured(
+        Credential::PersonalKey(api_key.to_string()),
+        &ConnectionSettings::from_env()?,
+    )?;
     let session = client.session().await?;
-    let issues = client
-        .issues(&IssueFilter::assigned_to_me(), None)
-        .await?
-        .items;
+
+    // Enough pages for the details asked for, if there are that many.
+    let filter = IssueFilter::assigned_to_me();
+    let mut page = client.issues(&filter, None).await?;
+    let mut issues = std::mem::take(&mut page.items);
+    while issues.len() < args.details {
+        let Some(cursor) = page.next else {
+            break;
+        };
+        page = client.issues(&filter, Some(&cursor)).await?;
+        issues.append(&mut page.items);
+    }
 
     let notifications = client.notifications(None).await?.items;
     let saved_views = client.custom_views().await?;
 
-    let mut details = Vec::new();
-    for issue in issues.iter().take(5) {
-        if let Some(detail) = client.issue_detail(&issue.id.clone().into()).await? {
-            details.push(detail);
-        }
-    }
+    // A few at a time, rather than every lookup at once.
+    let mut details: Vec<_> = futures::stream::iter(issues.iter().take(args.details).enumerate())
+        .map(|(index, issue)| {
+            let target = IssueRef::Id(issue.id.clone());
+            let client = &client;
+            async move {
+                client
+                    .issue_detail(&target)
+                    .await
+                    .map(|detail| (index, detail))
+            }
+        })
+        .buffer_unordered(RECORD_LOOKUPS)
+        .try_collect()
+        .await?;
+    details.sort_by_key(|(index, _)| *index);
+    let details: Vec<_> = details
+        .into_iter()
+        .filter_map(|(_, detail)| detail)
+        .collect();
 
     let mut saved_view_issues = std::collections::HashMap::new();
     for view in &saved_views {
@@ -251,7 +484,37 @@ async fn record(api_key: &str, args: RecordArgs) -> Result<()> {
         saved_view_issues.insert(view.id.clone(), page.items);
     }
 
+    let teams = client.teams().await?;
+    let mut workflow_states = std::collections::HashMap::new();
+    let mut team_members = std::collections::HashMap::new();
+    let mut users: Vec<api::User> = Vec::new();
+    for team in &teams {
+        workflow_states.insert(team.id.clone(), client.workflow_states(&team.id).await?);
+
+        let members = client.team_members(&team.id).await?;
+        for member in &members {
+            if !users.iter().any(|user| user.id == member.id) {
+                users.push(member.clone());
+            }
+        }
+        team_members.insert(team.id.clone(), members);
+    }
+
+    // The label picker searches as it goes, but a fixture has to hold them
+    // all up front.
+    let mut labels = Vec::new();
+    let mut after = None;
+    loop {
+        let page = client.search_labels("", after.as_ref()).await?;
+        labels.extend(page.items);
+        match page.next {
+            Some(cursor) => after = Some(cursor),
+            None => break,
+        }
+    }
+
     let fixture = Fixture {
+        version: FIXTURE_VERSION,
         viewer: session.user,
         org_name: session.org_name,
         org_url_key: session.org_url_key,
@@ -260,14 +523,46 @@ async fn record(api_key: &str, args: RecordArgs) -> Result<()> {
         issues,
         saved_view_issues,
         details,
+        teams,
+        workflow_states,
+        team_members,
+        users,
+        labels,
+    };
+
+    let fixture = if args.scrub {
+        api::scrub::scrub(fixture)
+    } else {
+        fixture
     };
 
-    if let Some(parent) = args.out.parent() {
+    write_fixture(&args.out, &fixture)
+}
+
+fn scrub(args: ScrubArgs) -> Result<()> {
+    let fixture = api::fixture::Fixture::load(&args.fixture)?;
+    let out = args
+        .out
+        .unwrap_or_else(|| args.fixture.with_extension("scrubbed.json"));
+
+    let input = std::fs::canonicalize(&args.fixture)?;
+    if std::fs::canonicalize(&out).is_ok_and(|out| out == input) {
+        return Err(anyhow!(
+            "Refusing to scrub {} over itself; pass a different --out",
+            args.fixture.display()
+        ));
+    }
+
+    write_fixture(&out, &api::scrub::scrub(fixture))
+}
+
+fn write_fixture(out: &std::path::Path, fixture: &api::fixture::Fixture) -> Result<()> {
+    if let Some(parent) = out.parent() {
         std::fs::create_dir_all(parent)?;
     }
 
-    std::fs::write(&args.out, serde_json::to_string_pretty(&fixture)?)?;
-    eprintln!("Wrote fixture to {}", args.out.display());
+    std::fs::write(out, serde_json::to_string_pretty(fixture)?)?;
+    eprintln!("Wrote fixture to {}", out.display());
 
     Ok(())
 }
@@ -7,7 +7,7 @@ use sha2::{Digest, Sha256};
 use tokio::io::{AsyncReadExt, AsyncWriteExt};
 use tokio::net::{TcpListener, TcpStream};
 
-use crate::api::{Credential, OAuthToken};
+use crate::api::{ConnectionSettings, Credential, OAuthToken};
 use crate::tui::platform::Platform;
 
 const CLIENT_ID: &str = "bc22accf2f8a13d57c8096ce8f332926";
@@ -26,7 +26,9 @@ const RESPONSE: &str = concat!(
     "</body></html>",
 );
 
-pub async fn login(platform: Platform) -> Result<Credential> {
+/// Signs in through the browser. The token exchange goes out the way API
+/// requests do, through any proxy and extra CA certs in `settings`.
+pub async fn login(platform: Platform, settings: &ConnectionSettings) -> Result<Credential> {
     let listener = TcpListener::bind(("127.0.0.1", REDIRECT_PORT))
         .await
         .with_context(|| {
@@ -46,12 +48,12 @@ pub async fn login(platform: Platform) -> Result<Credential> {
         .await
         .context("timed out waiting for the browser sign-in")??;
 
-    let token = exchange(&code, &verifier).await?;
+    let token = exchange(&code, &verifier, settings).await?;
 
     Ok(Credential::OAuth(token))
 }
 
-pub async fn refresh(refresh_token: &str) -> Result<OAuthToken> {
+pub async fn refresh(refresh_token: &str, settings: &ConnectionSettings) -> Result<OAuthToken> {
     let request = async {
         let body = form_body(&[
             ("grant_type", "refresh_token"),
@@ -59,7 +61,8 @@ pub async fn refresh(refresh_token: &str) -> Result<OAuthToken> {
             ("client_id", CLIENT_ID),
         ])?;
 
-        let response = reqwest::Client::new()
+        let response = settings
+            .http_client()?
             .post(TOKEN_ENDPOINT)
             .header("Content-Type", "application/x-www-form-urlencoded")
             .body(body)
@@ -158,7 +161,7 @@ impl TokenResponse {
     }
 }
 
-async fn exchange(code: &str, verifier: &str) -> Result<OAuthToken> {
+async fn exchange(code: &str, verifier: &str, settings: &ConnectionSettings) -> Result<OAuthToken> {
     let body = form_body(&[
         ("grant_type", "authorization_code"),
         ("code", code),
@@ -167,7 +170,8 @@ async fn exchange(code: &str, verifier: &str) -> Result<OAuthToken> {
         ("code_verifier", verifier),
     ])?;
 
-    let response = reqwest::Client::new()
+    let response = settings
+        .http_client()?
         .post(TOKEN_ENDPOINT)
         .header("Content-Type", "application/x-www-form-urlencoded")
         .body(body)
@@ -259,4 +263,16 @@ mod tests {
             "E9Melhoa2OwvFrEMTJguCHaoeK1t8URWbuGJSstw-cM"
         );
     }
+
+    #[tokio::test]
+    async fn token_requests_are_built_from_the_connection_settings() {
+        let settings = ConnectionSettings {
+            ca_certs: vec!["/nonexistent/corp-ca.pem".into()],
+            ..ConnectionSettings::default()
+        };
+
+        let error = refresh("r1", &settings).await.expect_err("no such CA file");
+
+        assert!(error.to_string().contains("corp-ca.pem"), "{error}");
+    }
 }
@@ -2,8 +2,10 @@ use std::path::{Path, PathBuf};
 
 use serde::{Deserialize, Serialize};
 
-use crate::api::{Credential, IssueSummary, NotificationItem, Timestamp};
+use crate::api::{ConnectionSettings, Credential, IssueSummary, NotificationItem, Timestamp};
+use crate::tui::drafts::Draft;
 use crate::tui::feed::{Feed, FeedKey, FeedStore, HasId, STALE_HORIZON};
+use crate::tui::templates::Template;
 
 const FEEDS_VERSION: u32 = 2;
 const FEED_ITEM_CAP: usize = 100;
@@ -14,6 +16,8 @@ pub struct Account {
     pub workspace_key: String,
     pub org_name: String,
     pub credential: Credential,
+    #[serde(default, skip_serializing_if = "ConnectionSettings::is_empty")]
+    pub connection: ConnectionSettings,
 }
 
 impl Account {
@@ -55,6 +59,18 @@ impl StateDir {
     fn recent(&self, namespace: &str) -> PathBuf {
         self.0.join(format!("recently-viewed-{namespace}.json"))
     }
+
+    fn drafts(&self, namespace: &str) -> PathBuf {
+        self.0.join(format!("drafts-{namespace}.json"))
+    }
+
+    fn recent_emoji(&self) -> PathBuf {
+        self.0.join("recent-emoji.json")
+    }
+
+    pub fn templates(&self) -> PathBuf {
+        self.0.join("templates")
+    }
 }
 
 pub fn load_accounts(dir: &StateDir) -> Accounts {
@@ -221,6 +237,60 @@ pub fn save_recent(dir: &StateDir, namespace: &str, issues: &[IssueSummary]) {
     }
 }
 
+pub fn load_drafts(dir: &StateDir, namespace: &str) -> Vec<Draft> {
+    std::fs::read_to_string(dir.drafts(namespace))
+        .ok()
+        .and_then(|raw| serde_json::from_str(&raw).ok())
+        .unwrap_or_default()
+}
+
+pub fn save_drafts(dir: &StateDir, namespace: &str, drafts: &[Draft]) {
+    if let Ok(json) = serde_json::to_string(drafts) {
+        write_atomic(&dir.drafts(namespace), &json);
+    }
+}
+
+/// Emoji follow the person rather than the workspace, so there is one
+/// list for every account.
+pub fn load_recent_emoji(dir: &StateDir) -> Vec<String> {
+    std::fs::read_to_string(dir.recent_emoji())
+        .ok()
+        .and_then(|raw| serde_json::from_str(&raw).ok())
+        .unwrap_or_default()
+}
+
+pub fn save_recent_emoji(dir: &StateDir, names: &[String]) {
+    if let Ok(json) = serde_json::to_string(names) {
+        write_atomic(&dir.recent_emoji(), &json);
+    }
+}
+
+/// Every `.md` file in the templates directory, named by its file stem
+/// and sorted by name.
+pub fn load_templates(dir: &StateDir) -> Vec<Template> {
+    let Ok(entries) = std::fs::read_dir(dir.templates()) else {
+        return Vec::new();
+    };
+
+    let mut templates: Vec<Template> = entries
+        .flatten()
+        .map(|entry| entry.path())
+        .filter(|path| path.extension().is_some_and(|ext| ext == "md"))
+        .filter_map(|path| {
+            let name = path.file_stem()?.to_string_lossy().into_owned();
+            let body = std::fs::read_to_string(&path).ok()?;
+
+            Some(Template {
+                name,
+                body: body.trim_end().to_string(),
+            })
+        })
+        .collect();
+
+    templates.sort_by(|a, b| a.name.cmp(&b.name));
+    templates
+}
+
 fn fresh_enough(now: Timestamp, fetched_at: Timestamp) -> bool {
     now.seconds_since(fetched_at) <= STALE_HORIZON
 }
@@ -316,6 +386,7 @@ mod tests {
             workspace_key: "acme".into(),
             org_name: "Acme".into(),
             credential: Credential::PersonalKey("k".into()),
+            connection: ConnectionSettings::default(),
         }];
         save_accounts(&state, &stored, Some("acme"));
 
@@ -325,6 +396,43 @@ mod tests {
         assert_eq!(loaded.active.as_deref(), Some("acme"));
     }
 
+    #[test]
+    fn accounts_without_connection_settings_still_load() {
+        let dir = tempfile::tempdir().unwrap();
+        let state = StateDir::at(dir.path().into());
+        let legacy = r#"{"version":1,"active":"acme","accounts":[{"workspace_key":"acme",
+            "org_name":"Acme","credential":{"PersonalKey":"k"}}]}"#;
+        std::fs::write(state.accounts(), legacy).unwrap();
+
+        let loaded = load_accounts(&state);
+
+        assert_eq!(loaded.accounts.len(), 1);
+        assert!(loaded.accounts[0].connection.is_empty());
+    }
+
+    #[test]
+    fn connection_settings_round_trip_per_account() {
+        let dir = tempfile::tempdir().unwrap();
+        let state = StateDir::at(dir.path().into());
+        let stored = vec![Account {
+            workspace_key: "acme".into(),
+            org_name: "Acme".into(),
+            credential: Credential::PersonalKey("k".into()),
+            connection: ConnectionSettings {
+                endpoint: Some("http://127.0.0.1:4000/graphql".into()),
+                proxy: Some("http://proxy.corp:3128".into()),
+                ca_certs: vec!["/etc/ssl/corp.pem".into()],
+                timeout_secs: Some(30),
+                connect_timeout_secs: Some(5),
+                cassette: None,
+            },
+        }];
+
+        save_accounts(&state, &stored, Some("acme"));
+
+        assert_eq!(load_accounts(&state).accounts, stored);
+    }
+
     #[test]
     fn a_cache_written_before_team_scoped_feeds_still_loads() {
         let legacy = r#"{"version":2,"issues":[[{"Issues":{"assigned_to_me":true,
@@ -345,6 +453,61 @@ mod tests {
         }
     }
 
+    #[test]
+    fn drafts_are_kept_per_namespace() {
+        let dir = tempfile::tempdir().unwrap();
+        let state = StateDir::at(dir.path().into());
+        let drafts = vec![Draft {
+            issue_id: IssueId::from_raw("i1"),
+            identifier: "DAN-1".into(),
+            team_id: TeamId::from_raw("t1"),
+            compose: crate::tui::overlay::Compose::Comment,
+            body: "half a thought".into(),
+            seen: None,
+            saved_at: Timestamp::from_epoch(1_000),
+        }];
+
+        save_drafts(&state, "ns-a", &drafts);
+
+        assert_eq!(load_drafts(&state, "ns-a"), drafts);
+        assert!(load_drafts(&state, "ns-b").is_empty());
+    }
+
+    #[test]
+    fn recent_emoji_round_trip() {
+        let dir = tempfile::tempdir().unwrap();
+        let state = StateDir::at(dir.path().into());
+        assert!(load_recent_emoji(&state).is_empty());
+
+        save_recent_emoji(&state, &["rocket".into(), "tada".into()]);
+
+        assert_eq!(load_recent_emoji(&state), ["rocket", "tada"]);
+    }
+
+    #[test]
+    fn templates_are_the_markdown_files_in_their_directory() {
+        let dir = tempfile::tempdir().unwrap();
+        let state = StateDir::at(dir.path().into());
+        assert!(load_templates(&state).is_empty());
+
+        std::fs::create_dir(state.templates()).unwrap();
+        std::fs::write(state.templates().join("released.md"), "Out in {{date}}.\n").unwrap();
+        std::fs::write(state.templates().join("needs repro.md"), "Steps?").unwrap();
+        std::fs::write(state.templates().join("notes.txt"), "not a template").unwrap();
+
+        let names: Vec<(String, String)> = load_templates(&state)
+            .into_iter()
+            .map(|template| (template.name, template.body))
+            .collect();
+        assert_eq!(
+            names,
+            [
+                ("needs repro".to_string(), "Steps?".to_string()),
+                ("released".to_string(), "Out in {{date}}.".to_string()),
+            ]
+        );
+    }
+
     #[test]
     fn namespace_is_stable_and_per_key() {
         assert_eq!(namespace("key-a"), namespace("key-a"));
@@ -1,10 +1,12 @@
 use crossterm::event::KeyCode;
-use KeyCode::{BackTab, Backspace, Char, Down, Enter, Esc, Left, PageDown, PageUp, Right, Tab, Up};
+use KeyCode::{
+    BackTab, Backspace, Char, Delete, Down, Enter, Esc, Left, PageDown, PageUp, Right, Tab, Up,
+};
 
 use super::keymap::{Binding, Keymap};
 use super::keys::{
-    Action, ConfirmInput, EditorInput, InputInput, LabelsInput, MenuInput, PickerInput,
-    ReactionInput, WorkspacesInput,
+    Action, ConfirmInput, ConflictInput, DraftsInput, EditorInput, EmojiInput, InputInput,
+    LabelsInput, LinksInput, MenuInput, PickerInput, ReactionInput, WorkspacesInput,
 };
 
 pub const BROWSE: Keymap<Action> = Keymap {
@@ -124,6 +126,11 @@ pub const BROWSE: Keymap<Action> = Keymap {
             keys: &[Char('w')],
             label: "workspaces",
         },
+        Binding {
+            action: Action::RunCommand,
+            keys: &[Char('!')],
+            label: "command",
+        },
         Binding {
             action: Action::Help,
             keys: &[Char('?')],
@@ -159,6 +166,11 @@ pub const GO_GROUP: Keymap<Action> = Keymap {
             keys: &[Char('s')],
             label: "search",
         },
+        Binding {
+            action: Action::Drafts,
+            keys: &[Char('d')],
+            label: "drafts",
+        },
     ],
 };
 
@@ -184,6 +196,11 @@ pub const EDIT_GROUP: Keymap<Action> = Keymap {
             keys: &[Char('l')],
             label: "labels",
         },
+        Binding {
+            action: Action::EditDescription,
+            keys: &[Char('d')],
+            label: "description",
+        },
     ],
 };
 
@@ -219,6 +236,11 @@ pub const DETAIL_KEYS: Keymap<Action> = Keymap {
             keys: &[Char('+')],
             label: "react",
         },
+        Binding {
+            action: Action::Links,
+            keys: &[Char('f')],
+            label: "links",
+        },
         Binding {
             action: Action::HistoryForward,
             keys: &[Tab],
@@ -254,6 +276,11 @@ pub const COMMENTS_KEYS: Keymap<Action> = Keymap {
             keys: &[Char('+')],
             label: "react",
         },
+        Binding {
+            action: Action::Links,
+            keys: &[Char('f')],
+            label: "links",
+        },
         Binding {
             action: Action::HistoryForward,
             keys: &[Tab],
@@ -295,9 +322,9 @@ pub const REACTIONS: Keymap<ReactionInput> = Keymap {
             label: "toggle",
         },
         Binding {
-            action: ReactionInput::Custom,
+            action: ReactionInput::More,
             keys: &[Char('c')],
-            label: "custom",
+            label: "more",
         },
         Binding {
             action: ReactionInput::Cancel,
@@ -332,6 +359,81 @@ pub const WORKSPACES: Keymap<WorkspacesInput> = Keymap {
     ],
 };
 
+pub const DRAFTS: Keymap<DraftsInput> = Keymap {
+    bindings: &[
+        Binding {
+            action: DraftsInput::Next,
+            keys: &[Char('j'), Down],
+            label: "move",
+        },
+        Binding {
+            action: DraftsInput::Prev,
+            keys: &[Char('k'), Up],
+            label: "move",
+        },
+        Binding {
+            action: DraftsInput::Resume,
+            keys: &[Enter],
+            label: "resume",
+        },
+        Binding {
+            action: DraftsInput::Discard,
+            keys: &[Char('d'), Delete],
+            label: "discard",
+        },
+        Binding {
+            action: DraftsInput::Cancel,
+            keys: &[Esc],
+            label: "cancel",
+        },
+    ],
+};
+
+pub const EMOJI: Keymap<EmojiInput> = Keymap {
+    bindings: &[
+        Binding {
+            action: EmojiInput::Next,
+            keys: &[Down],
+            label: "move",
+        },
+        Binding {
+            action: EmojiInput::Prev,
+            keys: &[Up],
+            label: "move",
+        },
+        Binding {
+            action: EmojiInput::React,
+            keys: &[Enter],
+            label: "react",
+        },
+        Binding {
+            action: EmojiInput::Erase,
+            keys: &[Backspace],
+            label: "erase",
+        },
+        Binding {
+            action: EmojiInput::Cancel,
+            keys: &[Esc],
+            label: "cancel",
+        },
+    ],
+};
+
+pub const LINKS: Keymap<LinksInput> = Keymap {
+    bindings: &[
+        Binding {
+            action: LinksInput::Erase,
+            keys: &[Backspace],
+            label: "erase",
+        },
+        Binding {
+            action: LinksInput::Cancel,
+            keys: &[Esc],
+            label: "cancel",
+        },
+    ],
+};
+
 pub const LABELS: Keymap<LabelsInput> = Keymap {
     bindings: &[
         Binding {
@@ -445,6 +547,26 @@ pub const CONFIRM: Keymap<ConfirmInput> = Keymap {
     ],
 };
 
+pub const CONFLICT: Keymap<ConflictInput> = Keymap {
+    bindings: &[
+        Binding {
+            action: ConflictInput::Overwrite,
+            keys: &[Char('o')],
+            label: "overwrite",
+        },
+        Binding {
+            action: ConflictInput::Reedit,
+            keys: &[Char('r'), Enter],
+            label: "re-edit theirs",
+        },
+        Binding {
+            action: ConflictInput::Discard,
+            keys: &[Esc, Char('d')],
+            label: "discard",
+        },
+    ],
+};
+
 pub const MENU: Keymap<MenuInput> = Keymap {
     bindings: &[
         Binding {
@@ -549,3 +671,66 @@ pub const EDITOR: Keymap<EditorInput> = Keymap {
         },
     ],
 };
+
+/// Ctrl chords in the editor. Terminals send Ctrl-/ as Ctrl-_ or Ctrl-7,
+/// so undo answers to all three.
+pub const EDITOR_CTRL: Keymap<EditorInput> = Keymap {
+    bindings: &[
+        Binding {
+            action: EditorInput::Undo,
+            keys: &[Char('/'), Char('_'), Char('7')],
+            label: "undo",
+        },
+        Binding {
+            action: EditorInput::Redo,
+            keys: &[Char('y')],
+            label: "redo",
+        },
+        Binding {
+            action: EditorInput::WordLeft,
+            keys: &[Left],
+            label: "word",
+        },
+        Binding {
+            action: EditorInput::WordRight,
+            keys: &[Right],
+            label: "word",
+        },
+        Binding {
+            action: EditorInput::EraseWord,
+            keys: &[Char('w'), Backspace],
+            label: "erase word",
+        },
+        Binding {
+            action: EditorInput::EraseWordForward,
+            keys: &[Delete],
+            label: "erase word",
+        },
+    ],
+};
+
+/// Alt chords in the editor, as readline has them.
+pub const EDITOR_ALT: Keymap<EditorInput> = Keymap {
+    bindings: &[
+        Binding {
+            action: EditorInput::WordLeft,
+            keys: &[Char('b'), Left],
+            label: "word",
+        },
+        Binding {
+            action: EditorInput::WordRight,
+            keys: &[Char('f'), Right],
+            label: "word",
+        },
+        Binding {
+            action: EditorInput::EraseWord,
+            keys: &[Backspace],
+            label: "erase word",
+        },
+        Binding {
+            action: EditorInput::EraseWordForward,
+            keys: &[Char('d')],
+            label: "erase word",
+        },
+    ],
+};
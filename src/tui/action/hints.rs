@@ -1,7 +1,7 @@
 use super::keymap::Hint;
 use super::keys::{
-    Action, ConfirmInput, EditorInput, InputInput, LabelsInput, MenuInput, PickerInput,
-    ReactionInput, WorkspacesInput,
+    Action, ConfirmInput, ConflictInput, DraftsInput, EditorInput, EmojiInput, InputInput,
+    LabelsInput, LinksInput, MenuInput, PickerInput, ReactionInput, WorkspacesInput,
 };
 
 pub const MY_WORK_HINTS: &[Hint<Action>] = &[
@@ -75,6 +75,10 @@ pub const DETAIL_HINTS: &[Hint<Action>] = &[
         keys: "+",
         label: "react",
     },
+    Hint::Literal {
+        keys: "f",
+        label: "links",
+    },
     Hint::Bound(Action::OpenInBrowser),
     Hint::Bound(Action::YankUrl),
     Hint::Bound(Action::ToggleZoom),
@@ -107,6 +111,10 @@ pub const COMMENTS_HINTS: &[Hint<Action>] = &[
         keys: "+",
         label: "react",
     },
+    Hint::Literal {
+        keys: "f",
+        label: "links",
+    },
     Hint::Bound(Action::Comment),
     Hint::Bound(Action::Ascend),
     Hint::Bound(Action::Quit),
@@ -134,6 +142,13 @@ pub const WORKSPACES_HINTS: &[Hint<WorkspacesInput>] = &[
     Hint::Bound(WorkspacesInput::Cancel),
 ];
 
+pub const DRAFTS_HINTS: &[Hint<DraftsInput>] = &[
+    Hint::Bound(DraftsInput::Next),
+    Hint::Bound(DraftsInput::Resume),
+    Hint::Bound(DraftsInput::Discard),
+    Hint::Bound(DraftsInput::Cancel),
+];
+
 pub const REACTIONS_HINTS: &[Hint<ReactionInput>] = &[
     Hint::Literal {
         keys: "h/l",
@@ -144,10 +159,32 @@ pub const REACTIONS_HINTS: &[Hint<ReactionInput>] = &[
         label: "row",
     },
     Hint::Bound(ReactionInput::Toggle),
-    Hint::Bound(ReactionInput::Custom),
+    Hint::Bound(ReactionInput::More),
     Hint::Bound(ReactionInput::Cancel),
 ];
 
+pub const EMOJI_HINTS: &[Hint<EmojiInput>] = &[
+    Hint::Literal {
+        keys: "type",
+        label: "search",
+    },
+    Hint::Bound(EmojiInput::Next),
+    Hint::Bound(EmojiInput::React),
+    Hint::Bound(EmojiInput::Cancel),
+];
+
+pub const LINKS_HINTS: &[Hint<LinksInput>] = &[
+    Hint::Literal {
+        keys: "a-z",
+        label: "open",
+    },
+    Hint::Literal {
+        keys: "A-Z",
+        label: "yank",
+    },
+    Hint::Bound(LinksInput::Cancel),
+];
+
 pub const LABELS_HINTS: &[Hint<LabelsInput>] = &[
     Hint::Literal {
         keys: "type",
@@ -189,6 +226,12 @@ pub const CONFIRM_HINTS: &[Hint<ConfirmInput>] = &[
     Hint::Bound(ConfirmInput::Reject),
 ];
 
+pub const CONFLICT_HINTS: &[Hint<ConflictInput>] = &[
+    Hint::Bound(ConflictInput::Overwrite),
+    Hint::Bound(ConflictInput::Reedit),
+    Hint::Bound(ConflictInput::Discard),
+];
+
 pub const MENU_HINTS: &[Hint<MenuInput>] = &[
     Hint::Bound(MenuInput::Next),
     Hint::Bound(MenuInput::SectionNext),
@@ -210,10 +253,77 @@ pub const EDITOR_HINTS: &[Hint<EditorInput>] = &[
         keys: "C-s",
         label: "post",
     },
+    Hint::Literal {
+        keys: "C-e",
+        label: "$EDITOR",
+    },
     Hint::Bound(EditorInput::Newline),
     Hint::Literal {
         keys: "↑/↓/←/→",
         label: "move",
     },
     Hint::Bound(EditorInput::Cancel),
+    Hint::Literal {
+        keys: "C-p",
+        label: "preview",
+    },
+    Hint::Literal {
+        keys: "C-t",
+        label: "template",
+    },
+    Hint::Literal {
+        keys: "C-/",
+        label: "undo",
+    },
+];
+
+pub const VI_NORMAL_HINTS: &[Hint<EditorInput>] = &[
+    Hint::Literal {
+        keys: "i/a/o",
+        label: "insert",
+    },
+    Hint::Literal {
+        keys: "v/V",
+        label: "visual",
+    },
+    Hint::Literal {
+        keys: "d/c/y",
+        label: "operate",
+    },
+    Hint::Literal {
+        keys: "\"+y",
+        label: "copy",
+    },
+    Hint::Literal {
+        keys: "ZZ",
+        label: "post",
+    },
+    Hint::Literal {
+        keys: "ZQ",
+        label: "cancel",
+    },
+];
+
+pub const VI_INSERT_HINTS: &[Hint<EditorInput>] = &[
+    Hint::Literal {
+        keys: "esc",
+        label: "normal",
+    },
+    Hint::Literal {
+        keys: "C-s",
+        label: "post",
+    },
+    Hint::Literal {
+        keys: "C-e",
+        label: "$EDITOR",
+    },
+    Hint::Bound(EditorInput::Newline),
+    Hint::Literal {
+        keys: "C-p",
+        label: "preview",
+    },
+    Hint::Literal {
+        keys: "C-t",
+        label: "template",
+    },
 ];
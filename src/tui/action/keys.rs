@@ -2,7 +2,8 @@ use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
 use KeyCode::Char;
 
 use super::bindings::{
-    BROWSE, CONFIRM, CTRL, EDITOR, INPUT, LABELS, MENU, PICKER, REACTIONS, WORKSPACES,
+    BROWSE, CONFIRM, CONFLICT, CTRL, DRAFTS, EDITOR, EDITOR_ALT, EDITOR_CTRL, EMOJI, INPUT, LABELS,
+    LINKS, MENU, PICKER, REACTIONS, WORKSPACES,
 };
 
 pub fn is_quit(key: &KeyEvent) -> bool {
@@ -12,6 +13,13 @@ pub fn is_quit(key: &KeyEvent) -> bool {
     )
 }
 
+pub fn is_suspend(key: &KeyEvent) -> bool {
+    matches!(
+        (key.modifiers, key.code),
+        (KeyModifiers::CONTROL, KeyCode::Char('z'))
+    )
+}
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum Action {
     Quit,
@@ -32,12 +40,14 @@ pub enum Action {
     Assign,
     SetPriority,
     SetLabels,
+    EditDescription,
     Comment,
     EnterComments,
     Reply,
     EditComment,
     DeleteComment,
     React,
+    Links,
     CycleGroup,
     CycleSort,
     ToggleZoom,
@@ -56,6 +66,8 @@ pub enum Action {
     HistoryBack,
     HistoryForward,
     Workspaces,
+    Drafts,
+    RunCommand,
     Help,
 }
 
@@ -73,6 +85,13 @@ pub enum ConfirmInput {
     Reject,
 }
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ConflictInput {
+    Overwrite,
+    Reedit,
+    Discard,
+}
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum MenuInput {
     Next,
@@ -99,7 +118,7 @@ pub enum ReactionInput {
     Up,
     Down,
     Toggle,
-    Custom,
+    More,
     Cancel,
 }
 
@@ -128,6 +147,12 @@ pub enum EditorInput {
     MoveRight,
     MoveUp,
     MoveDown,
+    Undo,
+    Redo,
+    WordLeft,
+    WordRight,
+    EraseWord,
+    EraseWordForward,
 }
 
 impl Action {
@@ -160,6 +185,12 @@ impl ConfirmInput {
     }
 }
 
+impl ConflictInput {
+    pub fn from_key(key: KeyEvent) -> Option<ConflictInput> {
+        CONFLICT.resolve(key)
+    }
+}
+
 impl MenuInput {
     pub fn from_key(key: KeyEvent) -> Option<MenuInput> {
         MENU.resolve(key)
@@ -174,6 +205,14 @@ impl InputInput {
 
 impl EditorInput {
     pub fn from_key(key: KeyEvent) -> Option<EditorInput> {
+        if key.modifiers.contains(KeyModifiers::CONTROL) {
+            return EDITOR_CTRL.resolve(key);
+        }
+
+        if key.modifiers.contains(KeyModifiers::ALT) {
+            return EDITOR_ALT.resolve(key);
+        }
+
         EDITOR.resolve(key)
     }
 }
@@ -198,6 +237,60 @@ impl WorkspacesInput {
     }
 }
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DraftsInput {
+    Next,
+    Prev,
+    Resume,
+    Discard,
+    Cancel,
+}
+
+impl DraftsInput {
+    pub fn from_key(key: KeyEvent) -> Option<DraftsInput> {
+        DRAFTS.resolve(key)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EmojiInput {
+    Next,
+    Prev,
+    React,
+    Erase,
+    Cancel,
+}
+
+impl EmojiInput {
+    pub fn from_key(key: KeyEvent) -> Option<EmojiInput> {
+        EMOJI.resolve(key)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LinksInput {
+    Erase,
+    Cancel,
+}
+
+impl LinksInput {
+    pub fn from_key(key: KeyEvent) -> Option<LinksInput> {
+        LINKS.resolve(key)
+    }
+}
+
 pub fn is_editor_submit(key: KeyEvent) -> bool {
     key.code == Char('s') && key.modifiers.contains(KeyModifiers::CONTROL)
 }
+
+pub fn is_editor_external(key: KeyEvent) -> bool {
+    key.code == Char('e') && key.modifiers.contains(KeyModifiers::CONTROL)
+}
+
+pub fn is_editor_preview(key: KeyEvent) -> bool {
+    key.code == Char('p') && key.modifiers.contains(KeyModifiers::CONTROL)
+}
+
+pub fn is_editor_templates(key: KeyEvent) -> bool {
+    key.code == Char('t') && key.modifiers.contains(KeyModifiers::CONTROL)
+}
@@ -60,5 +60,6 @@ pub const GLOBAL_MENU: &[Action] = &[
     Action::NextPanel,
     Action::PrevPanel,
     Action::Help,
+    Action::RunCommand,
     Action::Quit,
 ];
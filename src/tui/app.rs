@@ -1,24 +1,29 @@
+use std::collections::HashSet;
+
 use ratatui::widgets::ListState;
 
 use super::cache::{CacheStatus, Remote};
-use super::feed::{FeedKey, FeedRequest, FeedStore};
+use super::emoji::RecentEmoji;
+use super::feed::{FeedKey, FeedRequest};
 use super::focus::{
     navigate_list, scrolled, select_edge, Cursor, DetailFocus, DetailView, Direction, Edge, Focus,
     LeftPanel, Nav, Origin, Scroll, PANELS,
 };
 use super::message::{Commands, RuntimeCommand};
 use super::overlay::{
-    AssignOptions, Confirm, Editor, Find, Input, Labels, Menu, Overlay, Picker, PickerKind, Prefix,
-    Search, SearchPhase,
+    AssignOptions, Confirm, Editor, EditorKeys, Find, Input, Labels, Menu, Overlay, Picker,
+    PickerKind, Prefix, Registers, Search, SearchPhase,
 };
+use super::prefetch::{neighbours, PREFETCH_RADIUS};
 use super::saved_views::ViewSurface;
 use super::spinner::Spinner;
 use super::status::Status;
+use super::templates::Template;
 use super::view::{View, ViewKind, Views};
 use super::workspace::{TeamsPanel, WorkspaceData};
 use crate::api::{
-    Credential, IssueDetail, IssueId, IssueRef, IssueSummary, NotificationItem, OAuthToken, Page,
-    TeamId, Timestamp,
+    ConnectionSettings, Credential, IssueDetail, IssueId, IssueRef, IssueSummary, NotificationItem,
+    OAuthToken, Page, TeamId, Timestamp,
 };
 use crate::store::{Account, PersistedCache};
 
@@ -116,6 +121,18 @@ pub struct Ui {
     pub detail_scroll_max: usize,
     overlay: Overlay,
     pub find_query: Option<String>,
+    /// Set when the workspace is a fixture rather than a Linear account.
+    pub fixture: bool,
+    pub editor_keys: EditorKeys,
+    /// Whether editors open with the markdown preview showing; the last
+    /// toggle sticks for the session.
+    pub editor_preview: bool,
+    /// Vi registers outlive any one editor, so text yanked from one comment
+    /// can be put into the next.
+    pub registers: Registers,
+    pub recent_emoji: RecentEmoji,
+    /// Saved replies, read afresh each time a workspace opens.
+    pub templates: Vec<Template>,
 }
 
 pub struct SessionState {
@@ -196,11 +213,21 @@ impl SessionState {
         }
     }
 
-    pub fn upsert_account(&mut self, account: Account) {
-        self.accounts
-            .retain(|existing| existing.workspace_key != account.workspace_key);
+    pub fn upsert_account(&mut self, mut account: Account) -> &Account {
+        let existing = self
+            .accounts
+            .iter()
+            .position(|existing| existing.workspace_key == account.workspace_key)
+            .map(|index| self.accounts.remove(index));
+
+        if let Some(existing) = existing {
+            if account.connection.is_empty() {
+                account.connection = existing.connection;
+            }
+        }
 
         self.accounts.push(account);
+        &self.accounts[self.accounts.len() - 1]
     }
 
     #[must_use]
@@ -247,6 +274,16 @@ impl SessionState {
         self.active_oauth()?.refresh_token.clone()
     }
 
+    /// The connection settings saved with an account, for requests made
+    /// on its behalf outside the API client.
+    pub fn connection_for(&self, workspace_key: &str) -> ConnectionSettings {
+        self.accounts
+            .iter()
+            .find(|account| account.workspace_key == workspace_key)
+            .map(|account| account.connection.clone())
+            .unwrap_or_default()
+    }
+
     pub fn refresh_token_for(&self, workspace_key: &str) -> Option<String> {
         let account = self
             .accounts
@@ -283,6 +320,12 @@ impl App {
                 detail_scroll_max: 0,
                 overlay: Overlay::None,
                 find_query: None,
+                fixture: false,
+                editor_keys: EditorKeys::default(),
+                editor_preview: false,
+                registers: Registers::default(),
+                recent_emoji: RecentEmoji::default(),
+                templates: Vec::new(),
             },
             workspace: WorkspaceData::new(),
             session: SessionState::new(),
@@ -366,6 +409,12 @@ impl App {
             detail_scroll_max: _,
             overlay,
             find_query,
+            fixture: _,
+            editor_keys: _,
+            editor_preview: _,
+            registers: _,
+            recent_emoji: _,
+            templates: _,
         } = &mut self.ui;
 
         *focus = Focus::MyWork;
@@ -456,8 +505,8 @@ impl App {
         self.ui.focus.open_view_mut()
     }
 
-    pub fn view_render_parts(&mut self) -> (Option<&mut ViewSurface>, &FeedStore) {
-        (self.ui.focus.open_view_mut(), &self.workspace.feeds)
+    pub fn view_render_parts(&mut self) -> (Option<&mut ViewSurface>, &WorkspaceData) {
+        (self.ui.focus.open_view_mut(), &self.workspace)
     }
 
     pub fn open_view_surface(&mut self, surface: ViewSurface) {
@@ -622,6 +671,39 @@ impl App {
             .and_then(|i| self.active_issues().get(i))
     }
 
+    pub fn prefetch_window(&self) -> Vec<&IssueSummary> {
+        let (issues, order, selected) = match &self.ui.focus {
+            Focus::MyWork => {
+                let issues = self.active_issues();
+                (
+                    issues,
+                    (0..issues.len()).collect(),
+                    self.ui.list_state.selected(),
+                )
+            }
+            Focus::View(surface) => (
+                surface.issues(&self.workspace.feeds).unwrap_or_default(),
+                surface.ordered(&self.workspace.feeds),
+                surface.state.selected(),
+            ),
+            Focus::Recent => (
+                self.workspace.recently_viewed.as_slice(),
+                (0..self.workspace.recently_viewed.len()).collect(),
+                self.workspace.recent_state.selected(),
+            ),
+            Focus::SavedViews | Focus::Teams | Focus::Detail(..) => return Vec::new(),
+        };
+
+        let Some(selected) = selected else {
+            return Vec::new();
+        };
+
+        neighbours(selected, order.len(), PREFETCH_RADIUS)
+            .into_iter()
+            .filter_map(|position| order.get(position).and_then(|&index| issues.get(index)))
+            .collect()
+    }
+
     pub fn selected_notification(&self) -> Option<&NotificationItem> {
         self.ui
             .list_state
@@ -649,7 +731,11 @@ impl App {
             | Overlay::Search(_)
             | Overlay::Find(_)
             | Overlay::Reactions(_)
-            | Overlay::Workspaces(_) => false,
+            | Overlay::Emoji(_)
+            | Overlay::Workspaces(_)
+            | Overlay::Conflict(_)
+            | Overlay::Drafts(_)
+            | Overlay::Links(_) => false,
         }
     }
 
@@ -685,7 +771,11 @@ impl App {
             | Overlay::Search(_)
             | Overlay::Find(_)
             | Overlay::Reactions(_)
-            | Overlay::Workspaces(_) => {}
+            | Overlay::Emoji(_)
+            | Overlay::Workspaces(_)
+            | Overlay::Conflict(_)
+            | Overlay::Drafts(_)
+            | Overlay::Links(_) => {}
         }
     }
 
@@ -1100,6 +1190,37 @@ impl App {
             Focus::MyWork | Focus::Recent | Focus::SavedViews | Focus::Teams => None,
         }
     }
+
+    /// The identifier of an issue the workspace holds anywhere, for things
+    /// that only kept its id.
+    pub fn identifier_for(&self, id: &IssueId) -> Option<String> {
+        if let Some((detail, _)) = self.workspace.ready_detail(id) {
+            return Some(detail.identifier.clone());
+        }
+
+        self.held_issues()
+            .find(|issue| issue.id == *id)
+            .map(|issue| issue.identifier.clone())
+    }
+
+    /// Every issue the workspace holds, recently viewed first, once each.
+    pub fn known_issues(&self) -> Vec<IssueSummary> {
+        let mut seen = HashSet::new();
+
+        self.held_issues()
+            .filter(|issue| seen.insert(&issue.id))
+            .cloned()
+            .collect()
+    }
+
+    fn held_issues(&self) -> impl Iterator<Item = &IssueSummary> {
+        self.workspace.recently_viewed.iter().chain(
+            self.workspace
+                .feeds
+                .iter()
+                .flat_map(|(_, feed)| feed.items()),
+        )
+    }
 }
 
 impl Default for App {
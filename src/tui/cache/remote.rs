@@ -129,6 +129,7 @@ impl<T> Remote<T> {
     }
 
     pub fn begin(&mut self) {
+        let before = self.state();
         *self = match std::mem::take(self) {
             Remote::Missing | Remote::Loading => Remote::Loading,
             Remote::Ready { value, fetched_at }
@@ -140,6 +141,7 @@ impl<T> Remote<T> {
             } => Remote::Revalidating { value, fetched_at },
             Remote::Failed { last: None, .. } => Remote::Loading,
         };
+        self.trace(before);
     }
 
     pub fn begin_access(&mut self, now: Timestamp, policy: &RefreshPolicy) -> bool {
@@ -158,13 +160,16 @@ impl<T> Remote<T> {
     }
 
     pub fn set(&mut self, value: T, now: Timestamp) {
+        let before = self.state();
         *self = Remote::Ready {
             value,
             fetched_at: now,
         };
+        self.trace(before);
     }
 
     pub fn fail(&mut self, error: String) {
+        let before = self.state();
         *self = match std::mem::take(self) {
             Remote::Ready { value, fetched_at }
             | Remote::Stale { value, fetched_at }
@@ -175,13 +180,17 @@ impl<T> Remote<T> {
             Remote::Failed { last, .. } => Remote::Failed { error, last },
             Remote::Missing | Remote::Loading => Remote::Failed { error, last: None },
         };
+        self.trace(before);
     }
 
     pub fn bust(&mut self) {
+        let before = self.state();
         *self = Remote::Missing;
+        self.trace(before);
     }
 
     pub fn cancel(&mut self) {
+        let before = self.state();
         *self = match std::mem::take(self) {
             Remote::Loading => Remote::Missing,
             Remote::Revalidating { value, fetched_at } => Remote::Ready { value, fetched_at },
@@ -190,15 +199,62 @@ impl<T> Remote<T> {
             Remote::Stale { value, fetched_at } => Remote::Stale { value, fetched_at },
             Remote::Failed { error, last } => Remote::Failed { error, last },
         };
+        self.trace(before);
     }
 
     pub fn is_failed(&self) -> bool {
         matches!(self, Remote::Failed { .. })
     }
+
+    fn state(&self) -> &'static str {
+        match self {
+            Remote::Missing => "missing",
+            Remote::Loading => "loading",
+            Remote::Ready { .. } => "ready",
+            Remote::Stale { .. } => "stale",
+            Remote::Revalidating { .. } => "revalidating",
+            Remote::Failed { .. } => "failed",
+        }
+    }
+
+    fn trace(&self, before: &'static str) {
+        let after = self.state();
+        if before == after {
+            return;
+        }
+
+        crate::debug_log::record("cache", || {
+            let short = short_type_name(std::any::type_name::<T>());
+            match self {
+                Remote::Failed { error, .. } => format!("{short} {before} -> {after}: {error}"),
+                _ => format!("{short} {before} -> {after}"),
+            }
+        });
+    }
+}
+
+/// `Vec<crate::api::Issue>` reads better in a log as `Vec<Issue>`.
+fn short_type_name(name: &str) -> String {
+    let mut short = String::new();
+    let mut segment = String::new();
+
+    for c in name.chars() {
+        if c.is_alphanumeric() || c == '_' || c == ':' {
+            segment.push(c);
+        } else {
+            short.push_str(segment.rsplit("::").next().unwrap_or_default());
+            segment.clear();
+            short.push(c);
+        }
+    }
+    short.push_str(segment.rsplit("::").next().unwrap_or_default());
+
+    short
 }
 
 impl<T> Stale for Remote<T> {
     fn mark_stale(&mut self) {
+        let before = self.state();
         *self = match std::mem::take(self) {
             Remote::Ready { value, fetched_at } | Remote::Stale { value, fetched_at } => {
                 Remote::Stale { value, fetched_at }
@@ -210,5 +266,6 @@ impl<T> Stale for Remote<T> {
             Remote::Missing => Remote::Missing,
             Remote::Loading => Remote::Loading,
         };
+        self.trace(before);
     }
 }
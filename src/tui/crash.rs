@@ -0,0 +1,284 @@
+//! Crash reports. The run loop notes what it is doing as it goes so that a
+//! panic, wherever it happens, can say where the app was when it did.
+
+use std::backtrace::Backtrace;
+use std::collections::VecDeque;
+use std::panic::PanicHookInfo;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+
+use super::app::App;
+use super::focus::Focus;
+use super::journal::Recorded;
+use super::message::Message;
+use super::overlay::Overlay;
+
+/// How many of the latest events a report carries.
+const RECENT: usize = 32;
+
+static CONTEXT: Mutex<Context> = Mutex::new(Context::new());
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Context {
+    focus: &'static str,
+    overlay: &'static str,
+    recent: VecDeque<String>,
+}
+
+impl Context {
+    const fn new() -> Self {
+        Self {
+            focus: "none",
+            overlay: "none",
+            recent: VecDeque::new(),
+        }
+    }
+
+    fn note(&mut self, app: &App, event: String) {
+        self.focus = focus_kind(app.focus());
+        self.overlay = overlay_kind(app.overlay());
+
+        if self.recent.len() == RECENT {
+            self.recent.pop_front();
+        }
+        self.recent.push_back(event);
+    }
+}
+
+/// Records `event` along with where the app stands. The run loop calls this
+/// before handling the event, so a panic while handling it still names it.
+pub fn note(app: &App, event: impl FnOnce() -> String) {
+    // Described before locking: formatting can panic, and the panic hook
+    // needs the lock to write the report.
+    let event = event();
+
+    CONTEXT
+        .lock()
+        .unwrap_or_else(|e| e.into_inner())
+        .note(app, event);
+}
+
+/// How a key is noted. A character typed into text is noted only as
+/// `key <char>`, so a report never spells out a draft or a token.
+pub fn key(app: &App, key: KeyEvent) -> String {
+    let typed = matches!(key.code, KeyCode::Char(_))
+        && !key
+            .modifiers
+            .intersects(KeyModifiers::CONTROL | KeyModifiers::ALT)
+        && takes_text(app.overlay());
+
+    if typed {
+        "key <char>".into()
+    } else {
+        Recorded::<Message>::Input { key }.summary()
+    }
+}
+
+/// Installs a panic hook that puts the terminal back first, then writes a
+/// report under `dir` and says where it went. The previous hook still runs
+/// afterwards, so the panic message reaches the terminal as usual.
+///
+/// Only a panic on the calling thread, the one drawing the UI, ends the
+/// session. A panic in a spawned task is left to the previous hook, since
+/// the run loop carries on without it.
+pub fn install(dir: PathBuf) {
+    let previous = std::panic::take_hook();
+    let ui = std::thread::current().id();
+
+    std::panic::set_hook(Box::new(move |info| {
+        if std::thread::current().id() != ui {
+            previous(info);
+            return;
+        }
+
+        super::terminal::restore();
+
+        let context = CONTEXT.lock().unwrap_or_else(|e| e.into_inner()).clone();
+        let report = report(
+            &describe(info),
+            &Backtrace::force_capture().to_string(),
+            &context,
+        );
+
+        match write(&dir, &report) {
+            Ok(path) => eprintln!("linear-tui crashed; a report is at {}", path.display()),
+            Err(error) => eprintln!("linear-tui crashed and could not write a report: {error}"),
+        }
+
+        previous(info);
+    }));
+}
+
+fn describe(info: &PanicHookInfo<'_>) -> String {
+    let payload = info.payload();
+    let message = payload
+        .downcast_ref::<&str>()
+        .map(|message| message.to_string())
+        .or_else(|| payload.downcast_ref::<String>().cloned())
+        .unwrap_or_else(|| "(no message)".into());
+
+    match info.location() {
+        Some(location) => format!("{message} at {location}"),
+        None => message,
+    }
+}
+
+fn report(panic: &str, backtrace: &str, context: &Context) -> String {
+    let mut report = format!(
+        "linear-tui {}\n\npanic: {panic}\nfocus: {}\noverlay: {}\n\nlast {} events, oldest first:\n",
+        env!("CARGO_PKG_VERSION"),
+        context.focus,
+        context.overlay,
+        context.recent.len(),
+    );
+
+    for event in &context.recent {
+        report.push_str("  ");
+        report.push_str(event);
+        report.push('\n');
+    }
+
+    report.push_str("\nbacktrace:\n");
+    report.push_str(backtrace);
+    report.push('\n');
+
+    report
+}
+
+fn write(dir: &Path, report: &str) -> std::io::Result<PathBuf> {
+    let seconds = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|elapsed| elapsed.as_secs())
+        .unwrap_or_default();
+    let path = dir.join(format!("crash-{seconds}.txt"));
+
+    std::fs::create_dir_all(dir)?;
+    std::fs::write(&path, report)?;
+
+    Ok(path)
+}
+
+fn focus_kind(focus: &Focus) -> &'static str {
+    match focus {
+        Focus::MyWork => "my work",
+        Focus::Recent => "recent",
+        Focus::SavedViews => "saved views",
+        Focus::View(_) => "view",
+        Focus::Teams => "teams",
+        Focus::Detail(_) => "detail",
+    }
+}
+
+fn takes_text(overlay: &Overlay) -> bool {
+    matches!(
+        overlay,
+        Overlay::Input(_)
+            | Overlay::Editor(_)
+            | Overlay::Search(_)
+            | Overlay::Find(_)
+            | Overlay::Emoji(_)
+            | Overlay::Labels(_)
+    )
+}
+
+fn overlay_kind(overlay: &Overlay) -> &'static str {
+    match overlay {
+        Overlay::None => "none",
+        Overlay::Picker(_) => "picker",
+        Overlay::Confirm(_) => "confirm",
+        Overlay::Menu(_) => "menu",
+        Overlay::Prefix(_) => "prefix",
+        Overlay::Input(_) => "input",
+        Overlay::Editor(_) => "editor",
+        Overlay::Search(_) => "search",
+        Overlay::Find(_) => "find",
+        Overlay::Reactions(_) => "reactions",
+        Overlay::Emoji(_) => "emoji",
+        Overlay::Workspaces(_) => "workspaces",
+        Overlay::Labels(_) => "labels",
+        Overlay::Conflict(_) => "conflict",
+        Overlay::Drafts(_) => "drafts",
+        Overlay::Links(_) => "links",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tui::overlay::{Input, InputPurpose};
+
+    #[test]
+    fn only_the_latest_events_are_kept() {
+        let app = App::new();
+        let mut context = Context::new();
+
+        for index in 0..RECENT + 5 {
+            context.note(&app, format!("key {index}"));
+        }
+
+        assert_eq!(context.recent.len(), RECENT);
+        assert_eq!(context.recent.front().map(String::as_str), Some("key 5"));
+        assert_eq!(context.focus, "my work");
+    }
+
+    #[test]
+    fn describing_an_event_does_not_hold_the_context() {
+        let app = App::new();
+
+        note(&app, || {
+            note(&app, || "inner".into());
+            "outer".into()
+        });
+
+        let context = CONTEXT.lock().unwrap_or_else(|e| e.into_inner());
+        let at = |event: &str| context.recent.iter().position(|noted| noted == event);
+        assert!(at("inner") < at("outer"), "{:?}", context.recent);
+        assert!(at("inner").is_some());
+    }
+
+    #[test]
+    fn characters_typed_into_text_are_not_noted() {
+        let mut app = App::new();
+        assert_eq!(
+            key(&app, KeyEvent::from(KeyCode::Char('j'))),
+            "key Char('j')"
+        );
+
+        app.set_overlay(Overlay::Input(Input::new(InputPurpose::Jump, "Jump")));
+        assert_eq!(key(&app, KeyEvent::from(KeyCode::Char('x'))), "key <char>");
+        assert_eq!(key(&app, KeyEvent::from(KeyCode::Enter)), "key Enter");
+        assert_eq!(
+            key(
+                &app,
+                KeyEvent::new(KeyCode::Char('u'), KeyModifiers::CONTROL)
+            ),
+            "key Control+Char('u')"
+        );
+    }
+
+    #[test]
+    fn a_report_says_where_the_app_was() {
+        let context = Context {
+            focus: "detail",
+            overlay: "picker",
+            recent: VecDeque::from(["key Enter".to_string(), "message IssueLoaded".to_string()]),
+        };
+
+        let report = report(
+            "index out of bounds at src/tui/update.rs:10:5",
+            "0: main",
+            &context,
+        );
+
+        assert!(report.starts_with(&format!("linear-tui {}", env!("CARGO_PKG_VERSION"))));
+        assert!(report.contains("panic: index out of bounds"));
+        assert!(report.contains("focus: detail\noverlay: picker"));
+        assert!(
+            report.contains("last 2 events, oldest first:\n  key Enter\n  message IssueLoaded\n")
+        );
+        assert!(report.contains("backtrace:\n0: main"));
+    }
+}
@@ -0,0 +1,220 @@
+use serde::{Deserialize, Serialize};
+
+use super::overlay::Compose;
+use crate::api::{IssueId, TeamId, Timestamp};
+
+/// How many drafts are kept; past that the oldest go first.
+const DRAFT_CAP: usize = 50;
+
+/// How long unsaved drafts wait before the next autosave writes them.
+const AUTOSAVE_SECS: i64 = 2;
+
+/// Text written in the editor but not yet sent, one per issue and compose.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Draft {
+    pub issue_id: IssueId,
+    pub identifier: String,
+    pub team_id: TeamId,
+    pub compose: Compose,
+    pub body: String,
+    /// When the text being edited was loaded, so a restored edit still
+    /// notices changes made since.
+    pub seen: Option<Timestamp>,
+    pub saved_at: Timestamp,
+}
+
+impl Draft {
+    pub fn is_for(&self, issue_id: &IssueId, compose: &Compose) -> bool {
+        self.issue_id == *issue_id && self.compose == *compose
+    }
+
+    pub fn preview(&self) -> &str {
+        self.body
+            .lines()
+            .map(str::trim)
+            .find(|line| !line.is_empty())
+            .unwrap_or_default()
+    }
+}
+
+/// The active workspace's drafts, newest first.
+#[derive(Debug, Default)]
+pub struct Drafts {
+    items: Vec<Draft>,
+    dirty: bool,
+    written_at: Timestamp,
+}
+
+impl Drafts {
+    pub fn list(&self) -> &[Draft] {
+        &self.items
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.items.is_empty()
+    }
+
+    pub fn get(&self, issue_id: &IssueId, compose: &Compose) -> Option<&Draft> {
+        self.items
+            .iter()
+            .find(|draft| draft.is_for(issue_id, compose))
+    }
+
+    pub fn has(&self, issue_id: &IssueId) -> bool {
+        self.items.iter().any(|draft| draft.issue_id == *issue_id)
+    }
+
+    /// Keeps `draft` in place of any for the same compose. A blank body
+    /// discards it instead.
+    pub fn save(&mut self, draft: Draft) {
+        if draft.body.trim().is_empty() {
+            self.discard(&draft.issue_id, &draft.compose);
+            return;
+        }
+
+        if let Some(index) = self
+            .items
+            .iter()
+            .position(|existing| existing.is_for(&draft.issue_id, &draft.compose))
+        {
+            if self.items[index].body == draft.body {
+                return;
+            }
+            self.items.remove(index);
+        }
+
+        self.items.insert(0, draft);
+        self.items.truncate(DRAFT_CAP);
+        self.dirty = true;
+    }
+
+    pub fn discard(&mut self, issue_id: &IssueId, compose: &Compose) -> bool {
+        let before = self.items.len();
+        self.items.retain(|draft| !draft.is_for(issue_id, compose));
+
+        let discarded = self.items.len() != before;
+        self.dirty |= discarded;
+        discarded
+    }
+
+    /// Adds drafts read from disk. Anything written this session is newer
+    /// and wins.
+    pub fn merge(&mut self, loaded: Vec<Draft>) {
+        for draft in loaded {
+            if self.get(&draft.issue_id, &draft.compose).is_none() {
+                self.items.push(draft);
+            }
+        }
+
+        self.items
+            .sort_by_key(|draft| std::cmp::Reverse(draft.saved_at));
+        self.items.truncate(DRAFT_CAP);
+    }
+
+    /// The drafts to write, once changes have waited long enough to be
+    /// worth a write of their own.
+    pub fn due(&mut self, now: Timestamp) -> Option<Vec<Draft>> {
+        if now.seconds_since(self.written_at) < AUTOSAVE_SECS {
+            return None;
+        }
+
+        self.unsaved(now)
+    }
+
+    /// The drafts to write if anything changed since the last write.
+    pub fn unsaved(&mut self, now: Timestamp) -> Option<Vec<Draft>> {
+        if !self.dirty {
+            return None;
+        }
+
+        self.dirty = false;
+        self.written_at = now;
+
+        Some(self.items.clone())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::api::CommentId;
+
+    fn draft(issue: &str, compose: Compose, body: &str, saved_at: i64) -> Draft {
+        Draft {
+            issue_id: IssueId::from_raw(issue),
+            identifier: issue.to_uppercase(),
+            team_id: TeamId::from_raw("t1"),
+            compose,
+            body: body.into(),
+            seen: None,
+            saved_at: Timestamp::from_epoch(saved_at),
+        }
+    }
+
+    fn bodies(drafts: &Drafts) -> Vec<&str> {
+        drafts
+            .list()
+            .iter()
+            .map(|draft| draft.body.as_str())
+            .collect()
+    }
+
+    #[test]
+    fn each_compose_keeps_its_own_draft_and_the_newest_leads() {
+        let mut drafts = Drafts::default();
+        let reply = Compose::Reply {
+            parent_id: CommentId::from_raw("c1"),
+        };
+
+        drafts.save(draft("i1", Compose::Comment, "first", 1));
+        drafts.save(draft("i1", reply.clone(), "reply", 2));
+        drafts.save(draft("i1", Compose::Comment, "first, reworded", 3));
+
+        assert_eq!(bodies(&drafts), vec!["first, reworded", "reply"]);
+        assert_eq!(
+            drafts
+                .get(&IssueId::from_raw("i1"), &reply)
+                .map(Draft::preview),
+            Some("reply")
+        );
+    }
+
+    #[test]
+    fn emptying_a_draft_discards_it() {
+        let mut drafts = Drafts::default();
+        drafts.save(draft("i1", Compose::Description, "notes", 1));
+        drafts.save(draft("i1", Compose::Description, "  \n", 2));
+
+        assert!(drafts.is_empty());
+        assert!(!drafts.has(&IssueId::from_raw("i1")));
+    }
+
+    #[test]
+    fn loaded_drafts_never_overwrite_newer_ones() {
+        let mut drafts = Drafts::default();
+        drafts.save(draft("i1", Compose::Comment, "typed just now", 10));
+
+        drafts.merge(vec![
+            draft("i1", Compose::Comment, "from last session", 5),
+            draft("i2", Compose::Comment, "another issue", 7),
+        ]);
+
+        assert_eq!(bodies(&drafts), vec!["typed just now", "another issue"]);
+    }
+
+    #[test]
+    fn autosave_waits_for_a_pause_between_writes() {
+        let mut drafts = Drafts::default();
+        drafts.save(draft("i1", Compose::Comment, "a", 0));
+
+        assert!(drafts.due(Timestamp::from_epoch(100)).is_some());
+
+        drafts.save(draft("i1", Compose::Comment, "ab", 100));
+        assert!(drafts.due(Timestamp::from_epoch(101)).is_none());
+        assert!(drafts.due(Timestamp::from_epoch(102)).is_some());
+        assert!(
+            drafts.due(Timestamp::from_epoch(200)).is_none(),
+            "nothing changed"
+        );
+    }
+}
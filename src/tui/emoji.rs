@@ -1,3 +1,9 @@
+use std::collections::HashSet;
+
+/// How many recently used emoji are remembered.
+const RECENT_CAP: usize = 24;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub struct PaletteEmoji {
     pub name: &'static str,
     pub glyph: &'static str,
@@ -44,6 +50,78 @@ pub fn glyph(name: &str) -> String {
         .unwrap_or_else(|| format!(":{name}:"))
 }
 
+fn by_shortcode(name: &str) -> Option<PaletteEmoji> {
+    let emoji = emojis::get_by_shortcode(name)?;
+    let name = emoji.shortcodes().find(|code| *code == name)?;
+
+    Some(PaletteEmoji {
+        name,
+        glyph: emoji.as_str(),
+    })
+}
+
+/// Every emoji with a shortcode matching `query`, best first: recently
+/// used, then the reaction palette, then shortcodes starting with the
+/// query, then any containing it. Each glyph appears once.
+pub fn search(query: &str, recent: &[String]) -> Vec<PaletteEmoji> {
+    let query = query.to_lowercase();
+
+    let favourites = recent
+        .iter()
+        .filter_map(|name| by_shortcode(name))
+        .chain(REACTION_PALETTE.iter().copied())
+        .filter(|entry| entry.name.contains(&query));
+    let matching = |starts: bool| {
+        let query = query.clone();
+        emojis::iter().filter_map(move |emoji| {
+            let name = emoji.shortcodes().find(|code| {
+                if starts {
+                    code.starts_with(&query)
+                } else {
+                    code.contains(&query)
+                }
+            })?;
+            Some(PaletteEmoji {
+                name,
+                glyph: emoji.as_str(),
+            })
+        })
+    };
+
+    let mut seen = HashSet::new();
+    favourites
+        .chain(matching(true))
+        .chain(matching(false))
+        .filter(|entry| seen.insert(entry.glyph))
+        .collect()
+}
+
+/// Shortcodes of the emoji used lately, newest first.
+#[derive(Debug, Clone, Default)]
+pub struct RecentEmoji(Vec<String>);
+
+impl RecentEmoji {
+    pub fn list(&self) -> &[String] {
+        &self.0
+    }
+
+    pub fn record(&mut self, name: &str) {
+        self.0.retain(|existing| existing != name);
+        self.0.insert(0, name.to_string());
+        self.0.truncate(RECENT_CAP);
+    }
+
+    /// Adds what was saved last session behind anything used since.
+    pub fn merge(&mut self, loaded: Vec<String>) {
+        for name in loaded {
+            if !self.0.contains(&name) {
+                self.0.push(name);
+            }
+        }
+        self.0.truncate(RECENT_CAP);
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -64,6 +142,42 @@ mod tests {
         );
     }
 
+    fn names(entries: &[PaletteEmoji]) -> Vec<&str> {
+        entries.iter().map(|entry| entry.name).collect()
+    }
+
+    #[test]
+    fn search_prefers_recent_then_palette_then_prefixes() {
+        assert_eq!(search("ro", &[]).first().map(|e| e.glyph), Some("🚀"));
+
+        let recent = vec!["rose".to_string()];
+        let found = search("ro", &recent);
+        assert_eq!(names(&found[..2]), vec!["rose", "rocket"]);
+
+        let prefix = found.iter().position(|e| e.name == "robot").unwrap();
+        let inside = found.iter().position(|e| e.name == "carrot").unwrap();
+        assert!(prefix < inside);
+    }
+
+    #[test]
+    fn search_lists_each_glyph_once() {
+        let found = search("thumbsup", &[]);
+        assert_eq!(found.iter().filter(|e| e.glyph == "👍").count(), 1);
+        assert!(search("definitely_not_an_emoji", &[]).is_empty());
+    }
+
+    #[test]
+    fn recent_emoji_lead_with_the_newest_and_keep_their_order_on_merge() {
+        let mut recent = RecentEmoji::default();
+        recent.record("tada");
+        recent.record("fire");
+        recent.record("tada");
+
+        recent.merge(vec!["eyes".into(), "fire".into()]);
+
+        assert_eq!(recent.list(), ["tada", "fire", "eyes"]);
+    }
+
     #[test]
     fn palette_glyphs_match_their_shortcodes() {
         for entry in REACTION_PALETTE {
@@ -1,10 +1,12 @@
 use crossterm::event::KeyEvent;
+use serde::{Deserialize, Serialize};
 
 use super::message::Message;
 use crate::api::Timestamp;
 
 pub enum Event {
     Input(KeyEvent),
+    Paste(String),
     Resize,
     Message { lane: Lane, message: Message },
     Tick(Timestamp),
@@ -18,13 +20,13 @@ pub enum Redraw {
     Skipped,
 }
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 pub enum Lane {
     Workspace(Generation),
     Host,
 }
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 pub struct Generation(u64);
 
 impl Generation {
@@ -17,7 +17,7 @@ pub trait HasId {
     fn feed_id(&self) -> &Self::Id;
 }
 
-#[derive(Debug, Clone, PartialEq, Eq)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub enum FeedRequest {
     Refresh,
     LoadMore { after: Cursor },
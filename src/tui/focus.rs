@@ -1,6 +1,7 @@
 use std::num::NonZeroUsize;
 
 use ratatui::widgets::ListState;
+use serde::{Deserialize, Serialize};
 
 use super::overlay::Search;
 use super::saved_views::ViewSurface;
@@ -248,7 +249,7 @@ pub enum Edge {
     Bottom,
 }
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 pub enum Reveal {
     Top,
     Bottom,
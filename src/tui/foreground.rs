@@ -0,0 +1,127 @@
+//! Lending the terminal to something else: the shell on Ctrl-Z, or an
+//! external program such as an editor or pager. Either way the TUI leaves
+//! the alternate screen, waits, then takes the terminal back and redraws
+//! from scratch.
+
+use std::io::{BufRead, Write};
+use std::process::Command;
+
+use crossterm::terminal::{enable_raw_mode, EnterAlternateScreen};
+
+use super::message::Foreground;
+use super::terminal::{self, Term};
+
+/// Stops the process until the shell resumes it with `fg`.
+#[cfg(unix)]
+pub fn suspend(terminal: &mut Term) -> std::io::Result<()> {
+    terminal::restore();
+
+    // SAFETY: raising a signal on our own process has no memory-safety
+    // preconditions; SIGTSTP's default action stops us until SIGCONT.
+    unsafe {
+        libc::raise(libc::SIGTSTP);
+    }
+
+    reclaim(terminal)
+}
+
+/// There is no job control to hand back to; the update layer already told
+/// the user so.
+#[cfg(not(unix))]
+pub fn suspend(_terminal: &mut Term) -> std::io::Result<()> {
+    Ok(())
+}
+
+/// Runs `command` in the foreground. The outer result is about the
+/// terminal; the inner one is what to tell the user about the program.
+pub fn run(terminal: &mut Term, command: &Foreground) -> std::io::Result<Result<(), String>> {
+    terminal::restore();
+
+    let outcome = match Command::new(&command.program)
+        .args(&command.args)
+        .envs(command.env.iter().map(|(key, value)| (key, value)))
+        .status()
+    {
+        Ok(status) if status.success() => Ok(()),
+        Ok(status) => Err(match status.code() {
+            Some(code) => format!("`{}` exited with status {code}", command.describe()),
+            None => format!("`{}` was interrupted", command.describe()),
+        }),
+        Err(error) => Err(format!("Couldn't run `{}`: {error}", command.program)),
+    };
+
+    if command.pause {
+        print!("\n[press enter to return]");
+        let _ = std::io::stdout().flush();
+        let _ = std::io::stdin().lock().read_line(&mut String::new());
+    }
+
+    reclaim(terminal)?;
+
+    Ok(outcome)
+}
+
+/// Opens `body` in the user's editor as a markdown file and returns what
+/// it held on exit. The inner error covers the editor failing or the file
+/// going missing; the text is left as it was in either case.
+pub fn edit(terminal: &mut Term, body: &str) -> std::io::Result<Result<String, String>> {
+    // A fresh, exclusively created file readable only by us, so nothing
+    // planted at a guessable path can see or redirect the text.
+    let mut file = match tempfile::Builder::new()
+        .prefix("linear-tui-")
+        .suffix(".md")
+        .tempfile()
+    {
+        Ok(file) => file,
+        Err(error) => return Ok(Err(format!("Couldn't create a temporary file: {error}"))),
+    };
+    let path = file.path().to_path_buf();
+
+    if let Err(error) = file.write_all(body.as_bytes()).and_then(|()| file.flush()) {
+        return Ok(Err(format!("Couldn't write {}: {error}", path.display())));
+    }
+
+    let outcome = run(terminal, &editor(&path))?.and_then(|()| {
+        std::fs::read_to_string(&path)
+            .map_err(|error| format!("Couldn't read {}: {error}", path.display()))
+    });
+    let _ = file.close();
+
+    // Most editors end the file with a newline the buffer never had.
+    Ok(outcome.map(|text| match text.strip_suffix('\n') {
+        Some(text) => text.to_string(),
+        None => text,
+    }))
+}
+
+/// `$VISUAL`, then `$EDITOR`, then `vi`. Either variable may carry its own
+/// arguments, as in `code --wait`.
+fn editor(path: &std::path::Path) -> Foreground {
+    let configured = ["VISUAL", "EDITOR"]
+        .into_iter()
+        .filter_map(|name| std::env::var(name).ok())
+        .find(|value| !value.trim().is_empty())
+        .unwrap_or_else(|| "vi".into());
+
+    let mut words = configured.split_whitespace().map(String::from);
+    let program = words.next().unwrap_or_else(|| "vi".into());
+    let mut args: Vec<String> = words.collect();
+    args.push(path.display().to_string());
+
+    Foreground {
+        program,
+        args,
+        env: Vec::new(),
+        pause: false,
+    }
+}
+
+/// Undoes `terminal::restore`. Clearing makes the next draw repaint every
+/// cell, since whatever ran in between has left the screen in an unknown
+/// state.
+fn reclaim(terminal: &mut Term) -> std::io::Result<()> {
+    enable_raw_mode()?;
+    crossterm::execute!(std::io::stdout(), EnterAlternateScreen)?;
+    terminal::resume_paste()?;
+    terminal.clear()
+}
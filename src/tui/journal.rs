@@ -0,0 +1,471 @@
+use std::borrow::Borrow;
+use std::fs::File;
+use std::io::{LineWriter, Write};
+use std::path::Path;
+
+use anyhow::{Context, Result};
+use crossterm::event::KeyEvent;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use super::app::App;
+use super::event::{Lane, Redraw};
+use super::message::Message;
+use super::overlay::EditorKeys;
+use super::{render, update};
+use crate::api::{Credential, Timestamp};
+use crate::store::{Account, PersistedCache};
+
+const REDACTED: &str = "[redacted]";
+
+/// One line of a journal: an event the run loop handled, stamped with the
+/// wall clock at the time.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct Entry<M = Message> {
+    pub at: Timestamp,
+    pub event: Recorded<M>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum Recorded<M = Message> {
+    /// How the session began, so a replay starts from the same place.
+    Start {
+        now: Timestamp,
+        accounts: Vec<Account>,
+        startup: Startup,
+        fixture: bool,
+        /// Older journals predate the setting and were all plain.
+        #[serde(default)]
+        editor_keys: EditorKeys,
+        width: u16,
+        height: u16,
+    },
+    Input {
+        key: KeyEvent,
+    },
+    Paste {
+        text: String,
+    },
+    Resize {
+        width: u16,
+        height: u16,
+    },
+    Tick {
+        now: Timestamp,
+    },
+    /// A run of ticks that neither redrew nor did anything, as one line.
+    /// `now` is the last of them.
+    Idle {
+        ticks: u32,
+        now: Timestamp,
+    },
+    /// A message that reached `update::apply`; stale ones never do.
+    Message {
+        lane: Lane,
+        message: M,
+    },
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum Startup {
+    Connected {
+        workspace: String,
+        feeds: Option<PersistedCache>,
+    },
+    Connecting,
+    Disconnected,
+}
+
+impl<M: Borrow<Message>> Recorded<M> {
+    pub fn summary(&self) -> String {
+        match self {
+            Recorded::Start { .. } => "start".into(),
+            Recorded::Input { key } if key.modifiers.is_empty() => format!("key {:?}", key.code),
+            Recorded::Input { key } => format!("key {}+{:?}", key.modifiers, key.code),
+            Recorded::Paste { text } => format!("paste {} chars", text.chars().count()),
+            Recorded::Resize { width, height } => format!("resize {width}x{height}"),
+            Recorded::Tick { .. } => "tick".into(),
+            Recorded::Idle { ticks, .. } => format!("idle for {ticks} ticks"),
+            Recorded::Message { message, .. } => format!("message {}", message.borrow().name()),
+        }
+    }
+}
+
+/// Appends events to a file as they happen. A write failure stops the
+/// journal rather than the session.
+pub struct Journal {
+    out: Option<LineWriter<File>>,
+    idle: Option<(u32, Timestamp)>,
+}
+
+impl Journal {
+    pub fn create(path: &Path) -> Result<Self> {
+        let file =
+            create_private(path).with_context(|| format!("creating journal {}", path.display()))?;
+
+        Ok(Self {
+            out: Some(LineWriter::new(file)),
+            idle: None,
+        })
+    }
+
+    /// Counts a tick that changed nothing. The run is written as one line
+    /// when something next happens.
+    pub fn idle(&mut self, now: Timestamp) {
+        let ticks = self.idle.map_or(0, |(ticks, _)| ticks);
+        self.idle = Some((ticks + 1, now));
+    }
+
+    pub fn record<M: Serialize>(&mut self, event: Recorded<M>) {
+        if let Some((ticks, now)) = self.idle.take() {
+            self.write(Recorded::<Message>::Idle { ticks, now });
+        }
+        self.write(event);
+    }
+
+    fn write<M: Serialize>(&mut self, event: Recorded<M>) {
+        let Some(out) = &mut self.out else {
+            return;
+        };
+
+        let entry = Entry {
+            at: Timestamp::now(),
+            event,
+        };
+        let written = serde_json::to_value(&entry)
+            .map(redact)
+            .map_err(std::io::Error::from)
+            .and_then(|entry| writeln!(out, "{entry}"));
+
+        if written.is_err() {
+            self.out = None;
+        }
+    }
+}
+
+/// A journal holds everything typed, so only its owner may read it.
+#[cfg(unix)]
+fn create_private(path: &Path) -> std::io::Result<File> {
+    use std::os::unix::fs::{OpenOptionsExt, PermissionsExt};
+
+    let file = std::fs::OpenOptions::new()
+        .write(true)
+        .create(true)
+        .truncate(true)
+        .mode(0o600)
+        .open(path)?;
+    // The mode only applies to a new file; one being reused is narrowed too.
+    file.set_permissions(std::fs::Permissions::from_mode(0o600))?;
+
+    Ok(file)
+}
+
+#[cfg(not(unix))]
+fn create_private(path: &Path) -> std::io::Result<File> {
+    File::create(path)
+}
+
+/// Credentials only ever sit under a `credential` key: on accounts, on
+/// sign-in and on token refresh. Each becomes a placeholder key so the
+/// journal still parses.
+fn redact(value: Value) -> Value {
+    match value {
+        Value::Object(map) => Value::Object(
+            map.into_iter()
+                .map(|(key, value)| {
+                    let value = if key == "credential" {
+                        serde_json::to_value(Credential::PersonalKey(REDACTED.into()))
+                            .unwrap_or(Value::Null)
+                    } else {
+                        redact(value)
+                    };
+                    (key, value)
+                })
+                .collect(),
+        ),
+        Value::Array(items) => Value::Array(items.into_iter().map(redact).collect()),
+        other => other,
+    }
+}
+
+pub fn load(path: &Path) -> Result<Vec<Entry>> {
+    let raw = std::fs::read_to_string(path)
+        .with_context(|| format!("reading journal {}", path.display()))?;
+
+    raw.lines()
+        .enumerate()
+        .filter(|(_, line)| !line.trim().is_empty())
+        .map(|(index, line)| {
+            serde_json::from_str(line)
+                .with_context(|| format!("parsing journal line {}", index + 1))
+        })
+        .collect()
+}
+
+/// Feeds journal entries into a fresh `App` exactly as the run loop did,
+/// drawing wherever it drew so render-derived state such as the viewport
+/// keeps pace. Only `update` is replayed: requests are not re-sent, their
+/// recorded answers arrive as messages instead, and workspace switches made
+/// by the runtime itself are not reproduced.
+pub struct Replay {
+    pub app: App,
+    pub width: u16,
+    pub height: u16,
+}
+
+impl Default for Replay {
+    fn default() -> Self {
+        Self {
+            app: App::new(),
+            width: 110,
+            height: 32,
+        }
+    }
+}
+
+impl Replay {
+    /// Applies one event, returning whether the run loop redrew after it.
+    pub fn apply(&mut self, event: Recorded) -> bool {
+        let app = &mut self.app;
+
+        let redraw = match event {
+            Recorded::Start {
+                now,
+                accounts,
+                startup,
+                fixture,
+                editor_keys,
+                width,
+                height,
+            } => {
+                (self.width, self.height) = (width, height);
+                app.now = now;
+                app.ui.fixture = fixture;
+                app.ui.editor_keys = editor_keys;
+                app.session.set_accounts(accounts);
+
+                match startup {
+                    Startup::Connected { workspace, feeds } => {
+                        let _ = app.session.activate(&workspace);
+                        if let Some(feeds) = feeds {
+                            update::restore_feeds(app, feeds);
+                        }
+                        let _ = update::initial_commands(app);
+                    }
+                    Startup::Connecting => {
+                        app.ui.status = Some(super::status::Status::ConnectingWorkspace);
+                    }
+                    Startup::Disconnected => update::open_workspaces(app),
+                }
+                true
+            }
+            Recorded::Input { key } => {
+                let _ = update::handle_key(app, key);
+                true
+            }
+            Recorded::Paste { text } => {
+                let _ = update::handle_paste(app, &text);
+                true
+            }
+            Recorded::Resize { width, height } => {
+                (self.width, self.height) = (width, height);
+                true
+            }
+            Recorded::Tick { now } => tick(app, now),
+            Recorded::Idle { ticks, now } => {
+                for _ in 0..ticks {
+                    tick(app, now);
+                }
+                false
+            }
+            Recorded::Message { message, .. } => {
+                let _ = update::apply(app, message);
+                true
+            }
+        };
+
+        if redraw {
+            self.frame(false);
+        }
+
+        redraw
+    }
+
+    pub fn frame(&mut self, styled: bool) -> String {
+        if styled {
+            render::render_styled_to_string(&mut self.app, self.width, self.height)
+        } else {
+            render::render_to_string(&mut self.app, self.width, self.height)
+        }
+    }
+}
+
+/// The run loop's tick, returning whether it redrew.
+fn tick(app: &mut App, now: Timestamp) -> bool {
+    let redraw = update::tick(app, now);
+    let _ = update::prefetch(app);
+    let _ = update::autosave(app);
+    let _ = update::search_completions(app);
+    let commands = app.maybe_refresh_token();
+
+    !commands.is_empty() || redraw == Redraw::Needed
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::api::{ConnectionSettings, Session, User, UserId};
+    use crossterm::event::{KeyCode, KeyModifiers};
+
+    fn account() -> Account {
+        Account {
+            workspace_key: "ws".into(),
+            org_name: "Workspace".into(),
+            credential: Credential::PersonalKey("lin_api_secret".into()),
+            connection: ConnectionSettings::default(),
+        }
+    }
+
+    fn written(events: Vec<Recorded>) -> String {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("session.journal");
+        let mut journal = Journal::create(&path).unwrap();
+
+        for event in events {
+            journal.record(event);
+        }
+        drop(journal);
+
+        std::fs::read_to_string(&path).unwrap()
+    }
+
+    #[test]
+    fn credentials_never_reach_the_journal() {
+        let raw = written(vec![
+            Recorded::Start {
+                now: Timestamp::from("2026-07-16T21:00:00Z"),
+                accounts: vec![account()],
+                startup: Startup::Connecting,
+                fixture: false,
+                editor_keys: EditorKeys::Plain,
+                width: 80,
+                height: 24,
+            },
+            Recorded::Message {
+                lane: Lane::Host,
+                message: Message::AccountAdded {
+                    account: Box::new(account()),
+                },
+            },
+        ]);
+
+        assert!(!raw.contains("lin_api_secret"), "{raw}");
+        assert_eq!(raw.matches(REDACTED).count(), 2);
+    }
+
+    #[test]
+    fn entries_round_trip_through_a_file() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("session.journal");
+        let mut journal = Journal::create(&path).unwrap();
+        journal.record(Recorded::<Message>::Input {
+            key: KeyEvent::new(KeyCode::Char('j'), KeyModifiers::NONE),
+        });
+        journal.record(Recorded::<Message>::Paste {
+            text: "two\nlines".into(),
+        });
+        journal.record(Recorded::Message {
+            lane: Lane::Host,
+            message: Message::SessionLoaded(Session {
+                user: User {
+                    id: UserId::from_raw("u"),
+                    name: "Dan".into(),
+                    display_name: "dan".into(),
+                    url: String::new(),
+                    is_me: true,
+                },
+                org_name: "Dan's Donuts".into(),
+                org_url_key: "dans-donuts".into(),
+            }),
+        });
+        drop(journal);
+
+        let summaries: Vec<String> = load(&path)
+            .unwrap()
+            .into_iter()
+            .map(|entry| entry.event.summary())
+            .collect();
+
+        assert_eq!(
+            summaries,
+            vec!["key Char('j')", "paste 9 chars", "message SessionLoaded"]
+        );
+    }
+
+    #[test]
+    fn idle_ticks_are_written_as_one_line() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("session.journal");
+        let mut journal = Journal::create(&path).unwrap();
+        for seconds in 0..3 {
+            journal.idle(Timestamp::from_epoch(seconds));
+        }
+        journal.record(Recorded::<Message>::Tick {
+            now: Timestamp::from_epoch(3),
+        });
+        drop(journal);
+
+        let events: Vec<Recorded> = load(&path)
+            .unwrap()
+            .into_iter()
+            .map(|entry| entry.event)
+            .collect();
+
+        assert!(matches!(
+            events.as_slice(),
+            [Recorded::Idle { ticks: 3, now }, Recorded::Tick { .. }]
+                if *now == Timestamp::from_epoch(2)
+        ));
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn only_the_owner_can_read_a_journal() {
+        use std::os::unix::fs::PermissionsExt;
+
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("session.journal");
+        Journal::create(&path).unwrap();
+
+        let mode = std::fs::metadata(&path).unwrap().permissions().mode();
+        assert_eq!(mode & 0o777, 0o600);
+    }
+
+    #[test]
+    fn a_replayed_session_lands_where_the_original_did() {
+        let mut replay = Replay::default();
+
+        replay.apply(Recorded::Start {
+            now: Timestamp::from("2026-07-16T21:00:00Z"),
+            accounts: vec![account()],
+            startup: Startup::Connected {
+                workspace: "ws".into(),
+                feeds: None,
+            },
+            fixture: true,
+            editor_keys: EditorKeys::Plain,
+            width: 90,
+            height: 20,
+        });
+        replay.apply(Recorded::Input {
+            key: KeyEvent::new(KeyCode::Char('?'), KeyModifiers::NONE),
+        });
+
+        assert_eq!((replay.width, replay.height), (90, 20));
+        assert!(replay.app.ui.fixture);
+        assert_eq!(replay.app.session.active_workspace(), Some("ws"));
+        assert!(replay.frame(false).contains("fixture"));
+    }
+}
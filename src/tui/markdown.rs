@@ -1,13 +1,15 @@
+mod highlight;
 mod style;
 mod table;
 
-use pulldown_cmark::{Event, Options, Parser, Tag, TagEnd};
+use pulldown_cmark::{CodeBlockKind, Event, Options, Parser, Tag, TagEnd};
 use ratatui::style::{Modifier, Style};
 use ratatui::text::{Line, Span};
 
+use highlight::{Highlighter, Lang};
 use style::{
     code_style, dim_style, heading_style, link_style, marker_style, mention_style, quote_style,
-    task_marker,
+    task_marker, token_style,
 };
 use table::Table;
 
@@ -15,21 +17,103 @@ const RULE_WIDTH: usize = 40;
 const PROFILE_PREFIX: &str = "https://linear.app/";
 const PROFILE_SEGMENT: &str = "/profiles/";
 
-pub fn render(input: &str, base: Style) -> Vec<Line<'static>> {
+fn options() -> Options {
     let mut options = Options::empty();
     options.insert(Options::ENABLE_STRIKETHROUGH);
     options.insert(Options::ENABLE_TABLES);
     options.insert(Options::ENABLE_TASKLISTS);
+    options
+}
 
+pub fn render(input: &str, base: Style) -> Vec<Line<'static>> {
     let mut writer = Writer::new(base);
 
-    for event in Parser::new_ext(input, options) {
+    for event in Parser::new_ext(input, options()) {
         writer.event(event);
     }
 
     writer.finish()
 }
 
+/// A link whose address the rendered text leaves out.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Link {
+    pub text: String,
+    pub url: String,
+    pub image: bool,
+}
+
+/// Every link and image in `input` in reading order, along with URLs
+/// pasted as plain text.
+pub fn links(input: &str) -> Vec<Link> {
+    let mut links = Vec::new();
+    let mut open: Option<Link> = None;
+    // Text arrives in pieces, so plain runs are joined before a URL is looked for.
+    let mut plain = String::new();
+
+    for event in Parser::new_ext(input, options()) {
+        let text = match &event {
+            Event::Text(text) | Event::Code(text) => Some(text.as_ref()),
+            _ => None,
+        };
+        match (text, open.as_mut()) {
+            (Some(text), Some(link)) => {
+                link.text.push_str(text);
+                continue;
+            }
+            (Some(text), None) => {
+                plain.push_str(text);
+                continue;
+            }
+            (None, _) => bare_urls(&mut plain, &mut links),
+        }
+        match event {
+            Event::Start(Tag::Link { dest_url, .. }) => open = Some(Link::to(&dest_url, false)),
+            Event::Start(Tag::Image { dest_url, .. }) => open = Some(Link::to(&dest_url, true)),
+            Event::End(TagEnd::Link | TagEnd::Image) => {
+                if let Some(mut link) = open.take() {
+                    if link.text.is_empty() {
+                        link.text.clone_from(&link.url);
+                    }
+                    links.push(link);
+                }
+            }
+            _ => {}
+        }
+    }
+    bare_urls(&mut plain, &mut links);
+
+    links
+}
+
+impl Link {
+    fn to(url: &str, image: bool) -> Self {
+        Self {
+            text: String::new(),
+            url: url.to_string(),
+            image,
+        }
+    }
+}
+
+/// Moves the URLs pasted into `plain` onto `links` and empties it.
+fn bare_urls(plain: &mut String, links: &mut Vec<Link>) {
+    for word in plain.split_whitespace() {
+        let Some(start) = word.find("https://").or_else(|| word.find("http://")) else {
+            continue;
+        };
+        let url = word[start..]
+            .trim_end_matches(['.', ',', ';', ':', '!', '?', ')', ']', '>', '\'', '"']);
+        if !url.ends_with("://") {
+            links.push(Link {
+                text: url.to_string(),
+                ..Link::to(url, false)
+            });
+        }
+    }
+    plain.clear();
+}
+
 struct ListCtx {
     next: Option<u64>,
 }
@@ -44,6 +128,7 @@ struct Writer {
     quote_depth: usize,
     pending_marker: Option<Vec<Span<'static>>>,
     code_buf: Option<String>,
+    code_lang: Option<Lang>,
     table: Option<Table>,
     cell: Option<Vec<Span<'static>>>,
 }
@@ -60,6 +145,7 @@ impl Writer {
             quote_depth: 0,
             pending_marker: None,
             code_buf: None,
+            code_lang: None,
             table: None,
             cell: None,
         }
@@ -242,10 +328,14 @@ impl Writer {
                 }
                 self.quote_depth += 1;
             }
-            Tag::CodeBlock(_) => {
+            Tag::CodeBlock(kind) => {
                 self.flush_line();
                 self.gap();
                 self.code_buf = Some(String::new());
+                self.code_lang = match kind {
+                    CodeBlockKind::Fenced(info) => Lang::from_fence(&info),
+                    CodeBlockKind::Indented => None,
+                };
             }
             Tag::List(start) => {
                 self.flush_line();
@@ -309,12 +399,24 @@ impl Writer {
             TagEnd::CodeBlock => {
                 let buf = self.code_buf.take().unwrap_or_default();
                 let content = buf.strip_suffix('\n').unwrap_or(&buf);
+                let mut highlighter = self.code_lang.take().map(Highlighter::new);
                 for line in content.split('\n') {
                     self.open_line();
                     self.spans
                         .push(Span::styled("▏ ".to_string(), dim_style(self.base)));
-                    self.spans
-                        .push(Span::styled(line.to_string(), code_style(self.base)));
+                    match &mut highlighter {
+                        Some(highlighter) => {
+                            for (token, text) in highlighter.line(line) {
+                                self.spans.push(Span::styled(
+                                    text.to_string(),
+                                    token_style(self.base, token),
+                                ));
+                            }
+                        }
+                        None => self
+                            .spans
+                            .push(Span::styled(line.to_string(), code_style(self.base))),
+                    }
                     self.flush_line();
                 }
             }
@@ -452,6 +554,19 @@ mod tests {
         );
     }
 
+    #[test]
+    fn fenced_languages_are_highlighted_and_others_stay_flat() {
+        let rust = "```rust\nfn main() { \"hi\" }\n```";
+        assert_eq!(lines(rust), vec!["▏ fn main() { \"hi\" }"]);
+        assert!(span_style(rust, "fn").add_modifier.contains(Modifier::BOLD));
+        assert_ne!(span_style(rust, "hi").fg, span_style(rust, "main").fg);
+
+        let unknown = "```cobol\nfn main\n```";
+        assert!(!span_style(unknown, "fn main")
+            .add_modifier
+            .contains(Modifier::BOLD));
+    }
+
     #[test]
     fn links_render_their_text_without_the_url() {
         assert_eq!(
@@ -598,4 +713,34 @@ mod tests {
     fn a_heading_is_separated_from_a_following_list() {
         assert_eq!(lines("## Steps\n- first"), vec!["Steps", "", "• first"]);
     }
+
+    #[test]
+    fn links_images_and_pasted_urls_are_collected_in_order() {
+        let found = links(
+            "See [the spec](https://example.com/spec) and ![shot](https://cdn.example.com/a.png).\n\n\
+             Logs at https://logs.example.com/run/42, <https://auto.example.com>",
+        );
+        let found: Vec<_> = found
+            .iter()
+            .map(|link| (link.text.as_str(), link.url.as_str(), link.image))
+            .collect();
+
+        assert_eq!(
+            found,
+            vec![
+                ("the spec", "https://example.com/spec", false),
+                ("shot", "https://cdn.example.com/a.png", true),
+                (
+                    "https://logs.example.com/run/42",
+                    "https://logs.example.com/run/42",
+                    false
+                ),
+                (
+                    "https://auto.example.com",
+                    "https://auto.example.com",
+                    false
+                ),
+            ]
+        );
+    }
 }
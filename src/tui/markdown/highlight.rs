@@ -0,0 +1,653 @@
+use std::ops::Range;
+
+/// Fence languages worth colouring. Anything else stays flat.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(super) enum Lang {
+    Rust,
+    TypeScript,
+    Python,
+    Sql,
+    Shell,
+    Json,
+    Yaml,
+    Diff,
+}
+
+impl Lang {
+    /// Reads the first word of a fence's info string, as in ```` ```ts ````
+    /// or ```` ```rust,ignore ````.
+    pub(super) fn from_fence(info: &str) -> Option<Lang> {
+        let name = info
+            .split(|c: char| c.is_whitespace() || c == ',' || c == '{')
+            .next()?
+            .to_ascii_lowercase();
+
+        let lang = match name.as_str() {
+            "rust" | "rs" => Lang::Rust,
+            "typescript" | "ts" | "tsx" | "javascript" | "js" | "jsx" => Lang::TypeScript,
+            "python" | "py" => Lang::Python,
+            "sql" | "psql" | "postgresql" => Lang::Sql,
+            "sh" | "bash" | "shell" | "zsh" | "console" => Lang::Shell,
+            "json" | "jsonc" => Lang::Json,
+            "yaml" | "yml" => Lang::Yaml,
+            "diff" | "patch" => Lang::Diff,
+            _ => return None,
+        };
+
+        Some(lang)
+    }
+
+    fn grammar(self) -> &'static Grammar {
+        match self {
+            Lang::Rust => &RUST,
+            Lang::TypeScript => &TYPESCRIPT,
+            Lang::Python => &PYTHON,
+            Lang::Sql => &SQL,
+            Lang::Shell => &SHELL,
+            Lang::Json => &JSON,
+            Lang::Yaml => &YAML,
+            Lang::Diff => &PLAIN,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(super) enum Token {
+    Plain,
+    Keyword,
+    Type,
+    String,
+    Number,
+    Comment,
+    Added,
+    Removed,
+}
+
+struct Grammar {
+    keywords: &'static [&'static str],
+    types: &'static [&'static str],
+    /// Identifiers starting with a capital are taken for types.
+    capital_types: bool,
+    case_insensitive: bool,
+    line_comment: Option<&'static str>,
+    block_comment: Option<(&'static str, &'static str)>,
+    quotes: &'static [char],
+    /// Openers whose string may run over several lines.
+    long_strings: &'static [&'static str],
+    /// `"key":` and `key:` are keys rather than values.
+    keys: bool,
+    /// `$name` and `${name}` are variables.
+    variables: bool,
+}
+
+const PLAIN: Grammar = Grammar {
+    keywords: &[],
+    types: &[],
+    capital_types: false,
+    case_insensitive: false,
+    line_comment: None,
+    block_comment: None,
+    quotes: &[],
+    long_strings: &[],
+    keys: false,
+    variables: false,
+};
+
+const RUST: Grammar = Grammar {
+    keywords: &[
+        "as", "async", "await", "break", "const", "continue", "crate", "dyn", "else", "enum",
+        "extern", "false", "fn", "for", "if", "impl", "in", "let", "loop", "match", "mod", "move",
+        "mut", "pub", "ref", "return", "self", "Self", "static", "struct", "super", "trait",
+        "true", "type", "unsafe", "use", "where", "while",
+    ],
+    types: &[
+        "bool", "char", "str", "u8", "u16", "u32", "u64", "u128", "usize", "i8", "i16", "i32",
+        "i64", "i128", "isize", "f32", "f64",
+    ],
+    capital_types: true,
+    line_comment: Some("//"),
+    block_comment: Some(("/*", "*/")),
+    quotes: &['"'],
+    ..PLAIN
+};
+
+const TYPESCRIPT: Grammar = Grammar {
+    keywords: &[
+        "as",
+        "async",
+        "await",
+        "break",
+        "case",
+        "catch",
+        "class",
+        "const",
+        "continue",
+        "default",
+        "delete",
+        "do",
+        "else",
+        "enum",
+        "export",
+        "extends",
+        "false",
+        "finally",
+        "for",
+        "from",
+        "function",
+        "if",
+        "implements",
+        "import",
+        "in",
+        "instanceof",
+        "interface",
+        "let",
+        "new",
+        "null",
+        "of",
+        "private",
+        "protected",
+        "public",
+        "readonly",
+        "return",
+        "static",
+        "super",
+        "switch",
+        "this",
+        "throw",
+        "true",
+        "try",
+        "type",
+        "typeof",
+        "undefined",
+        "var",
+        "void",
+        "while",
+        "yield",
+    ],
+    types: &[
+        "any", "boolean", "never", "number", "object", "string", "symbol", "unknown",
+    ],
+    capital_types: true,
+    line_comment: Some("//"),
+    block_comment: Some(("/*", "*/")),
+    quotes: &['"', '\''],
+    long_strings: &["`"],
+    ..PLAIN
+};
+
+const PYTHON: Grammar = Grammar {
+    keywords: &[
+        "and", "as", "assert", "async", "await", "break", "class", "continue", "def", "del",
+        "elif", "else", "except", "False", "finally", "for", "from", "global", "if", "import",
+        "in", "is", "lambda", "None", "nonlocal", "not", "or", "pass", "raise", "return", "True",
+        "try", "while", "with", "yield",
+    ],
+    types: &[
+        "bool", "bytes", "dict", "float", "int", "list", "object", "set", "str", "tuple",
+    ],
+    capital_types: true,
+    line_comment: Some("#"),
+    quotes: &['"', '\''],
+    long_strings: &["\"\"\"", "'''"],
+    ..PLAIN
+};
+
+const SQL: Grammar = Grammar {
+    keywords: &[
+        "add",
+        "all",
+        "alter",
+        "and",
+        "as",
+        "asc",
+        "begin",
+        "between",
+        "by",
+        "case",
+        "commit",
+        "create",
+        "delete",
+        "desc",
+        "distinct",
+        "drop",
+        "else",
+        "end",
+        "exists",
+        "false",
+        "from",
+        "group",
+        "having",
+        "in",
+        "index",
+        "inner",
+        "insert",
+        "into",
+        "is",
+        "join",
+        "key",
+        "left",
+        "like",
+        "limit",
+        "not",
+        "null",
+        "offset",
+        "on",
+        "or",
+        "order",
+        "outer",
+        "primary",
+        "references",
+        "returning",
+        "right",
+        "rollback",
+        "select",
+        "set",
+        "table",
+        "then",
+        "true",
+        "union",
+        "update",
+        "values",
+        "when",
+        "where",
+        "with",
+    ],
+    types: &[
+        "bigint",
+        "boolean",
+        "char",
+        "date",
+        "decimal",
+        "int",
+        "integer",
+        "json",
+        "jsonb",
+        "numeric",
+        "serial",
+        "text",
+        "timestamp",
+        "timestamptz",
+        "uuid",
+        "varchar",
+    ],
+    case_insensitive: true,
+    line_comment: Some("--"),
+    block_comment: Some(("/*", "*/")),
+    quotes: &['\''],
+    ..PLAIN
+};
+
+const SHELL: Grammar = Grammar {
+    keywords: &[
+        "case", "do", "done", "elif", "else", "esac", "export", "fi", "for", "function", "if",
+        "in", "local", "return", "then", "until", "while",
+    ],
+    line_comment: Some("#"),
+    quotes: &['"', '\''],
+    variables: true,
+    ..PLAIN
+};
+
+const JSON: Grammar = Grammar {
+    keywords: &["true", "false", "null"],
+    quotes: &['"'],
+    keys: true,
+    ..PLAIN
+};
+
+const YAML: Grammar = Grammar {
+    keywords: &["true", "false", "null", "yes", "no", "on", "off"],
+    line_comment: Some("#"),
+    quotes: &['"', '\''],
+    keys: true,
+    ..PLAIN
+};
+
+/// Where the previous line left off, for comments and strings that span
+/// lines.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Carry {
+    Nothing,
+    Comment(&'static str),
+    String(&'static str),
+}
+
+/// Splits a code block into tokens one line at a time, remembering open
+/// comments and strings between lines.
+pub(super) struct Highlighter {
+    lang: Lang,
+    carry: Carry,
+}
+
+impl Highlighter {
+    pub(super) fn new(lang: Lang) -> Self {
+        Self {
+            lang,
+            carry: Carry::Nothing,
+        }
+    }
+
+    pub(super) fn line<'a>(&mut self, line: &'a str) -> Vec<(Token, &'a str)> {
+        if self.lang == Lang::Diff {
+            return vec![(diff_token(line), line)];
+        }
+
+        let mut out = Tokens::default();
+        let grammar = self.lang.grammar();
+        let mut at = 0;
+
+        while at < line.len() {
+            let rest = &line[at..];
+
+            if let Carry::Comment(close) | Carry::String(close) = self.carry {
+                let token = match self.carry {
+                    Carry::Comment(_) => Token::Comment,
+                    _ => Token::String,
+                };
+                let len = match find_close(rest, close) {
+                    Some(len) => {
+                        self.carry = Carry::Nothing;
+                        len
+                    }
+                    None => rest.len(),
+                };
+                at = out.push(token, at, len);
+                continue;
+            }
+
+            if let Some(prefix) = grammar.line_comment {
+                let spaced = prefix != "#"
+                    || line[..at]
+                        .chars()
+                        .next_back()
+                        .is_none_or(char::is_whitespace);
+                if rest.starts_with(prefix) && spaced {
+                    at = out.push(Token::Comment, at, rest.len());
+                    continue;
+                }
+            }
+
+            if let Some((open, close)) = grammar.block_comment {
+                if rest.starts_with(open) {
+                    self.carry = Carry::Comment(close);
+                    at = out.push(Token::Comment, at, open.len());
+                    continue;
+                }
+            }
+
+            if let Some(open) = grammar
+                .long_strings
+                .iter()
+                .find(|open| rest.starts_with(**open))
+            {
+                self.carry = Carry::String(open);
+                at = out.push(Token::String, at, open.len());
+                continue;
+            }
+
+            let Some(c) = rest.chars().next() else {
+                break;
+            };
+
+            if grammar.quotes.contains(&c) {
+                let len = find_close(&rest[1..], &rest[..1]).map_or(rest.len(), |len| len + 1);
+                let token = if grammar.keys && is_key(&rest[len..]) {
+                    Token::Type
+                } else {
+                    Token::String
+                };
+                at = out.push(token, at, len);
+                continue;
+            }
+
+            if grammar.variables && c == '$' {
+                let len = variable_len(rest);
+                if len > 1 {
+                    at = out.push(Token::Type, at, len);
+                    continue;
+                }
+            }
+
+            if c.is_ascii_digit() {
+                let len = word_len(rest, |c| c.is_ascii_alphanumeric() || c == '_' || c == '.');
+                at = out.push(Token::Number, at, len);
+                continue;
+            }
+
+            if c.is_alphabetic() || c == '_' {
+                let len = word_len(rest, |c| {
+                    c.is_alphanumeric() || c == '_' || (grammar.keys && c == '-')
+                });
+                let token = if grammar.keys && is_key(&rest[len..]) && starts_line(&line[..at]) {
+                    Token::Type
+                } else {
+                    classify(grammar, &rest[..len])
+                };
+                at = out.push(token, at, len);
+                continue;
+            }
+
+            at = out.push(Token::Plain, at, c.len_utf8());
+        }
+
+        out.0
+            .into_iter()
+            .map(|(token, range)| (token, &line[range]))
+            .collect()
+    }
+}
+
+#[derive(Default)]
+struct Tokens(Vec<(Token, Range<usize>)>);
+
+impl Tokens {
+    /// Records `len` bytes from `at` and returns where the next token
+    /// starts. Neighbours of the same kind become one token.
+    fn push(&mut self, token: Token, at: usize, len: usize) -> usize {
+        let end = at + len;
+
+        match self.0.last_mut() {
+            Some((last, range)) if *last == token && range.end == at => range.end = end,
+            _ => self.0.push((token, at..end)),
+        }
+
+        end
+    }
+}
+
+fn diff_token(line: &str) -> Token {
+    if line.starts_with("+++") || line.starts_with("---") || line.starts_with("@@") {
+        Token::Type
+    } else if line.starts_with('+') {
+        Token::Added
+    } else if line.starts_with('-') {
+        Token::Removed
+    } else if line.starts_with("diff ") || line.starts_with("index ") {
+        Token::Comment
+    } else {
+        Token::Plain
+    }
+}
+
+fn classify(grammar: &Grammar, word: &str) -> Token {
+    let matches = |list: &[&str]| {
+        if grammar.case_insensitive {
+            list.iter().any(|entry| entry.eq_ignore_ascii_case(word))
+        } else {
+            list.contains(&word)
+        }
+    };
+
+    if matches(grammar.keywords) {
+        Token::Keyword
+    } else if matches(grammar.types)
+        || (grammar.capital_types && word.starts_with(|c: char| c.is_uppercase()))
+    {
+        Token::Type
+    } else {
+        Token::Plain
+    }
+}
+
+/// The byte just past `close`, skipping any escaped with a backslash.
+fn find_close(text: &str, close: &str) -> Option<usize> {
+    let mut escaped = false;
+
+    for (index, c) in text.char_indices() {
+        if escaped {
+            escaped = false;
+        } else if c == '\\' {
+            escaped = true;
+        } else if text[index..].starts_with(close) {
+            return Some(index + close.len());
+        }
+    }
+
+    None
+}
+
+fn word_len(text: &str, keep: impl Fn(char) -> bool) -> usize {
+    text.char_indices()
+        .find(|(_, c)| !keep(*c))
+        .map_or(text.len(), |(index, _)| index)
+}
+
+fn variable_len(text: &str) -> usize {
+    if text[1..].starts_with('{') {
+        return text.find('}').map_or(text.len(), |end| end + 1);
+    }
+
+    1 + word_len(&text[1..], |c| c.is_alphanumeric() || c == '_')
+}
+
+fn is_key(after: &str) -> bool {
+    let after = after.trim_start();
+    after.starts_with(':') && !after.starts_with("::")
+}
+
+/// YAML keys lead their line, after any indent or list dash.
+fn starts_line(before: &str) -> bool {
+    before
+        .trim_start()
+        .trim_start_matches('-')
+        .trim()
+        .is_empty()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn tokens(lang: Lang, code: &str) -> Vec<Vec<(Token, String)>> {
+        let mut highlighter = Highlighter::new(lang);
+
+        code.lines()
+            .map(|line| {
+                highlighter
+                    .line(line)
+                    .into_iter()
+                    .map(|(token, text)| (token, text.to_string()))
+                    .collect()
+            })
+            .collect()
+    }
+
+    fn kinds(lang: Lang, line: &str, token: Token) -> Vec<String> {
+        tokens(lang, line)
+            .concat()
+            .into_iter()
+            .filter(|(kind, _)| *kind == token)
+            .map(|(_, text)| text)
+            .collect()
+    }
+
+    #[test]
+    fn fence_names_and_aliases_pick_a_language() {
+        assert_eq!(Lang::from_fence("rust,ignore"), Some(Lang::Rust));
+        assert_eq!(Lang::from_fence("TSX"), Some(Lang::TypeScript));
+        assert_eq!(Lang::from_fence("yml"), Some(Lang::Yaml));
+        assert_eq!(Lang::from_fence("brainfuck"), None);
+        assert_eq!(Lang::from_fence(""), None);
+    }
+
+    #[test]
+    fn a_line_is_covered_exactly_once() {
+        let line = "let total: u32 = add(1, 2); // sum ✓";
+        let joined: String = tokens(Lang::Rust, line)
+            .concat()
+            .into_iter()
+            .map(|(_, text)| text)
+            .collect();
+
+        assert_eq!(joined, line);
+    }
+
+    #[test]
+    fn rust_keywords_types_strings_and_comments() {
+        let line = r#"pub fn name(&self) -> Option<String> { "a \"b\"" } // why"#;
+
+        assert_eq!(
+            kinds(Lang::Rust, line, Token::Keyword),
+            ["pub", "fn", "self"]
+        );
+        assert_eq!(kinds(Lang::Rust, line, Token::Type), ["Option", "String"]);
+        assert_eq!(kinds(Lang::Rust, line, Token::String), [r#""a \"b\"""#]);
+        assert_eq!(kinds(Lang::Rust, line, Token::Comment), ["// why"]);
+    }
+
+    #[test]
+    fn block_comments_and_long_strings_carry_across_lines() {
+        let rust = tokens(Lang::Rust, "/* one\ntwo */ let");
+        assert_eq!(rust[1][0], (Token::Comment, "two */".to_string()));
+        assert_eq!(rust[1].last().unwrap().0, Token::Keyword);
+
+        let python = tokens(Lang::Python, "x = \"\"\"\ndoc\n\"\"\"\ndef");
+        assert_eq!(python[1], [(Token::String, "doc".to_string())]);
+        assert_eq!(python[3], [(Token::Keyword, "def".to_string())]);
+    }
+
+    #[test]
+    fn sql_keywords_ignore_case() {
+        let line = "SELECT id FROM issues where title = 'x' -- note";
+
+        assert_eq!(
+            kinds(Lang::Sql, line, Token::Keyword),
+            ["SELECT", "FROM", "where"]
+        );
+        assert_eq!(kinds(Lang::Sql, line, Token::Comment), ["-- note"]);
+    }
+
+    #[test]
+    fn shell_variables_and_hash_only_after_space() {
+        let line = "echo \"$HOME\" ${PATH}#x $1 # done";
+
+        assert_eq!(kinds(Lang::Shell, line, Token::Type), ["${PATH}", "$1"]);
+        assert_eq!(kinds(Lang::Shell, line, Token::Comment), ["# done"]);
+    }
+
+    #[test]
+    fn json_and_yaml_keys_stand_apart_from_values() {
+        let json = r#"{"name": "oven", "hot": true, "temp": 240}"#;
+        assert_eq!(
+            kinds(Lang::Json, json, Token::Type),
+            [r#""name""#, r#""hot""#, r#""temp""#]
+        );
+        assert_eq!(kinds(Lang::Json, json, Token::String), [r#""oven""#]);
+        assert_eq!(kinds(Lang::Json, json, Token::Number), ["240"]);
+
+        let yaml = "  - run-on: ubuntu # ci";
+        assert_eq!(kinds(Lang::Yaml, yaml, Token::Type), ["run-on"]);
+        assert_eq!(kinds(Lang::Yaml, yaml, Token::Comment), ["# ci"]);
+    }
+
+    #[test]
+    fn diff_lines_take_their_marker() {
+        let lines = tokens(Lang::Diff, "@@ -1 +1 @@\n-old\n+new\n same");
+
+        let kinds: Vec<Token> = lines.iter().map(|line| line[0].0).collect();
+        assert_eq!(
+            kinds,
+            [Token::Type, Token::Removed, Token::Added, Token::Plain]
+        );
+    }
+}
@@ -2,6 +2,7 @@ use pulldown_cmark::HeadingLevel;
 use ratatui::style::Style;
 use ratatui::text::Span;
 
+use super::highlight::Token;
 use crate::tui::render::theme;
 
 pub(super) fn heading_style(base: Style, level: HeadingLevel) -> Style {
@@ -16,6 +17,19 @@ pub(super) fn code_style(base: Style) -> Style {
     base.patch(theme::code())
 }
 
+pub(super) fn token_style(base: Style, token: Token) -> Style {
+    base.patch(match token {
+        Token::Plain => theme::code(),
+        Token::Keyword => theme::code_keyword(),
+        Token::Type => theme::code_type(),
+        Token::String => theme::code_string(),
+        Token::Number => theme::code_number(),
+        Token::Comment => theme::code_comment(),
+        Token::Added => theme::code_added(),
+        Token::Removed => theme::code_removed(),
+    })
+}
+
 pub(super) fn mention_style(base: Style) -> Style {
     base.patch(theme::person())
 }
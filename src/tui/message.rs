@@ -1,14 +1,18 @@
+use serde::{Deserialize, Serialize};
+
+use super::drafts::Draft;
 use super::feed::{FeedKey, FeedRequest};
 use super::focus::Reveal;
 use super::overlay::Compose;
+use super::templates::Template;
 use crate::api::{
     CommentId, Credential, IssueDetail, IssueId, IssueRef, IssueSummary, IssueUpdate, Label,
     NotificationItem, Page, ReactionId, ReactionTarget, SavedView, Session, StateOption, Team,
-    TeamId, User,
+    TeamId, Timestamp, User,
 };
 use crate::store::{Account, PersistedCache};
 
-#[derive(Debug)]
+#[derive(Debug, Serialize, Deserialize)]
 pub enum Message {
     SessionLoaded(Session),
     FeedLoaded {
@@ -28,8 +32,12 @@ pub enum Message {
         detail: Box<IssueDetail>,
         reveal: Reveal,
     },
+    DetailPrefetched(Box<IssueDetail>),
     RecentLoaded(Vec<IssueSummary>),
     RecentCleared,
+    DraftsLoaded(Vec<Draft>),
+    RecentEmojiLoaded(Vec<String>),
+    TemplatesLoaded(Vec<Template>),
     StatesLoaded {
         team_id: TeamId,
         states: Vec<StateOption>,
@@ -46,14 +54,29 @@ pub enum Message {
         query: String,
         labels: Vec<Label>,
     },
+    IssuesFound {
+        query: String,
+        issues: Vec<IssueSummary>,
+    },
     IssueUpdated {
         id: IssueId,
     },
+    /// `draft` is what was sent, so the draft it came from can go.
     CommentPosted {
         id: IssueId,
+        draft: Box<ComposeRecovery>,
     },
     CommentEdited {
         id: IssueId,
+        draft: Box<ComposeRecovery>,
+    },
+    DescriptionEdited {
+        id: IssueId,
+        draft: Box<ComposeRecovery>,
+    },
+    EditConflict {
+        detail: Box<IssueDetail>,
+        draft: Box<ComposeRecovery>,
     },
     CommentDeleted {
         id: IssueId,
@@ -74,15 +97,60 @@ pub enum Message {
     RefreshFailed {
         workspace_key: String,
     },
+    /// The text an external editor left behind for the open `Editor`.
+    EditedExternally {
+        issue_id: IssueId,
+        body: String,
+    },
     Failed {
         target: FailureTarget,
         error: RequestError,
     },
 }
 
-#[derive(Debug, Clone)]
+impl Message {
+    /// The variant's name, for logs that must not pay to describe a page of
+    /// issues.
+    pub fn name(&self) -> &'static str {
+        match self {
+            Message::SessionLoaded(_) => "SessionLoaded",
+            Message::FeedLoaded { .. } => "FeedLoaded",
+            Message::InboxLoaded { .. } => "InboxLoaded",
+            Message::CustomViewsLoaded(_) => "CustomViewsLoaded",
+            Message::TeamsLoaded { .. } => "TeamsLoaded",
+            Message::DetailLoaded { .. } => "DetailLoaded",
+            Message::DetailPrefetched(_) => "DetailPrefetched",
+            Message::RecentLoaded(_) => "RecentLoaded",
+            Message::RecentCleared => "RecentCleared",
+            Message::DraftsLoaded(_) => "DraftsLoaded",
+            Message::RecentEmojiLoaded(_) => "RecentEmojiLoaded",
+            Message::TemplatesLoaded(_) => "TemplatesLoaded",
+            Message::StatesLoaded { .. } => "StatesLoaded",
+            Message::MembersLoaded { .. } => "MembersLoaded",
+            Message::UsersFound { .. } => "UsersFound",
+            Message::LabelsFound { .. } => "LabelsFound",
+            Message::IssuesFound { .. } => "IssuesFound",
+            Message::IssueUpdated { .. } => "IssueUpdated",
+            Message::CommentPosted { .. } => "CommentPosted",
+            Message::CommentEdited { .. } => "CommentEdited",
+            Message::DescriptionEdited { .. } => "DescriptionEdited",
+            Message::EditConflict { .. } => "EditConflict",
+            Message::CommentDeleted { .. } => "CommentDeleted",
+            Message::ReactionToggled { .. } => "ReactionToggled",
+            Message::AccountAdded { .. } => "AccountAdded",
+            Message::LoginSucceeded { .. } => "LoginSucceeded",
+            Message::TokenRefreshed { .. } => "TokenRefreshed",
+            Message::RefreshFailed { .. } => "RefreshFailed",
+            Message::EditedExternally { .. } => "EditedExternally",
+            Message::Failed { .. } => "Failed",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub enum RequestError {
     Unauthorised(String),
+    RateLimited(String),
     Other(String),
 }
 
@@ -92,13 +160,15 @@ impl From<&crate::api::ApiError> for RequestError {
 
         if error.is_auth() {
             RequestError::Unauthorised(message)
+        } else if error.is_rate_limited() {
+            RequestError::RateLimited(message)
         } else {
             RequestError::Other(message)
         }
     }
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub enum FailureTarget {
     Session,
     Feed(FeedKey),
@@ -106,20 +176,59 @@ pub enum FailureTarget {
     CustomViews,
     Teams,
     Detail,
+    Prefetch { id: IssueId },
     States { team_id: TeamId },
     Members { team_id: TeamId },
     UserSearch,
     LabelSearch,
+    IssueSearch,
     Compose(Box<ComposeRecovery>),
     Ephemeral,
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ComposeRecovery {
     pub issue_id: IssueId,
     pub team_id: TeamId,
     pub compose: Compose,
     pub body: String,
+    pub seen: Option<Timestamp>,
+}
+
+impl ComposeRecovery {
+    pub fn command(&self) -> ApiCommand {
+        let issue_id = self.issue_id.clone();
+        let team_id = self.team_id.clone();
+        let body = self.body.clone();
+
+        match &self.compose {
+            Compose::Comment => ApiCommand::CreateComment {
+                issue_id,
+                team_id,
+                body,
+                parent_id: None,
+            },
+            Compose::Reply { parent_id } => ApiCommand::CreateComment {
+                issue_id,
+                team_id,
+                body,
+                parent_id: Some(parent_id.clone()),
+            },
+            Compose::Edit { comment_id } => ApiCommand::UpdateComment {
+                issue_id,
+                team_id,
+                comment_id: comment_id.clone(),
+                body,
+                seen: self.seen,
+            },
+            Compose::Description => ApiCommand::UpdateDescription {
+                issue_id,
+                team_id,
+                body,
+                seen: self.seen,
+            },
+        }
+    }
 }
 
 #[derive(Debug, Clone)]
@@ -255,6 +364,9 @@ pub enum ApiCommand {
         target: IssueRef,
         reveal: Reveal,
     },
+    PrefetchDetail {
+        id: IssueId,
+    },
     LoadStates {
         team_id: TeamId,
     },
@@ -267,6 +379,10 @@ pub enum ApiCommand {
     SearchLabels {
         query: String,
     },
+    /// Issues for the editor's reference completion.
+    SearchIssues {
+        query: String,
+    },
     UpdateIssue {
         id: IssueId,
         update: IssueUpdate,
@@ -282,6 +398,13 @@ pub enum ApiCommand {
         team_id: TeamId,
         comment_id: CommentId,
         body: String,
+        seen: Option<Timestamp>,
+    },
+    UpdateDescription {
+        issue_id: IssueId,
+        team_id: TeamId,
+        body: String,
+        seen: Option<Timestamp>,
     },
     DeleteComment {
         issue_id: IssueId,
@@ -304,6 +427,11 @@ pub enum StoreCommand {
     LoadRecent,
     SaveRecent(Vec<IssueSummary>),
     ClearRecent,
+    LoadDrafts,
+    SaveDrafts(Vec<Draft>),
+    LoadRecentEmoji,
+    SaveRecentEmoji(Vec<String>),
+    LoadTemplates,
 }
 
 #[derive(Debug, Clone)]
@@ -315,10 +443,61 @@ pub enum PlatformCommand {
 #[derive(Debug, Clone)]
 pub enum RuntimeCommand {
     SwitchWorkspace(Box<Account>),
-    AddAccount { credential: Credential },
-    RefreshToken { workspace_key: String },
+    AddAccount {
+        credential: Credential,
+    },
+    RefreshToken {
+        workspace_key: String,
+    },
     Reconnect,
     BeginLogin,
+    /// Hand the terminal back to the shell and stop, as Ctrl-Z would.
+    Suspend,
+    Foreground(Foreground),
+    /// Open `body` in `$VISUAL` or `$EDITOR`, answering with
+    /// `Message::EditedExternally` once it exits.
+    EditExternally {
+        issue_id: IssueId,
+        body: String,
+    },
+}
+
+/// An external program that takes over the terminal until it exits, after
+/// which the TUI picks up where it left off.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Foreground {
+    pub program: String,
+    pub args: Vec<String>,
+    pub env: Vec<(String, String)>,
+    /// Wait for Enter before returning, so output that would otherwise be
+    /// wiped by the redraw can be read.
+    pub pause: bool,
+}
+
+/// The platform's shell and the flag that hands it a command line.
+#[cfg(unix)]
+const SHELL: (&str, &str) = ("sh", "-c");
+#[cfg(not(unix))]
+const SHELL: (&str, &str) = ("cmd", "/C");
+
+impl Foreground {
+    pub fn shell(line: &str) -> Self {
+        let (program, flag) = SHELL;
+
+        Self {
+            program: program.into(),
+            args: vec![flag.into(), line.into()],
+            env: Vec::new(),
+            pause: true,
+        }
+    }
+
+    pub fn describe(&self) -> String {
+        match self.args.as_slice() {
+            [flag, line] if (self.program.as_str(), flag.as_str()) == SHELL => line.clone(),
+            _ => self.program.clone(),
+        }
+    }
 }
 
 impl ApiCommand {
@@ -330,6 +509,7 @@ impl ApiCommand {
             ApiCommand::LoadCustomViews => FailureTarget::CustomViews,
             ApiCommand::LoadTeams => FailureTarget::Teams,
             ApiCommand::LoadDetail { .. } => FailureTarget::Detail,
+            ApiCommand::PrefetchDetail { id } => FailureTarget::Prefetch { id: id.clone() },
             ApiCommand::LoadStates { team_id } => FailureTarget::States {
                 team_id: team_id.clone(),
             },
@@ -338,6 +518,7 @@ impl ApiCommand {
             },
             ApiCommand::SearchUsers { .. } => FailureTarget::UserSearch,
             ApiCommand::SearchLabels { .. } => FailureTarget::LabelSearch,
+            ApiCommand::SearchIssues { .. } => FailureTarget::IssueSearch,
             ApiCommand::CreateComment {
                 issue_id,
                 team_id,
@@ -353,12 +534,14 @@ impl ApiCommand {
                     None => Compose::Comment,
                 },
                 body: body.clone(),
+                seen: None,
             })),
             ApiCommand::UpdateComment {
                 issue_id,
                 team_id,
                 comment_id,
                 body,
+                seen,
             } => FailureTarget::Compose(Box::new(ComposeRecovery {
                 issue_id: issue_id.clone(),
                 team_id: team_id.clone(),
@@ -366,6 +549,19 @@ impl ApiCommand {
                     comment_id: comment_id.clone(),
                 },
                 body: body.clone(),
+                seen: *seen,
+            })),
+            ApiCommand::UpdateDescription {
+                issue_id,
+                team_id,
+                body,
+                seen,
+            } => FailureTarget::Compose(Box::new(ComposeRecovery {
+                issue_id: issue_id.clone(),
+                team_id: team_id.clone(),
+                compose: Compose::Description,
+                body: body.clone(),
+                seen: *seen,
             })),
             ApiCommand::UpdateIssue { .. }
             | ApiCommand::DeleteComment { .. }
@@ -1,26 +1,34 @@
 pub mod action;
 pub mod app;
 pub mod cache;
+pub mod crash;
 pub mod display;
+pub mod drafts;
 pub mod emoji;
 pub mod event;
 pub mod feed;
 pub mod focus;
+pub mod foreground;
+pub mod journal;
 pub mod layout;
 pub mod markdown;
 pub mod message;
 pub mod overlay;
 pub mod platform;
+pub mod prefetch;
 pub mod render;
 pub mod run;
 pub mod saved_views;
+pub mod script;
 pub mod spinner;
 pub mod status;
 pub mod team;
+pub mod templates;
+pub mod terminal;
 pub mod update;
 pub mod view;
 pub mod workspace;
 
 pub use app::App;
 pub use render::{render, render_styled_to_string, render_to_string};
-pub use run::run;
+pub use run::{run, run_fixture};
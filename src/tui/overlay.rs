@@ -1,14 +1,23 @@
+use std::collections::HashSet;
 use std::num::NonZeroUsize;
 
+mod vi;
+
+pub use vi::{EditorKeys, Mode as ViMode, Registers, Reply as ViReply, Vi};
+
 use ratatui::widgets::ListState;
+use serde::{Deserialize, Serialize};
 
 use super::action::{self, Action};
+use super::drafts::Draft;
 use super::emoji::{self, PaletteEmoji};
 use super::focus::{Direction, Edge, Focus};
-use super::message::Effect;
+use super::markdown::Link;
+use super::message::{ComposeRecovery, Effect};
+use super::templates::Template;
 use crate::api::{
-    CommentId, IssueId, Label, LabelId, Priority, Reaction, ReactionTarget, StateId, StateOption,
-    TeamId, User, UserId,
+    CommentId, IssueDetail, IssueId, IssueSummary, Label, LabelId, Priority, Reaction,
+    ReactionTarget, StateId, StateOption, TeamId, Timestamp, User, UserId,
 };
 use crate::store::Account;
 
@@ -234,6 +243,34 @@ pub struct Confirm {
     pub command: Effect,
 }
 
+/// An edit that lost a race: `theirs` is what the issue holds now and
+/// `updated_at` is the stamp a re-edit is seeded from.
+pub struct Conflict {
+    pub draft: ComposeRecovery,
+    pub theirs: String,
+    pub updated_at: Timestamp,
+}
+
+impl Conflict {
+    pub fn new(draft: ComposeRecovery, current: &IssueDetail) -> Self {
+        let theirs = match &draft.compose {
+            Compose::Description => current.description.clone(),
+            Compose::Edit { comment_id } => current
+                .comments
+                .iter()
+                .find(|comment| &comment.id == comment_id)
+                .map(|comment| comment.body.clone()),
+            Compose::Comment | Compose::Reply { .. } => None,
+        };
+
+        Self {
+            draft,
+            theirs: theirs.unwrap_or_default(),
+            updated_at: current.updated_at,
+        }
+    }
+}
+
 pub enum MenuRow {
     Header(&'static str),
     Item {
@@ -406,10 +443,6 @@ pub struct Find {
 pub enum InputPurpose {
     Jump,
     Search,
-    CustomReaction {
-        issue_id: IssueId,
-        target: ReactionTarget,
-    },
     AssignSearch {
         issue: IssueId,
         label: String,
@@ -417,6 +450,7 @@ pub enum InputPurpose {
     },
     AddWorkspaceKey,
     AddWorkspaceEnvVar,
+    RunCommand,
 }
 
 pub enum WorkspaceRow {
@@ -468,6 +502,131 @@ impl Workspaces {
     }
 }
 
+pub struct DraftList {
+    pub drafts: Vec<Draft>,
+    pub state: ListState,
+}
+
+impl DraftList {
+    pub fn new(drafts: &[Draft]) -> Self {
+        Self {
+            drafts: drafts.to_vec(),
+            state: ListState::default().with_selected(Some(0)),
+        }
+    }
+
+    pub fn selected(&self) -> Option<&Draft> {
+        self.state
+            .selected()
+            .and_then(|index| self.drafts.get(index))
+    }
+
+    /// Drops the highlighted draft from the list, returning it.
+    pub fn remove_selected(&mut self) -> Option<Draft> {
+        let index = self.state.selected()?;
+        if index >= self.drafts.len() {
+            return None;
+        }
+
+        let draft = self.drafts.remove(index);
+        self.state
+            .select(Some(index.min(self.drafts.len().saturating_sub(1))));
+
+        Some(draft)
+    }
+}
+
+/// Keys labels are made of, home row first.
+const HINT_KEYS: &[u8] = b"asdfghjklqwertyuiopzxcvbnm";
+
+#[derive(Debug, Clone)]
+pub struct LinkHint {
+    pub label: String,
+    pub link: Link,
+    /// Where the link was written: the description or a comment's author.
+    pub source: String,
+}
+
+/// Every link in an issue under a short label. Typing a label follows its
+/// link; typing it in capitals yanks the URL instead.
+pub struct LinkHints {
+    pub hints: Vec<LinkHint>,
+    pub typed: String,
+    pub yank: bool,
+}
+
+impl LinkHints {
+    /// Labels `links` in order, keeping the first of any repeated URL.
+    /// Beyond one letter's worth every label takes two, so no label is
+    /// the start of another.
+    pub fn new(links: Vec<(String, Link)>) -> Self {
+        let mut seen = HashSet::new();
+        let links: Vec<_> = links
+            .into_iter()
+            .filter(|(_, link)| seen.insert(link.url.clone()))
+            .take(HINT_KEYS.len() * HINT_KEYS.len())
+            .collect();
+        let wide = links.len() > HINT_KEYS.len();
+
+        let hints = links
+            .into_iter()
+            .enumerate()
+            .map(|(index, (source, link))| LinkHint {
+                label: hint_label(index, wide),
+                link,
+                source,
+            })
+            .collect();
+
+        Self {
+            hints,
+            typed: String::new(),
+            yank: false,
+        }
+    }
+
+    /// Takes a typed key, returning the hint once a whole label is in.
+    /// A key that leads nowhere starts the label over.
+    pub fn type_char(&mut self, c: char) -> Option<LinkHint> {
+        if !c.is_ascii_alphabetic() {
+            return None;
+        }
+        if self.typed.is_empty() {
+            self.yank = c.is_ascii_uppercase();
+        }
+        self.typed.push(c.to_ascii_lowercase());
+
+        if !self
+            .hints
+            .iter()
+            .any(|hint| hint.label.starts_with(&self.typed))
+        {
+            self.typed.clear();
+            return None;
+        }
+
+        self.hints
+            .iter()
+            .find(|hint| hint.label == self.typed)
+            .cloned()
+    }
+
+    pub fn erase(&mut self) {
+        self.typed.pop();
+    }
+}
+
+fn hint_label(index: usize, wide: bool) -> String {
+    let key = |index: usize| char::from(HINT_KEYS[index]);
+    if wide {
+        [key(index / HINT_KEYS.len()), key(index % HINT_KEYS.len())]
+            .iter()
+            .collect()
+    } else {
+        key(index).to_string()
+    }
+}
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum Section {
     Current,
@@ -614,6 +773,60 @@ impl Reactions {
     }
 }
 
+/// Reacting with any emoji rather than just the palette, searched by
+/// shortcode.
+pub struct EmojiPicker {
+    pub issue_id: IssueId,
+    pub target: ReactionTarget,
+    pub query: String,
+    pub results: Vec<PaletteEmoji>,
+    pub state: ListState,
+    recent: Vec<String>,
+}
+
+impl EmojiPicker {
+    pub fn new(issue_id: IssueId, target: ReactionTarget, recent: &[String]) -> Self {
+        Self {
+            issue_id,
+            target,
+            query: String::new(),
+            results: emoji::search("", recent),
+            state: ListState::default().with_selected(Some(0)),
+            recent: recent.to_vec(),
+        }
+    }
+
+    pub fn type_char(&mut self, c: char) {
+        self.query.push(c);
+        self.refresh();
+    }
+
+    pub fn erase(&mut self) {
+        self.query.pop();
+        self.refresh();
+    }
+
+    fn refresh(&mut self) {
+        self.results = emoji::search(&self.query, &self.recent);
+        self.state.select(Some(0));
+    }
+
+    /// The highlighted shortcode, or what was typed when nothing matches,
+    /// so a workspace's own emoji can still be used by name.
+    pub fn chosen(&self) -> Option<String> {
+        let selected = self
+            .state
+            .selected()
+            .and_then(|index| self.results.get(index));
+
+        match selected {
+            Some(entry) => Some(entry.name.to_string()),
+            None => Some(self.query.trim().trim_matches(':').to_string())
+                .filter(|name| !name.is_empty()),
+        }
+    }
+}
+
 pub struct Input {
     pub purpose: InputPurpose,
     pub prompt: &'static str,
@@ -669,53 +882,78 @@ impl Input {
     }
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub enum Cell {
     Char(char),
     Mention(Mention),
+    Reference(Reference),
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub struct Mention {
     pub display: String,
     pub url: String,
 }
 
-#[derive(Debug, Clone, Copy)]
+/// A link to another issue. Posted as the issue's URL, which Linear shows
+/// as a reference to it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Reference {
+    pub identifier: String,
+    pub url: String,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
 pub struct Position {
     pub row: usize,
     pub col: usize,
 }
 
+/// What the completion menu offers.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Completion {
+    People,
+    Issues,
+    Emoji,
+    Templates,
+}
+
 pub struct MentionMenu {
     anchor: Position,
+    pub kind: Completion,
     pub query: String,
     pub state: ListState,
+    /// Ticks since the query last changed, while a search waits to go out.
+    search_idle: Option<u32>,
 }
 
 impl MentionMenu {
-    fn new(anchor: Position) -> Self {
+    fn new(anchor: Position, kind: Completion) -> Self {
         Self {
             anchor,
+            kind,
             query: String::new(),
             state: ListState::default().with_selected(Some(0)),
+            search_idle: None,
         }
     }
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub enum Compose {
     Comment,
     Reply { parent_id: CommentId },
     Edit { comment_id: CommentId },
+    Description,
 }
 
 impl Compose {
-    fn title(&self) -> &'static str {
+    pub fn title(&self) -> &'static str {
         match self {
             Compose::Comment => "Comment",
             Compose::Reply { .. } => "Reply",
             Compose::Edit { .. } => "Edit",
+            Compose::Description => "Description",
         }
     }
 }
@@ -728,8 +966,67 @@ pub struct Editor {
     row: usize,
     col: usize,
     pub compose: Compose,
+    pub seen: Option<Timestamp>,
     members: Vec<User>,
+    /// Issues the workspace already holds, offered before search answers.
+    issues: Vec<IssueSummary>,
+    recent_emoji: Vec<String>,
+    /// Saved replies, already filled in for this issue.
+    templates: Vec<Template>,
     mention: Option<MentionMenu>,
+    vi: Option<Box<Vi>>,
+    history: Box<History>,
+    preview: bool,
+    /// The text the editor opened with, so only real changes become drafts.
+    opened_with: String,
+}
+
+/// How many steps undo reaches back.
+const HISTORY: usize = 200;
+
+/// Shortcode completion shows the best few; the picker has the rest.
+const EMOJI_CANDIDATES: usize = 20;
+
+/// Quiet ticks before issue completion searches for what was typed.
+pub const ISSUE_SEARCH_IDLE_TICKS: u32 = 3;
+
+/// Issues completion holds; the oldest answers make way for new ones.
+const ISSUE_CAP: usize = 200;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum EditKind {
+    Typing,
+    Erasing,
+    Other,
+}
+
+#[derive(Clone)]
+struct Snapshot {
+    lines: Vec<Vec<Cell>>,
+    row: usize,
+    col: usize,
+}
+
+#[derive(Default)]
+struct History {
+    undo: Vec<Snapshot>,
+    redo: Vec<Snapshot>,
+    /// The last edit and where it left the cursor. Typing on from there
+    /// joins the same undo step rather than starting one per letter.
+    last: Option<(EditKind, Position)>,
+    /// The buffer as a vi command found it. Everything up to the command's
+    /// end, insert mode included, undoes as one step.
+    open: Option<Snapshot>,
+}
+
+impl History {
+    fn push(&mut self, before: Snapshot) {
+        self.undo.push(before);
+        if self.undo.len() > HISTORY {
+            self.undo.remove(0);
+        }
+        self.redo.clear();
+    }
 }
 
 impl Editor {
@@ -742,8 +1039,16 @@ impl Editor {
             row: 0,
             col: 0,
             compose,
+            seen: None,
             members: Vec::new(),
+            issues: Vec::new(),
+            recent_emoji: Vec::new(),
+            templates: Vec::new(),
             mention: None,
+            vi: None,
+            history: Box::default(),
+            preview: false,
+            opened_with: String::new(),
         }
     }
 
@@ -758,10 +1063,103 @@ impl Editor {
         editor.row = editor.lines.len() - 1;
         editor.col = editor.lines[editor.row].len();
         editor.settle();
+        editor.opened_with = editor.text();
 
         editor
     }
 
+    /// Swaps the whole buffer for `body`, as written by an external editor.
+    /// Member profile URLs become mentions and known issue URLs references
+    /// again, so a round trip through `text` leaves them as they were.
+    pub fn replace_text(&mut self, body: &str) {
+        self.close_mention();
+
+        self.edit(EditKind::Other, |editor| {
+            editor.lines = body.split('\n').map(|line| editor.cells(line)).collect();
+            editor.row = editor.lines.len() - 1;
+            editor.col = editor.lines[editor.row].len();
+            editor.settle();
+        });
+    }
+
+    /// A bracketed paste, as a single undo step. Line endings are
+    /// normalised and member profile URLs become mentions, as they do
+    /// coming back from an external editor.
+    pub fn paste(&mut self, text: &str) {
+        self.close_mention();
+
+        let text = text.replace("\r\n", "\n").replace('\r', "\n");
+        let cells: Vec<Vec<Cell>> = text.split('\n').map(|line| self.cells(line)).collect();
+
+        self.edit(EditKind::Other, |editor| {
+            let end = editor.insert_lines(editor.position(), &cells);
+            editor.row = end.row;
+            editor.col = end.col;
+            editor.settle();
+        });
+    }
+
+    fn cells(&self, line: &str) -> Vec<Cell> {
+        let mut cells = Vec::new();
+        let mut rest = line;
+
+        while let Some(c) = rest.chars().next() {
+            let link = if rest.starts_with("http") {
+                self.link_at(rest)
+            } else {
+                None
+            };
+
+            match link {
+                Some((len, cell)) => {
+                    rest = &rest[len..];
+                    cells.push(cell);
+                }
+                None => {
+                    cells.push(Cell::Char(c));
+                    rest = &rest[c.len_utf8()..];
+                }
+            }
+        }
+
+        cells
+    }
+
+    /// The mention or reference `rest` starts with, and its length. The
+    /// longest match wins, so `…/profiles/sam` never swallows the start of
+    /// `…/profiles/samantha`.
+    fn link_at(&self, rest: &str) -> Option<(usize, Cell)> {
+        let starts = |url: &str| !url.is_empty() && rest.starts_with(url);
+        let member = self
+            .members
+            .iter()
+            .filter(|user| starts(&user.url))
+            .max_by_key(|user| user.url.len());
+        let issue = self
+            .issues
+            .iter()
+            .filter(|issue| starts(&issue.url))
+            .max_by_key(|issue| issue.url.len());
+
+        match (member, issue) {
+            (Some(user), issue) if issue.is_none_or(|issue| user.url.len() > issue.url.len()) => {
+                let mention = Mention {
+                    display: user.display_name.clone(),
+                    url: user.url.clone(),
+                };
+                Some((user.url.len(), Cell::Mention(mention)))
+            }
+            (_, Some(issue)) => {
+                let reference = Reference {
+                    identifier: issue.identifier.clone(),
+                    url: issue.url.clone(),
+                };
+                Some((issue.url.len(), Cell::Reference(reference)))
+            }
+            _ => None,
+        }
+    }
+
     fn settle(&mut self) {
         if self.lines.is_empty() {
             self.lines.push(Vec::new());
@@ -785,6 +1183,84 @@ impl Editor {
         &self.lines
     }
 
+    fn position(&self) -> Position {
+        Position {
+            row: self.row,
+            col: self.col,
+        }
+    }
+
+    fn snapshot(&self) -> Snapshot {
+        Snapshot {
+            lines: self.lines.clone(),
+            row: self.row,
+            col: self.col,
+        }
+    }
+
+    fn restore(&mut self, snapshot: Snapshot) {
+        self.close_mention();
+        self.lines = snapshot.lines;
+        self.row = snapshot.row;
+        self.col = snapshot.col;
+        self.history.last = None;
+        self.settle();
+    }
+
+    /// Runs one edit, filing the buffer as it was before as an undo step
+    /// unless the edit carries on from the last one.
+    fn edit(&mut self, kind: EditKind, apply: impl FnOnce(&mut Self)) {
+        let before = self.snapshot();
+        apply(self);
+
+        if self.lines == before.lines {
+            return;
+        }
+
+        let start = Position {
+            row: before.row,
+            col: before.col,
+        };
+        let carries_on = kind != EditKind::Other && self.history.last == Some((kind, start));
+        if self.history.open.is_none() && !carries_on {
+            self.history.push(before);
+        }
+        self.history.last = Some((kind, self.position()));
+    }
+
+    fn begin_change(&mut self) {
+        self.history.open = Some(self.snapshot());
+        self.history.last = None;
+    }
+
+    fn end_change(&mut self) {
+        if let Some(before) = self.history.open.take() {
+            if before.lines != self.lines {
+                self.history.push(before);
+            }
+        }
+        self.history.last = None;
+    }
+
+    /// False once there is nothing left to undo.
+    pub fn undo(&mut self) -> bool {
+        self.end_change();
+
+        let Some(before) = self.history.undo.pop() else {
+            return false;
+        };
+        self.history.redo.push(self.snapshot());
+        self.restore(before);
+        true
+    }
+
+    pub fn redo(&mut self) {
+        if let Some(after) = self.history.redo.pop() {
+            self.history.undo.push(self.snapshot());
+            self.restore(after);
+        }
+    }
+
     pub fn row(&self) -> usize {
         self.row
     }
@@ -797,17 +1273,18 @@ impl Editor {
         self.mention.as_ref()
     }
 
+    pub fn previewing(&self) -> bool {
+        self.preview
+    }
+
+    pub fn set_preview(&mut self, on: bool) {
+        self.preview = on;
+    }
+
     pub fn text(&self) -> String {
         self.lines
             .iter()
-            .map(|line| {
-                line.iter()
-                    .map(|cell| match cell {
-                        Cell::Char(c) => c.to_string(),
-                        Cell::Mention(mention) => mention.url.clone(),
-                    })
-                    .collect::<String>()
-            })
+            .map(|line| line_text(line))
             .collect::<Vec<_>>()
             .join("\n")
     }
@@ -816,44 +1293,112 @@ impl Editor {
         self.lines.iter().all(|line| line.is_empty())
     }
 
+    pub fn modified(&self) -> bool {
+        self.text() != self.opened_with
+    }
+
+    /// The text a restored draft stands in for, so undoing everything the
+    /// draft changed counts as unmodified again.
+    pub fn set_original(&mut self, original: &str) {
+        self.opened_with = original.into();
+    }
+
     fn line_len(&self, row: usize) -> usize {
         self.lines[row].len()
     }
 
     pub fn insert_char(&mut self, c: char) {
-        self.lines[self.row].insert(self.col, Cell::Char(c));
-        self.col += 1;
-        self.settle();
+        self.edit(EditKind::Typing, |editor| {
+            editor.lines[editor.row].insert(editor.col, Cell::Char(c));
+            editor.col += 1;
+            editor.settle();
+        });
+
+        // Each word typed is its own undo step.
+        if c.is_whitespace() {
+            self.history.last = None;
+        }
     }
 
-    fn insert_mention(&mut self, display: String, url: String) {
-        self.lines[self.row].insert(self.col, Cell::Mention(Mention { display, url }));
-        self.col += 1;
-        self.settle();
+    pub fn newline(&mut self) {
+        self.close_mention();
+
+        self.edit(EditKind::Other, |editor| {
+            let tail = editor.lines[editor.row].split_off(editor.col);
+
+            editor.lines.insert(editor.row + 1, tail);
+            editor.row += 1;
+            editor.col = 0;
+            editor.settle();
+        });
     }
 
-    pub fn newline(&mut self) {
+    pub fn backspace(&mut self) {
+        self.edit(EditKind::Erasing, |editor| {
+            if editor.col > 0 {
+                editor.col -= 1;
+                editor.lines[editor.row].remove(editor.col);
+            } else if editor.row > 0 {
+                let current = editor.lines.remove(editor.row);
+
+                editor.row -= 1;
+                editor.col = editor.line_len(editor.row);
+                editor.lines[editor.row].extend(current);
+            }
+            editor.settle();
+        });
+    }
+
+    /// Erases back to the start of the word, taking a mention whole.
+    pub fn erase_word(&mut self) {
         self.close_mention();
 
-        let tail = self.lines[self.row].split_off(self.col);
+        self.edit(EditKind::Other, |editor| {
+            let end = editor.position();
+            let start = editor.word_back(end, false);
+            editor.remove(start, end);
+            editor.row = start.row;
+            editor.col = start.col;
+            editor.settle();
+        });
+    }
+
+    /// Erases to the start of the next word, or only the line break when
+    /// the cursor is at the end of a line.
+    pub fn erase_word_forward(&mut self) {
+        self.close_mention();
+
+        self.edit(EditKind::Other, |editor| {
+            let start = editor.position();
+            let len = editor.line_len(start.row);
+            let end = match editor.word_start(start, false) {
+                end if end.row > start.row && start.col < len => Position {
+                    row: start.row,
+                    col: len,
+                },
+                end if end.row > start.row => editor.next(start).unwrap_or(start),
+                end => end,
+            };
+            editor.remove(start, end);
+            editor.settle();
+        });
+    }
+
+    pub fn move_word_left(&mut self) {
+        self.close_mention();
 
-        self.lines.insert(self.row + 1, tail);
-        self.row += 1;
-        self.col = 0;
+        let to = self.word_back(self.position(), false);
+        self.row = to.row;
+        self.col = to.col;
         self.settle();
     }
 
-    pub fn backspace(&mut self) {
-        if self.col > 0 {
-            self.col -= 1;
-            self.lines[self.row].remove(self.col);
-        } else if self.row > 0 {
-            let current = self.lines.remove(self.row);
+    pub fn move_word_right(&mut self) {
+        self.close_mention();
 
-            self.row -= 1;
-            self.col = self.line_len(self.row);
-            self.lines[self.row].extend(current);
-        }
+        let to = self.word_start(self.position(), false);
+        self.row = to.row;
+        self.col = to.col;
         self.settle();
     }
 
@@ -905,7 +1450,7 @@ impl Editor {
         {
             None => true,
             Some(Cell::Char(c)) => c.is_whitespace(),
-            Some(Cell::Mention(_)) => true,
+            Some(Cell::Mention(_) | Cell::Reference(_)) => true,
         }
     }
 
@@ -926,28 +1471,227 @@ impl Editor {
             .collect()
     }
 
+    pub fn set_issues(&mut self, mut issues: Vec<IssueSummary>) {
+        issues.truncate(ISSUE_CAP);
+        self.issues = issues;
+    }
+
+    /// Search answers lead, ahead of the issues already known.
+    pub fn add_issues(&mut self, found: Vec<IssueSummary>) {
+        let known = std::mem::take(&mut self.issues);
+        self.issues = found;
+        for issue in known {
+            if !self.issues.iter().any(|found| found.id == issue.id) {
+                self.issues.push(issue);
+            }
+        }
+        self.issues.truncate(ISSUE_CAP);
+
+        if let Some(mention) = &mut self.mention {
+            mention.state.select(Some(0));
+        }
+    }
+
+    /// Searches once typing pauses, rather than on every key. `wanted` is
+    /// false when the query is nothing worth searching for.
+    pub fn queue_issue_search(&mut self, wanted: bool) {
+        if let Some(menu) = &mut self.mention {
+            menu.search_idle = wanted.then_some(0);
+        }
+    }
+
+    /// The query to search for, once it has gone unchanged for long enough.
+    pub fn due_issue_search(&mut self) -> Option<String> {
+        let menu = self.mention.as_mut()?;
+        let idle = menu.search_idle.as_mut()?;
+
+        if *idle < ISSUE_SEARCH_IDLE_TICKS {
+            *idle += 1;
+            return None;
+        }
+
+        menu.search_idle = None;
+        Some(menu.query.clone())
+    }
+
+    pub fn issue_candidates(&self, query: &str) -> Vec<&IssueSummary> {
+        let needle = query.to_lowercase();
+
+        self.issues
+            .iter()
+            .filter(|issue| {
+                issue.identifier.to_lowercase().contains(&needle)
+                    || issue
+                        .title
+                        .as_deref()
+                        .is_some_and(|title| title.to_lowercase().contains(&needle))
+            })
+            .collect()
+    }
+
+    pub fn set_recent_emoji(&mut self, recent: &[String]) {
+        self.recent_emoji = recent.to_vec();
+    }
+
+    /// Nothing is offered until a letter follows the colon, so prose such
+    /// as a list introduced by `:` is left alone.
+    pub fn emoji_candidates(&self, query: &str) -> Vec<PaletteEmoji> {
+        if query.is_empty() {
+            return Vec::new();
+        }
+
+        let mut found = emoji::search(query, &self.recent_emoji);
+        found.truncate(EMOJI_CANDIDATES);
+        found
+    }
+
+    /// The emoji an accept would insert, so its use can be remembered.
+    pub fn selected_emoji(&self) -> Option<PaletteEmoji> {
+        let menu = self
+            .mention
+            .as_ref()
+            .filter(|menu| menu.kind == Completion::Emoji)?;
+
+        self.emoji_candidates(&menu.query)
+            .get(menu.state.selected()?)
+            .copied()
+    }
+
+    pub fn set_templates(&mut self, templates: Vec<Template>) {
+        self.templates = templates;
+    }
+
+    pub fn has_templates(&self) -> bool {
+        !self.templates.is_empty()
+    }
+
+    pub fn template_candidates(&self, query: &str) -> Vec<&Template> {
+        let needle = query.to_lowercase();
+
+        self.templates
+            .iter()
+            .filter(|template| template.name.to_lowercase().contains(&needle))
+            .collect()
+    }
+
+    pub fn completion_count(&self) -> usize {
+        self.mention
+            .as_ref()
+            .map_or(0, |menu| self.completion_len(menu))
+    }
+
+    fn completion_len(&self, menu: &MentionMenu) -> usize {
+        match menu.kind {
+            Completion::People => self.candidates(&menu.query).len(),
+            Completion::Issues => self.issue_candidates(&menu.query).len(),
+            Completion::Emoji => self.emoji_candidates(&menu.query).len(),
+            Completion::Templates => self.template_candidates(&menu.query).len(),
+        }
+    }
+
     pub fn open_mention(&mut self) {
-        self.insert_char('@');
-        self.mention = Some(MentionMenu::new(Position {
-            row: self.row,
-            col: self.col - 1,
-        }));
+        self.open_completion('@', Completion::People);
+    }
+
+    pub fn open_reference(&mut self) {
+        self.open_completion('#', Completion::Issues);
+    }
+
+    pub fn open_emoji(&mut self) {
+        self.open_completion(':', Completion::Emoji);
+    }
+
+    /// Templates have no trigger character: what is typed after opening
+    /// narrows them by name and is replaced by the one picked.
+    pub fn open_templates(&mut self) {
+        self.mention = Some(MentionMenu::new(self.position(), Completion::Templates));
+    }
+
+    fn open_completion(&mut self, trigger: char, kind: Completion) {
+        self.insert_char(trigger);
+        self.mention = Some(MentionMenu::new(
+            Position {
+                row: self.row,
+                col: self.col - 1,
+            },
+            kind,
+        ));
         self.settle();
     }
 
+    /// Opens issue completion when the word just typed is a team's key and
+    /// a dash, as in `ENG-`, the word itself becoming the query.
+    pub fn open_reference_at_key(&mut self) -> bool {
+        let line = &self.lines[self.row];
+        let start = line[..self.col]
+            .iter()
+            .rposition(|cell| !matches!(cell, Cell::Char(c) if !c.is_whitespace()))
+            .map_or(0, |index| index + 1);
+        let word: String = line[start..self.col]
+            .iter()
+            .filter_map(|cell| match cell {
+                Cell::Char(c) => Some(*c),
+                Cell::Mention(_) | Cell::Reference(_) => None,
+            })
+            .collect();
+
+        let Some(key) = word.strip_suffix('-').filter(|key| !key.is_empty()) else {
+            return false;
+        };
+        let known = self
+            .issues
+            .iter()
+            .any(|issue| issue.identifier.rsplit_once('-').map(|(team, _)| team) == Some(key));
+        if !known {
+            return false;
+        }
+
+        let mut menu = MentionMenu::new(
+            Position {
+                row: self.row,
+                col: start,
+            },
+            Completion::Issues,
+        );
+        menu.query = word;
+        self.mention = Some(menu);
+        true
+    }
+
     pub fn close_mention(&mut self) {
         self.mention = None;
     }
 
+    /// Issue references and shortcodes are a single word, so a space ends
+    /// one.
     pub fn mention_type(&mut self, c: char) {
         self.insert_char(c);
-        if let Some(mention) = &mut self.mention {
-            mention.query.push(c);
-            mention.state.select(Some(0));
+        match &mut self.mention {
+            Some(mention)
+                if matches!(mention.kind, Completion::Issues | Completion::Emoji)
+                    && c.is_whitespace() =>
+            {
+                self.mention = None;
+            }
+            Some(mention) => {
+                mention.query.push(c);
+                mention.state.select(Some(0));
+            }
+            None => {}
         }
     }
 
     pub fn mention_backspace(&mut self) {
+        // With nothing typed yet there is no trigger to take back.
+        if self
+            .mention
+            .as_ref()
+            .is_some_and(|menu| menu.kind == Completion::Templates && menu.query.is_empty())
+        {
+            self.mention = None;
+            return;
+        }
+
         self.backspace();
         match &mut self.mention {
             Some(mention) if !mention.query.is_empty() => {
@@ -962,7 +1706,7 @@ impl Editor {
         let Some(mention) = &self.mention else {
             return;
         };
-        let Some(len) = NonZeroUsize::new(self.candidates(&mention.query).len()) else {
+        let Some(len) = NonZeroUsize::new(self.completion_len(mention)) else {
             return;
         };
 
@@ -986,25 +1730,70 @@ impl Editor {
             return;
         };
 
-        let query = mention.query.to_lowercase();
-        let picked = self
-            .members
-            .iter()
-            .filter(|user| user.display_name.to_lowercase().contains(&query))
-            .nth(selected)
-            .map(|user| (user.display_name.clone(), user.url.clone()));
+        let picked: Option<Vec<Vec<Cell>>> = match mention.kind {
+            Completion::People => self.candidates(&mention.query).get(selected).map(|user| {
+                vec![vec![Cell::Mention(Mention {
+                    display: user.display_name.clone(),
+                    url: user.url.clone(),
+                })]]
+            }),
+            Completion::Issues => {
+                self.issue_candidates(&mention.query)
+                    .get(selected)
+                    .map(|issue| {
+                        vec![vec![Cell::Reference(Reference {
+                            identifier: issue.identifier.clone(),
+                            url: if issue.url.is_empty() {
+                                issue.identifier.clone()
+                            } else {
+                                issue.url.clone()
+                            },
+                        })]]
+                    })
+            }
+            Completion::Emoji => self
+                .emoji_candidates(&mention.query)
+                .get(selected)
+                .map(|entry| vec![entry.glyph.chars().map(Cell::Char).collect()]),
+            Completion::Templates => {
+                self.template_candidates(&mention.query)
+                    .get(selected)
+                    .map(|template| {
+                        template
+                            .body
+                            .split('\n')
+                            .map(|line| self.cells(line))
+                            .collect()
+                    })
+            }
+        };
 
-        let Some((display, url)) = picked else {
+        let Some(lines) = picked else {
             return;
         };
 
-        self.lines[mention.anchor.row].drain(mention.anchor.col..self.col);
-        self.col = mention.anchor.col;
-        self.settle();
-        self.insert_mention(display, url);
+        self.edit(EditKind::Other, |editor| {
+            editor.lines[mention.anchor.row].drain(mention.anchor.col..editor.col);
+            editor.col = mention.anchor.col;
+
+            let end = editor.insert_lines(editor.position(), &lines);
+            editor.row = end.row;
+            editor.col = end.col;
+            editor.settle();
+        });
     }
 }
 
+fn line_text(line: &[Cell]) -> String {
+    line.iter()
+        .map(|cell| match cell {
+            Cell::Char(c) => c.to_string(),
+            Cell::Mention(mention) => mention.url.clone(),
+            Cell::Reference(reference) => reference.url.clone(),
+        })
+        .collect()
+}
+
 #[derive(Debug, Clone)]
 pub struct Search {
     pub query: String,
@@ -1033,14 +1822,18 @@ pub enum Overlay {
     Search(Search),
     Find(Find),
     Reactions(Reactions),
+    Emoji(EmojiPicker),
     Workspaces(Workspaces),
     Labels(Labels),
+    Conflict(Conflict),
+    Drafts(DraftList),
+    Links(LinkHints),
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::api::UserId;
+    use crate::api::{StateType, UserId, WorkflowState};
 
     fn editor_with_member() -> Editor {
         let mut editor = Editor::new(
@@ -1060,6 +1853,42 @@ mod tests {
         editor
     }
 
+    #[test]
+    fn replaced_text_turns_the_longest_matching_profile_url_into_a_mention() {
+        let mut editor = editor_with_member();
+        editor.set_members(vec![
+            User {
+                id: UserId::from_raw("u_dan"),
+                name: "dan".into(),
+                display_name: "dan".into(),
+                url: "https://linear.app/dans-donuts/profiles/dan".into(),
+                is_me: true,
+            },
+            User {
+                id: UserId::from_raw("u_danielle"),
+                name: "danielle".into(),
+                display_name: "danielle".into(),
+                url: "https://linear.app/dans-donuts/profiles/danielle".into(),
+                is_me: false,
+            },
+        ]);
+
+        editor.replace_text("cc https://linear.app/dans-donuts/profiles/danielle!");
+
+        let mentions: Vec<&str> = editor.lines()[0]
+            .iter()
+            .filter_map(|cell| match cell {
+                Cell::Mention(mention) => Some(mention.display.as_str()),
+                Cell::Char(_) | Cell::Reference(_) => None,
+            })
+            .collect();
+        assert_eq!(mentions, vec!["danielle"]);
+        assert_eq!(
+            editor.text(),
+            "cc https://linear.app/dans-donuts/profiles/danielle!"
+        );
+    }
+
     #[test]
     fn accept_mention_replaces_the_typed_query() {
         let mut editor = editor_with_member();
@@ -1100,4 +1929,262 @@ mod tests {
 
         assert!(editor.mention().is_none());
     }
+
+    fn typed(editor: &mut Editor, text: &str) {
+        for c in text.chars() {
+            editor.insert_char(c);
+        }
+    }
+
+    #[test]
+    fn undo_takes_back_a_word_at_a_time_and_redo_puts_it_back() {
+        let mut editor = editor_with_member();
+        typed(&mut editor, "ship it now");
+
+        editor.undo();
+        assert_eq!(editor.text(), "ship it ");
+        editor.undo();
+        assert_eq!(editor.text(), "ship ");
+
+        editor.redo();
+        assert_eq!(editor.text(), "ship it ");
+        assert_eq!(editor.col(), "ship it ".len());
+    }
+
+    #[test]
+    fn a_fresh_edit_clears_the_redo_history() {
+        let mut editor = editor_with_member();
+        typed(&mut editor, "one ");
+        editor.undo();
+
+        typed(&mut editor, "two");
+        editor.redo();
+
+        assert_eq!(editor.text(), "two");
+    }
+
+    #[test]
+    fn moving_away_starts_a_new_undo_step() {
+        let mut editor = editor_with_member();
+        typed(&mut editor, "ab");
+        editor.move_left();
+        typed(&mut editor, "x");
+
+        editor.undo();
+
+        assert_eq!(editor.text(), "ab");
+    }
+
+    #[test]
+    fn word_erasure_takes_a_mention_whole_and_undo_restores_it() {
+        let mut editor = editor_with_member();
+        typed(&mut editor, "cc ");
+        editor.open_mention();
+        editor.mention_type('d');
+        editor.accept_mention();
+
+        editor.erase_word();
+        assert_eq!(editor.text(), "cc ");
+
+        editor.undo();
+        assert!(matches!(editor.lines()[0].last(), Some(Cell::Mention(_))));
+    }
+
+    #[test]
+    fn erasing_forward_stops_at_the_line_end_then_joins_lines() {
+        let mut editor = editor_with_member();
+        editor.paste("one two\nthree");
+        editor.row = 0;
+        editor.col = 4;
+
+        editor.erase_word_forward();
+        assert_eq!(editor.text(), "one \nthree");
+
+        editor.erase_word_forward();
+        assert_eq!(editor.text(), "one three");
+    }
+
+    #[test]
+    fn word_motions_cross_lines() {
+        let mut editor = editor_with_member();
+        editor.paste("one two\nthree");
+
+        editor.move_word_left();
+        assert_eq!((editor.row(), editor.col()), (1, 0));
+        editor.move_word_left();
+        assert_eq!((editor.row(), editor.col()), (0, 4));
+        editor.move_word_right();
+        assert_eq!((editor.row(), editor.col()), (1, 0));
+    }
+
+    #[test]
+    fn a_multi_line_paste_is_one_undo_step() {
+        let mut editor = editor_with_member();
+        typed(&mut editor, "see ");
+
+        editor.paste("first\r\nsecond https://linear.app/dans-donuts/profiles/dan");
+        assert_eq!(editor.lines().len(), 2);
+        assert!(matches!(editor.lines()[1].last(), Some(Cell::Mention(_))));
+        assert_eq!((editor.row(), editor.col()), (1, "second ".len() + 1));
+
+        editor.undo();
+        assert_eq!(editor.text(), "see ");
+    }
+
+    fn known_issue(identifier: &str, title: &str) -> IssueSummary {
+        IssueSummary {
+            id: IssueId::from_raw(identifier),
+            identifier: identifier.into(),
+            title: Some(title.into()),
+            state: WorkflowState {
+                name: "Todo".into(),
+                state_type: StateType::Unstarted,
+            },
+            priority: Priority::None,
+            assignee: None,
+            labels: Vec::new(),
+            url: format!("https://linear.app/dans-donuts/issue/{identifier}"),
+            branch_name: String::new(),
+            team_id: TeamId::from_raw("t1"),
+            updated_at: Timestamp::default(),
+        }
+    }
+
+    fn editor_with_issues() -> Editor {
+        let mut editor = editor_with_member();
+        editor.set_issues(vec![
+            known_issue("DAN-7", "Oven runs cold"),
+            known_issue("DAN-12", "Glaze recipe"),
+        ]);
+        editor
+    }
+
+    #[test]
+    fn accepting_an_issue_inserts_a_reference_posted_as_its_url() {
+        let mut editor = editor_with_issues();
+        typed(&mut editor, "see ");
+        editor.open_reference();
+        for c in "glaze".chars() {
+            editor.mention_type(c);
+        }
+
+        editor.accept_mention();
+
+        assert!(matches!(
+            editor.lines()[0].last(),
+            Some(Cell::Reference(reference)) if reference.identifier == "DAN-12"
+        ));
+        assert_eq!(
+            editor.text(),
+            "see https://linear.app/dans-donuts/issue/DAN-12"
+        );
+    }
+
+    #[test]
+    fn a_team_key_and_dash_opens_issue_completion_on_the_word() {
+        let mut editor = editor_with_issues();
+        typed(&mut editor, "well-");
+        assert!(!editor.open_reference_at_key());
+
+        typed(&mut editor, " DAN-");
+        assert!(editor.open_reference_at_key());
+        assert_eq!(
+            editor.mention().map(|menu| menu.query.as_str()),
+            Some("DAN-")
+        );
+
+        editor.mention_type('7');
+        editor.accept_mention();
+        assert_eq!(
+            editor.text(),
+            "well- https://linear.app/dans-donuts/issue/DAN-7"
+        );
+    }
+
+    #[test]
+    fn a_space_ends_issue_completion_but_not_a_mention() {
+        let mut editor = editor_with_issues();
+        editor.open_reference();
+        editor.mention_type(' ');
+        assert!(editor.mention().is_none());
+
+        editor.open_mention();
+        editor.mention_type(' ');
+        assert!(editor.mention().is_some());
+    }
+
+    #[test]
+    fn search_answers_lead_the_issues_already_known() {
+        let mut editor = editor_with_issues();
+        editor.open_reference();
+
+        editor.add_issues(vec![
+            known_issue("DAN-40", "Fryer"),
+            known_issue("DAN-7", "Oven runs cold"),
+        ]);
+
+        let identifiers: Vec<&str> = editor
+            .issue_candidates("")
+            .iter()
+            .map(|issue| issue.identifier.as_str())
+            .collect();
+        assert_eq!(identifiers, vec!["DAN-40", "DAN-7", "DAN-12"]);
+    }
+
+    #[test]
+    fn replaced_text_turns_known_issue_urls_into_references() {
+        let mut editor = editor_with_issues();
+
+        editor.replace_text("dup of https://linear.app/dans-donuts/issue/DAN-12");
+
+        assert!(matches!(
+            editor.lines()[0].last(),
+            Some(Cell::Reference(reference)) if reference.identifier == "DAN-12"
+        ));
+        assert_eq!(
+            editor.text(),
+            "dup of https://linear.app/dans-donuts/issue/DAN-12"
+        );
+    }
+
+    fn link(url: &str) -> (String, Link) {
+        (
+            "description".into(),
+            Link {
+                text: url.into(),
+                url: url.into(),
+                image: false,
+            },
+        )
+    }
+
+    #[test]
+    fn many_links_take_two_letter_labels_and_repeats_are_dropped() {
+        let few = LinkHints::new(vec![
+            link("https://a"),
+            link("https://b"),
+            link("https://a"),
+        ]);
+        let labels: Vec<_> = few.hints.iter().map(|hint| hint.label.as_str()).collect();
+        assert_eq!(labels, vec!["a", "s"]);
+
+        let many = LinkHints::new((0..30).map(|n| link(&format!("https://{n}"))).collect());
+        let labels: Vec<_> = many.hints.iter().map(|hint| hint.label.as_str()).collect();
+        assert_eq!(&labels[..3], &["aa", "as", "ad"]);
+        assert_eq!(labels[29], "sf");
+    }
+
+    #[test]
+    fn a_key_that_matches_no_label_starts_over() {
+        let mut hints = LinkHints::new((0..30).map(|n| link(&format!("https://{n}"))).collect());
+
+        assert!(hints.type_char('S').is_none());
+        assert!(hints.type_char('z').is_none());
+        assert_eq!(hints.typed, "");
+
+        assert!(hints.type_char('S').is_none());
+        let hint = hints.type_char('F').expect("a whole label");
+        assert_eq!(hint.link.url, "https://29");
+        assert!(hints.yank);
+    }
 }
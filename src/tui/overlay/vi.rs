@@ -0,0 +1,1735 @@
+//! Vi-style modal editing for the `Editor`, switched on by
+//! `LINEAR_TUI_EDITOR_KEYS=vi`. Insert mode is the plain editor; normal and
+//! visual mode keys are handled here.
+
+use std::collections::HashMap;
+
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use serde::{Deserialize, Serialize};
+
+use super::{Cell, Editor, Position};
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum EditorKeys {
+    #[default]
+    Plain,
+    Vi,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Mode {
+    Normal,
+    Insert,
+    Visual { anchor: Position, linewise: bool },
+}
+
+/// What the overlay should do once a normal or visual mode key is handled.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Reply {
+    Stay,
+    Submit,
+    Cancel,
+    /// A yank or delete went to `"+` or `"*`.
+    Clipboard(String),
+}
+
+pub struct Vi {
+    mode: Mode,
+    pending: String,
+    last_change: Option<Change>,
+    recording: Option<Recording>,
+    /// Set while `.` replays a change, which is one undo step however
+    /// many times it runs.
+    replaying: bool,
+}
+
+/// The last change, kept for `.`: the keys that made it and, when it
+/// entered insert mode, what was typed before leaving.
+#[derive(Debug, Clone)]
+struct Change {
+    keys: String,
+    inserted: Option<Vec<Vec<Cell>>>,
+}
+
+struct Recording {
+    keys: String,
+    start: Position,
+}
+
+/// Shared by every editor in a session, so a yank in one comment can be
+/// put into the next.
+#[derive(Debug, Clone, Default)]
+pub struct Registers {
+    slots: HashMap<char, Register>,
+}
+
+#[derive(Debug, Clone)]
+struct Register {
+    lines: Vec<Vec<Cell>>,
+    linewise: bool,
+}
+
+impl Register {
+    fn text(&self) -> String {
+        let text = self
+            .lines
+            .iter()
+            .map(|line| super::line_text(line))
+            .collect::<Vec<_>>()
+            .join("\n");
+
+        if self.linewise {
+            text + "\n"
+        } else {
+            text
+        }
+    }
+
+    /// The contents `times` over, so a counted put is one insertion.
+    fn repeated(&self, times: usize) -> Register {
+        let lines = if self.linewise {
+            let len = self.lines.len().saturating_mul(times);
+            self.lines.iter().cycle().take(len).cloned().collect()
+        } else {
+            let mut lines = vec![Vec::new()];
+            for _ in 0..times {
+                let Some((first, rest)) = self.lines.split_first() else {
+                    break;
+                };
+                if let Some(last) = lines.last_mut() {
+                    last.extend(first.iter().cloned());
+                }
+                lines.extend(rest.iter().cloned());
+            }
+            lines
+        };
+
+        Register {
+            lines,
+            linewise: self.linewise,
+        }
+    }
+}
+
+impl Registers {
+    /// Files a yank or delete the way vim does: always in `"`, yanks also
+    /// in `0`, and in the named register if one was given. The clipboard
+    /// registers hand back text for the platform to copy.
+    fn store(&mut self, name: Option<char>, register: Register, yank: bool) -> Option<String> {
+        if name == Some('_') {
+            return None;
+        }
+
+        if yank {
+            self.slots.insert('0', register.clone());
+        }
+
+        let clipboard = match name {
+            Some(name @ ('+' | '*')) => {
+                self.slots.insert(name, register.clone());
+                Some(register.text())
+            }
+            Some(name @ 'a'..='z') => {
+                self.slots.insert(name, register.clone());
+                None
+            }
+            _ => None,
+        };
+
+        self.slots.insert('"', register);
+        clipboard
+    }
+
+    fn get(&self, name: Option<char>) -> Option<&Register> {
+        self.slots.get(&name.unwrap_or('"'))
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Class {
+    Blank,
+    Word,
+    Punct,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Kind {
+    Exclusive,
+    Inclusive,
+    Linewise,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Motion {
+    Left,
+    Right,
+    Down,
+    Up,
+    WordStart {
+        big: bool,
+    },
+    WordEnd {
+        big: bool,
+    },
+    WordBack {
+        big: bool,
+    },
+    LineStart,
+    FirstNonBlank,
+    LineEnd,
+    FirstLine,
+    LastLine,
+    Find {
+        target: char,
+        till: bool,
+        back: bool,
+    },
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Object {
+    Word { big: bool },
+    Quote(char),
+    Bracket(char, char),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Operator {
+    Delete,
+    Change,
+    Yank,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Target {
+    Motion(Motion),
+    Object { object: Object, inner: bool },
+    Line,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Action {
+    Move(Motion),
+    Operate(Operator, Target),
+    /// An operator applied to the visual selection.
+    Selection(Operator),
+    DeleteChar {
+        back: bool,
+    },
+    Put {
+        before: bool,
+    },
+    Insert(Entry),
+    Join,
+    ToggleCase,
+    Replace(char),
+    Visual {
+        linewise: bool,
+    },
+    SwapAnchor,
+    Repeat,
+    Undo,
+    Redo,
+    Submit,
+    Cancel,
+}
+
+/// The ways into insert mode: `i`, `a`, `I`, `A`, `o` and `O`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Entry {
+    Before,
+    After,
+    LineStart,
+    LineEnd,
+    Below,
+    Above,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct Command {
+    register: Option<char>,
+    count: Option<usize>,
+    action: Action,
+}
+
+enum Parse {
+    Incomplete,
+    Invalid,
+    Done(Command),
+}
+
+/// A stretch of the buffer an operator works on.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Span {
+    Chars { start: Position, end: Position },
+    Rows { first: usize, last: usize },
+}
+
+impl Vi {
+    pub fn new() -> Self {
+        Self {
+            mode: Mode::Normal,
+            pending: String::new(),
+            last_change: None,
+            recording: None,
+            replaying: false,
+        }
+    }
+
+    pub fn mode(&self) -> Mode {
+        self.mode
+    }
+
+    /// The mode and any half-typed command, for the editor's title.
+    pub fn status(&self) -> String {
+        let mode = match self.mode {
+            Mode::Normal => "normal",
+            Mode::Insert => "insert",
+            Mode::Visual {
+                linewise: false, ..
+            } => "visual",
+            Mode::Visual { linewise: true, .. } => "visual line",
+        };
+
+        if self.pending.is_empty() {
+            mode.to_string()
+        } else {
+            format!("{mode} {}", self.pending)
+        }
+    }
+
+    fn key(&mut self, editor: &mut Editor, key: KeyEvent, registers: &mut Registers) -> Reply {
+        if key.code == KeyCode::Esc {
+            self.pending.clear();
+            if matches!(self.mode, Mode::Visual { .. }) {
+                self.mode = Mode::Normal;
+            }
+            return Reply::Stay;
+        }
+
+        if key.code == KeyCode::Char('r') && key.modifiers.contains(KeyModifiers::CONTROL) {
+            self.pending.clear();
+            let redo = Command {
+                register: None,
+                count: None,
+                action: Action::Redo,
+            };
+            let reply = self.run(editor, redo, registers);
+            editor.clamp_to_line();
+            return reply;
+        }
+
+        match key_char(key) {
+            Some(c) => self.type_char(editor, c, registers),
+            None => Reply::Stay,
+        }
+    }
+
+    fn type_char(&mut self, editor: &mut Editor, c: char, registers: &mut Registers) -> Reply {
+        self.pending.push(c);
+
+        let visual = matches!(self.mode, Mode::Visual { .. });
+        let command = match parse(&self.pending, visual) {
+            Parse::Incomplete => return Reply::Stay,
+            Parse::Invalid => {
+                self.pending.clear();
+                return Reply::Stay;
+            }
+            Parse::Done(command) => command,
+        };
+
+        let keys = std::mem::take(&mut self.pending);
+        let changes = changes(command.action) && !self.replaying;
+        if changes {
+            editor.begin_change();
+        }
+        let reply = self.run(editor, command, registers);
+
+        if repeatable(command.action) {
+            match self.mode {
+                Mode::Insert => {
+                    self.recording = Some(Recording {
+                        keys,
+                        start: editor.position(),
+                    })
+                }
+                Mode::Normal | Mode::Visual { .. } => {
+                    self.last_change = Some(Change {
+                        keys,
+                        inserted: None,
+                    })
+                }
+            }
+        }
+
+        if self.mode != Mode::Insert {
+            if changes {
+                editor.end_change();
+            }
+            editor.clamp_to_line();
+        }
+
+        reply
+    }
+
+    fn leave_insert(&mut self, editor: &mut Editor) {
+        if let Some(recording) = self.recording.take() {
+            let end = editor.position();
+            let inserted = (end >= recording.start).then(|| editor.copy(recording.start, end));
+
+            self.last_change = Some(Change {
+                keys: recording.keys,
+                inserted,
+            });
+        }
+
+        self.mode = Mode::Normal;
+        if !self.replaying {
+            editor.end_change();
+        }
+        editor.close_mention();
+        editor.col = editor.col.saturating_sub(1);
+        editor.clamp_to_line();
+    }
+
+    fn run(&mut self, editor: &mut Editor, command: Command, registers: &mut Registers) -> Reply {
+        let count = command.count.unwrap_or(1).max(1);
+        let register = command.register;
+
+        match command.action {
+            Action::Move(motion) => {
+                if let Some((target, _)) = editor.motion(motion, command.count) {
+                    editor.row = target.row;
+                    editor.col = target.col;
+                    if matches!(motion, Motion::FirstLine | Motion::LastLine) {
+                        editor.col = editor.first_non_blank(editor.row);
+                    }
+                }
+                Reply::Stay
+            }
+            Action::Operate(operator, target) => {
+                let span = match target {
+                    Target::Line => {
+                        let first = editor.row;
+                        let last = first.saturating_add(count - 1).min(editor.lines.len() - 1);
+                        Some(Span::Rows { first, last })
+                    }
+                    Target::Motion(Motion::WordStart { big })
+                        if operator == Operator::Change
+                            && editor.class(editor.position(), big) != Class::Blank =>
+                    {
+                        // `cw` on a word changes to its end, as `ce` would,
+                        // leaving the following space alone.
+                        let mut end = editor.word_end_from(editor.position(), big);
+                        for _ in 1..count {
+                            end = editor.word_end(end, big);
+                        }
+                        Some(Span::Chars {
+                            start: editor.position(),
+                            end: editor.after(end),
+                        })
+                    }
+                    Target::Motion(motion) => editor.motion_span(motion, command.count),
+                    Target::Object { object, inner } => editor.object(object, inner),
+                };
+
+                match span {
+                    Some(span) => self.operate(editor, operator, span, register, registers),
+                    None => Reply::Stay,
+                }
+            }
+            Action::Selection(operator) => {
+                let Mode::Visual { anchor, linewise } = self.mode else {
+                    return Reply::Stay;
+                };
+                self.mode = Mode::Normal;
+
+                let (start, end) = order(anchor, editor.position());
+                let span = if linewise {
+                    Span::Rows {
+                        first: start.row,
+                        last: end.row,
+                    }
+                } else {
+                    Span::Chars {
+                        start,
+                        end: editor.after(end),
+                    }
+                };
+
+                self.operate(editor, operator, span, register, registers)
+            }
+            Action::DeleteChar { back } => {
+                let len = editor.lines[editor.row].len();
+                let span = if back {
+                    let start = editor.col.saturating_sub(count);
+                    Span::Chars {
+                        start: Position {
+                            row: editor.row,
+                            col: start,
+                        },
+                        end: editor.position(),
+                    }
+                } else {
+                    Span::Chars {
+                        start: editor.position(),
+                        end: Position {
+                            row: editor.row,
+                            col: editor.col.saturating_add(count).min(len),
+                        },
+                    }
+                };
+                self.operate(editor, Operator::Delete, span, register, registers)
+            }
+            Action::Put { before } => {
+                if let Some(contents) = registers.get(register) {
+                    editor.put(&contents.repeated(count), before);
+                }
+                Reply::Stay
+            }
+            Action::Insert(entry) => {
+                editor.enter(entry);
+                self.mode = Mode::Insert;
+                Reply::Stay
+            }
+            Action::Join => {
+                for _ in 0..count.saturating_sub(1).max(1) {
+                    if !editor.join() {
+                        break;
+                    }
+                }
+                Reply::Stay
+            }
+            Action::ToggleCase => {
+                editor.toggle_case(count);
+                Reply::Stay
+            }
+            Action::Replace(c) => {
+                editor.replace_chars(c, count);
+                Reply::Stay
+            }
+            Action::Visual { linewise } => {
+                self.mode = match self.mode {
+                    Mode::Visual {
+                        linewise: current, ..
+                    } if current == linewise => Mode::Normal,
+                    Mode::Visual { anchor, .. } => Mode::Visual { anchor, linewise },
+                    Mode::Normal | Mode::Insert => Mode::Visual {
+                        anchor: editor.position(),
+                        linewise,
+                    },
+                };
+                Reply::Stay
+            }
+            Action::SwapAnchor => {
+                if let Mode::Visual { anchor, linewise } = self.mode {
+                    self.mode = Mode::Visual {
+                        anchor: editor.position(),
+                        linewise,
+                    };
+                    editor.row = anchor.row;
+                    editor.col = anchor.col;
+                }
+                Reply::Stay
+            }
+            Action::Repeat => self.repeat(editor, count, registers),
+            Action::Undo => {
+                for _ in 0..count {
+                    if !editor.undo() {
+                        break;
+                    }
+                }
+                Reply::Stay
+            }
+            Action::Redo => {
+                editor.redo();
+                Reply::Stay
+            }
+            Action::Submit => Reply::Submit,
+            Action::Cancel => Reply::Cancel,
+        }
+    }
+
+    fn operate(
+        &mut self,
+        editor: &mut Editor,
+        operator: Operator,
+        span: Span,
+        register: Option<char>,
+        registers: &mut Registers,
+    ) -> Reply {
+        let contents = match span {
+            Span::Chars { start, end } => Register {
+                lines: editor.copy(start, end),
+                linewise: false,
+            },
+            Span::Rows { first, last } => Register {
+                lines: editor.lines[first..=last].to_vec(),
+                linewise: true,
+            },
+        };
+        let clipboard = registers.store(register, contents, operator == Operator::Yank);
+
+        match (operator, span) {
+            (Operator::Yank, Span::Chars { start, .. }) => {
+                editor.row = start.row;
+                editor.col = start.col;
+            }
+            (Operator::Yank, Span::Rows { first, .. }) => editor.row = first,
+            (Operator::Delete | Operator::Change, Span::Chars { start, end }) => {
+                editor.remove(start, end);
+                editor.row = start.row;
+                editor.col = start.col;
+            }
+            (Operator::Delete, Span::Rows { first, last }) => {
+                editor.lines.drain(first..=last);
+                if editor.lines.is_empty() {
+                    editor.lines.push(Vec::new());
+                }
+                editor.row = first.min(editor.lines.len() - 1);
+                editor.col = editor.first_non_blank(editor.row);
+            }
+            (Operator::Change, Span::Rows { first, last }) => {
+                editor.lines.drain(first..=last);
+                editor.lines.insert(first, Vec::new());
+                editor.row = first;
+                editor.col = 0;
+            }
+        }
+
+        if operator == Operator::Change {
+            self.mode = Mode::Insert;
+        }
+
+        match clipboard {
+            Some(text) => Reply::Clipboard(text),
+            None => Reply::Stay,
+        }
+    }
+
+    fn repeat(&mut self, editor: &mut Editor, count: usize, registers: &mut Registers) -> Reply {
+        let Some(change) = self.last_change.clone() else {
+            return Reply::Stay;
+        };
+
+        editor.begin_change();
+        self.replaying = true;
+
+        let mut reply = Reply::Stay;
+        for _ in 0..count {
+            for c in change.keys.chars() {
+                reply = self.type_char(editor, c, registers);
+            }
+
+            if self.mode == Mode::Insert {
+                if let Some(inserted) = &change.inserted {
+                    let end = editor.insert_lines(editor.position(), inserted);
+                    editor.row = end.row;
+                    editor.col = end.col;
+                }
+                self.leave_insert(editor);
+            }
+        }
+
+        self.replaying = false;
+        editor.end_change();
+        reply
+    }
+}
+
+impl Default for Vi {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Editor {
+    /// Starts in normal mode, so a seeded cursor past the end of its line
+    /// comes back onto the last character.
+    pub fn enable_vi(&mut self) {
+        self.vi = Some(Box::new(Vi::new()));
+        self.clamp_to_line();
+    }
+
+    pub fn vi(&self) -> Option<&Vi> {
+        self.vi.as_deref()
+    }
+
+    /// The selected stretch while in visual mode, in buffer order, and
+    /// whether it covers whole lines.
+    pub fn selection(&self) -> Option<(Position, Position, bool)> {
+        match self.vi.as_ref()?.mode {
+            Mode::Visual { anchor, linewise } => {
+                let (start, end) = order(anchor, self.position());
+                Some((start, end, linewise))
+            }
+            Mode::Normal | Mode::Insert => None,
+        }
+    }
+
+    /// Handles a key in normal or visual mode.
+    pub fn vi_key(&mut self, key: KeyEvent, registers: &mut Registers) -> Reply {
+        let Some(mut vi) = self.vi.take() else {
+            return Reply::Stay;
+        };
+
+        let reply = vi.key(self, key, registers);
+        self.vi = Some(vi);
+        reply
+    }
+
+    /// Esc in insert mode: back to normal, remembering what was typed so
+    /// `.` can type it again.
+    pub fn vi_leave_insert(&mut self) {
+        if let Some(mut vi) = self.vi.take() {
+            vi.leave_insert(self);
+            self.vi = Some(vi);
+        }
+    }
+
+    /// Normal mode rests on a character rather than after the last one.
+    fn clamp_to_line(&mut self) {
+        self.settle();
+        let len = self.lines[self.row].len();
+        if len > 0 && self.col >= len {
+            self.col = len - 1;
+        }
+    }
+
+    fn cell(&self, at: Position) -> Option<&Cell> {
+        self.lines.get(at.row)?.get(at.col)
+    }
+
+    /// The position after `at`, stepping over the line break at the end
+    /// of each line but the last.
+    pub(super) fn next(&self, at: Position) -> Option<Position> {
+        if at.col < self.lines[at.row].len() {
+            Some(Position {
+                row: at.row,
+                col: at.col + 1,
+            })
+        } else if at.row + 1 < self.lines.len() {
+            Some(Position {
+                row: at.row + 1,
+                col: 0,
+            })
+        } else {
+            None
+        }
+    }
+
+    fn prev(&self, at: Position) -> Option<Position> {
+        if at.col > 0 {
+            Some(Position {
+                row: at.row,
+                col: at.col - 1,
+            })
+        } else if at.row > 0 {
+            Some(Position {
+                row: at.row - 1,
+                col: self.lines[at.row - 1].len(),
+            })
+        } else {
+            None
+        }
+    }
+
+    /// The exclusive end of a span that takes in `at`: the next position
+    /// for a character, `at` itself for a line break or the buffer's end.
+    fn after(&self, at: Position) -> Position {
+        match self.cell(at) {
+            Some(_) => Position {
+                row: at.row,
+                col: at.col + 1,
+            },
+            None => at,
+        }
+    }
+
+    fn class(&self, at: Position, big: bool) -> Class {
+        match self.cell(at) {
+            None => Class::Blank,
+            Some(Cell::Char(c)) if c.is_whitespace() => Class::Blank,
+            Some(Cell::Mention(_) | Cell::Reference(_)) => Class::Word,
+            Some(Cell::Char(_)) if big => Class::Word,
+            Some(Cell::Char(c)) if c.is_alphanumeric() || *c == '_' => Class::Word,
+            Some(Cell::Char(_)) => Class::Punct,
+        }
+    }
+
+    fn is_empty_line(&self, at: Position) -> bool {
+        at.col == 0 && self.lines[at.row].is_empty()
+    }
+
+    fn first_non_blank(&self, row: usize) -> usize {
+        self.lines[row]
+            .iter()
+            .position(|cell| !matches!(cell, Cell::Char(c) if c.is_whitespace()))
+            .unwrap_or(0)
+    }
+
+    pub(super) fn word_start(&self, mut at: Position, big: bool) -> Position {
+        let class = self.class(at, big);
+        if class != Class::Blank {
+            while self.class(at, big) == class {
+                match self.next(at) {
+                    Some(next) => at = next,
+                    None => return at,
+                }
+            }
+        }
+
+        while self.class(at, big) == Class::Blank {
+            match self.next(at) {
+                Some(next) => at = next,
+                None => return at,
+            }
+            if self.is_empty_line(at) {
+                break;
+            }
+        }
+
+        at
+    }
+
+    fn word_end(&self, at: Position, big: bool) -> Position {
+        let Some(mut at) = self.next(at) else {
+            return at;
+        };
+
+        while self.class(at, big) == Class::Blank {
+            match self.next(at) {
+                Some(next) => at = next,
+                None => return at,
+            }
+        }
+
+        self.word_end_from(at, big)
+    }
+
+    fn word_end_from(&self, mut at: Position, big: bool) -> Position {
+        let class = self.class(at, big);
+        while let Some(next) = self.next(at) {
+            if self.class(next, big) != class || next.row != at.row {
+                break;
+            }
+            at = next;
+        }
+        at
+    }
+
+    pub(super) fn word_back(&self, at: Position, big: bool) -> Position {
+        let Some(mut at) = self.prev(at) else {
+            return at;
+        };
+
+        while self.class(at, big) == Class::Blank && !self.is_empty_line(at) {
+            match self.prev(at) {
+                Some(prev) => at = prev,
+                None => return at,
+            }
+        }
+
+        if self.is_empty_line(at) {
+            return at;
+        }
+
+        let class = self.class(at, big);
+        while let Some(prev) = self.prev(at) {
+            if self.class(prev, big) != class || prev.row != at.row {
+                break;
+            }
+            at = prev;
+        }
+        at
+    }
+
+    fn motion(&self, motion: Motion, count: Option<usize>) -> Option<(Position, Kind)> {
+        let times = count.unwrap_or(1).max(1);
+        let here = self.position();
+        let last_row = self.lines.len() - 1;
+
+        let found = match motion {
+            Motion::Left => (
+                Position {
+                    row: here.row,
+                    col: here.col.saturating_sub(times),
+                },
+                Kind::Exclusive,
+            ),
+            Motion::Right => (
+                Position {
+                    row: here.row,
+                    col: here
+                        .col
+                        .saturating_add(times)
+                        .min(self.lines[here.row].len()),
+                },
+                Kind::Exclusive,
+            ),
+            Motion::Down => (
+                Position {
+                    row: here.row.saturating_add(times).min(last_row),
+                    col: here.col,
+                },
+                Kind::Linewise,
+            ),
+            Motion::Up => (
+                Position {
+                    row: here.row.saturating_sub(times),
+                    col: here.col,
+                },
+                Kind::Linewise,
+            ),
+            Motion::WordStart { big } => (
+                (0..times).fold(here, |at, _| self.word_start(at, big)),
+                Kind::Exclusive,
+            ),
+            Motion::WordEnd { big } => (
+                (0..times).fold(here, |at, _| self.word_end(at, big)),
+                Kind::Inclusive,
+            ),
+            Motion::WordBack { big } => (
+                (0..times).fold(here, |at, _| self.word_back(at, big)),
+                Kind::Exclusive,
+            ),
+            Motion::LineStart => (
+                Position {
+                    row: here.row,
+                    col: 0,
+                },
+                Kind::Exclusive,
+            ),
+            Motion::FirstNonBlank => (
+                Position {
+                    row: here.row,
+                    col: self.first_non_blank(here.row),
+                },
+                Kind::Exclusive,
+            ),
+            Motion::LineEnd => {
+                let row = here.row.saturating_add(times - 1).min(last_row);
+                let len = self.lines[row].len();
+                (
+                    Position {
+                        row,
+                        col: len.saturating_sub(1),
+                    },
+                    Kind::Inclusive,
+                )
+            }
+            Motion::FirstLine => (
+                Position {
+                    row: count.map_or(0, |line| line.saturating_sub(1).min(last_row)),
+                    col: 0,
+                },
+                Kind::Linewise,
+            ),
+            Motion::LastLine => (
+                Position {
+                    row: count.map_or(last_row, |line| line.saturating_sub(1).min(last_row)),
+                    col: 0,
+                },
+                Kind::Linewise,
+            ),
+            Motion::Find { target, till, back } => {
+                let line = &self.lines[here.row];
+                let matches =
+                    |col: &usize| matches!(line.get(*col), Some(Cell::Char(c)) if *c == target);
+
+                let col = if back {
+                    (0..here.col).rev().filter(matches).nth(times - 1)?
+                } else {
+                    (here.col + 1..line.len()).filter(matches).nth(times - 1)?
+                };
+
+                match (till, back) {
+                    (false, false) => (Position { row: here.row, col }, Kind::Inclusive),
+                    (true, false) => (
+                        Position {
+                            row: here.row,
+                            col: col - 1,
+                        },
+                        Kind::Inclusive,
+                    ),
+                    (false, true) => (Position { row: here.row, col }, Kind::Exclusive),
+                    (true, true) => (
+                        Position {
+                            row: here.row,
+                            col: col + 1,
+                        },
+                        Kind::Exclusive,
+                    ),
+                }
+            }
+        };
+
+        Some(found)
+    }
+
+    fn motion_span(&self, motion: Motion, count: Option<usize>) -> Option<Span> {
+        let (target, kind) = self.motion(motion, count)?;
+        let (start, end) = order(self.position(), target);
+
+        let span = match kind {
+            Kind::Linewise => Span::Rows {
+                first: start.row,
+                last: end.row,
+            },
+            Kind::Inclusive => Span::Chars {
+                start,
+                end: self.after(end),
+            },
+            // An exclusive motion that lands at the start of a later line
+            // stops at the end of the one before, so `dw` on a line's last
+            // word leaves the line break alone.
+            Kind::Exclusive if end.col == 0 && end.row > start.row => Span::Chars {
+                start,
+                end: Position {
+                    row: end.row - 1,
+                    col: self.lines[end.row - 1].len(),
+                },
+            },
+            Kind::Exclusive => Span::Chars { start, end },
+        };
+
+        Some(span)
+    }
+
+    fn object(&self, object: Object, inner: bool) -> Option<Span> {
+        let here = self.position();
+        let line = &self.lines[here.row];
+        let at = |col| Position { row: here.row, col };
+
+        match object {
+            Object::Word { big } => {
+                if line.is_empty() {
+                    return None;
+                }
+
+                let class = self.class(here, big);
+                let same = |col: usize| self.class(at(col), big) == class;
+                let blank = |col: usize| self.class(at(col), big) == Class::Blank;
+
+                let mut start = here.col;
+                while start > 0 && same(start - 1) {
+                    start -= 1;
+                }
+                let mut end = here.col + 1;
+                while end < line.len() && same(end) {
+                    end += 1;
+                }
+
+                if !inner {
+                    if class == Class::Blank {
+                        let word = self.class(at(end), big);
+                        while end < line.len() && self.class(at(end), big) == word {
+                            end += 1;
+                        }
+                    } else if end < line.len() && blank(end) {
+                        while end < line.len() && blank(end) {
+                            end += 1;
+                        }
+                    } else {
+                        while start > 0 && blank(start - 1) {
+                            start -= 1;
+                        }
+                    }
+                }
+
+                Some(Span::Chars {
+                    start: at(start),
+                    end: at(end),
+                })
+            }
+            Object::Quote(quote) => {
+                let quotes: Vec<usize> = (0..line.len())
+                    .filter(|col| matches!(line[*col], Cell::Char(c) if c == quote))
+                    .collect();
+
+                let (open, close) = quotes
+                    .chunks_exact(2)
+                    .map(|pair| (pair[0], pair[1]))
+                    .find(|(open, close)| *open <= here.col && here.col <= *close)
+                    .or_else(|| {
+                        quotes
+                            .chunks_exact(2)
+                            .map(|pair| (pair[0], pair[1]))
+                            .find(|(open, _)| *open > here.col)
+                    })?;
+
+                if inner {
+                    Some(Span::Chars {
+                        start: at(open + 1),
+                        end: at(close),
+                    })
+                } else {
+                    let mut end = close + 1;
+                    while end < line.len()
+                        && matches!(line[end], Cell::Char(c) if c.is_whitespace())
+                    {
+                        end += 1;
+                    }
+                    Some(Span::Chars {
+                        start: at(open),
+                        end: at(end),
+                    })
+                }
+            }
+            Object::Bracket(open, close) => {
+                let is = |at: Position, wanted: char| matches!(self.cell(at), Some(Cell::Char(c)) if *c == wanted);
+
+                let mut depth = 0;
+                let mut start = here;
+                loop {
+                    if is(start, close) && start != here {
+                        depth += 1;
+                    } else if is(start, open) {
+                        if depth == 0 {
+                            break;
+                        }
+                        depth -= 1;
+                    }
+                    start = self.prev(start)?;
+                }
+
+                let mut depth = 0;
+                let mut end = self.next(start)?;
+                loop {
+                    if is(end, open) {
+                        depth += 1;
+                    } else if is(end, close) {
+                        if depth == 0 {
+                            break;
+                        }
+                        depth -= 1;
+                    }
+                    end = self.next(end)?;
+                }
+
+                if inner {
+                    Some(Span::Chars {
+                        start: self.next(start)?,
+                        end,
+                    })
+                } else {
+                    Some(Span::Chars {
+                        start,
+                        end: self.after(end),
+                    })
+                }
+            }
+        }
+    }
+
+    /// The cells from `start` up to `end`, one entry per line.
+    fn copy(&self, start: Position, end: Position) -> Vec<Vec<Cell>> {
+        if start >= end {
+            return vec![Vec::new()];
+        }
+
+        if start.row == end.row {
+            return vec![self.lines[start.row][start.col..end.col].to_vec()];
+        }
+
+        let mut copied = vec![self.lines[start.row][start.col..].to_vec()];
+        copied.extend(self.lines[start.row + 1..end.row].iter().cloned());
+        copied.push(self.lines[end.row][..end.col].to_vec());
+        copied
+    }
+
+    pub(super) fn remove(&mut self, start: Position, end: Position) {
+        if start >= end {
+            return;
+        }
+
+        if start.row == end.row {
+            self.lines[start.row].drain(start.col..end.col);
+            return;
+        }
+
+        let tail = self.lines[end.row].split_off(end.col);
+        self.lines[start.row].truncate(start.col);
+        self.lines.drain(start.row + 1..=end.row);
+        self.lines[start.row].extend(tail);
+    }
+
+    /// Inserts `cells` at `at`, returning the position just after them.
+    pub(super) fn insert_lines(&mut self, at: Position, cells: &[Vec<Cell>]) -> Position {
+        let Some((first, rest)) = cells.split_first() else {
+            return at;
+        };
+
+        let tail = self.lines[at.row].split_off(at.col);
+        self.lines[at.row].extend(first.iter().cloned());
+
+        let mut end = Position {
+            row: at.row,
+            col: at.col + first.len(),
+        };
+        for (offset, line) in rest.iter().enumerate() {
+            self.lines.insert(at.row + 1 + offset, line.clone());
+            end = Position {
+                row: at.row + 1 + offset,
+                col: line.len(),
+            };
+        }
+
+        self.lines[end.row].extend(tail);
+        end
+    }
+
+    fn put(&mut self, contents: &Register, before: bool) {
+        if contents.linewise {
+            let row = if before { self.row } else { self.row + 1 };
+            for (offset, line) in contents.lines.iter().enumerate() {
+                self.lines.insert(row + offset, line.clone());
+            }
+            self.row = row;
+            self.col = self.first_non_blank(row);
+            return;
+        }
+
+        let col = if before || self.lines[self.row].is_empty() {
+            self.col
+        } else {
+            self.col + 1
+        };
+        let end = self.insert_lines(Position { row: self.row, col }, &contents.lines);
+        self.row = end.row;
+        self.col = end.col.saturating_sub(1);
+    }
+
+    fn enter(&mut self, entry: Entry) {
+        match entry {
+            Entry::Before => {}
+            Entry::After => self.col = (self.col + 1).min(self.lines[self.row].len()),
+            Entry::LineStart => self.col = self.first_non_blank(self.row),
+            Entry::LineEnd => self.col = self.lines[self.row].len(),
+            Entry::Below => {
+                self.lines.insert(self.row + 1, Vec::new());
+                self.row += 1;
+                self.col = 0;
+            }
+            Entry::Above => {
+                self.lines.insert(self.row, Vec::new());
+                self.col = 0;
+            }
+        }
+    }
+
+    /// False on the last line, where there is nothing to join.
+    fn join(&mut self) -> bool {
+        if self.row + 1 >= self.lines.len() {
+            return false;
+        }
+
+        let next = self.lines.remove(self.row + 1);
+        let next: Vec<Cell> = next
+            .into_iter()
+            .skip_while(|cell| matches!(cell, Cell::Char(c) if c.is_whitespace()))
+            .collect();
+
+        let line = &mut self.lines[self.row];
+        let ends_blank = matches!(line.last(), Some(Cell::Char(c)) if c.is_whitespace());
+        self.col = line.len();
+        if !line.is_empty() && !next.is_empty() && !ends_blank {
+            line.push(Cell::Char(' '));
+        }
+        line.extend(next);
+        true
+    }
+
+    fn toggle_case(&mut self, count: usize) {
+        let line = &mut self.lines[self.row];
+        let end = self.col.saturating_add(count).min(line.len());
+
+        for cell in &mut line[self.col..end] {
+            if let Cell::Char(c) = cell {
+                *c = if c.is_uppercase() {
+                    c.to_lowercase().next().unwrap_or(*c)
+                } else {
+                    c.to_uppercase().next().unwrap_or(*c)
+                };
+            }
+        }
+
+        self.col = end;
+    }
+
+    fn replace_chars(&mut self, with: char, count: usize) {
+        let line = &mut self.lines[self.row];
+        let Some(end) = self.col.checked_add(count).filter(|end| *end <= line.len()) else {
+            return;
+        };
+
+        for cell in &mut line[self.col..end] {
+            *cell = Cell::Char(with);
+        }
+        self.col += count - 1;
+    }
+}
+
+fn order(a: Position, b: Position) -> (Position, Position) {
+    if a <= b {
+        (a, b)
+    } else {
+        (b, a)
+    }
+}
+
+/// Normal mode reads keys as vim names them; arrows and friends stand in
+/// for their letters.
+fn key_char(key: KeyEvent) -> Option<char> {
+    if key
+        .modifiers
+        .intersects(KeyModifiers::CONTROL | KeyModifiers::ALT)
+    {
+        return None;
+    }
+
+    match key.code {
+        KeyCode::Char(c) => Some(c),
+        KeyCode::Left | KeyCode::Backspace => Some('h'),
+        KeyCode::Right => Some('l'),
+        KeyCode::Up => Some('k'),
+        KeyCode::Down | KeyCode::Enter => Some('j'),
+        KeyCode::Home => Some('0'),
+        KeyCode::End => Some('$'),
+        _ => None,
+    }
+}
+
+fn repeatable(action: Action) -> bool {
+    match action {
+        Action::Operate(operator, _) => operator != Operator::Yank,
+        Action::DeleteChar { .. }
+        | Action::Put { .. }
+        | Action::Insert(_)
+        | Action::Join
+        | Action::ToggleCase
+        | Action::Replace(_) => true,
+        Action::Move(_)
+        | Action::Selection(_)
+        | Action::Visual { .. }
+        | Action::SwapAnchor
+        | Action::Repeat
+        | Action::Undo
+        | Action::Redo
+        | Action::Submit
+        | Action::Cancel => false,
+    }
+}
+
+/// Whether the action can change the buffer, and so opens an undo step.
+fn changes(action: Action) -> bool {
+    match action {
+        Action::Selection(operator) => operator != Operator::Yank,
+        action => repeatable(action),
+    }
+}
+
+/// Reads `["x][count]command` from the keys typed so far.
+fn parse(keys: &str, visual: bool) -> Parse {
+    let mut chars = keys.chars().peekable();
+
+    let register = if chars.peek() == Some(&'"') {
+        chars.next();
+        match chars.next() {
+            Some(name) => Some(name),
+            None => return Parse::Incomplete,
+        }
+    } else {
+        None
+    };
+
+    let count = read_count(&mut chars);
+    let Some(c) = chars.next() else {
+        return Parse::Incomplete;
+    };
+
+    let action = match c {
+        'd' | 'x' if visual => Action::Selection(Operator::Delete),
+        'c' | 's' if visual => Action::Selection(Operator::Change),
+        'y' if visual => Action::Selection(Operator::Yank),
+        'o' if visual => Action::SwapAnchor,
+        'd' | 'c' | 'y' => {
+            let operator = match c {
+                'd' => Operator::Delete,
+                'c' => Operator::Change,
+                _ => Operator::Yank,
+            };
+            let inner_count = read_count(&mut chars);
+
+            let target = match chars.next() {
+                None => return Parse::Incomplete,
+                Some(same) if same == c => Target::Line,
+                Some(kind @ ('i' | 'a')) => match chars.next() {
+                    None => return Parse::Incomplete,
+                    Some(name) => match object(name) {
+                        Some(object) => Target::Object {
+                            object,
+                            inner: kind == 'i',
+                        },
+                        None => return Parse::Invalid,
+                    },
+                },
+                Some(first) => match motion(first, &mut chars) {
+                    Ok(Some(motion)) => Target::Motion(motion),
+                    Ok(None) => return Parse::Invalid,
+                    Err(()) => return Parse::Incomplete,
+                },
+            };
+
+            let count = match (count, inner_count) {
+                (None, None) => None,
+                (outer, inner) => Some(
+                    outer
+                        .unwrap_or(1)
+                        .saturating_mul(inner.unwrap_or(1))
+                        .min(MAX_COUNT),
+                ),
+            };
+
+            return Parse::Done(Command {
+                register,
+                count,
+                action: Action::Operate(operator, target),
+            });
+        }
+        'x' => Action::DeleteChar { back: false },
+        'X' => Action::DeleteChar { back: true },
+        'D' => Action::Operate(Operator::Delete, Target::Motion(Motion::LineEnd)),
+        'C' => Action::Operate(Operator::Change, Target::Motion(Motion::LineEnd)),
+        'Y' => Action::Operate(Operator::Yank, Target::Line),
+        's' => Action::Operate(Operator::Change, Target::Motion(Motion::Right)),
+        'S' => Action::Operate(Operator::Change, Target::Line),
+        'p' => Action::Put { before: false },
+        'P' => Action::Put { before: true },
+        'i' => Action::Insert(Entry::Before),
+        'a' => Action::Insert(Entry::After),
+        'I' => Action::Insert(Entry::LineStart),
+        'A' => Action::Insert(Entry::LineEnd),
+        'o' => Action::Insert(Entry::Below),
+        'O' => Action::Insert(Entry::Above),
+        'J' => Action::Join,
+        '~' => Action::ToggleCase,
+        'r' => match chars.next() {
+            Some(with) => Action::Replace(with),
+            None => return Parse::Incomplete,
+        },
+        'v' => Action::Visual { linewise: false },
+        'V' => Action::Visual { linewise: true },
+        '.' => Action::Repeat,
+        'u' if !visual => Action::Undo,
+        'Z' => match chars.next() {
+            Some('Z') => Action::Submit,
+            Some('Q') => Action::Cancel,
+            Some(_) => return Parse::Invalid,
+            None => return Parse::Incomplete,
+        },
+        first => match motion(first, &mut chars) {
+            Ok(Some(motion)) => Action::Move(motion),
+            Ok(None) => return Parse::Invalid,
+            Err(()) => return Parse::Incomplete,
+        },
+    };
+
+    Parse::Done(Command {
+        register,
+        count,
+        action,
+    })
+}
+
+/// Counts stop here, as vim's do, so a long run of digits can't overflow
+/// or leave a command repeating for minutes.
+const MAX_COUNT: usize = 999_999;
+
+fn read_count(chars: &mut std::iter::Peekable<std::str::Chars<'_>>) -> Option<usize> {
+    let mut count: Option<usize> = None;
+
+    while let Some(digit) = chars.peek().and_then(|c| c.to_digit(10)) {
+        // A leading zero is the `0` motion, not a count.
+        if digit == 0 && count.is_none() {
+            break;
+        }
+        count = Some(
+            count
+                .unwrap_or(0)
+                .saturating_mul(10)
+                .saturating_add(digit as usize)
+                .min(MAX_COUNT),
+        );
+        chars.next();
+    }
+
+    count
+}
+
+/// `Err` means the motion needs another key, as `f` and `g` do.
+fn motion(
+    first: char,
+    chars: &mut std::iter::Peekable<std::str::Chars<'_>>,
+) -> Result<Option<Motion>, ()> {
+    let found = match first {
+        'h' => Motion::Left,
+        'l' | ' ' => Motion::Right,
+        'j' => Motion::Down,
+        'k' => Motion::Up,
+        'w' => Motion::WordStart { big: false },
+        'W' => Motion::WordStart { big: true },
+        'e' => Motion::WordEnd { big: false },
+        'E' => Motion::WordEnd { big: true },
+        'b' => Motion::WordBack { big: false },
+        'B' => Motion::WordBack { big: true },
+        '0' => Motion::LineStart,
+        '^' => Motion::FirstNonBlank,
+        '$' => Motion::LineEnd,
+        'G' => Motion::LastLine,
+        'g' => match chars.next() {
+            Some('g') => Motion::FirstLine,
+            Some(_) => return Ok(None),
+            None => return Err(()),
+        },
+        'f' | 't' | 'F' | 'T' => match chars.next() {
+            Some(target) => Motion::Find {
+                target,
+                till: first == 't' || first == 'T',
+                back: first == 'F' || first == 'T',
+            },
+            None => return Err(()),
+        },
+        _ => return Ok(None),
+    };
+
+    Ok(Some(found))
+}
+
+fn object(name: char) -> Option<Object> {
+    let object = match name {
+        'w' => Object::Word { big: false },
+        'W' => Object::Word { big: true },
+        '"' | '\'' | '`' => Object::Quote(name),
+        '(' | ')' | 'b' => Object::Bracket('(', ')'),
+        '[' | ']' => Object::Bracket('[', ']'),
+        '{' | '}' | 'B' => Object::Bracket('{', '}'),
+        '<' | '>' => Object::Bracket('<', '>'),
+        _ => return None,
+    };
+
+    Some(object)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::api::{IssueId, TeamId};
+    use crate::tui::overlay::Compose;
+
+    fn editor(text: &str) -> Editor {
+        let mut editor = Editor::seeded(
+            IssueId::from_raw("i1"),
+            TeamId::from_raw("t1"),
+            Compose::Comment,
+            text,
+        );
+        editor.enable_vi();
+        editor.row = 0;
+        editor.col = 0;
+        editor
+    }
+
+    /// Types `keys` as the overlay would: vi handles normal and visual
+    /// mode, insert mode types plainly, and `<esc>` leaves insert mode.
+    fn press(editor: &mut Editor, registers: &mut Registers, keys: &str) -> Vec<Reply> {
+        let mut replies = Vec::new();
+        let mut rest = keys;
+
+        while let Some(c) = rest.chars().next() {
+            let escape = rest.starts_with("<esc>");
+            rest = if escape {
+                &rest["<esc>".len()..]
+            } else {
+                &rest[c.len_utf8()..]
+            };
+
+            let inserting = editor.vi().map(Vi::mode) == Some(Mode::Insert);
+            match (inserting, escape) {
+                (true, true) => editor.vi_leave_insert(),
+                (true, false) => editor.insert_char(c),
+                (false, true) => {
+                    replies.push(editor.vi_key(KeyEvent::from(KeyCode::Esc), registers))
+                }
+                (false, false) => {
+                    replies.push(editor.vi_key(KeyEvent::from(KeyCode::Char(c)), registers))
+                }
+            }
+        }
+
+        replies
+    }
+
+    fn typed(text: &str, keys: &str) -> (String, Position) {
+        let mut editor = editor(text);
+        press(&mut editor, &mut Registers::default(), keys);
+        (editor.text(), editor.position())
+    }
+
+    fn at(row: usize, col: usize) -> Position {
+        Position { row, col }
+    }
+
+    #[test]
+    fn ciw_changes_the_word_under_the_cursor() {
+        assert_eq!(
+            typed("ship the fix today", "wwciwpatch<esc>"),
+            ("ship the patch today".into(), at(0, 13))
+        );
+    }
+
+    #[test]
+    fn word_motions_cross_lines_and_stop_on_punctuation() {
+        let mut editor = editor("foo.bar baz\nqux");
+        let mut registers = Registers::default();
+
+        press(&mut editor, &mut registers, "w");
+        assert_eq!(editor.position(), at(0, 3));
+        press(&mut editor, &mut registers, "W");
+        assert_eq!(editor.position(), at(0, 8));
+        press(&mut editor, &mut registers, "w");
+        assert_eq!(editor.position(), at(1, 0));
+        press(&mut editor, &mut registers, "b");
+        assert_eq!(editor.position(), at(0, 8));
+        press(&mut editor, &mut registers, "e");
+        assert_eq!(editor.position(), at(0, 10));
+    }
+
+    #[test]
+    fn operators_take_counts_and_motions() {
+        assert_eq!(typed("one two three four", "d2w").0, "three four");
+        assert_eq!(typed("one two three four", "2dw").0, "three four");
+        assert_eq!(typed("one two three", "wD").0, "one ");
+        assert_eq!(typed("one two three", "cwuno<esc>").0, "uno two three");
+        assert_eq!(typed("a(b, c)d", "fbci(x<esc>").0, "a(x)d");
+        assert_eq!(typed("say \"hi there\" now", "fhda\"").0, "say now");
+        assert_eq!(typed("one\ntwo\nthree", "jdd").0, "one\nthree");
+        assert_eq!(typed("one\ntwo\nthree", "2dd").0, "three");
+        assert_eq!(typed("one two", "dt ").0, " two");
+    }
+
+    #[test]
+    fn huge_counts_are_capped_rather_than_overflowing() {
+        let twenty = "99999999999999999999";
+
+        assert_eq!(
+            typed("one two", &format!("{twenty}l")),
+            ("one two".into(), at(0, 6))
+        );
+        assert_eq!(typed("one\ntwo", &format!("{twenty}J")).0, "one two");
+        assert_eq!(typed("one two", &format!("{twenty}~")).0, "ONE TWO");
+        assert_eq!(typed("one two", &format!("{twenty}x")).0, "");
+        assert_eq!(typed("one two", &format!("x{twenty}u")).0, "one two");
+        assert_eq!(typed("one\ntwo", &format!("{twenty}dd")).0, "");
+        assert_eq!(typed("ab", &format!("x{twenty}.")).0, "");
+        assert_eq!(typed("abcd", "x2.u").0, "bcd");
+        assert_eq!(typed("ab", &format!("x{twenty}p")).0.len(), MAX_COUNT + 1);
+    }
+
+    #[test]
+    fn dw_on_the_last_word_keeps_the_line_break() {
+        assert_eq!(typed("one two\nthree", "wdw").0, "one \nthree");
+    }
+
+    #[test]
+    fn yank_and_put_round_trip_through_the_unnamed_register() {
+        assert_eq!(typed("one\ntwo", "yyjp").0, "one\ntwo\none");
+        assert_eq!(typed("one two", "yiwP").0, "oneone two");
+        assert_eq!(typed("one two", "xp").0, "noe two");
+    }
+
+    #[test]
+    fn dot_repeats_the_last_change_with_its_typed_text() {
+        assert_eq!(typed("a b c", "ciwx<esc>w.w.").0, "x x x");
+        assert_eq!(typed("one\ntwo\nthree", "dd.").0, "three");
+        assert_eq!(typed("abc", "Afoo<esc>.").0, "abcfoofoo");
+    }
+
+    #[test]
+    fn visual_mode_operates_on_the_selection() {
+        assert_eq!(typed("one two three", "wvey").0, "one two three");
+        assert_eq!(typed("one two three", "wved").0, "one  three");
+        assert_eq!(typed("one\ntwo\nthree", "Vjd").0, "three");
+        assert_eq!(typed("one two", "vecuno<esc>").0, "uno two");
+    }
+
+    #[test]
+    fn clipboard_registers_ask_for_a_copy() {
+        let mut editor = editor("one two");
+        let mut registers = Registers::default();
+
+        let replies = press(&mut editor, &mut registers, "\"+yiw");
+
+        assert_eq!(replies.last(), Some(&Reply::Clipboard("one".into())));
+        assert_eq!(
+            registers.get(Some('+')).map(Register::text),
+            Some("one".into())
+        );
+        assert_eq!(registers.get(None).map(Register::text), Some("one".into()));
+    }
+
+    #[test]
+    fn named_registers_keep_their_text_past_later_deletes() {
+        let mut editor = editor("one two");
+        let mut registers = Registers::default();
+
+        press(&mut editor, &mut registers, "\"ayiwwdiw\"ap");
+
+        assert_eq!(editor.text(), "one one");
+    }
+
+    #[test]
+    fn zz_and_zq_submit_and_cancel() {
+        let mut editor = editor("done");
+        let mut registers = Registers::default();
+
+        assert_eq!(
+            press(&mut editor, &mut registers, "ZZ").last(),
+            Some(&Reply::Submit)
+        );
+        assert_eq!(
+            press(&mut editor, &mut registers, "ZQ").last(),
+            Some(&Reply::Cancel)
+        );
+    }
+
+    #[test]
+    fn the_title_shows_the_mode_and_pending_keys() {
+        let mut editor = editor("one");
+        let mut registers = Registers::default();
+
+        press(&mut editor, &mut registers, "2d");
+        assert_eq!(editor.vi().map(Vi::status), Some("normal 2d".into()));
+
+        press(&mut editor, &mut registers, "<esc>v");
+        assert_eq!(editor.vi().map(Vi::status), Some("visual".into()));
+    }
+
+    #[test]
+    fn u_undoes_a_whole_change_including_what_insert_mode_typed() {
+        let mut editor = editor("one two");
+        let mut registers = Registers::default();
+
+        press(&mut editor, &mut registers, "wciwthree<esc>x");
+        assert_eq!(editor.text(), "one thre");
+
+        press(&mut editor, &mut registers, "u");
+        assert_eq!(editor.text(), "one three");
+        press(&mut editor, &mut registers, "u");
+        assert_eq!(editor.text(), "one two");
+
+        let ctrl_r = KeyEvent::new(KeyCode::Char('r'), KeyModifiers::CONTROL);
+        editor.vi_key(ctrl_r, &mut registers);
+        assert_eq!(editor.text(), "one three");
+    }
+}
@@ -0,0 +1,397 @@
+use super::cache::{Access, Cache, RefreshPolicy, Remote};
+use super::workspace::RenderedDetail;
+use crate::api::{IssueDetail, IssueId, IssueRef, Timestamp};
+
+pub const PREFETCH_RADIUS: usize = 2;
+pub const PREFETCH_IDLE_TICKS: u32 = 3;
+pub const PREFETCH_CONCURRENCY: usize = 2;
+pub const PREFETCH_BUDGET: u32 = 30;
+pub const PREFETCH_REFRESH: RefreshPolicy = RefreshPolicy::new(2 * 60, 15 * 60);
+const BUDGET_WINDOW: i64 = 60;
+const RATE_LIMIT_BACKOFF: i64 = 5 * 60;
+const CAPACITY: usize = 64;
+
+pub struct Warm {
+    pub detail: IssueDetail,
+    pub markdown: RenderedDetail,
+}
+
+#[derive(Default)]
+struct Budget {
+    window_start: Timestamp,
+    spent: u32,
+    paused_until: Option<Timestamp>,
+}
+
+impl Budget {
+    fn available(&mut self, now: Timestamp) -> u32 {
+        if self.paused_until.is_some_and(|until| now < until) {
+            return 0;
+        }
+
+        self.paused_until = None;
+
+        if now.seconds_since(self.window_start) >= BUDGET_WINDOW {
+            self.window_start = now;
+            self.spent = 0;
+        }
+
+        PREFETCH_BUDGET.saturating_sub(self.spent)
+    }
+
+    fn back_off(&mut self, now: Timestamp) {
+        self.paused_until = Some(Timestamp::from_epoch(now.epoch() + RATE_LIMIT_BACKOFF));
+    }
+}
+
+#[derive(Default)]
+pub struct Prefetcher {
+    details: Cache<IssueId, Remote<Warm>>,
+    anchor: Option<IssueId>,
+    idle: u32,
+    budget: Budget,
+}
+
+impl Prefetcher {
+    pub fn observe(&mut self, anchor: Option<&IssueId>) {
+        if self.anchor.as_ref() == anchor {
+            self.idle = self.idle.saturating_add(1);
+        } else {
+            self.anchor = anchor.cloned();
+            self.idle = 0;
+        }
+    }
+
+    pub fn settled(&self) -> bool {
+        self.anchor.is_some() && self.idle >= PREFETCH_IDLE_TICKS
+    }
+
+    pub fn plan(&mut self, window: &[(IssueId, Timestamp)], now: Timestamp) -> Vec<IssueId> {
+        if !self.settled() {
+            return Vec::new();
+        }
+
+        let slots = PREFETCH_CONCURRENCY.saturating_sub(self.in_flight());
+        let allowance = self.budget.available(now) as usize;
+
+        let wanted: Vec<IssueId> = window
+            .iter()
+            .filter(|(id, updated_at)| self.wants(id, *updated_at, now))
+            .map(|(id, _)| id.clone())
+            .take(slots.min(allowance))
+            .collect();
+
+        for id in &wanted {
+            let entry = self.details.get_or_default(id);
+
+            if entry.access(now, &PREFETCH_REFRESH) == Access::Bust {
+                entry.bust();
+            }
+
+            entry.begin();
+            self.budget.spent += 1;
+        }
+
+        self.evict();
+
+        wanted
+    }
+
+    fn wants(&self, id: &IssueId, updated_at: Timestamp, now: Timestamp) -> bool {
+        let Some(entry) = self.details.get(id) else {
+            return true;
+        };
+
+        if entry.in_flight() || entry.is_failed() {
+            return false;
+        }
+
+        let outdated = entry
+            .value()
+            .is_some_and(|warm| warm.detail.updated_at < updated_at);
+
+        outdated || entry.access(now, &PREFETCH_REFRESH) != Access::Skip
+    }
+
+    pub fn in_flight(&self) -> usize {
+        self.details
+            .iter()
+            .filter(|(_, entry)| entry.in_flight())
+            .count()
+    }
+
+    pub fn settle(&mut self, detail: IssueDetail, now: Timestamp) {
+        let markdown = RenderedDetail::render(&detail);
+
+        self.details
+            .get_or_default(&detail.id)
+            .set(Warm { detail, markdown }, now);
+    }
+
+    pub fn fail(&mut self, id: &IssueId, error: String) {
+        self.details.get_or_default(id).fail(error);
+    }
+
+    pub fn back_off(&mut self, now: Timestamp) {
+        self.budget.back_off(now);
+    }
+
+    pub fn warm(&self, id: &IssueId) -> Option<&Warm> {
+        self.details.get(id).and_then(Remote::value)
+    }
+
+    pub fn lookup(&self, target: &IssueRef, now: Timestamp) -> Option<(&Warm, Timestamp, bool)> {
+        self.details.iter().find_map(|(_, entry)| {
+            let warm = entry
+                .value()
+                .filter(|warm| target.matches_detail(&warm.detail))?;
+            let fresh = !entry.in_flight() && entry.access(now, &PREFETCH_REFRESH) == Access::Skip;
+
+            Some((warm, entry.fetched_at(), fresh))
+        })
+    }
+
+    pub fn supersede(&mut self, detail: &IssueDetail) {
+        let outdated = self
+            .warm(&detail.id)
+            .is_some_and(|warm| warm.detail.updated_at < detail.updated_at);
+
+        if outdated {
+            self.forget(&detail.id);
+        }
+    }
+
+    pub fn forget(&mut self, id: &IssueId) {
+        self.details.remove(id);
+    }
+
+    pub fn cancel(&mut self) {
+        for entry in self.details.values_mut() {
+            entry.cancel();
+        }
+
+        self.anchor = None;
+        self.idle = 0;
+    }
+
+    fn evict(&mut self) {
+        let excess = self.details.len().saturating_sub(CAPACITY);
+
+        if excess == 0 {
+            return;
+        }
+
+        let mut idle: Vec<(Timestamp, IssueId)> = self
+            .details
+            .iter()
+            .filter(|(_, entry)| !entry.in_flight())
+            .map(|(id, entry)| (entry.fetched_at(), id.clone()))
+            .collect();
+
+        idle.sort();
+
+        for (_, id) in idle.into_iter().take(excess) {
+            self.details.remove(&id);
+        }
+    }
+}
+
+pub fn neighbours(selected: usize, len: usize, radius: usize) -> Vec<usize> {
+    if selected >= len {
+        return Vec::new();
+    }
+
+    let mut positions = vec![selected];
+
+    for step in 1..=radius {
+        if selected + step < len {
+            positions.push(selected + step);
+        }
+
+        if let Some(before) = selected.checked_sub(step) {
+            positions.push(before);
+        }
+    }
+
+    positions
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::api::{Priority, StateType, TeamId, WorkflowState};
+
+    fn at(seconds: i64) -> Timestamp {
+        Timestamp::from_epoch(seconds)
+    }
+
+    fn detail(id: &str, updated_at: i64) -> IssueDetail {
+        IssueDetail {
+            id: IssueId::from_raw(id),
+            identifier: id.to_uppercase(),
+            title: None,
+            description: None,
+            url: String::new(),
+            state: WorkflowState {
+                name: "Todo".into(),
+                state_type: StateType::Unstarted,
+            },
+            priority: Priority::None,
+            assignee: None,
+            labels: Vec::new(),
+            comments: Vec::new(),
+            reactions: Vec::new(),
+            branch_name: String::new(),
+            team_id: TeamId::from_raw("t"),
+            updated_at: at(updated_at),
+        }
+    }
+
+    fn window(ids: &[&str]) -> Vec<(IssueId, Timestamp)> {
+        ids.iter()
+            .map(|id| (IssueId::from_raw(*id), at(0)))
+            .collect()
+    }
+
+    fn settled_on(prefetcher: &mut Prefetcher, id: &str) {
+        let anchor = IssueId::from_raw(id);
+
+        for _ in 0..=PREFETCH_IDLE_TICKS {
+            prefetcher.observe(Some(&anchor));
+        }
+    }
+
+    #[test]
+    fn neighbours_alternate_outward_from_the_selection() {
+        assert_eq!(neighbours(3, 10, 2), vec![3, 4, 2, 5, 1]);
+        assert_eq!(neighbours(0, 10, 2), vec![0, 1, 2]);
+        assert_eq!(neighbours(9, 10, 2), vec![9, 8, 7]);
+        assert!(neighbours(0, 0, 2).is_empty());
+    }
+
+    #[test]
+    fn nothing_is_planned_until_the_cursor_rests() {
+        let mut prefetcher = Prefetcher::default();
+        let anchor = IssueId::from_raw("a");
+
+        prefetcher.observe(Some(&anchor));
+        assert!(prefetcher.plan(&window(&["a"]), at(0)).is_empty());
+
+        settled_on(&mut prefetcher, "a");
+        assert_eq!(prefetcher.plan(&window(&["a"]), at(0)).len(), 1);
+    }
+
+    #[test]
+    fn moving_the_cursor_restarts_the_idle_wait() {
+        let mut prefetcher = Prefetcher::default();
+
+        settled_on(&mut prefetcher, "a");
+        prefetcher.observe(Some(&IssueId::from_raw("b")));
+
+        assert!(!prefetcher.settled());
+    }
+
+    #[test]
+    fn concurrency_is_bounded_across_plans() {
+        let mut prefetcher = Prefetcher::default();
+        settled_on(&mut prefetcher, "a");
+
+        let ids = window(&["a", "b", "c", "d", "e"]);
+
+        assert_eq!(prefetcher.plan(&ids, at(0)).len(), PREFETCH_CONCURRENCY);
+        assert!(prefetcher.plan(&ids, at(0)).is_empty());
+
+        prefetcher.settle(detail("a", 0), at(0));
+        assert_eq!(prefetcher.plan(&ids, at(0)), vec![IssueId::from_raw("c")]);
+    }
+
+    #[test]
+    fn the_budget_caps_requests_per_window() {
+        let mut prefetcher = Prefetcher::default();
+        settled_on(&mut prefetcher, "a");
+
+        let mut planned = 0;
+        for n in 0..PREFETCH_BUDGET * 2 {
+            let id = format!("i{n}");
+            for id in prefetcher.plan(&window(&[&id]), at(0)) {
+                prefetcher.settle(detail(id.as_str(), 0), at(0));
+                planned += 1;
+            }
+        }
+
+        assert_eq!(planned, PREFETCH_BUDGET);
+        assert_eq!(
+            prefetcher.plan(&window(&["next"]), at(BUDGET_WINDOW)).len(),
+            1
+        );
+    }
+
+    #[test]
+    fn a_rate_limit_pauses_prefetching() {
+        let mut prefetcher = Prefetcher::default();
+        settled_on(&mut prefetcher, "a");
+
+        prefetcher.back_off(at(0));
+
+        assert!(prefetcher.plan(&window(&["a"]), at(60)).is_empty());
+        assert_eq!(
+            prefetcher
+                .plan(&window(&["a"]), at(RATE_LIMIT_BACKOFF))
+                .len(),
+            1
+        );
+    }
+
+    #[test]
+    fn a_newer_row_refetches_a_warm_detail() {
+        let mut prefetcher = Prefetcher::default();
+        settled_on(&mut prefetcher, "a");
+        prefetcher.settle(detail("a", 10), at(0));
+
+        let unchanged = vec![(IssueId::from_raw("a"), at(10))];
+        let touched = vec![(IssueId::from_raw("a"), at(20))];
+
+        assert!(prefetcher.plan(&unchanged, at(0)).is_empty());
+        assert_eq!(prefetcher.plan(&touched, at(0)).len(), 1);
+    }
+
+    #[test]
+    fn a_failed_prefetch_is_not_retried() {
+        let mut prefetcher = Prefetcher::default();
+        settled_on(&mut prefetcher, "a");
+
+        let ids = window(&["a"]);
+        prefetcher.plan(&ids, at(0));
+        prefetcher.fail(&IssueId::from_raw("a"), "boom".into());
+
+        assert!(prefetcher.plan(&ids, at(0)).is_empty());
+    }
+
+    #[test]
+    fn cancel_releases_in_flight_slots() {
+        let mut prefetcher = Prefetcher::default();
+        settled_on(&mut prefetcher, "a");
+        prefetcher.plan(&window(&["a", "b"]), at(0));
+
+        prefetcher.cancel();
+
+        assert_eq!(prefetcher.in_flight(), 0);
+        assert!(!prefetcher.settled());
+    }
+
+    #[test]
+    fn lookup_matches_by_identifier_and_reports_freshness() {
+        let mut prefetcher = Prefetcher::default();
+        prefetcher.settle(detail("a", 0), at(0));
+
+        let target = IssueRef::Identifier("A".into());
+
+        let (_, _, fresh) = prefetcher.lookup(&target, at(1)).expect("a warm detail");
+        assert!(fresh);
+
+        let (_, _, fresh) = prefetcher
+            .lookup(&target, at(PREFETCH_REFRESH.fresh_for))
+            .expect("a warm detail");
+        assert!(!fresh);
+    }
+}
@@ -10,10 +10,10 @@ use super::app::{Active, App, Ui, Zoom};
 use super::feed::{Feed, FeedKey, FeedStore};
 use super::focus::{DetailView, Focus, LeftPanel, Scroll, PANELS};
 use super::layout;
-use super::overlay::{Menu, ModalOverlay, Overlay, Picker, PrefixUnder, Search};
+use super::overlay::{Menu, ModalOverlay, Overlay, Picker, PrefixUnder, Search, ViMode};
 use super::spinner::Spinner;
 use super::view::{ViewKind, Views};
-use super::workspace::WorkspaceData;
+use super::workspace::{RenderedDetail, WorkspaceData};
 use crate::api::{IssueDetail, IssueSummary, Timestamp};
 
 mod format;
@@ -69,6 +69,7 @@ pub fn render(app: &mut App, frame: &mut Frame) {
         OverlayProps {
             in_flight: overlay_in_flight,
             spinner: app.ui.spinner,
+            now: app.now,
         },
         frame,
     );
@@ -94,6 +95,7 @@ fn render_zoomed(app: &mut App, frame: &mut Frame, area: Rect) {
 struct OverlayProps {
     in_flight: bool,
     spinner: Spinner,
+    now: Timestamp,
 }
 
 fn render_overlay(
@@ -105,7 +107,11 @@ fn render_overlay(
     use ratatui::widgets::Clear;
 
     let frame_area = frame.area();
-    let OverlayProps { in_flight, spinner } = props;
+    let OverlayProps {
+        in_flight,
+        spinner,
+        now,
+    } = props;
 
     match overlay {
         Overlay::Picker(picker) => render_picker(picker, in_flight, spinner, frame),
@@ -121,10 +127,15 @@ fn render_overlay(
             overlays::input::render(input, frame, area);
         }
         Overlay::Editor(editor) => {
-            let area = overlays::editor::area(frame_area);
+            let area = overlays::editor::area(frame_area, editor);
             frame.render_widget(Clear, area);
             overlays::editor::render(editor, frame, area);
         }
+        Overlay::Conflict(conflict) => {
+            let area = overlays::conflict::area(frame_area);
+            frame.render_widget(Clear, area);
+            overlays::conflict::render(conflict, frame, area);
+        }
         Overlay::Search(search) => render_search(search, feeds, spinner, frame),
         Overlay::Prefix(prefix) => match &mut prefix.under {
             PrefixUnder::Modal(ModalOverlay::Picker(picker)) => {
@@ -147,6 +158,12 @@ fn render_overlay(
             frame.render_widget(Clear, area);
             overlays::reactions::render(reactions, frame, area);
         }
+        Overlay::Emoji(picker) => {
+            let area = overlays::emoji::area(frame_area);
+
+            frame.render_widget(Clear, area);
+            overlays::emoji::render(picker, frame, area);
+        }
         Overlay::Labels(labels) => {
             let area = overlays::labels::area(frame_area);
 
@@ -159,6 +176,18 @@ fn render_overlay(
             frame.render_widget(Clear, area);
             overlays::workspaces::render(workspaces, frame, area);
         }
+        Overlay::Drafts(drafts) => {
+            let area = overlays::drafts::area(frame_area);
+
+            frame.render_widget(Clear, area);
+            overlays::drafts::render(drafts, now, frame, area);
+        }
+        Overlay::Links(hints) => {
+            let area = overlays::links::area(hints, frame_area);
+
+            frame.render_widget(Clear, area);
+            overlays::links::render(hints, frame, area);
+        }
         Overlay::Find(_) | Overlay::None => {}
     }
 }
@@ -230,11 +259,10 @@ fn ready_detail<'w>(
     views: &Views,
     view_state: &ListState,
     list_state: &ListState,
-) -> Option<&'w IssueDetail> {
-    let detail = workspace.detail().value()?;
+) -> Option<(&'w IssueDetail, &'w RenderedDetail)> {
     let selected = selected_issue(workspace, views, view_state, list_state)?;
 
-    (detail.id == selected.id).then_some(detail)
+    workspace.ready_detail(&selected.id)
 }
 
 fn render_panel(
@@ -285,6 +313,7 @@ fn render_panel(
                     list_state,
                     emphasis,
                     spinner: *spinner,
+                    drafts: &workspace.drafts,
                 },
             );
         }
@@ -292,6 +321,7 @@ fn render_panel(
             frame,
             rect,
             &app.workspace.recently_viewed,
+            &app.workspace.drafts,
             &mut app.workspace.recent_state,
             emphasis,
         ),
@@ -322,10 +352,10 @@ fn render_view_surface(
 ) -> Viewport {
     let spinner = app.ui.spinner;
     let now = app.now;
-    let (view, feeds) = app.view_render_parts();
+    let (view, workspace) = app.view_render_parts();
 
     match view {
-        Some(view) => surfaces::view::render(frame, area, feeds, view, spinner, emphasis, now),
+        Some(view) => surfaces::view::render(frame, area, workspace, view, spinner, emphasis, now),
         None => Viewport((area.height as usize).saturating_sub(2)),
     }
 }
@@ -373,12 +403,12 @@ fn render_my_work_right(app: &mut App, frame: &mut Frame, area: Rect) {
         &app.ui.view_state,
         &app.ui.list_state,
     ) {
-        Some(detail) => {
+        Some((detail, markdown)) => {
             surfaces::detail::render_reading(
                 frame,
                 area,
                 detail,
-                app.workspace.detail_markdown(),
+                markdown,
                 surfaces::detail::ReadingProps {
                     now: app.now,
                     selected: None,
@@ -445,7 +475,7 @@ fn render_right(app: &mut App, frame: &mut Frame, area: Rect) {
                     surfaces::saved_views::render_preview(
                         frame,
                         area,
-                        &app.workspace.feeds,
+                        &app.workspace,
                         &id,
                         &name,
                         spinner,
@@ -522,7 +552,11 @@ fn footer_state(app: &App) -> surfaces::footer::Footer {
         },
     };
 
-    Footer::Normal { left, workspace }
+    Footer::Normal {
+        left,
+        workspace,
+        badge: app.ui.fixture.then_some("fixture"),
+    }
 }
 
 fn find_bar_state(app: &App) -> Option<surfaces::footer::FindBar> {
@@ -563,6 +597,7 @@ fn footer_hint(app: &App) -> String {
     match app.overlay() {
         Overlay::Menu(_) => return action::MENU.hint_bar(action::MENU_HINTS),
         Overlay::Confirm(_) => return action::CONFIRM.hint_bar(action::CONFIRM_HINTS),
+        Overlay::Conflict(_) => return action::CONFLICT.hint_bar(action::CONFLICT_HINTS),
         Overlay::Picker(picker) if picker.searchable() => {
             return action::PICKER.hint_bar(action::SEARCHABLE_PICKER_HINTS)
         }
@@ -571,10 +606,20 @@ fn footer_hint(app: &App) -> String {
         }
         Overlay::Prefix(prefix) => return format!("{}   esc cancel", prefix.keymap.summary()),
         Overlay::Input(_) => return action::INPUT.hint_bar(action::INPUT_HINTS),
-        Overlay::Editor(_) => return action::EDITOR.hint_bar(action::EDITOR_HINTS),
+        Overlay::Editor(editor) => {
+            let hints = match editor.vi().map(|vi| vi.mode()) {
+                None => action::EDITOR_HINTS,
+                Some(ViMode::Insert) => action::VI_INSERT_HINTS,
+                Some(ViMode::Normal | ViMode::Visual { .. }) => action::VI_NORMAL_HINTS,
+            };
+            return action::EDITOR.hint_bar(hints);
+        }
         Overlay::Reactions(_) => return action::REACTIONS.hint_bar(action::REACTIONS_HINTS),
+        Overlay::Emoji(_) => return action::EMOJI.hint_bar(action::EMOJI_HINTS),
         Overlay::Labels(_) => return action::LABELS.hint_bar(action::LABELS_HINTS),
         Overlay::Workspaces(_) => return action::WORKSPACES.hint_bar(action::WORKSPACES_HINTS),
+        Overlay::Drafts(_) => return action::DRAFTS.hint_bar(action::DRAFTS_HINTS),
+        Overlay::Links(_) => return action::LINKS.hint_bar(action::LINKS_HINTS),
         Overlay::Find(_) | Overlay::None => {}
     }
 
@@ -0,0 +1,51 @@
+use ratatui::{
+    layout::{Constraint, Layout, Rect},
+    text::{Line, Span, Text},
+    widgets::{Block, Paragraph, Wrap},
+    Frame,
+};
+
+use super::super::theme;
+use crate::tui::action;
+use crate::tui::layout;
+use crate::tui::overlay::Conflict;
+
+pub fn area(frame_area: Rect) -> Rect {
+    layout::centred_rect(frame_area, 80, 60)
+}
+
+pub fn render(conflict: &Conflict, frame: &mut Frame, area: Rect) {
+    let block = Block::bordered()
+        .title("Edit conflict")
+        .border_style(theme::accent());
+    let inner = block.inner(area);
+    frame.render_widget(block, area);
+
+    let [body, keys] = Layout::vertical([Constraint::Min(1), Constraint::Length(1)]).areas(inner);
+    let [theirs, yours] =
+        Layout::horizontal([Constraint::Percentage(50), Constraint::Percentage(50)]).areas(body);
+
+    render_side("Theirs", &conflict.theirs, frame, theirs);
+    render_side("Yours", &conflict.draft.body, frame, yours);
+
+    frame.render_widget(
+        Paragraph::new(Line::from(Span::styled(
+            action::CONFLICT.hint_bar(action::CONFLICT_HINTS),
+            theme::dim(),
+        ))),
+        keys,
+    );
+}
+
+fn render_side(title: &str, body: &str, frame: &mut Frame, area: Rect) {
+    let block = Block::bordered()
+        .title(title.to_string())
+        .border_style(theme::dim());
+
+    frame.render_widget(
+        Paragraph::new(Text::from(body.to_string()))
+            .block(block)
+            .wrap(Wrap { trim: false }),
+        area,
+    );
+}
@@ -0,0 +1,66 @@
+use ratatui::{
+    layout::Rect,
+    style::{Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, List, ListItem},
+    Frame,
+};
+
+use super::super::format;
+use super::super::theme;
+use crate::api::Timestamp;
+use crate::tui::drafts::Draft;
+use crate::tui::layout;
+use crate::tui::overlay::DraftList;
+
+/// Wide enough for "Description", the longest kind.
+const KIND_WIDTH: usize = 11;
+
+pub fn area(frame_area: Rect) -> Rect {
+    layout::centred_rect_fixed(frame_area, 70, 14)
+}
+
+pub fn render(drafts: &mut DraftList, now: Timestamp, frame: &mut Frame, area: Rect) {
+    let width = area.width.saturating_sub(2) as usize;
+    let id_width = drafts
+        .drafts
+        .iter()
+        .map(|draft| format::width(&draft.identifier))
+        .max()
+        .unwrap_or(0);
+    let items: Vec<ListItem> = drafts
+        .drafts
+        .iter()
+        .map(|draft| ListItem::new(row(draft, id_width, width, now)))
+        .collect();
+
+    let list = List::new(items)
+        .block(
+            Block::bordered()
+                .title(format!("Drafts ({})", drafts.drafts.len()))
+                .border_style(theme::accent()),
+        )
+        .highlight_style(Style::new().add_modifier(Modifier::REVERSED));
+
+    frame.render_stateful_widget(list, area, &mut drafts.state);
+}
+
+fn row(draft: &Draft, id_width: usize, width: usize, now: Timestamp) -> Line<'static> {
+    let age = draft.saved_at.age_short(now);
+    let left_w = id_width + 2 + KIND_WIDTH + 2;
+    let gap = 2;
+    let preview_area = width.saturating_sub(left_w + format::width(&age) + gap);
+    let preview = format::fit(draft.preview(), preview_area);
+    let pad = preview_area.saturating_sub(format::width(&preview)) + gap;
+
+    Line::from(vec![
+        Span::styled(format!("{:<id_width$}  ", draft.identifier), theme::dim()),
+        Span::styled(
+            format!("{:<KIND_WIDTH$}  ", draft.compose.title()),
+            theme::accent(),
+        ),
+        Span::styled(preview, theme::TEXT),
+        Span::raw(" ".repeat(pad)),
+        Span::styled(age, theme::dim()),
+    ])
+}
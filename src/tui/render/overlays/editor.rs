@@ -1,8 +1,10 @@
+use std::ops::Range;
+
 use ratatui::{
-    layout::Rect,
+    layout::{Constraint, Layout, Rect},
     style::{Modifier, Style},
     text::{Line, Span, Text},
-    widgets::{Block, Clear, ListItem, ListState, Paragraph},
+    widgets::{Block, Clear, ListItem, ListState, Paragraph, Wrap},
     Frame,
 };
 
@@ -10,16 +12,69 @@ use super::super::format;
 use super::super::theme::{self, Emphasis};
 use super::super::widgets::StyledList;
 use crate::tui::layout;
-use crate::tui::overlay::{Cell, Editor, MentionMenu};
+use crate::tui::markdown;
+use crate::tui::overlay::{Cell, Completion, Editor, MentionMenu};
+
+/// Below this width the preview goes under the input rather than beside it.
+const SIDE_BY_SIDE: u16 = 100;
 
-pub fn area(frame_area: Rect) -> Rect {
-    layout::centred_rect(frame_area, 70, 50)
+pub fn area(frame_area: Rect, editor: &Editor) -> Rect {
+    if editor.previewing() {
+        layout::centred_rect(frame_area, 90, 80)
+    } else {
+        layout::centred_rect(frame_area, 70, 50)
+    }
 }
 
 pub fn render(editor: &Editor, frame: &mut Frame, area: Rect) {
+    if !editor.previewing() {
+        render_input(editor, frame, area);
+        return;
+    }
+
+    let [input, preview] = if area.width >= SIDE_BY_SIDE {
+        Layout::horizontal([Constraint::Percentage(50); 2]).areas(area)
+    } else {
+        Layout::vertical([Constraint::Percentage(50); 2]).areas(area)
+    };
+
+    render_input(editor, frame, input);
+    render_preview(editor, frame, preview);
+}
+
+/// The buffer as it will read once posted, styled as the detail pane
+/// styles comments. It scrolls in step with the cursor's row.
+fn render_preview(editor: &Editor, frame: &mut Frame, area: Rect) {
+    let lines = markdown::render(&editor.text(), Style::default());
+    let text = if lines.is_empty() {
+        Text::from(Span::styled("Nothing to preview yet", theme::dim()))
+    } else {
+        Text::from(lines)
+    };
+
+    let emphasis = Emphasis::Blurred;
     let block = Block::bordered()
-        .title(editor.title)
-        .border_style(theme::accent());
+        .title(Span::styled("Preview", emphasis.title()))
+        .border_style(emphasis.border());
+    let paragraph = Paragraph::new(text).wrap(Wrap { trim: false });
+
+    let inner_width = area.width.saturating_sub(2);
+    let inner_height = area.height.saturating_sub(2) as usize;
+    let overflow = paragraph
+        .line_count(inner_width)
+        .saturating_sub(inner_height);
+    let last_row = editor.lines().len().saturating_sub(1).max(1);
+    let scroll = overflow * editor.row().min(last_row) / last_row;
+
+    frame.render_widget(paragraph.block(block).scroll((scroll as u16, 0)), area);
+}
+
+fn render_input(editor: &Editor, frame: &mut Frame, area: Rect) {
+    let title = match editor.vi() {
+        Some(vi) => format!("{} · {}", editor.title, vi.status()),
+        None => editor.title.to_string(),
+    };
+    let block = Block::bordered().title(title).border_style(theme::accent());
 
     let inner_height = (area.height.saturating_sub(2) as usize).max(1);
     let offset = editor.row().saturating_sub(inner_height - 1);
@@ -32,7 +87,7 @@ pub fn render(editor: &Editor, frame: &mut Frame, area: Rect) {
         .take(inner_height)
         .map(|(row, cells)| {
             let cursor = (row == editor.row()).then_some(editor.col());
-            editor_line(cells, cursor)
+            editor_line(cells, cursor, selected(editor, row, cells.len()))
         })
         .collect();
 
@@ -58,6 +113,7 @@ fn cell_width(cell: &Cell) -> u16 {
     let rendered = match cell {
         Cell::Char(c) => c.to_string(),
         Cell::Mention(mention) => format!("@{}", mention.display),
+        Cell::Reference(reference) => reference.identifier.clone(),
     };
 
     format::width(&rendered) as u16
@@ -67,7 +123,32 @@ fn cursor_column(cells: &[Cell], col: usize) -> u16 {
     1 + cells.iter().take(col).map(cell_width).sum::<u16>()
 }
 
-fn editor_line(cells: &[Cell], cursor: Option<usize>) -> Line<'static> {
+/// The columns of `row` inside the visual selection, end exclusive.
+fn selected(editor: &Editor, row: usize, len: usize) -> Option<Range<usize>> {
+    let (start, end, linewise) = editor.selection()?;
+
+    if row < start.row || row > end.row {
+        return None;
+    }
+
+    if linewise {
+        return Some(0..len.max(1));
+    }
+
+    let from = if row == start.row { start.col } else { 0 };
+    let to = if row == end.row {
+        end.col + 1
+    } else {
+        len.max(1)
+    };
+    Some(from..to)
+}
+
+fn editor_line(
+    cells: &[Cell],
+    cursor: Option<usize>,
+    selected: Option<Range<usize>>,
+) -> Line<'static> {
     let mut spans = vec![Span::raw(" ".to_string())];
 
     for (index, cell) in cells.iter().enumerate() {
@@ -76,9 +157,13 @@ fn editor_line(cells: &[Cell], cursor: Option<usize>) -> Line<'static> {
             Cell::Mention(mention) => {
                 Span::styled(format!("@{}", mention.display), theme::person())
             }
+            Cell::Reference(reference) => Span::styled(reference.identifier.clone(), theme::link()),
         };
 
-        if cursor == Some(index) {
+        let in_selection = selected
+            .as_ref()
+            .is_some_and(|range| range.contains(&index));
+        if cursor == Some(index) || in_selection {
             span.style = span.style.add_modifier(Modifier::REVERSED);
         }
 
@@ -101,16 +186,81 @@ fn render_mention_popup(
     frame: &mut Frame,
     editor_area: Rect,
 ) {
-    let candidates = editor.candidates(&mention.query);
+    let (title, items, width): (_, Vec<ListItem>, _) = match mention.kind {
+        Completion::People => {
+            let items = editor
+                .candidates(&mention.query)
+                .iter()
+                .map(|user| {
+                    ListItem::new(Line::from(Span::styled(
+                        format!("@{}", user.display_name),
+                        theme::person(),
+                    )))
+                })
+                .collect();
+            ("Mention", items, 40)
+        }
+        Completion::Issues => {
+            let items = editor
+                .issue_candidates(&mention.query)
+                .iter()
+                .map(|issue| {
+                    ListItem::new(Line::from(vec![
+                        Span::styled(format!("{} ", issue.identifier), theme::link()),
+                        Span::styled(issue.title.clone().unwrap_or_default(), theme::TEXT),
+                    ]))
+                })
+                .collect();
+            ("Issue", items, 60)
+        }
+        Completion::Emoji => {
+            let items = editor
+                .emoji_candidates(&mention.query)
+                .iter()
+                .map(|entry| {
+                    ListItem::new(Line::from(vec![
+                        Span::raw(format!("{}  ", entry.glyph)),
+                        Span::styled(format!(":{}:", entry.name), theme::TEXT),
+                    ]))
+                })
+                .collect();
+            ("Emoji", items, 40)
+        }
+        Completion::Templates => {
+            let templates = editor.template_candidates(&mention.query);
+            let name_width = templates
+                .iter()
+                .map(|template| format::width(&template.name))
+                .max()
+                .unwrap_or(0);
+            let items = templates
+                .iter()
+                .map(|template| {
+                    let preview = template
+                        .body
+                        .lines()
+                        .map(str::trim)
+                        .find(|line| !line.is_empty())
+                        .unwrap_or_default();
+
+                    ListItem::new(Line::from(vec![
+                        Span::styled(format!("{:<name_width$}  ", template.name), theme::accent()),
+                        Span::styled(preview.to_string(), theme::dim()),
+                    ]))
+                })
+                .collect();
+            ("Template", items, 60)
+        }
+    };
 
-    if candidates.is_empty() {
+    if items.is_empty() {
         return;
     }
 
     let selected = mention.state.selected();
 
-    let visible = candidates.len().min(6) as u16;
-    let width = editor_area.width.saturating_sub(4).min(40);
+    let visible = items.len().min(6) as u16;
+    let width = editor_area.width.saturating_sub(4).min(width);
     let height = visible + 2;
     let area = Rect {
         x: editor_area.x + 2,
@@ -119,21 +269,11 @@ fn render_mention_popup(
         height,
     };
 
-    let items: Vec<ListItem> = candidates
-        .iter()
-        .map(|user| {
-            ListItem::new(Line::from(Span::styled(
-                format!("@{}", user.display_name),
-                theme::person(),
-            )))
-        })
-        .collect();
-
     frame.render_widget(Clear, area);
 
     let mut state = ListState::default().with_selected(selected);
 
-    let mentions = StyledList::new("Mention")
+    let mentions = StyledList::new(title)
         .items(items)
         .emphasis(Emphasis::Focused)
         .state(&mut state);
@@ -0,0 +1,63 @@
+use ratatui::{
+    layout::{Constraint, Layout, Rect},
+    text::{Line, Span},
+    widgets::{Block, List, ListItem, Paragraph},
+    Frame,
+};
+
+use super::super::theme::{self, Emphasis};
+use crate::tui::emoji::PaletteEmoji;
+use crate::tui::layout;
+use crate::tui::overlay::EmojiPicker;
+
+pub fn area(frame_area: Rect) -> Rect {
+    layout::centred_rect_fixed(frame_area, 52, 16)
+}
+
+pub fn render(picker: &mut EmojiPicker, frame: &mut Frame, area: Rect) {
+    let block = Block::bordered()
+        .title("Emoji")
+        .border_style(theme::accent());
+    let inner = block.inner(area);
+    frame.render_widget(block, area);
+
+    let [search_area, list_area] =
+        Layout::vertical([Constraint::Length(1), Constraint::Min(0)]).areas(inner);
+
+    frame.render_widget(Paragraph::new(search_line(picker)), search_area);
+
+    if picker.results.is_empty() {
+        frame.render_widget(
+            Paragraph::new(Line::from(Span::styled(
+                "no match, enter reacts with the name as typed",
+                theme::dim(),
+            ))),
+            list_area,
+        );
+        return;
+    }
+
+    let items: Vec<ListItem> = picker.results.iter().map(row_item).collect();
+    let list = List::new(items)
+        .highlight_style(Emphasis::Focused.highlight())
+        .scroll_padding(1);
+
+    frame.render_stateful_widget(list, list_area, &mut picker.state);
+}
+
+fn search_line(picker: &EmojiPicker) -> Line<'static> {
+    let query = if picker.query.is_empty() {
+        Span::styled("type to search", theme::dim())
+    } else {
+        Span::styled(picker.query.clone(), theme::TEXT)
+    };
+
+    Line::from(vec![Span::styled("search: ", theme::dim()), query])
+}
+
+fn row_item(entry: &PaletteEmoji) -> ListItem<'static> {
+    ListItem::new(Line::from(vec![
+        Span::raw(format!("{}  ", entry.glyph)),
+        Span::styled(format!(":{}:", entry.name), theme::TEXT),
+    ]))
+}
@@ -0,0 +1,92 @@
+use ratatui::{
+    layout::Rect,
+    style::Modifier,
+    text::{Line, Span},
+    widgets::{Block, List, ListItem},
+    Frame,
+};
+
+use super::super::format;
+use super::super::theme;
+use crate::tui::layout;
+use crate::tui::overlay::{LinkHint, LinkHints};
+
+/// Rows shown before the list is cut off; typing a label's first key
+/// narrows it well below this.
+const MAX_ROWS: usize = 20;
+
+pub fn area(hints: &LinkHints, frame_area: Rect) -> Rect {
+    let rows = shown(hints).count().clamp(1, MAX_ROWS);
+    layout::centred_rect_fixed(frame_area, 80, rows as u16 + 2)
+}
+
+pub fn render(hints: &LinkHints, frame: &mut Frame, area: Rect) {
+    let width = area.width.saturating_sub(2) as usize;
+    let label_width = hints
+        .hints
+        .iter()
+        .map(|hint| hint.label.len())
+        .max()
+        .unwrap_or(0);
+    let items: Vec<ListItem> = shown(hints)
+        .map(|hint| ListItem::new(row(hint, &hints.typed, label_width, width)))
+        .collect();
+
+    let list = List::new(items).block(
+        Block::bordered()
+            .title(format!("Links ({})", hints.hints.len()))
+            .border_style(theme::accent()),
+    );
+
+    frame.render_widget(list, area);
+}
+
+fn shown(hints: &LinkHints) -> impl Iterator<Item = &LinkHint> {
+    hints
+        .hints
+        .iter()
+        .filter(|hint| hint.label.starts_with(&hints.typed))
+}
+
+fn row(hint: &LinkHint, typed: &str, label_width: usize, width: usize) -> Line<'static> {
+    let (done, rest) = hint.label.split_at(typed.len());
+    let pad = label_width - hint.label.len() + 2;
+    let source = format::fit(&hint.source, 16);
+    let kind = if hint.link.image { "image  " } else { "" };
+
+    let left_w = label_width + 2 + format::width(kind);
+    let right_w = format::width(&source) + 2;
+    let body_w = width.saturating_sub(left_w + right_w);
+    // A pasted URL is its own text, so it gets the whole width.
+    let bare = hint.link.text == hint.link.url;
+    let text = format::fit(&hint.link.text, if bare { body_w } else { body_w / 2 });
+    let url = if bare {
+        String::new()
+    } else {
+        format::fit(
+            &hint.link.url,
+            body_w.saturating_sub(format::width(&text) + 2),
+        )
+    };
+    let url_w = if bare { 0 } else { format::width(&url) + 2 };
+    let gap = body_w.saturating_sub(format::width(&text) + url_w) + 2;
+
+    let mut spans = vec![
+        Span::styled(done.to_string(), theme::dim()),
+        Span::styled(
+            rest.to_string(),
+            theme::accent().add_modifier(Modifier::BOLD),
+        ),
+        Span::raw(" ".repeat(pad)),
+        Span::styled(kind, theme::dim()),
+        Span::styled(text, theme::TEXT),
+    ];
+    if !bare {
+        spans.push(Span::raw("  "));
+        spans.push(Span::styled(url, theme::dim()));
+    }
+    spans.push(Span::raw(" ".repeat(gap)));
+    spans.push(Span::styled(source, theme::dim()));
+
+    Line::from(spans)
+}
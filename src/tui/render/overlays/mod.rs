@@ -1,7 +1,11 @@
 pub mod confirm;
+pub mod conflict;
+pub mod drafts;
 pub mod editor;
+pub mod emoji;
 pub mod input;
 pub mod labels;
+pub mod links;
 pub mod menu;
 pub mod picker;
 pub mod prefix;
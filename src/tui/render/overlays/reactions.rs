@@ -12,7 +12,7 @@ use crate::tui::overlay::{ReactionChoice, Reactions, Section};
 
 const LABEL_WIDTH: u16 = 9;
 const CELL_WIDTH: u16 = 5;
-const HINT: &str = "h/l move · j/k row · enter toggle · c custom · esc";
+const HINT: &str = "h/l move · j/k row · enter toggle · c more · esc";
 
 pub fn area(reactions: &Reactions, frame_area: Rect) -> Rect {
     let current = reactions
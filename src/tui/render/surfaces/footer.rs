@@ -12,7 +12,11 @@ use crate::tui::layout;
 
 pub enum Footer {
     Find(FindBar),
-    Normal { left: FooterLeft, workspace: String },
+    Normal {
+        left: FooterLeft,
+        workspace: String,
+        badge: Option<&'static str>,
+    },
 }
 
 pub enum FindBar {
@@ -40,9 +44,15 @@ pub fn render(frame: &mut Frame, area: Rect, footer: Footer) {
         Footer::Find(find) => {
             frame.render_widget(Paragraph::new(find_bar(find)), area);
         }
-        Footer::Normal { left, workspace } => {
+        Footer::Normal {
+            left,
+            workspace,
+            badge,
+        } => {
+            let badge = badge.map(|badge| format!(" {badge} "));
+            let badge_width = badge.as_deref().map_or(0, |badge| format::width(badge) + 1);
             let [left_area, right_area] =
-                layout::split_footer(area, format::width(&workspace) as u16 + 1);
+                layout::split_footer(area, (format::width(&workspace) + badge_width) as u16 + 1);
 
             frame.render_widget(
                 Paragraph::new(left_line(left, left_area.width as usize)),
@@ -50,14 +60,25 @@ pub fn render(frame: &mut Frame, area: Rect, footer: Footer) {
             );
 
             frame.render_widget(
-                Paragraph::new(Line::from(Span::styled(workspace, theme::workspace())))
-                    .alignment(Alignment::Right),
+                Paragraph::new(workspace_line(badge, workspace)).alignment(Alignment::Right),
                 right_area,
             );
         }
     }
 }
 
+fn workspace_line(badge: Option<String>, workspace: String) -> Line<'static> {
+    let mut spans = Vec::new();
+
+    if let Some(badge) = badge {
+        spans.push(Span::styled(badge, theme::badge()));
+        spans.push(Span::raw(" "));
+    }
+    spans.push(Span::styled(workspace, theme::workspace()));
+
+    Line::from(spans)
+}
+
 fn left_line(left: FooterLeft, width: usize) -> Line<'static> {
     match left {
         FooterLeft::Status { text, is_error } => {
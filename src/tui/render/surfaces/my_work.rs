@@ -7,6 +7,7 @@ use super::super::widgets::{
 };
 use crate::api::{IssueSummary, NotificationItem};
 use crate::tui::cache::CacheStatus;
+use crate::tui::drafts::Drafts;
 use crate::tui::spinner::Spinner;
 use crate::tui::view::View;
 
@@ -24,6 +25,7 @@ pub struct MyWorkProps<'a> {
     pub list_state: &'a mut ListState,
     pub emphasis: Emphasis,
     pub spinner: Spinner,
+    pub drafts: &'a Drafts,
 }
 
 pub fn render(frame: &mut Frame, area: Rect, props: MyWorkProps) {
@@ -36,6 +38,7 @@ pub fn render(frame: &mut Frame, area: Rect, props: MyWorkProps) {
         list_state,
         emphasis,
         spinner,
+        drafts,
     } = props;
 
     let max_title = area.width.saturating_sub(2) as usize;
@@ -49,9 +52,11 @@ pub fn render(frame: &mut Frame, area: Rect, props: MyWorkProps) {
 
     let (mut items, total, empty) = match content {
         MyWorkContent::Inbox { items } => (notification_items(items), items.len(), "Inbox empty"),
-        MyWorkContent::Issues { issues } => {
-            (issue_items(issues), issues.len(), "No issues in this view")
-        }
+        MyWorkContent::Issues { issues } => (
+            issue_items(issues, drafts),
+            issues.len(),
+            "No issues in this view",
+        ),
     };
 
     if appending && total > 0 {
@@ -8,17 +8,19 @@ use ratatui::{
 use super::super::theme::Emphasis;
 use super::super::widgets::{issue_items, preview_text, text_panel, StyledList};
 use crate::api::IssueSummary;
+use crate::tui::drafts::Drafts;
 
 pub fn render(
     frame: &mut Frame,
     area: Rect,
     issues: &[IssueSummary],
+    drafts: &Drafts,
     state: &mut ListState,
     emphasis: Emphasis,
 ) {
     let selected = state.selected();
     let total = issues.len();
-    let items = issue_items(issues);
+    let items = issue_items(issues, drafts);
 
     let list = StyledList::new("Recently viewed")
         .items(items)
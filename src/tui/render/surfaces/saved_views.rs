@@ -8,14 +8,15 @@ use ratatui::{
 use super::super::format::id_column_width;
 use super::super::theme::Emphasis;
 use super::super::widgets::{
-    breakdown_line, placeholder, view_items, view_title, PlaceholderText, StyledList,
+    breakdown_line, placeholder, view_items, view_title, PlaceholderText, RowContext, StyledList,
 };
 use super::{feed_count, feed_placeholder, feed_truncated};
 use crate::api::{Timestamp, ViewId};
 use crate::tui::display::{self, GroupBy, SortBy};
-use crate::tui::feed::{Feed, FeedKey, FeedStore};
+use crate::tui::feed::{Feed, FeedKey};
 use crate::tui::saved_views::SavedViewsPanel;
 use crate::tui::spinner::Spinner;
+use crate::tui::workspace::WorkspaceData;
 
 pub fn render(
     frame: &mut Frame,
@@ -59,13 +60,13 @@ pub fn render(
 pub fn render_preview(
     frame: &mut Frame,
     area: Rect,
-    feeds: &FeedStore,
+    workspace: &WorkspaceData,
     id: &ViewId,
     name: &str,
     spinner: Spinner,
     now: Timestamp,
 ) {
-    let feed = feeds.get(&FeedKey::View(id.clone()));
+    let feed = workspace.feeds.get(&FeedKey::View(id.clone()));
 
     let block = Block::bordered()
         .title(Span::styled(
@@ -99,7 +100,13 @@ pub fn render_preview(
 
     let id_width = id_column_width(issues);
     let width = rows[1].width as usize;
-    let (items, _) = view_items(issues, &groups, GroupBy::Status, None, id_width, width, now);
+    let context = RowContext {
+        id_width,
+        width,
+        now,
+        drafts: &workspace.drafts,
+    };
+    let (items, _) = view_items(issues, &groups, GroupBy::Status, None, context);
 
     frame.render_widget(List::new(items), rows[1]);
 }
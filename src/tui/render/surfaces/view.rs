@@ -7,26 +7,27 @@ use ratatui::{
 
 use super::super::format::id_column_width;
 use super::super::theme::{self, Emphasis};
-use super::super::widgets::{breakdown_line, loading_more_row, view_items, view_title};
+use super::super::widgets::{breakdown_line, loading_more_row, view_items, view_title, RowContext};
 use super::{feed_count, feed_placeholder, feed_truncated, Viewport};
 use crate::api::Timestamp;
-use crate::tui::feed::{Feed, FeedStore};
+use crate::tui::feed::Feed;
 use crate::tui::saved_views::ViewSurface;
 use crate::tui::spinner::Spinner;
 use crate::tui::team::TeamMode;
+use crate::tui::workspace::WorkspaceData;
 
 pub const VIEW_HEADER_ROWS: u16 = 3;
 
 pub fn render(
     frame: &mut Frame,
     area: Rect,
-    feeds: &FeedStore,
+    workspace: &WorkspaceData,
     view: &mut ViewSurface,
     spinner: Spinner,
     emphasis: Emphasis,
     now: Timestamp,
 ) -> Viewport {
-    let feed = feeds.get(&view.key());
+    let feed = workspace.feeds.get(&view.key());
 
     let block = Block::bordered()
         .title(Span::styled(
@@ -74,14 +75,18 @@ pub fn render(
 
     let id_width = id_column_width(issues);
     let width = rows[1].width as usize;
+    let context = RowContext {
+        id_width,
+        width,
+        now,
+        drafts: &workspace.drafts,
+    };
     let (mut items, selected_row) = view_items(
         issues,
         &groups,
         view.display.group,
         view.state.selected(),
-        id_width,
-        width,
-        now,
+        context,
     );
 
     if feed.is_some_and(|feed| feed.appending()) {
@@ -242,6 +242,38 @@ pub fn code() -> Style {
     Style::new().fg(slot(|o| o.code, Color::Green))
 }
 
+/// Highlighted code borrows the palette's slots, so a theme file recolours
+/// it too. Without colour, keywords stay bold and comments dim.
+pub fn code_keyword() -> Style {
+    Style::new()
+        .fg(slot(|o| o.heading, Color::Magenta))
+        .add_modifier(Modifier::BOLD)
+}
+
+pub fn code_type() -> Style {
+    Style::new().fg(slot(|o| o.workspace, Color::Cyan))
+}
+
+pub fn code_string() -> Style {
+    Style::new().fg(slot(|o| o.accent, ACCENT_COLOUR))
+}
+
+pub fn code_number() -> Style {
+    Style::new().fg(slot(|o| o.person, Color::Blue))
+}
+
+pub fn code_comment() -> Style {
+    dim().add_modifier(Modifier::ITALIC)
+}
+
+pub fn code_added() -> Style {
+    Style::new().fg(slot(|o| o.done, Color::Green))
+}
+
+pub fn code_removed() -> Style {
+    Style::new().fg(slot(|o| o.error, Color::Red))
+}
+
 pub fn done() -> Style {
     Style::new().fg(slot(|o| o.done, Color::Green))
 }
@@ -252,6 +284,10 @@ pub fn link() -> Style {
         .add_modifier(Modifier::UNDERLINED)
 }
 
+pub fn badge() -> Style {
+    find_label()
+}
+
 pub fn find_label() -> Style {
     Style::new()
         .fg(slot(|o| o.accent, ACCENT_COLOUR))
@@ -7,11 +7,12 @@ use super::super::format;
 use super::super::theme;
 use crate::api::{IssueSummary, Label, Timestamp, User};
 use crate::tui::display::{Column, GroupBy};
+use crate::tui::drafts::Drafts;
 
-pub fn issue_items(issues: &[IssueSummary]) -> Vec<ListItem<'static>> {
+pub fn issue_items(issues: &[IssueSummary], drafts: &Drafts) -> Vec<ListItem<'static>> {
     issues
         .iter()
-        .map(|issue| ListItem::new(issue_item(issue)))
+        .map(|issue| ListItem::new(issue_item(issue, drafts.has(&issue.id))))
         .collect()
 }
 
@@ -34,7 +35,11 @@ fn label_chip(label: &Label) -> Span<'static> {
     Span::styled(format!(" {} ", label.name), theme::label_chip(label.colour))
 }
 
-fn issue_item(issue: &IssueSummary) -> Line<'static> {
+fn draft_marker() -> Span<'static> {
+    Span::styled("✎ draft", theme::accent())
+}
+
+fn issue_item(issue: &IssueSummary, drafted: bool) -> Line<'static> {
     let mut spans = vec![
         theme::priority_glyph(issue.priority),
         Span::raw(" "),
@@ -45,6 +50,11 @@ fn issue_item(issue: &IssueSummary) -> Line<'static> {
         Span::styled(title(issue), theme::TEXT),
     ];
 
+    if drafted {
+        spans.push(Span::raw(" "));
+        spans.push(draft_marker());
+    }
+
     if let Some(assignee) = &issue.assignee {
         spans.push(Span::raw(" "));
         spans.push(assignee_span(assignee));
@@ -64,6 +74,7 @@ pub(super) fn issue_row(
     id_width: usize,
     width: usize,
     now: Timestamp,
+    drafted: bool,
 ) -> Line<'static> {
     let omit = group.omitted_column();
 
@@ -87,6 +98,11 @@ pub(super) fn issue_row(
 
     let mut right: Vec<Span> = Vec::new();
 
+    if drafted {
+        right.push(draft_marker());
+        right.push(Span::raw("  "));
+    }
+
     if omit != Some(Column::State) {
         right.push(state_span(issue));
         right.push(Span::raw("  "));
@@ -167,6 +183,7 @@ mod tests {
             8,
             80,
             Timestamp::default(),
+            false,
         );
         assert_eq!(&text(&row)[..12], "!!! DAN2-7  ");
     }
@@ -175,7 +192,7 @@ mod tests {
     fn a_row_fills_exactly_the_given_width_when_the_title_fits() {
         let issue = issue("DAN2-7", "a short title");
         for width in [70, 90, 120] {
-            let row = issue_row(&issue, GroupBy::None, 6, width, Timestamp::default());
+            let row = issue_row(&issue, GroupBy::None, 6, width, Timestamp::default(), false);
             assert_eq!(row.width(), width, "row should fill width {width}");
         }
     }
@@ -184,7 +201,7 @@ mod tests {
     fn a_wide_glyph_title_still_fills_exactly_the_width() {
         let issue = issue("DAN2-7", "日本語のタイトル");
         for width in [70, 90, 120] {
-            let row = issue_row(&issue, GroupBy::None, 6, width, Timestamp::default());
+            let row = issue_row(&issue, GroupBy::None, 6, width, Timestamp::default(), false);
             assert_eq!(
                 row.width(),
                 width,
@@ -193,13 +210,22 @@ mod tests {
         }
     }
 
+    #[test]
+    fn a_drafted_issue_is_marked_and_still_fills_the_width() {
+        let issue = issue("DAN2-7", "a short title");
+        let row = issue_row(&issue, GroupBy::None, 6, 90, Timestamp::default(), true);
+
+        assert!(text(&row).contains("✎ draft"), "{}", text(&row));
+        assert_eq!(row.width(), 90);
+    }
+
     #[test]
     fn a_long_title_is_truncated_with_an_ellipsis() {
         let issue = issue(
             "DAN2-7",
             "a very long title that will not fit into the narrow column at all",
         );
-        let row = issue_row(&issue, GroupBy::None, 6, 40, Timestamp::default());
+        let row = issue_row(&issue, GroupBy::None, 6, 40, Timestamp::default(), false);
         assert!(
             text(&row).contains('…'),
             "narrow row should truncate: {}",
@@ -212,17 +238,31 @@ mod tests {
     fn grouping_omits_the_redundant_column() {
         let issue = issue("DAN2-7", "title");
 
-        let by_priority = issue_row(&issue, GroupBy::Priority, 6, 80, Timestamp::default());
+        let by_priority = issue_row(
+            &issue,
+            GroupBy::Priority,
+            6,
+            80,
+            Timestamp::default(),
+            false,
+        );
         assert!(text(&by_priority).starts_with("    "));
         assert!(!text(&by_priority).contains("!!!"));
 
-        let by_status = issue_row(&issue, GroupBy::Status, 6, 80, Timestamp::default());
+        let by_status = issue_row(&issue, GroupBy::Status, 6, 80, Timestamp::default(), false);
         assert!(!text(&by_status).contains("In Progress"));
 
-        let by_assignee = issue_row(&issue, GroupBy::Assignee, 6, 80, Timestamp::default());
+        let by_assignee = issue_row(
+            &issue,
+            GroupBy::Assignee,
+            6,
+            80,
+            Timestamp::default(),
+            false,
+        );
         assert!(!text(&by_assignee).contains("dan"));
 
-        let none = issue_row(&issue, GroupBy::None, 6, 80, Timestamp::default());
+        let none = issue_row(&issue, GroupBy::None, 6, 80, Timestamp::default(), false);
         assert!(text(&none).contains("!!!"));
         assert!(text(&none).contains("In Progress"));
         assert!(text(&none).contains("dan"));
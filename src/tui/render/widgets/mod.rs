@@ -15,7 +15,7 @@ pub use issue_row::issue_items;
 pub use placeholder::{placeholder, PlaceholderText};
 pub use preview::{notification_preview_text, preview_text};
 pub use reactions::reaction_chips;
-pub use rows::{breakdown_line, loading_more_row, notification_items, view_items};
+pub use rows::{breakdown_line, loading_more_row, notification_items, view_items, RowContext};
 pub use scrollable_text::ScrollableText;
 pub use styled_list::StyledList;
 pub use tabs::view_tabs;
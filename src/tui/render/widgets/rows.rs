@@ -7,17 +7,31 @@ use super::super::theme;
 use super::issue_row::issue_row;
 use crate::api::{IssueSummary, NotificationItem, Timestamp};
 use crate::tui::display::{self, GroupBy};
+use crate::tui::drafts::Drafts;
 use crate::tui::spinner::Spinner;
 
+/// What every issue row in a list is laid out and marked against.
+#[derive(Clone, Copy)]
+pub struct RowContext<'a> {
+    pub id_width: usize,
+    pub width: usize,
+    pub now: Timestamp,
+    pub drafts: &'a Drafts,
+}
+
 pub fn view_items(
     issues: &[IssueSummary],
     groups: &[display::Group],
     group_by: GroupBy,
     selected: Option<usize>,
-    id_width: usize,
-    width: usize,
-    now: Timestamp,
+    context: RowContext,
 ) -> (Vec<ListItem<'static>>, Option<usize>) {
+    let RowContext {
+        id_width,
+        width,
+        now,
+        drafts,
+    } = context;
     let mut items: Vec<ListItem> = Vec::new();
     let mut flat = 0usize;
     let mut selected_row = None;
@@ -35,12 +49,15 @@ pub fn view_items(
                 selected_row = Some(items.len());
             }
 
+            let issue = &issues[index];
+
             items.push(ListItem::new(issue_row(
-                &issues[index],
+                issue,
                 group_by,
                 id_width,
                 width,
                 now,
+                drafts.has(&issue.id),
             )));
 
             flat += 1;
@@ -6,20 +6,27 @@ use crossterm::event::{Event as CrosstermEvent, EventStream, KeyEventKind};
 use futures::StreamExt;
 use ratatui::{backend::CrosstermBackend, Terminal};
 use tokio::sync::mpsc::{self, UnboundedReceiver, UnboundedSender};
+use tokio::task::{AbortHandle, JoinHandle};
 
 use super::app::App;
 use super::event::{Event, Generation, Lane, Redraw};
 use super::feed::FeedKey;
+use super::journal::{Journal, Recorded, Startup};
 use super::message::{
-    ApiCommand, Commands, Effect, FailureTarget, Message, PlatformCommand, RequestError,
-    RuntimeCommand, StoreCommand,
+    ApiCommand, Commands, ComposeRecovery, Effect, Effects, FailureTarget, Foreground, Message,
+    PlatformCommand, RequestError, RuntimeCommand, StoreCommand,
 };
+use super::overlay::Compose;
 use super::platform::Platform;
-use super::{render, update};
-use crate::api::{Credential, LinearApi, Timestamp};
+use super::{crash, foreground, render, update};
+use crate::api::{
+    ApiError, ApiResult, ConnectionSettings, Credential, IssueDetail, IssueId, IssueRef,
+    IssueUpdate, LinearApi, Session, Timestamp,
+};
 use crate::store::{Account, StateDir};
 
-pub type ClientFactory = Arc<dyn Fn(Credential) -> Arc<dyn LinearApi> + Send + Sync>;
+pub type ClientFactory =
+    Arc<dyn Fn(Credential, &ConnectionSettings) -> ApiResult<Arc<dyn LinearApi>> + Send + Sync>;
 
 type Tx = UnboundedSender<(Lane, Message)>;
 
@@ -35,12 +42,32 @@ struct Runtime {
     tx: Tx,
     platform: Platform,
     state: StateDir,
+    prefetches: Vec<AbortHandle>,
+    journal: Option<Journal>,
+    /// Set by a runtime command that needs the terminal; `drive` owns it,
+    /// so the handoff happens there once the current event is done.
+    handoff: Option<Handoff>,
+}
+
+enum Handoff {
+    Suspend,
+    Foreground(Foreground),
+    Edit { issue_id: IssueId, body: String },
 }
 
 impl Runtime {
     fn lane(&self) -> Lane {
         Lane::Workspace(self.generation)
     }
+
+    fn retarget(&mut self, api: Arc<dyn LinearApi>, namespace: String) {
+        self.generation = self.generation.next();
+        self.conn = Some(Connection { api, namespace });
+
+        for task in self.prefetches.drain(..) {
+            task.abort();
+        }
+    }
 }
 
 pub async fn run(
@@ -49,11 +76,9 @@ pub async fn run(
     bootstrap: Option<Credential>,
     make_client: ClientFactory,
     state: StateDir,
+    journal: Option<Journal>,
 ) -> Result<()> {
-    let (tx, mut rx) = mpsc::unbounded_channel::<(Lane, Message)>();
-    let mut events = EventStream::new();
-    let mut ticker = tokio::time::interval(Duration::from_millis(120));
-    let platform = Platform::host();
+    let (tx, rx) = mpsc::unbounded_channel::<(Lane, Message)>();
 
     let loaded = crate::store::load_accounts(&state);
     app.session.set_accounts(loaded.accounts.clone());
@@ -65,26 +90,151 @@ pub async fn run(
         generation: Generation::START,
         make_client,
         tx,
-        platform,
+        platform: Platform::host(),
         state,
+        prefetches: Vec::new(),
+        journal,
+        handoff: None,
     };
 
-    match (&rt.conn, bootstrap) {
+    let startup = match (&rt.conn, bootstrap) {
         (Some(conn), _) => {
-            if let Some(cache) = crate::store::load_feeds(&rt.state, &conn.namespace) {
+            let feeds = crate::store::load_feeds(&rt.state, &conn.namespace);
+            if let Some(cache) = feeds.clone() {
                 update::restore_feeds(app, cache);
             }
 
             for effect in update::initial_commands(app) {
                 run_effect(&mut rt, effect);
             }
+
+            Startup::Connected {
+                workspace: app.session.active_workspace().unwrap_or_default().into(),
+                feeds,
+            }
         }
         (None, Some(credential)) => {
             app.ui.status = Some(super::status::Status::ConnectingWorkspace);
             add_account(&rt, credential);
+            Startup::Connecting
         }
-        (None, None) => update::open_workspaces(app),
+        (None, None) => {
+            update::open_workspaces(app);
+            Startup::Disconnected
+        }
+    };
+
+    journal_start(&mut rt, app, terminal, startup)?;
+    drive(terminal, app, rt, rx).await
+}
+
+/// Runs against a fixture instead of a Linear account. The fixture stands
+/// in as the only workspace, and every client the runtime asks for
+/// resolves back to it, so reconnects land on the same data.
+pub async fn run_fixture(
+    terminal: &mut Terminal<CrosstermBackend<std::io::Stdout>>,
+    app: &mut App,
+    api: Arc<dyn LinearApi>,
+    state: StateDir,
+    journal: Option<Journal>,
+) -> Result<()> {
+    let (tx, rx) = mpsc::unbounded_channel::<(Lane, Message)>();
+
+    let account = fixture_account(api.session().await?);
+    let make_client = fixture_factory(api, account.credential.clone());
+    let loaded = crate::store::Accounts {
+        accounts: vec![account],
+        active: None,
+    };
+
+    app.ui.fixture = true;
+    app.session.set_accounts(loaded.accounts.clone());
+
+    let mut rt = Runtime {
+        conn: startup_connection(app, &loaded, &make_client),
+        generation: Generation::START,
+        make_client,
+        tx,
+        platform: Platform::host(),
+        state,
+        prefetches: Vec::new(),
+        journal,
+        handoff: None,
+    };
+
+    for effect in update::initial_commands(app) {
+        run_effect(&mut rt, effect);
+    }
+
+    let startup = Startup::Connected {
+        workspace: app.session.active_workspace().unwrap_or_default().into(),
+        feeds: None,
+    };
+    journal_start(&mut rt, app, terminal, startup)?;
+    drive(terminal, app, rt, rx).await
+}
+
+/// The account a fixture poses as, so the app treats it as signed in.
+pub fn fixture_account(session: Session) -> Account {
+    Account {
+        workspace_key: session.org_url_key,
+        org_name: session.org_name,
+        credential: Credential::PersonalKey(String::new()),
+        connection: ConnectionSettings::default(),
+    }
+}
+
+/// Serves the fixture for its own account and refuses any other, so a demo
+/// cannot be pointed at a real workspace.
+fn fixture_factory(api: Arc<dyn LinearApi>, credential: Credential) -> ClientFactory {
+    Arc::new(move |offered, _| {
+        if offered == credential {
+            Ok(Arc::clone(&api))
+        } else {
+            Err(ApiError::Fixture(
+                "a fixture is the only workspace while it runs".into(),
+            ))
+        }
+    })
+}
+
+fn journal_start(
+    rt: &mut Runtime,
+    app: &App,
+    terminal: &Terminal<CrosstermBackend<std::io::Stdout>>,
+    startup: Startup,
+) -> Result<()> {
+    if let Some(journal) = &mut rt.journal {
+        let size = terminal.size()?;
+
+        journal.record::<Message>(Recorded::Start {
+            now: app.now,
+            accounts: app.session.accounts().to_vec(),
+            startup,
+            fixture: app.ui.fixture,
+            editor_keys: app.ui.editor_keys,
+            width: size.width,
+            height: size.height,
+        });
+    }
+
+    Ok(())
+}
+
+fn journal(rt: &mut Runtime, event: Recorded<&Message>) {
+    if let Some(journal) = &mut rt.journal {
+        journal.record(event);
     }
+}
+
+async fn drive(
+    terminal: &mut Terminal<CrosstermBackend<std::io::Stdout>>,
+    app: &mut App,
+    mut rt: Runtime,
+    mut rx: UnboundedReceiver<(Lane, Message)>,
+) -> Result<()> {
+    let mut events = EventStream::new();
+    let mut ticker = tokio::time::interval(Duration::from_millis(120));
 
     terminal.draw(|frame| render::render(app, frame))?;
 
@@ -94,22 +244,61 @@ pub async fn run(
             Event::Ignored => continue,
             Event::Tick(now) => {
                 let redraw = update::tick(app, now);
-
+                let effects: Effects = update::prefetch(app)
+                    .into_iter()
+                    .chain(update::autosave(app))
+                    .chain(update::search_completions(app))
+                    .collect();
                 let commands = app.maybe_refresh_token();
+
+                let idle = redraw == Redraw::Skipped && effects.is_empty() && commands.is_empty();
+                match &mut rt.journal {
+                    Some(journal) if idle => journal.idle(now),
+                    _ => journal(&mut rt, Recorded::Tick { now }),
+                }
+
+                for effect in effects {
+                    run_effect(&mut rt, effect);
+                }
+
                 if commands.is_empty() && redraw == Redraw::Skipped {
                     continue;
                 }
                 run_commands(&mut rt, app, commands);
             }
-            Event::Resize => {}
+            Event::Resize => {
+                let size = terminal.size()?;
+                let event = Recorded::Resize {
+                    width: size.width,
+                    height: size.height,
+                };
+                crash::note(app, || event.summary());
+                journal(&mut rt, event);
+            }
             Event::Input(key) => {
+                let event = Recorded::Input { key };
+                crash::note(app, || crash::key(app, key));
+                journal(&mut rt, event);
                 let commands = update::handle_key(app, key);
                 run_commands(&mut rt, app, commands);
             }
+            Event::Paste(text) => {
+                let event = Recorded::Paste { text: text.clone() };
+                crash::note(app, || event.summary());
+                journal(&mut rt, event);
+                let commands = update::handle_paste(app, &text);
+                run_commands(&mut rt, app, commands);
+            }
             Event::Message { lane, message } => {
                 if is_stale(lane, rt.generation) {
                     continue;
                 }
+                let event = Recorded::Message {
+                    lane,
+                    message: &message,
+                };
+                crash::note(app, || event.summary());
+                journal(&mut rt, event);
                 let commands = update::apply(app, message);
                 run_commands(&mut rt, app, commands);
             }
@@ -119,12 +308,34 @@ pub async fn run(
             break;
         }
 
+        if let Some(handoff) = rt.handoff.take() {
+            // The stream reads stdin on a thread of its own, which would
+            // race whatever takes the terminal, so it is dropped for the
+            // duration and a fresh one takes over after.
+            drop(std::mem::replace(&mut events, EventStream::new()));
+            hand_off(terminal, &rt.tx, handoff)?;
+        }
+
         terminal.draw(|frame| render::render(app, frame))?;
     }
 
+    save_unsaved_drafts(&rt, app);
+
     Ok(())
 }
 
+/// Drafts are written a few seconds apart, so any still waiting are written
+/// now, before the session or workspace goes.
+fn save_unsaved_drafts(rt: &Runtime, app: &mut App) {
+    let Some(conn) = &rt.conn else {
+        return;
+    };
+
+    if let Some(drafts) = app.workspace.drafts.unsaved(app.now) {
+        crate::store::save_drafts(&rt.state, &conn.namespace, &drafts);
+    }
+}
+
 fn is_stale(lane: Lane, generation: Generation) -> bool {
     match lane {
         Lane::Workspace(sent) => sent != generation,
@@ -143,12 +354,20 @@ fn startup_connection(
         .and_then(|key| loaded.accounts.iter().find(|a| a.workspace_key == key))
         .or_else(|| loaded.accounts.first())?;
 
+    let api = match make_client(account.credential.clone(), &account.connection) {
+        Ok(api) => api,
+        Err(error) => {
+            app.ui.status = Some(super::status::Status::Error(error.to_string()));
+            return None;
+        }
+    };
+
     if !app.session.activate(&account.workspace_key) {
         return None;
     }
 
     Some(Connection {
-        api: make_client(account.credential.clone()),
+        api,
         namespace: account.namespace(),
     })
 }
@@ -179,7 +398,15 @@ fn run_commands(rt: &mut Runtime, app: &mut App, commands: Commands) {
 fn run_effect(rt: &mut Runtime, effect: Effect) {
     match effect {
         Effect::Api(command) => match &rt.conn {
-            Some(conn) => dispatch_api(conn, rt.lane(), &rt.tx, command),
+            Some(conn) => {
+                let prefetch = matches!(command, ApiCommand::PrefetchDetail { .. });
+                let task = dispatch_api(conn, rt.lane(), &rt.tx, command);
+
+                if prefetch {
+                    rt.prefetches.retain(|task| !task.is_finished());
+                    rt.prefetches.push(task.abort_handle());
+                }
+            }
             None => settle_offline(rt, &command),
         },
         Effect::Store(command) => match &rt.conn {
@@ -203,7 +430,37 @@ fn run_runtime(rt: &mut Runtime, app: &mut App, command: RuntimeCommand) {
         RuntimeCommand::RefreshToken { workspace_key } => refresh_token(rt, app, workspace_key),
         RuntimeCommand::Reconnect => reconnect(rt, app),
         RuntimeCommand::BeginLogin => begin_login(rt),
+        RuntimeCommand::Suspend => rt.handoff = Some(Handoff::Suspend),
+        RuntimeCommand::Foreground(command) => rt.handoff = Some(Handoff::Foreground(command)),
+        RuntimeCommand::EditExternally { issue_id, body } => {
+            rt.handoff = Some(Handoff::Edit { issue_id, body })
+        }
+    }
+}
+
+fn hand_off(
+    terminal: &mut Terminal<CrosstermBackend<std::io::Stdout>>,
+    tx: &Tx,
+    handoff: Handoff,
+) -> Result<()> {
+    match handoff {
+        Handoff::Suspend => foreground::suspend(terminal)?,
+        Handoff::Foreground(command) => {
+            if let Err(error) = foreground::run(terminal, &command)? {
+                crate::debug_log::record("platform", || error.clone());
+                let _ = tx.send((Lane::Host, ephemeral_failure(error)));
+            }
+        }
+        Handoff::Edit { issue_id, body } => {
+            let message = match foreground::edit(terminal, &body)? {
+                Ok(body) => Message::EditedExternally { issue_id, body },
+                Err(error) => ephemeral_failure(error),
+            };
+            let _ = tx.send((Lane::Host, message));
+        }
     }
+
+    Ok(())
 }
 
 fn settle_offline(rt: &Runtime, command: &ApiCommand) {
@@ -216,6 +473,14 @@ fn settle_offline(rt: &Runtime, command: &ApiCommand) {
 }
 
 fn switch_workspace(rt: &mut Runtime, app: &mut App, account: Account) {
+    let api = match (rt.make_client)(account.credential.clone(), &account.connection) {
+        Ok(api) => api,
+        Err(error) => {
+            app.ui.status = Some(super::status::Status::Error(error.to_string()));
+            return;
+        }
+    };
+
     if !app.session.activate(&account.workspace_key) {
         return;
     }
@@ -224,12 +489,9 @@ fn switch_workspace(rt: &mut Runtime, app: &mut App, account: Account) {
         let cache = crate::store::build_cache(&app.workspace.feeds, &app.workspace.inbox, app.now);
         crate::store::save_feeds(&rt.state, &conn.namespace, &cache);
     }
+    save_unsaved_drafts(rt, app);
 
-    rt.generation = rt.generation.next();
-    rt.conn = Some(Connection {
-        api: (rt.make_client)(account.credential.clone()),
-        namespace: account.namespace(),
-    });
+    rt.retarget(api, account.namespace());
 
     app.reset_workspace();
 
@@ -251,8 +513,14 @@ fn switch_workspace(rt: &mut Runtime, app: &mut App, account: Account) {
 }
 
 fn add_account(rt: &Runtime, credential: Credential) {
-    let api = (rt.make_client)(credential.clone());
     let tx = rt.tx.clone();
+    let api = match (rt.make_client)(credential.clone(), &ConnectionSettings::default()) {
+        Ok(api) => api,
+        Err(error) => {
+            let _ = tx.send((Lane::Host, ephemeral_failure(error.to_string())));
+            return;
+        }
+    };
 
     tokio::spawn(async move {
         let message = match api.session().await {
@@ -261,6 +529,7 @@ fn add_account(rt: &Runtime, credential: Credential) {
                     workspace_key: session.org_url_key,
                     org_name: session.org_name,
                     credential,
+                    connection: ConnectionSettings::default(),
                 }),
             },
             Err(error) => ephemeral_failure(error.to_string()),
@@ -280,9 +549,10 @@ fn refresh_token(rt: &Runtime, app: &App, workspace_key: String) {
     };
 
     let tx = rt.tx.clone();
+    let settings = oauth_settings(&app.session.connection_for(&workspace_key));
 
     tokio::spawn(async move {
-        let message = match crate::oauth::refresh(&refresh_token).await {
+        let message = match crate::oauth::refresh(&refresh_token, &settings).await {
             Ok(token) => Message::TokenRefreshed {
                 workspace_key,
                 credential: Credential::OAuth(token),
@@ -299,11 +569,15 @@ fn reconnect(rt: &mut Runtime, app: &mut App) {
         return;
     };
 
-    rt.generation = rt.generation.next();
-    rt.conn = Some(Connection {
-        api: (rt.make_client)(account.credential.clone()),
-        namespace: account.namespace(),
-    });
+    let api = match (rt.make_client)(account.credential.clone(), &account.connection) {
+        Ok(api) => api,
+        Err(error) => {
+            app.ui.status = Some(super::status::Status::Error(error.to_string()));
+            return;
+        }
+    };
+
+    rt.retarget(api, account.namespace());
 
     crate::store::save_accounts(
         &rt.state,
@@ -321,9 +595,10 @@ fn reconnect(rt: &mut Runtime, app: &mut App) {
 fn begin_login(rt: &Runtime) {
     let tx = rt.tx.clone();
     let platform = rt.platform;
+    let settings = oauth_settings(&ConnectionSettings::default());
 
     tokio::spawn(async move {
-        let message = match crate::oauth::login(platform).await {
+        let message = match crate::oauth::login(platform, &settings).await {
             Ok(credential) => Message::LoginSucceeded { credential },
             Err(error) => ephemeral_failure(error.to_string()),
         };
@@ -332,15 +607,40 @@ fn begin_login(rt: &Runtime) {
     });
 }
 
+/// The settings OAuth requests go out with, layered as the client
+/// factory layers them: the environment over what the account saved.
+fn oauth_settings(account: &ConnectionSettings) -> ConnectionSettings {
+    ConnectionSettings::from_env()
+        .unwrap_or_default()
+        .over(account)
+}
+
 fn classify(polled: Option<std::io::Result<CrosstermEvent>>) -> Event {
     match polled {
         None => Event::Closed,
         Some(Ok(CrosstermEvent::Key(key))) if key.kind == KeyEventKind::Press => Event::Input(key),
+        Some(Ok(CrosstermEvent::Paste(text))) => Event::Paste(text),
         Some(Ok(CrosstermEvent::Resize(..))) => Event::Resize,
         Some(_) => Event::Ignored,
     }
 }
 
+/// The issue as it stands now, if it moved on since the editor was seeded.
+/// Without a `seen` stamp there is nothing to compare against.
+async fn changed_since(
+    api: &dyn LinearApi,
+    issue_id: &IssueId,
+    seen: Option<Timestamp>,
+) -> ApiResult<Option<IssueDetail>> {
+    let Some(seen) = seen else {
+        return Ok(None);
+    };
+
+    let current = api.issue_detail(&IssueRef::Id(issue_id.clone())).await?;
+
+    Ok(current.filter(|detail| detail.updated_at != seen))
+}
+
 fn failed(target: FailureTarget, error: &crate::api::ApiError) -> Message {
     Message::Failed {
         target,
@@ -355,135 +655,230 @@ fn ephemeral_failure(error: String) -> Message {
     }
 }
 
-fn dispatch_api(conn: &Connection, lane: Lane, tx: &Tx, command: ApiCommand) {
+fn dispatch_api(conn: &Connection, lane: Lane, tx: &Tx, command: ApiCommand) -> JoinHandle<()> {
     let api = Arc::clone(&conn.api);
     let tx = tx.clone();
-    let on_failure = command.failure_target();
 
     tokio::spawn(async move {
-        let message: Option<Message> = match command {
-            ApiCommand::LoadSession => Some(match api.session().await {
-                Ok(session) => Message::SessionLoaded(session),
-                Err(error) => failed(on_failure, &error),
-            }),
-            ApiCommand::LoadFeed { key, request } => {
-                let after = request.cursor().cloned();
-                let result = match &key {
-                    FeedKey::Issues(filter) => api.issues(filter, after.as_ref()).await,
-                    FeedKey::View(id) => api.custom_view_issues(id, after.as_ref()).await,
-                    FeedKey::Search(term) => api.search_issues(term, after.as_ref()).await,
-                };
+        let message = execute_api(api.as_ref(), command).await;
+        let _ = tx.send((lane, message));
+    })
+}
 
-                Some(match result {
-                    Ok(page) => Message::FeedLoaded { key, request, page },
-                    Err(error) => failed(on_failure, &error),
-                })
-            }
-            ApiCommand::LoadInboxFeed { request } => {
-                let after = request.cursor().cloned();
+/// Runs one API command to completion and returns the message it settles
+/// into, failures included.
+pub async fn execute_api(api: &dyn LinearApi, command: ApiCommand) -> Message {
+    let on_failure = command.failure_target();
 
-                Some(match api.notifications(after.as_ref()).await {
-                    Ok(page) => Message::InboxLoaded { request, page },
-                    Err(error) => failed(on_failure, &error),
-                })
-            }
-            ApiCommand::LoadCustomViews => Some(match api.custom_views().await {
-                Ok(views) => Message::CustomViewsLoaded(views),
-                Err(error) => failed(on_failure, &error),
-            }),
-            ApiCommand::LoadTeams => Some(match api.teams().await {
-                Ok(teams) => Message::TeamsLoaded { teams },
+    match command {
+        ApiCommand::LoadSession => match api.session().await {
+            Ok(session) => Message::SessionLoaded(session),
+            Err(error) => failed(on_failure, &error),
+        },
+        ApiCommand::LoadFeed { key, request } => {
+            let after = request.cursor().cloned();
+            let result = match &key {
+                FeedKey::Issues(filter) => api.issues(filter, after.as_ref()).await,
+                FeedKey::View(id) => api.custom_view_issues(id, after.as_ref()).await,
+                FeedKey::Search(term) => api.search_issues(term, after.as_ref()).await,
+            };
+
+            match result {
+                Ok(page) => Message::FeedLoaded { key, request, page },
                 Err(error) => failed(on_failure, &error),
-            }),
-            ApiCommand::LoadDetail { target, reveal } => {
-                Some(match api.issue_detail(&target).await {
-                    Ok(Some(detail)) => Message::DetailLoaded {
-                        detail: Box::new(detail),
-                        reveal,
-                    },
-                    Ok(None) => Message::Failed {
-                        target: on_failure,
-                        error: RequestError::Other(format!("Issue {target} not found")),
-                    },
-                    Err(error) => failed(on_failure, &error),
-                })
             }
-            ApiCommand::LoadStates { team_id } => Some(match api.workflow_states(&team_id).await {
-                Ok(states) => Message::StatesLoaded { team_id, states },
-                Err(error) => failed(on_failure, &error),
-            }),
-            ApiCommand::SearchUsers { query } => Some(match api.search_users(&query).await {
-                Ok(users) => Message::UsersFound { query, users },
-                Err(error) => failed(on_failure, &error),
-            }),
-            ApiCommand::SearchLabels { query } => Some(match api.search_labels(&query).await {
-                Ok(labels) => Message::LabelsFound { query, labels },
-                Err(error) => failed(on_failure, &error),
-            }),
-            ApiCommand::LoadMembers { team_id } => Some(match api.team_members(&team_id).await {
-                Ok(members) => Message::MembersLoaded { team_id, members },
+        }
+        ApiCommand::LoadInboxFeed { request } => {
+            let after = request.cursor().cloned();
+
+            match api.notifications(after.as_ref()).await {
+                Ok(page) => Message::InboxLoaded { request, page },
                 Err(error) => failed(on_failure, &error),
-            }),
-            ApiCommand::UpdateIssue { id, update } => {
-                Some(match api.update_issue(&id, update).await {
-                    Ok(()) => Message::IssueUpdated { id },
-                    Err(error) => failed(on_failure, &error),
-                })
             }
-            ApiCommand::CreateComment {
-                issue_id,
-                body,
-                parent_id,
-                ..
-            } => Some(
-                match api
-                    .create_comment(&issue_id, &body, parent_id.as_ref())
-                    .await
-                {
-                    Ok(()) => Message::CommentPosted { id: issue_id },
-                    Err(error) => failed(on_failure, &error),
+        }
+        ApiCommand::LoadCustomViews => match api.custom_views().await {
+            Ok(views) => Message::CustomViewsLoaded(views),
+            Err(error) => failed(on_failure, &error),
+        },
+        ApiCommand::LoadTeams => match api.teams().await {
+            Ok(teams) => Message::TeamsLoaded { teams },
+            Err(error) => failed(on_failure, &error),
+        },
+        ApiCommand::LoadDetail { target, reveal } => match api.issue_detail(&target).await {
+            Ok(Some(detail)) => Message::DetailLoaded {
+                detail: Box::new(detail),
+                reveal,
+            },
+            Ok(None) => Message::Failed {
+                target: on_failure,
+                error: RequestError::Other(format!("Issue {target} not found")),
+            },
+            Err(error) => failed(on_failure, &error),
+        },
+        ApiCommand::PrefetchDetail { id } => {
+            match api.issue_detail(&IssueRef::Id(id.clone())).await {
+                Ok(Some(detail)) => Message::DetailPrefetched(Box::new(detail)),
+                Ok(None) => Message::Failed {
+                    target: on_failure,
+                    error: RequestError::Other(format!("Issue {id} not found")),
                 },
-            ),
-            ApiCommand::UpdateComment {
-                issue_id,
-                comment_id,
-                body,
-                ..
-            } => Some(match api.update_comment(&comment_id, &body).await {
-                Ok(()) => Message::CommentEdited { id: issue_id },
                 Err(error) => failed(on_failure, &error),
-            }),
-            ApiCommand::DeleteComment {
-                issue_id,
-                comment_id,
-            } => Some(match api.delete_comment(&comment_id).await {
-                Ok(()) => Message::CommentDeleted { id: issue_id },
-                Err(error) => failed(on_failure, &error),
-            }),
-            ApiCommand::CreateReaction {
-                issue_id,
-                target,
-                emoji,
-            } => Some(match api.create_reaction(&target, &emoji).await {
-                Ok(()) => Message::ReactionToggled { id: issue_id },
+            }
+        }
+        ApiCommand::LoadStates { team_id } => match api.workflow_states(&team_id).await {
+            Ok(states) => Message::StatesLoaded { team_id, states },
+            Err(error) => failed(on_failure, &error),
+        },
+        ApiCommand::SearchUsers { query } => match api.search_users(&query).await {
+            Ok(users) => Message::UsersFound { query, users },
+            Err(error) => failed(on_failure, &error),
+        },
+        ApiCommand::SearchLabels { query } => match api.search_labels(&query, None).await {
+            Ok(page) => Message::LabelsFound {
+                query,
+                labels: page.items,
+            },
+            Err(error) => failed(on_failure, &error),
+        },
+        ApiCommand::SearchIssues { query } => match api.search_issues(&query, None).await {
+            Ok(page) => Message::IssuesFound {
+                query,
+                issues: page.items,
+            },
+            Err(error) => failed(on_failure, &error),
+        },
+        ApiCommand::LoadMembers { team_id } => match api.team_members(&team_id).await {
+            Ok(members) => Message::MembersLoaded { team_id, members },
+            Err(error) => failed(on_failure, &error),
+        },
+        ApiCommand::UpdateIssue { id, update } => match api.update_issue(&id, update).await {
+            Ok(()) => Message::IssueUpdated { id },
+            Err(error) => failed(on_failure, &error),
+        },
+        ApiCommand::CreateComment {
+            issue_id,
+            team_id,
+            body,
+            parent_id,
+        } => match api
+            .create_comment(&issue_id, &body, parent_id.as_ref())
+            .await
+        {
+            Ok(()) => Message::CommentPosted {
+                id: issue_id.clone(),
+                draft: Box::new(ComposeRecovery {
+                    issue_id,
+                    team_id,
+                    compose: match parent_id {
+                        Some(parent_id) => Compose::Reply { parent_id },
+                        None => Compose::Comment,
+                    },
+                    body,
+                    seen: None,
+                }),
+            },
+            Err(error) => failed(on_failure, &error),
+        },
+        ApiCommand::UpdateComment {
+            issue_id,
+            team_id,
+            comment_id,
+            body,
+            seen,
+        } => match changed_since(api, &issue_id, seen).await {
+            Ok(Some(detail)) => Message::EditConflict {
+                detail: Box::new(detail),
+                draft: Box::new(ComposeRecovery {
+                    issue_id,
+                    team_id,
+                    compose: Compose::Edit { comment_id },
+                    body,
+                    seen,
+                }),
+            },
+            Ok(None) => match api.update_comment(&comment_id, &body).await {
+                Ok(()) => Message::CommentEdited {
+                    id: issue_id.clone(),
+                    draft: Box::new(ComposeRecovery {
+                        issue_id,
+                        team_id,
+                        compose: Compose::Edit { comment_id },
+                        body,
+                        seen,
+                    }),
+                },
                 Err(error) => failed(on_failure, &error),
-            }),
-            ApiCommand::DeleteReaction {
-                issue_id,
-                reaction_id,
-            } => Some(match api.delete_reaction(&reaction_id).await {
-                Ok(()) => Message::ReactionToggled { id: issue_id },
+            },
+            Err(error) => failed(on_failure, &error),
+        },
+        ApiCommand::UpdateDescription {
+            issue_id,
+            team_id,
+            body,
+            seen,
+        } => match changed_since(api, &issue_id, seen).await {
+            Ok(Some(detail)) => Message::EditConflict {
+                detail: Box::new(detail),
+                draft: Box::new(ComposeRecovery {
+                    issue_id,
+                    team_id,
+                    compose: Compose::Description,
+                    body,
+                    seen,
+                }),
+            },
+            Ok(None) => match api
+                .update_issue(&issue_id, IssueUpdate::Description(body.clone()))
+                .await
+            {
+                Ok(()) => Message::DescriptionEdited {
+                    id: issue_id.clone(),
+                    draft: Box::new(ComposeRecovery {
+                        issue_id,
+                        team_id,
+                        compose: Compose::Description,
+                        body,
+                        seen,
+                    }),
+                },
                 Err(error) => failed(on_failure, &error),
-            }),
-        };
-
-        if let Some(message) = message {
-            let _ = tx.send((lane, message));
-        }
-    });
+            },
+            Err(error) => failed(on_failure, &error),
+        },
+        ApiCommand::DeleteComment {
+            issue_id,
+            comment_id,
+        } => match api.delete_comment(&comment_id).await {
+            Ok(()) => Message::CommentDeleted { id: issue_id },
+            Err(error) => failed(on_failure, &error),
+        },
+        ApiCommand::CreateReaction {
+            issue_id,
+            target,
+            emoji,
+        } => match api.create_reaction(&target, &emoji).await {
+            Ok(()) => Message::ReactionToggled { id: issue_id },
+            Err(error) => failed(on_failure, &error),
+        },
+        ApiCommand::DeleteReaction {
+            issue_id,
+            reaction_id,
+        } => match api.delete_reaction(&reaction_id).await {
+            Ok(()) => Message::ReactionToggled { id: issue_id },
+            Err(error) => failed(on_failure, &error),
+        },
+    }
 }
 
 fn dispatch_store(state: &StateDir, namespace: &str, tx: &Tx, lane: Lane, command: StoreCommand) {
+    // Drafts are written here, in order, like the final write on the way
+    // out: a detached write could land after a newer one and put older
+    // text back, or share its temp file.
+    if let StoreCommand::SaveDrafts(drafts) = &command {
+        crate::store::save_drafts(state, namespace, drafts);
+        return;
+    }
+
     let tx = tx.clone();
     let state = state.clone();
     let namespace = namespace.to_string();
@@ -505,6 +900,21 @@ fn dispatch_store(state: &StateDir, namespace: &str, tx: &Tx, lane: Lane, comman
                 crate::store::save_recent(&state, &namespace, &[]);
                 Some(Message::RecentCleared)
             }
+            StoreCommand::LoadDrafts => Some(Message::DraftsLoaded(crate::store::load_drafts(
+                &state, &namespace,
+            ))),
+            // Written in place, above.
+            StoreCommand::SaveDrafts(_) => None,
+            StoreCommand::LoadRecentEmoji => Some(Message::RecentEmojiLoaded(
+                crate::store::load_recent_emoji(&state),
+            )),
+            StoreCommand::SaveRecentEmoji(names) => {
+                crate::store::save_recent_emoji(&state, &names);
+                None
+            }
+            StoreCommand::LoadTemplates => Some(Message::TemplatesLoaded(
+                crate::store::load_templates(&state),
+            )),
         };
 
         if let Some(message) = message {
@@ -517,6 +927,10 @@ fn dispatch_platform(platform: Platform, tx: &Tx, command: PlatformCommand) {
     let tx = tx.clone();
 
     tokio::spawn(async move {
+        let label = match &command {
+            PlatformCommand::OpenUrl(_) => "open url",
+            PlatformCommand::CopyToClipboard(_) => "copy to clipboard",
+        };
         let outcome = match command {
             PlatformCommand::OpenUrl(url) => {
                 tokio::task::spawn_blocking(move || platform.open_url(&url)).await
@@ -526,13 +940,15 @@ fn dispatch_platform(platform: Platform, tx: &Tx, command: PlatformCommand) {
             }
         };
 
-        let message = match outcome {
+        let failure = match outcome {
             Ok(Ok(())) => None,
-            Ok(Err(error)) => Some(ephemeral_failure(error.to_string())),
-            Err(error) => Some(ephemeral_failure(error.to_string())),
+            Ok(Err(error)) => Some(error.to_string()),
+            Err(error) => Some(error.to_string()),
         };
 
-        if let Some(message) = message {
+        if let Some(error) = failure {
+            crate::debug_log::record("platform", || format!("{label} failed: {error}"));
+            let message = ephemeral_failure(error);
             let _ = tx.send((Lane::Host, message));
         }
     });
@@ -544,6 +960,9 @@ mod tests {
     use crate::api::fixture::FixtureClient;
     use crate::api::Page;
     use crate::tui::feed::Feed;
+    use crate::tui::overlay::Overlay;
+    use crate::tui::status::Status;
+    use crossterm::event::{KeyCode, KeyEvent};
     use tempfile::TempDir;
 
     fn offline_runtime() -> (Runtime, UnboundedReceiver<(Lane, Message)>, TempDir) {
@@ -554,7 +973,7 @@ mod tests {
         let dir = tempfile::tempdir().expect("a temporary state directory");
         let (tx, rx) = mpsc::unbounded_channel();
         let make_client: ClientFactory =
-            Arc::new(|_| Arc::new(FixtureClient::sample()) as Arc<dyn LinearApi>);
+            Arc::new(|_, _| Ok(Arc::new(FixtureClient::sample()) as Arc<dyn LinearApi>));
 
         let rt = Runtime {
             conn: None,
@@ -563,6 +982,9 @@ mod tests {
             tx,
             platform,
             state: StateDir::at(dir.path().into()),
+            prefetches: Vec::new(),
+            journal: None,
+            handoff: None,
         };
 
         (rt, rx, dir)
@@ -581,11 +1003,63 @@ mod tests {
         (rt, rx, dir)
     }
 
+    #[test]
+    fn drafts_are_written_before_the_dispatch_returns() {
+        let (mut rt, _rx, _dir) = connected_runtime("ws");
+        let draft = crate::tui::drafts::Draft {
+            issue_id: crate::api::IssueId::from_raw("i1"),
+            identifier: "DAN-1".into(),
+            team_id: crate::api::TeamId::from_raw("t1"),
+            compose: crate::tui::overlay::Compose::Comment,
+            body: "half a thought".into(),
+            seen: None,
+            saved_at: Timestamp::from_epoch(0),
+        };
+
+        run_effect(
+            &mut rt,
+            Effect::Store(StoreCommand::SaveDrafts(vec![draft.clone()])),
+        );
+
+        assert_eq!(crate::store::load_drafts(&rt.state, "ws"), vec![draft]);
+    }
+
+    #[tokio::test]
+    async fn a_fixture_stands_in_as_the_only_workspace() {
+        let api: Arc<dyn LinearApi> = Arc::new(FixtureClient::sample());
+        let session = api.session().await.unwrap();
+        let account = fixture_account(session.clone());
+
+        assert_eq!(account.workspace_key, session.org_url_key);
+        assert_eq!(account.org_name, session.org_name);
+        assert!(account.connection.is_empty());
+
+        let make_client = fixture_factory(api, account.credential.clone());
+        let settings = ConnectionSettings::default();
+        assert!(make_client(account.credential.clone(), &settings).is_ok());
+        for other in [
+            Credential::PersonalKey("lin_api_real".into()),
+            Credential::EnvVar("LINEAR_API_KEY".into()),
+        ] {
+            assert!(
+                matches!(make_client(other, &settings), Err(ApiError::Fixture(_))),
+                "a fixture must not add or switch to another workspace"
+            );
+        }
+
+        let mut app = App::new();
+        app.ui.fixture = true;
+        let _ = update::handle_key(&mut app, KeyEvent::from(KeyCode::Char('w')));
+        assert!(matches!(app.overlay(), Overlay::None));
+        assert_eq!(app.ui.status, Some(Status::FixtureOnly));
+    }
+
     fn account(workspace_key: &str) -> Account {
         Account {
             workspace_key: workspace_key.to_string(),
             org_name: workspace_key.to_string(),
             credential: Credential::PersonalKey("k".into()),
+            connection: ConnectionSettings::default(),
         }
     }
 
@@ -656,6 +1130,24 @@ mod tests {
         );
     }
 
+    #[test]
+    fn a_foreground_command_waits_for_the_loop_to_hand_over_the_terminal() {
+        let (mut rt, mut rx, _dir) = offline_runtime();
+        let mut app = App::new();
+
+        run_runtime(
+            &mut rt,
+            &mut app,
+            RuntimeCommand::Foreground(Foreground::shell("true")),
+        );
+
+        assert!(matches!(
+            rt.handoff,
+            Some(Handoff::Foreground(ref command)) if command.describe() == "true"
+        ));
+        assert!(rx.try_recv().is_err(), "nothing runs until drive hands off");
+    }
+
     #[tokio::test]
     async fn an_account_added_after_a_generation_bump_still_lands() {
         let (mut rt, mut rx, _dir) = offline_runtime();
@@ -808,6 +1300,7 @@ mod tests {
             workspace_key: "ws".into(),
             org_name: "Known".into(),
             credential: Credential::PersonalKey("k".into()),
+            connection: ConnectionSettings::default(),
         });
         assert!(app.session.activate("ws"));
 
@@ -815,6 +1308,7 @@ mod tests {
             workspace_key: "nope".into(),
             org_name: "Unknown".into(),
             credential: Credential::PersonalKey("k".into()),
+            connection: ConnectionSettings::default(),
         };
 
         switch_workspace(&mut rt, &mut app, unknown);
@@ -837,4 +1331,64 @@ mod tests {
             other => panic!("expected an offline store effect to settle, got {other:?}"),
         }
     }
+
+    #[tokio::test]
+    async fn a_generation_bump_aborts_in_flight_prefetches() {
+        let (mut rt, mut rx, _dir) = connected_runtime("ws");
+
+        run_effect(
+            &mut rt,
+            Effect::Api(ApiCommand::PrefetchDetail {
+                id: crate::api::IssueId::from_raw("i1"),
+            }),
+        );
+        assert_eq!(rt.prefetches.len(), 1);
+
+        rt.retarget(Arc::new(FixtureClient::sample()), "ws".into());
+        tokio::time::sleep(Duration::from_millis(50)).await;
+
+        assert!(rt.prefetches.is_empty());
+        assert!(
+            rx.try_recv().is_err(),
+            "a prefetch for the superseded connection must not spend a request"
+        );
+    }
+
+    fn description_edit(seen: Option<Timestamp>) -> Effect {
+        Effect::Api(ApiCommand::UpdateDescription {
+            issue_id: crate::api::IssueId::from_raw("i1"),
+            team_id: crate::api::TeamId::from_raw("t_pizza"),
+            body: "Mine".into(),
+            seen,
+        })
+    }
+
+    #[tokio::test]
+    async fn an_edit_seeded_from_an_older_issue_reports_a_conflict() {
+        let (mut rt, mut rx, _dir) = connected_runtime("ws");
+
+        run_effect(&mut rt, description_edit(Some(Timestamp::from_epoch(0))));
+
+        match rx.recv().await {
+            Some((_, Message::EditConflict { detail, draft })) => {
+                assert_eq!(detail.identifier, "DAN2-7");
+                assert_eq!(draft.body, "Mine");
+                assert!(matches!(draft.compose, Compose::Description));
+            }
+            other => panic!("expected an edit conflict, got {other:?}"),
+        }
+    }
+
+    #[tokio::test]
+    async fn an_edit_seeded_from_the_current_issue_saves() {
+        let (mut rt, mut rx, _dir) = connected_runtime("ws");
+        let current = Timestamp::from("2026-07-16T18:40:00Z");
+
+        run_effect(&mut rt, description_edit(Some(current)));
+
+        assert!(matches!(
+            rx.recv().await,
+            Some((_, Message::DescriptionEdited { .. }))
+        ));
+    }
 }
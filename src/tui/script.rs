@@ -0,0 +1,204 @@
+use std::collections::VecDeque;
+
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+
+use super::app::App;
+use super::message::{Commands, Effect, Message, StoreCommand};
+use super::{run, update};
+use crate::api::LinearApi;
+
+/// A chain of effects that keeps producing more is cut off here rather
+/// than spinning forever.
+const SETTLE_LIMIT: usize = 256;
+
+/// Printed on a line of its own after each `snap` frame, so the frames can
+/// be told apart whatever they hold.
+pub const FRAME_SEPARATOR: &str = "\u{c}";
+
+/// What a key script asks the headless renderer to do next.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Step {
+    Key(KeyEvent),
+    /// Print the frame as it stands before carrying on.
+    Snap,
+}
+
+/// Parses a whitespace-separated key script such as `j j enter e s`.
+///
+/// Single characters are typed as-is, keys are named (`enter`, `esc`,
+/// `tab`, `shift+tab`, `space`, `bksp`, `up`, `pgdown`…), `ctrl+` and
+/// `alt+` add modifiers, a double-quoted run types each of its characters,
+/// and `snap` prints an intermediate frame. Lines starting with `//` are
+/// comments, so scripts can live in annotated files.
+pub fn parse(script: &str) -> Result<Vec<Step>, String> {
+    let mut steps = Vec::new();
+
+    for line in script.lines() {
+        let mut rest = line.trim_start();
+        if rest.starts_with("//") {
+            continue;
+        }
+
+        while !rest.is_empty() {
+            if let Some(quoted) = rest.strip_prefix('"') {
+                let (text, after) = quoted
+                    .split_once('"')
+                    .ok_or_else(|| format!("unterminated text in {line:?}"))?;
+                steps.extend(text.chars().map(|c| Step::Key(plain(KeyCode::Char(c)))));
+                rest = after.trim_start();
+                continue;
+            }
+
+            let end = rest.find(char::is_whitespace).unwrap_or(rest.len());
+            steps.push(step(&rest[..end])?);
+            rest = rest[end..].trim_start();
+        }
+    }
+
+    Ok(steps)
+}
+
+fn step(token: &str) -> Result<Step, String> {
+    if token == "snap" {
+        return Ok(Step::Snap);
+    }
+
+    key(token).map(Step::Key)
+}
+
+fn key(token: &str) -> Result<KeyEvent, String> {
+    if let Some(rest) = token.strip_prefix("ctrl+") {
+        return modified(rest, KeyModifiers::CONTROL);
+    }
+
+    if let Some(rest) = token.strip_prefix("alt+") {
+        return modified(rest, KeyModifiers::ALT);
+    }
+
+    let mut chars = token.chars();
+    if let (Some(c), None) = (chars.next(), chars.next()) {
+        return Ok(plain(KeyCode::Char(c)));
+    }
+
+    let code = match token {
+        "enter" => KeyCode::Enter,
+        "esc" => KeyCode::Esc,
+        "tab" => KeyCode::Tab,
+        "shift+tab" | "backtab" => KeyCode::BackTab,
+        "space" => KeyCode::Char(' '),
+        "bksp" | "backspace" => KeyCode::Backspace,
+        "del" | "delete" => KeyCode::Delete,
+        "up" => KeyCode::Up,
+        "down" => KeyCode::Down,
+        "left" => KeyCode::Left,
+        "right" => KeyCode::Right,
+        "home" => KeyCode::Home,
+        "end" => KeyCode::End,
+        "pgup" => KeyCode::PageUp,
+        "pgdown" => KeyCode::PageDown,
+        _ => return Err(format!("unknown key {token:?}")),
+    };
+
+    Ok(plain(code))
+}
+
+fn modified(rest: &str, modifier: KeyModifiers) -> Result<KeyEvent, String> {
+    let inner = key(rest)?;
+
+    Ok(KeyEvent::new(inner.code, inner.modifiers | modifier))
+}
+
+fn plain(code: KeyCode) -> KeyEvent {
+    KeyEvent::new(code, KeyModifiers::NONE)
+}
+
+/// Runs `commands` and everything they lead to against `api`, applying
+/// each result before moving on, so the app ends up as it would once the
+/// live runtime went quiet. Store and platform effects have nowhere to go
+/// headlessly; the store ones answer as an empty state directory would.
+pub async fn settle(app: &mut App, api: &dyn LinearApi, commands: Commands) {
+    let mut pending: VecDeque<Effect> = match commands {
+        Commands::Effects(effects) => effects.into_iter().collect(),
+        Commands::Runtime(_) => VecDeque::new(),
+    };
+
+    for _ in 0..SETTLE_LIMIT {
+        let Some(effect) = pending.pop_front() else {
+            return;
+        };
+
+        let message = match effect {
+            Effect::Api(command) => run::execute_api(api, command).await,
+            Effect::Store(StoreCommand::LoadRecent) => Message::RecentLoaded(Vec::new()),
+            Effect::Store(StoreCommand::ClearRecent) => Message::RecentCleared,
+            Effect::Store(StoreCommand::LoadDrafts) => Message::DraftsLoaded(Vec::new()),
+            Effect::Store(StoreCommand::LoadRecentEmoji) => Message::RecentEmojiLoaded(Vec::new()),
+            Effect::Store(StoreCommand::LoadTemplates) => Message::TemplatesLoaded(Vec::new()),
+            Effect::Store(_) | Effect::Platform(_) => continue,
+        };
+
+        if let Commands::Effects(effects) = update::apply(app, message) {
+            pending.extend(effects);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn keys(script: &str) -> Vec<KeyEvent> {
+        parse(script)
+            .unwrap()
+            .into_iter()
+            .filter_map(|step| match step {
+                Step::Key(key) => Some(key),
+                Step::Snap => None,
+            })
+            .collect()
+    }
+
+    #[test]
+    fn names_characters_and_modifiers_parse() {
+        assert_eq!(
+            keys("j enter shift+tab space ctrl+c"),
+            vec![
+                plain(KeyCode::Char('j')),
+                plain(KeyCode::Enter),
+                plain(KeyCode::BackTab),
+                plain(KeyCode::Char(' ')),
+                KeyEvent::new(KeyCode::Char('c'), KeyModifiers::CONTROL),
+            ]
+        );
+    }
+
+    #[test]
+    fn quoted_text_types_each_character() {
+        assert_eq!(
+            keys("c \"hi there\" esc"),
+            ['c', 'h', 'i', ' ', 't', 'h', 'e', 'r', 'e']
+                .map(|c| plain(KeyCode::Char(c)))
+                .into_iter()
+                .chain([plain(KeyCode::Esc)])
+                .collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    fn snaps_and_comments_are_understood() {
+        assert_eq!(
+            parse("j snap\n// the second row\nk").unwrap(),
+            vec![
+                Step::Key(plain(KeyCode::Char('j'))),
+                Step::Snap,
+                Step::Key(plain(KeyCode::Char('k'))),
+            ]
+        );
+    }
+
+    #[test]
+    fn unknown_keys_and_open_quotes_are_refused() {
+        assert!(parse("j wibble").unwrap_err().contains("wibble"));
+        assert!(parse("\"open").unwrap_err().contains("unterminated"));
+    }
+}
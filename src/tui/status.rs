@@ -9,17 +9,31 @@ pub enum Status {
     Cancelled,
     PostingComment,
     SavingComment,
+    SavingDescription,
     RecentCleared,
+    NoDrafts,
+    NoTemplates,
+    NoLinks,
+    DraftSaved,
+    DraftRestored,
+    DraftDiscarded,
     IssueUpdated,
     CommentPosted,
     CommentEdited,
+    DescriptionUpdated,
+    EditConflict,
+    EditConflictKept,
+    EditDiscarded,
     CommentDeleted,
     CopiedUrl,
+    CopiedLink,
     Applying,
     NeedHighlightedIssue,
     NeedOpenIssue,
     ConnectingWorkspace,
     AwaitingBrowser,
+    SuspendUnsupported,
+    FixtureOnly,
     Error(String),
 }
 
@@ -39,17 +53,35 @@ impl fmt::Display for Status {
             Status::Cancelled => "Cancelled",
             Status::PostingComment => "Posting comment…",
             Status::SavingComment => "Saving comment…",
+            Status::SavingDescription => "Saving description…",
             Status::RecentCleared => "Recently viewed cleared",
+            Status::NoDrafts => "No drafts saved",
+            Status::NoTemplates => {
+                "No templates: add .md files to templates/ in the state directory"
+            }
+            Status::NoLinks => "No links in this issue",
+            Status::DraftSaved => "Draft saved",
+            Status::DraftRestored => "Restored your draft",
+            Status::DraftDiscarded => "Draft discarded",
             Status::IssueUpdated => "Issue updated",
             Status::CommentPosted => "Comment posted",
             Status::CommentEdited => "Comment updated",
+            Status::DescriptionUpdated => "Description updated",
+            Status::EditConflict => "Someone else changed this issue while you were editing",
+            Status::EditConflictKept => {
+                "Someone else changed this issue; your text was kept in drafts"
+            }
+            Status::EditDiscarded => "Edit discarded",
             Status::CommentDeleted => "Comment deleted",
             Status::CopiedUrl => "Copied issue URL to clipboard",
+            Status::CopiedLink => "Copied link to clipboard",
             Status::Applying => "Applying…",
             Status::NeedHighlightedIssue => "Highlight an issue first",
             Status::NeedOpenIssue => "Open the issue first (enter)",
             Status::ConnectingWorkspace => "Connecting…",
             Status::AwaitingBrowser => "Waiting for browser sign-in…",
+            Status::SuspendUnsupported => "Ctrl-Z suspends only on Unix terminals",
+            Status::FixtureOnly => "A fixture is the only workspace while it runs",
             Status::Error(message) => return f.write_str(message),
         };
         f.write_str(text)
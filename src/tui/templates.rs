@@ -0,0 +1,105 @@
+use serde::{Deserialize, Serialize};
+
+/// A saved reply, kept as `<name>.md` in the templates directory so it
+/// can be written with any editor.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Template {
+    pub name: String,
+    pub body: String,
+}
+
+/// What each `{{placeholder}}` in a template stands for.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Fill {
+    pub identifier: String,
+    pub title: String,
+    pub assignee: String,
+    pub me: String,
+    pub date: String,
+}
+
+impl Fill {
+    fn value(&self, key: &str) -> Option<&str> {
+        let value = match key {
+            "identifier" => &self.identifier,
+            "title" => &self.title,
+            "assignee" => &self.assignee,
+            "me" => &self.me,
+            "date" => &self.date,
+            _ => return None,
+        };
+
+        Some(value)
+    }
+}
+
+impl Template {
+    /// Replaces known placeholders in one pass, so a title that itself
+    /// reads `{{date}}` stays as written. Unknown ones are left alone.
+    pub fn expand(&self, fill: &Fill) -> Template {
+        let mut body = String::with_capacity(self.body.len());
+        let mut rest = self.body.as_str();
+
+        while let Some(open) = rest.find("{{") {
+            let after = &rest[open + 2..];
+            let Some(close) = after.find("}}") else {
+                break;
+            };
+
+            body.push_str(&rest[..open]);
+            match fill.value(after[..close].trim()) {
+                Some(value) => body.push_str(value),
+                None => body.push_str(&rest[open..open + 2 + close + 2]),
+            }
+            rest = &after[close + 2..];
+        }
+        body.push_str(rest);
+
+        Template {
+            name: self.name.clone(),
+            body,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn template(body: &str) -> Template {
+        Template {
+            name: "released".into(),
+            body: body.into(),
+        }
+    }
+
+    fn fill() -> Fill {
+        Fill {
+            identifier: "DAN-7".into(),
+            title: "Oven {{date}}".into(),
+            assignee: "sam".into(),
+            me: "dan".into(),
+            date: "2026-07-16".into(),
+        }
+    }
+
+    #[test]
+    fn placeholders_expand_once() {
+        let expanded = template(
+            "{{identifier}} ({{ title }}) shipped {{date}} by {{me}}, thanks {{assignee}}",
+        )
+        .expand(&fill());
+
+        assert_eq!(
+            expanded.body,
+            "DAN-7 (Oven {{date}}) shipped 2026-07-16 by dan, thanks sam"
+        );
+    }
+
+    #[test]
+    fn unknown_and_unclosed_placeholders_stay_as_written() {
+        let expanded = template("{{version}} and {{date").expand(&fill());
+
+        assert_eq!(expanded.body, "{{version}} and {{date");
+    }
+}
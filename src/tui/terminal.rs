@@ -0,0 +1,29 @@
+//! Taking the terminal over and giving it back. On top of what ratatui
+//! does, bracketed paste is switched on so that a paste arrives as one
+//! event rather than a key per character.
+
+use std::io::Stdout;
+
+use crossterm::event::{DisableBracketedPaste, EnableBracketedPaste};
+use ratatui::backend::CrosstermBackend;
+use ratatui::Terminal;
+
+pub type Term = Terminal<CrosstermBackend<Stdout>>;
+
+pub fn init() -> Term {
+    let terminal = ratatui::init();
+    // Without it pastes still work, one key at a time.
+    let _ = crossterm::execute!(std::io::stdout(), EnableBracketedPaste);
+    terminal
+}
+
+pub fn restore() {
+    let _ = crossterm::execute!(std::io::stdout(), DisableBracketedPaste);
+    ratatui::restore();
+}
+
+/// Switches paste back on after `restore`, for a terminal coming back
+/// from a suspend or an external program.
+pub fn resume_paste() -> std::io::Result<()> {
+    crossterm::execute!(std::io::stdout(), EnableBracketedPaste)
+}
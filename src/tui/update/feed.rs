@@ -18,6 +18,9 @@ pub fn initial_commands(app: &mut App) -> Effects {
 
     commands.extend([
         Effect::Store(StoreCommand::LoadRecent),
+        Effect::Store(StoreCommand::LoadDrafts),
+        Effect::Store(StoreCommand::LoadRecentEmoji),
+        Effect::Store(StoreCommand::LoadTemplates),
         Effect::Api(ApiCommand::LoadCustomViews),
     ]);
 
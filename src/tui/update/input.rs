@@ -3,9 +3,10 @@ use ratatui::widgets::ListState;
 
 use super::feed::{force_feed, load_more, reload};
 use super::issue::{
-    clear_recent, enter_comments, open_assign_picker, open_comment_input, open_delete_comment,
-    open_edit_editor, open_in_browser, open_issue, open_labels, open_priority_picker,
-    open_reactions, open_reply_editor, open_status_picker, toggle_reaction, yank_url,
+    clear_recent, enter_comments, follow_link, open_assign_picker, open_comment_input,
+    open_delete_comment, open_description_editor, open_drafts, open_edit_editor, open_editor,
+    open_in_browser, open_issue, open_labels, open_links, open_priority_picker, open_reactions,
+    open_reply_editor, open_status_picker, resume_draft, toggle_reaction, use_emoji, yank_url,
 };
 use super::nav::{
     ascend, cycle_panel, cycle_view, cycle_view_group, cycle_view_sort, descend, history_step,
@@ -15,17 +16,21 @@ use crate::api::Credential;
 use crate::api::IssueRef;
 use crate::api::IssueUpdate;
 use crate::tui::action::{
-    self, Action, ConfirmInput, EditorInput, InputInput, LabelsInput, MenuInput, PickerInput,
-    ReactionInput, WorkspacesInput,
+    self, Action, ConfirmInput, ConflictInput, DraftsInput, EditorInput, EmojiInput, InputInput,
+    LabelsInput, LinksInput, MenuInput, PickerInput, ReactionInput, WorkspacesInput,
 };
 use crate::tui::app::App;
 use crate::tui::feed::FeedKey;
 use crate::tui::focus::{navigate_list, select_edge, DetailView, Direction, Edge, Focus, Origin};
-use crate::tui::message::{ApiCommand, Commands, Effect, Effects, RuntimeCommand};
+use crate::tui::message::{
+    ApiCommand, Commands, ComposeRecovery, Effect, Effects, Foreground, PlatformCommand,
+    RuntimeCommand,
+};
 use crate::tui::overlay::{
-    AssignOptions, Compose, Confirm, Editor, Find, Input, InputPurpose, LabelResults, Labels, Menu,
-    ModalOverlay, Overlay, Picker, PickerAction, PickerKind, Prefix, PrefixUnder, Reactions,
-    Search, SearchPhase, WorkspaceRow, Workspaces,
+    AssignOptions, Completion, Compose, Confirm, Conflict, DraftList, Editor, EmojiPicker, Find,
+    Input, InputPurpose, LabelResults, Labels, LinkHints, Menu, ModalOverlay, Overlay, Picker,
+    PickerAction, PickerKind, Prefix, PrefixUnder, Reactions, Search, SearchPhase, ViMode, ViReply,
+    WorkspaceRow, Workspaces,
 };
 use crate::tui::status::Status;
 
@@ -357,9 +362,6 @@ fn submit_input(app: &mut App, input: Input) -> Outcome {
 
             Outcome::with(Overlay::Search(Search::new(query)), command)
         }
-        InputPurpose::CustomReaction { issue_id, target } => {
-            toggle_reaction(app, &issue_id, target, &query).into_dismiss()
-        }
         InputPurpose::AssignSearch { issue, label, team } => {
             let picker = Picker {
                 kind: PickerKind::Assign(AssignOptions::Matching {
@@ -390,24 +392,76 @@ fn submit_input(app: &mut App, input: Input) -> Outcome {
             }),
             Status::ConnectingWorkspace,
         ),
+        InputPurpose::RunCommand => {
+            let mut command = Foreground::shell(&query);
+            if let Some(issue) = app.open_target() {
+                command.env = vec![
+                    ("LINEAR_ISSUE".into(), issue.identifier),
+                    ("LINEAR_ISSUE_URL".into(), issue.url),
+                    ("LINEAR_ISSUE_BRANCH".into(), issue.branch_name),
+                ];
+            }
+
+            Outcome::dismiss(Commands::runtime(RuntimeCommand::Foreground(command)))
+        }
     }
 }
 
-pub(super) fn apply_editor(editor: Editor, key: KeyEvent) -> Outcome {
+pub(super) fn apply_editor(app: &mut App, mut editor: Editor, key: KeyEvent) -> Outcome {
     if action::is_editor_submit(key) {
         return submit_editor(editor);
     }
 
+    if action::is_editor_external(key) {
+        let command = RuntimeCommand::EditExternally {
+            issue_id: editor.issue_id.clone(),
+            body: editor.text(),
+        };
+        return Outcome::with(Overlay::Editor(editor), Commands::runtime(command));
+    }
+
+    if action::is_editor_preview(key) {
+        app.ui.editor_preview = !editor.previewing();
+        editor.set_preview(app.ui.editor_preview);
+        return Outcome::set(Overlay::Editor(editor));
+    }
+
     if editor.mention().is_some() {
-        apply_mention(editor, key)
-    } else {
-        edit(editor, key)
+        return apply_mention(app, editor, key);
+    }
+
+    match editor.vi().map(|vi| vi.mode()) {
+        None => edit(app, editor, key),
+        Some(ViMode::Insert) if key.code == KeyCode::Esc => {
+            editor.vi_leave_insert();
+            Outcome::set(Overlay::Editor(editor))
+        }
+        Some(ViMode::Insert) => edit(app, editor, key),
+        Some(ViMode::Normal | ViMode::Visual { .. }) => {
+            match editor.vi_key(key, &mut app.ui.registers) {
+                ViReply::Stay => Outcome::set(Overlay::Editor(editor)),
+                ViReply::Submit => submit_editor(editor),
+                ViReply::Cancel => cancel_editor(app, &editor),
+                ViReply::Clipboard(text) => Outcome::with(
+                    Overlay::Editor(editor),
+                    Effect::Platform(PlatformCommand::CopyToClipboard(text)),
+                ),
+            }
+        }
     }
 }
 
-fn edit(mut editor: Editor, key: KeyEvent) -> Outcome {
+fn edit(app: &App, mut editor: Editor, key: KeyEvent) -> Outcome {
+    if action::is_editor_templates(key) {
+        if !editor.has_templates() {
+            return Outcome::set_reporting(Overlay::Editor(editor), Status::NoTemplates);
+        }
+        editor.open_templates();
+        return Outcome::set(Overlay::Editor(editor));
+    }
+
     match EditorInput::from_key(key) {
-        Some(EditorInput::Cancel) => Outcome::set_reporting(Overlay::None, Status::Cancelled),
+        Some(EditorInput::Cancel) => cancel_editor(app, &editor),
         Some(EditorInput::Newline) => {
             editor.newline();
             Outcome::set(Overlay::Editor(editor))
@@ -432,13 +486,48 @@ fn edit(mut editor: Editor, key: KeyEvent) -> Outcome {
             editor.move_down();
             Outcome::set(Overlay::Editor(editor))
         }
+        Some(EditorInput::Undo) => {
+            editor.undo();
+            Outcome::set(Overlay::Editor(editor))
+        }
+        Some(EditorInput::Redo) => {
+            editor.redo();
+            Outcome::set(Overlay::Editor(editor))
+        }
+        Some(EditorInput::WordLeft) => {
+            editor.move_word_left();
+            Outcome::set(Overlay::Editor(editor))
+        }
+        Some(EditorInput::WordRight) => {
+            editor.move_word_right();
+            Outcome::set(Overlay::Editor(editor))
+        }
+        Some(EditorInput::EraseWord) => {
+            editor.erase_word();
+            Outcome::set(Overlay::Editor(editor))
+        }
+        Some(EditorInput::EraseWordForward) => {
+            editor.erase_word_forward();
+            Outcome::set(Overlay::Editor(editor))
+        }
         None => match key.code {
             KeyCode::Char('@') if is_plain(key) && editor.at_word_boundary() => {
                 editor.open_mention();
                 Outcome::set(Overlay::Editor(editor))
             }
+            KeyCode::Char('#') if is_plain(key) && editor.at_word_boundary() => {
+                editor.open_reference();
+                Outcome::set(Overlay::Editor(editor))
+            }
+            KeyCode::Char(':') if is_plain(key) && editor.at_word_boundary() => {
+                editor.open_emoji();
+                Outcome::set(Overlay::Editor(editor))
+            }
             KeyCode::Char(c) if is_plain(key) => {
                 editor.insert_char(c);
+                if c == '-' && editor.open_reference_at_key() {
+                    return search_issues(editor);
+                }
                 Outcome::set(Overlay::Editor(editor))
             }
             _ => Outcome::set(Overlay::Editor(editor)),
@@ -451,60 +540,91 @@ pub(super) fn is_plain(key: KeyEvent) -> bool {
         .intersects(KeyModifiers::CONTROL | KeyModifiers::ALT)
 }
 
-fn apply_mention(mut editor: Editor, key: KeyEvent) -> Outcome {
+fn apply_mention(app: &mut App, mut editor: Editor, key: KeyEvent) -> Outcome {
     match key.code {
         KeyCode::Up => editor.mention_move(Direction::Prev),
         KeyCode::Down => editor.mention_move(Direction::Next),
-        KeyCode::Enter => editor.accept_mention(),
+        // A colon that nothing matches was only punctuation.
+        KeyCode::Enter
+            if editor.completion_count() == 0
+                && editor.mention().map(|menu| menu.kind) == Some(Completion::Emoji) =>
+        {
+            editor.newline();
+        }
+        KeyCode::Enter => {
+            let emoji = editor.selected_emoji();
+            editor.accept_mention();
+            if let Some(emoji) = emoji {
+                return Outcome::with(Overlay::Editor(editor), use_emoji(app, emoji.name));
+            }
+        }
         KeyCode::Esc | KeyCode::Left | KeyCode::Right => editor.close_mention(),
-        KeyCode::Backspace => editor.mention_backspace(),
-        KeyCode::Char(c) if is_plain(key) => editor.mention_type(c),
+        KeyCode::Backspace => {
+            editor.mention_backspace();
+            return search_issues(editor);
+        }
+        KeyCode::Char(c) if is_plain(key) => {
+            editor.mention_type(c);
+            return search_issues(editor);
+        }
         _ => {}
     }
 
     Outcome::set(Overlay::Editor(editor))
 }
 
+/// Issue completion searches once the query settles; what the workspace
+/// already holds shows in the meantime.
+fn search_issues(mut editor: Editor) -> Outcome {
+    let wanted = editor.mention().is_some_and(|menu| {
+        menu.kind == Completion::Issues && menu.query.chars().any(char::is_alphanumeric)
+    });
+    editor.queue_issue_search(wanted);
+
+    Outcome::set(Overlay::Editor(editor))
+}
+
+/// Closing the editor keeps what was typed: it is already a draft.
+fn cancel_editor(app: &App, editor: &Editor) -> Outcome {
+    let drafted = app
+        .workspace
+        .drafts
+        .get(&editor.issue_id, &editor.compose)
+        .is_some();
+
+    Outcome::set_reporting(
+        Overlay::None,
+        if drafted {
+            Status::DraftSaved
+        } else {
+            Status::Cancelled
+        },
+    )
+}
+
+/// The draft is kept until the result arrives, in case sending fails.
 fn submit_editor(editor: Editor) -> Outcome {
     if editor.is_empty() {
         return Outcome::close();
     }
 
-    let body = editor.text();
-    let issue_id = editor.issue_id;
-    let team_id = editor.target_team;
-
-    let (command, status) = match editor.compose {
-        Compose::Comment => (
-            Effect::Api(ApiCommand::CreateComment {
-                issue_id,
-                team_id,
-                body,
-                parent_id: None,
-            }),
-            Status::PostingComment,
-        ),
-        Compose::Reply { parent_id } => (
-            Effect::Api(ApiCommand::CreateComment {
-                issue_id,
-                team_id,
-                body,
-                parent_id: Some(parent_id),
-            }),
-            Status::PostingComment,
-        ),
-        Compose::Edit { comment_id } => (
-            Effect::Api(ApiCommand::UpdateComment {
-                issue_id,
-                team_id,
-                comment_id,
-                body,
-            }),
-            Status::SavingComment,
-        ),
+    let draft = ComposeRecovery {
+        body: editor.text(),
+        issue_id: editor.issue_id,
+        team_id: editor.target_team,
+        compose: editor.compose,
+        seen: editor.seen,
     };
 
-    Outcome::dismiss_reporting(command, status)
+    Outcome::dismiss_reporting(Effect::Api(draft.command()), compose_status(&draft.compose))
+}
+
+fn compose_status(compose: &Compose) -> Status {
+    match compose {
+        Compose::Comment | Compose::Reply { .. } => Status::PostingComment,
+        Compose::Edit { .. } => Status::SavingComment,
+        Compose::Description => Status::SavingDescription,
+    }
 }
 
 pub(super) fn apply_search(app: &mut App, mut search: Search, key: KeyEvent) -> Outcome {
@@ -637,17 +757,71 @@ pub(super) fn apply_reactions(app: &mut App, mut reactions: Reactions, key: KeyE
             }
             None => Outcome::close(),
         },
-        ReactionInput::Custom => Outcome::set(Overlay::Input(Input::new(
-            InputPurpose::CustomReaction {
-                issue_id: reactions.issue_id,
-                target: reactions.target,
-            },
-            "React with an emoji",
+        ReactionInput::More => Outcome::set(Overlay::Emoji(EmojiPicker::new(
+            reactions.issue_id,
+            reactions.target,
+            app.ui.recent_emoji.list(),
         ))),
         ReactionInput::Cancel => Outcome::set_reporting(Overlay::None, Status::Cancelled),
     }
 }
 
+pub(super) fn apply_emoji(app: &mut App, mut picker: EmojiPicker, key: KeyEvent) -> Outcome {
+    match EmojiInput::from_key(key) {
+        Some(EmojiInput::Next) => {
+            let len = picker.results.len();
+            navigate_list(&mut picker.state, len, Direction::Next);
+        }
+        Some(EmojiInput::Prev) => {
+            let len = picker.results.len();
+            navigate_list(&mut picker.state, len, Direction::Prev);
+        }
+        Some(EmojiInput::Erase) => picker.erase(),
+        Some(EmojiInput::Cancel) => {
+            return Outcome::set_reporting(Overlay::None, Status::Cancelled)
+        }
+        Some(EmojiInput::React) => {
+            return match picker.chosen() {
+                Some(name) => {
+                    toggle_reaction(app, &picker.issue_id, picker.target, &name).into_dismiss()
+                }
+                None => Outcome::close(),
+            }
+        }
+        None => match key.code {
+            KeyCode::Char(c) if is_plain(key) => picker.type_char(c),
+            _ => {}
+        },
+    }
+
+    Outcome::set(Overlay::Emoji(picker))
+}
+
+pub(super) fn apply_links(app: &mut App, mut hints: LinkHints, key: KeyEvent) -> Outcome {
+    match LinksInput::from_key(key) {
+        Some(LinksInput::Cancel) => {
+            return Outcome::set_reporting(Overlay::None, Status::Cancelled)
+        }
+        Some(LinksInput::Erase) => hints.erase(),
+        None => match key.code {
+            KeyCode::Char(c) if is_plain(key) => {
+                if let Some(hint) = hints.type_char(c) {
+                    if hints.yank {
+                        return Outcome::dismiss_reporting(
+                            Effect::Platform(PlatformCommand::CopyToClipboard(hint.link.url)),
+                            Status::CopiedLink,
+                        );
+                    }
+                    return Outcome::dismiss(follow_link(app, &hint));
+                }
+            }
+            _ => {}
+        },
+    }
+
+    Outcome::set(Overlay::Links(hints))
+}
+
 pub(super) fn apply_labels(mut labels: Labels, key: KeyEvent) -> Outcome {
     match LabelsInput::from_key(key) {
         Some(LabelsInput::Cancel) => Outcome::set_reporting(Overlay::None, Status::Cancelled),
@@ -683,7 +857,7 @@ pub(super) fn apply_labels(mut labels: Labels, key: KeyEvent) -> Outcome {
     }
 }
 
-fn search_labels(mut labels: Labels) -> Outcome {
+pub(super) fn search_labels(mut labels: Labels) -> Outcome {
     let query = labels.query.clone();
     labels.results = LabelResults::Loading;
     labels.state.select(Some(0));
@@ -693,6 +867,47 @@ fn search_labels(mut labels: Labels) -> Outcome {
     )
 }
 
+pub(super) fn apply_drafts(app: &mut App, mut drafts: DraftList, key: KeyEvent) -> Outcome {
+    let Some(input) = DraftsInput::from_key(key) else {
+        return Outcome::set(Overlay::Drafts(drafts));
+    };
+
+    let len = drafts.drafts.len();
+
+    match input {
+        DraftsInput::Next => {
+            navigate_list(&mut drafts.state, len, Direction::Next);
+            Outcome::set(Overlay::Drafts(drafts))
+        }
+        DraftsInput::Prev => {
+            navigate_list(&mut drafts.state, len, Direction::Prev);
+            Outcome::set(Overlay::Drafts(drafts))
+        }
+        DraftsInput::Cancel => Outcome::close(),
+        DraftsInput::Resume => match drafts.selected().cloned() {
+            Some(draft) => {
+                let effects = resume_draft(app, draft);
+                Outcome::with(app.take_overlay(), effects)
+            }
+            None => Outcome::close(),
+        },
+        DraftsInput::Discard => {
+            if let Some(draft) = drafts.remove_selected() {
+                app.workspace
+                    .drafts
+                    .discard(&draft.issue_id, &draft.compose);
+            }
+
+            let overlay = if drafts.drafts.is_empty() {
+                Overlay::None
+            } else {
+                Overlay::Drafts(drafts)
+            };
+            Outcome::set_reporting(overlay, Status::DraftDiscarded)
+        }
+    }
+}
+
 pub(super) fn apply_workspaces(
     app: &mut App,
     mut workspaces: Workspaces,
@@ -776,12 +991,14 @@ pub(super) fn apply_action(app: &mut App, action: Action) -> Effects {
         Action::Assign => open_assign_picker(app).write(app),
         Action::SetPriority => open_priority_picker(app).write(app),
         Action::SetLabels => open_labels(app).write(app),
+        Action::EditDescription => open_description_editor(app).write(app),
         Action::Comment => open_comment_input(app).write(app),
         Action::EnterComments => enter_comments(app).write(app),
-        Action::Reply => open_reply_editor(app),
+        Action::Reply => open_reply_editor(app).write(app),
         Action::EditComment => open_edit_editor(app).write(app),
         Action::DeleteComment => open_delete_comment(app).write(app),
         Action::React => open_reactions(app),
+        Action::Links => open_links(app).write(app),
         Action::CycleGroup => {
             cycle_view_group(app);
             Effects::default()
@@ -833,10 +1050,22 @@ pub(super) fn apply_action(app: &mut App, action: Action) -> Effects {
             open_menu(app);
             Effects::default()
         }
+        Action::Workspaces if app.ui.fixture => {
+            app.ui.status = Some(Status::FixtureOnly);
+            Effects::default()
+        }
         Action::Workspaces => {
             super::open_workspaces(app);
             Effects::default()
         }
+        Action::Drafts => open_drafts(app).write(app),
+        Action::RunCommand => {
+            app.set_overlay(Overlay::Input(Input::new(
+                InputPurpose::RunCommand,
+                "Run a shell command",
+            )));
+            Effects::default()
+        }
     }
 }
 
@@ -848,6 +1077,51 @@ pub(super) fn apply_confirm(confirm: Confirm, input: Option<ConfirmInput>) -> Ou
     }
 }
 
+pub(super) fn apply_conflict(
+    app: &mut App,
+    conflict: Conflict,
+    input: Option<ConflictInput>,
+) -> Outcome {
+    match input {
+        Some(ConflictInput::Overwrite) => {
+            let draft = ComposeRecovery {
+                seen: None,
+                ..conflict.draft
+            };
+
+            Outcome::dismiss_reporting(Effect::Api(draft.command()), compose_status(&draft.compose))
+        }
+        Some(ConflictInput::Reedit) => {
+            let Conflict {
+                draft,
+                theirs,
+                updated_at,
+            } = conflict;
+            // Their version on top, with the text that lost the race kept
+            // below it to merge from.
+            let seed = if theirs.is_empty() {
+                draft.body
+            } else {
+                format!("{theirs}\n\n{}", draft.body)
+            };
+            let effects = open_editor(
+                app,
+                draft.issue_id,
+                draft.compose,
+                draft.team_id,
+                Some(&seed),
+                Some(updated_at),
+            );
+
+            Outcome::with(app.take_overlay(), effects)
+        }
+        Some(ConflictInput::Discard) => {
+            Outcome::set_reporting(Overlay::None, Status::EditDiscarded)
+        }
+        None => Outcome::set(Overlay::Conflict(conflict)),
+    }
+}
+
 pub(super) fn apply_picker(mut picker: Picker, key: KeyEvent) -> Outcome {
     match key.code {
         KeyCode::Char('/') if picker.searchable() => {
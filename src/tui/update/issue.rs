@@ -4,17 +4,22 @@ use super::input::Report;
 use super::nav::clamp_selection;
 use crate::api::{
     IssueId, IssueRef, IssueSummary, Label, Priority, Reaction, ReactionTarget, StateOption,
-    TeamId, User,
+    TeamId, Timestamp, User,
 };
 use crate::tui::app::{App, FocusedIssue};
 use crate::tui::cache::{RefreshPolicy, Remote};
+use crate::tui::drafts::Draft;
 use crate::tui::focus::{DetailFocus, DetailView, Focus, Origin, Reveal};
-use crate::tui::message::{ApiCommand, Effect, Effects, PlatformCommand, StoreCommand};
+use crate::tui::markdown;
+use crate::tui::message::{
+    ApiCommand, ComposeRecovery, Effect, Effects, PlatformCommand, StoreCommand,
+};
 use crate::tui::overlay::{
-    AssignOptions, Compose, Confirm, Editor, Labels, Overlay, Picker, PickerItem, PickerKind,
-    Reactions,
+    AssignOptions, Compose, Confirm, DraftList, Editor, EditorKeys, Labels, LinkHint, LinkHints,
+    Overlay, Picker, PickerItem, PickerKind, Reactions,
 };
 use crate::tui::status::Status;
+use crate::tui::templates::{Fill, Template};
 
 const STATES_REFRESH: RefreshPolicy = RefreshPolicy::new(60 * 60, 24 * 60 * 60);
 const MEMBERS_REFRESH: RefreshPolicy = RefreshPolicy::new(60 * 60, 24 * 60 * 60);
@@ -32,25 +37,32 @@ pub(super) fn enter_comments(app: &mut App) -> Report {
     Effects::default().into()
 }
 
-pub(super) fn open_reply_editor(app: &mut App) -> Effects {
+pub(super) fn open_reply_editor(app: &mut App) -> Report {
     let Some(detail) = app.open_detail() else {
-        return Effects::default();
+        return Effects::default().into();
     };
     let issue_id = detail.id.clone();
     let team_id = detail.team_id.clone();
     let Some(selected) = app.comment_cursor() else {
-        return Effects::default();
+        return Effects::default().into();
     };
     let Some(threaded) = detail
         .threaded_comments()
         .get(selected)
         .map(|t| t.comment.reply_parent())
     else {
-        return Effects::default();
+        return Effects::default().into();
     };
     let parent_id = threaded;
 
-    open_editor(app, issue_id, Compose::Reply { parent_id }, team_id, None)
+    open_compose(
+        app,
+        issue_id,
+        Compose::Reply { parent_id },
+        team_id,
+        None,
+        None,
+    )
 }
 
 pub(super) fn open_edit_editor(app: &mut App) -> Report {
@@ -66,11 +78,12 @@ pub(super) fn open_edit_editor(app: &mut App) -> Report {
                 threaded.comment.id.clone(),
                 threaded.comment.body.clone(),
                 threaded.comment.is_mine,
+                detail.updated_at,
             )
         })
     });
 
-    let Some((issue_id, team_id, comment_id, body, is_mine)) = picked else {
+    let Some((issue_id, team_id, comment_id, body, is_mine, seen)) = picked else {
         return Effects::default().into();
     };
 
@@ -78,14 +91,34 @@ pub(super) fn open_edit_editor(app: &mut App) -> Report {
         return Report::status(Status::NotYourComment);
     }
 
-    open_editor(
+    open_compose(
         app,
         issue_id,
         Compose::Edit { comment_id },
         team_id,
         Some(&body),
+        Some(seen),
+    )
+}
+
+pub(super) fn open_description_editor(app: &mut App) -> Report {
+    let Some(detail) = app.open_detail() else {
+        return Report::status(Status::NeedOpenIssue);
+    };
+
+    let issue_id = detail.id.clone();
+    let team_id = detail.team_id.clone();
+    let body = detail.description.clone().unwrap_or_default();
+    let seen = detail.updated_at;
+
+    open_compose(
+        app,
+        issue_id,
+        Compose::Description,
+        team_id,
+        Some(&body),
+        Some(seen),
     )
-    .into()
 }
 
 pub(super) fn open_reactions(app: &mut App) -> Effects {
@@ -126,8 +159,50 @@ pub(super) fn open_reactions(app: &mut App) -> Effects {
     Effects::default()
 }
 
+pub(super) fn open_links(app: &mut App) -> Report {
+    let Some(detail) = app.open_detail() else {
+        return Report::status(Status::NeedOpenIssue);
+    };
+
+    let description = detail
+        .description
+        .iter()
+        .flat_map(|text| markdown::links(text))
+        .map(|link| ("description".to_string(), link));
+    let comments = detail.threaded_comments().into_iter().flat_map(|threaded| {
+        let author = threaded.comment.author.as_deref().unwrap_or("unknown");
+        markdown::links(&threaded.comment.body)
+            .into_iter()
+            .map(move |link| (author.to_string(), link))
+    });
+    let hints = LinkHints::new(description.chain(comments).collect());
+
+    if hints.hints.is_empty() {
+        return Report::status(Status::NoLinks);
+    }
+
+    app.set_overlay(Overlay::Links(hints));
+
+    Effects::default().into()
+}
+
+/// Linear issue links open here; anything else goes to the browser.
+pub(super) fn follow_link(app: &mut App, hint: &LinkHint) -> Effects {
+    match IssueRef::from_linear_url(&hint.link.url) {
+        Some(target) => {
+            let origin = app.take_origin();
+            open_issue(app, target, None, origin)
+        }
+        None => Effects::one(Effect::Platform(PlatformCommand::OpenUrl(
+            hint.link.url.clone(),
+        ))),
+    }
+}
+
+/// Reacting with an emoji counts as using it; taking a reaction back
+/// does not.
 pub(super) fn toggle_reaction(
-    app: &App,
+    app: &mut App,
     issue_id: &IssueId,
     target: ReactionTarget,
     emoji: &str,
@@ -150,19 +225,30 @@ pub(super) fn toggle_reaction(
         .iter()
         .find(|reaction| reaction.mine && reaction.emoji == emoji);
 
-    let command = match mine {
-        Some(reaction) => Effect::Api(ApiCommand::DeleteReaction {
+    if let Some(reaction) = mine {
+        return Effects::one(Effect::Api(ApiCommand::DeleteReaction {
             issue_id,
             reaction_id: reaction.id.clone(),
-        }),
-        None => Effect::Api(ApiCommand::CreateReaction {
-            issue_id,
-            target,
-            emoji: emoji.to_string(),
-        }),
-    };
+        }))
+        .into();
+    }
+
+    let mut effects = Effects::one(Effect::Api(ApiCommand::CreateReaction {
+        issue_id,
+        target,
+        emoji: emoji.to_string(),
+    }));
+    effects.extend(use_emoji(app, emoji));
 
-    Effects::one(command).into()
+    effects.into()
+}
+
+pub(super) fn use_emoji(app: &mut App, name: &str) -> Effects {
+    app.ui.recent_emoji.record(name);
+
+    Effects::one(Effect::Store(StoreCommand::SaveRecentEmoji(
+        app.ui.recent_emoji.list().to_vec(),
+    )))
 }
 
 pub(super) fn open_delete_comment(app: &mut App) -> Report {
@@ -223,6 +309,11 @@ pub(super) fn open_issue(
     }
 
     app.workspace.bust_detail();
+
+    if app.workspace.adopt_prefetched(&target, app.now) == Some(true) {
+        return settle_prefetched(app);
+    }
+
     app.workspace.begin_detail();
 
     Effects::one(Effect::Api(ApiCommand::LoadDetail {
@@ -231,6 +322,21 @@ pub(super) fn open_issue(
     }))
 }
 
+fn settle_prefetched(app: &mut App) -> Effects {
+    let Some(detail) = app.workspace.detail().value() else {
+        return Effects::default();
+    };
+    let id = detail.id.clone();
+    let summary = IssueSummary::from_detail(detail);
+
+    app.refocus_detail_issue(id.into());
+    app.record_recent(summary);
+
+    Effects::one(Effect::Store(StoreCommand::SaveRecent(
+        app.workspace.recently_viewed.clone(),
+    )))
+}
+
 pub(super) fn clear_recent(app: &mut App) {
     match app.focus() {
         Focus::Recent if !app.workspace.recently_viewed.is_empty() => {
@@ -323,7 +429,7 @@ pub(super) fn open_comment_input(app: &mut App) -> Report {
         Err(status) => return Report::status(status),
     };
 
-    open_editor(app, target.id, Compose::Comment, target.team_id, None).into()
+    open_compose(app, target.id, Compose::Comment, target.team_id, None, None)
 }
 
 pub(super) fn status_items(states: &[StateOption]) -> Vec<PickerItem> {
@@ -426,11 +532,17 @@ pub(super) fn place_editor(
     compose: Compose,
     team_id: &TeamId,
     seed: Option<&str>,
+    seen: Option<Timestamp>,
 ) {
     let mut editor = match seed {
         Some(body) => Editor::seeded(issue_id, team_id.clone(), compose, body),
         None => Editor::new(issue_id, team_id.clone(), compose),
     };
+    editor.seen = seen;
+    if app.ui.editor_keys == EditorKeys::Vi {
+        editor.enable_vi();
+    }
+    editor.set_preview(app.ui.editor_preview);
     editor.set_members(
         app.workspace
             .members
@@ -439,21 +551,174 @@ pub(super) fn place_editor(
             .cloned()
             .unwrap_or_default(),
     );
+    let issues = app.known_issues();
+    editor.set_templates(filled_templates(app, &editor.issue_id, &issues));
+    editor.set_issues(issues);
+    editor.set_recent_emoji(app.ui.recent_emoji.list());
     app.set_overlay(Overlay::Editor(editor));
 }
 
+/// Placeholders come from the loaded detail when there is one, else from
+/// the summary a list holds.
+fn filled_templates(app: &App, issue_id: &IssueId, known: &[IssueSummary]) -> Vec<Template> {
+    let (identifier, title, assignee) = match app.workspace.ready_detail(issue_id) {
+        Some((detail, _)) => (&detail.identifier, &detail.title, &detail.assignee),
+        None => match known.iter().find(|issue| issue.id == *issue_id) {
+            Some(issue) => (&issue.identifier, &issue.title, &issue.assignee),
+            None => return app.ui.templates.clone(),
+        },
+    };
+
+    let fill = Fill {
+        identifier: identifier.clone(),
+        title: title.clone().unwrap_or_default(),
+        assignee: assignee.as_ref().map_or_else(
+            || "Unassigned".to_string(),
+            |user| user.display_name.clone(),
+        ),
+        me: app
+            .workspace
+            .session
+            .value()
+            .map(|session| session.user.display_name.clone())
+            .unwrap_or_default(),
+        date: app.now.date(),
+    };
+
+    app.ui
+        .templates
+        .iter()
+        .map(|template| template.expand(&fill))
+        .collect()
+}
+
 pub(super) fn open_editor(
     app: &mut App,
     issue_id: IssueId,
     compose: Compose,
     team_id: TeamId,
     seed: Option<&str>,
+    seen: Option<Timestamp>,
 ) -> Effects {
-    place_editor(app, issue_id, compose, &team_id, seed);
+    place_editor(app, issue_id, compose, &team_id, seed, seen);
 
     access_members(app, &team_id)
 }
 
+/// Opens the editor for `compose`, picking up a saved draft in place of
+/// `seed` when there is one.
+fn open_compose(
+    app: &mut App,
+    issue_id: IssueId,
+    compose: Compose,
+    team_id: TeamId,
+    seed: Option<&str>,
+    seen: Option<Timestamp>,
+) -> Report {
+    let Some(draft) = app.workspace.drafts.get(&issue_id, &compose).cloned() else {
+        return open_editor(app, issue_id, compose, team_id, seed, seen).into();
+    };
+
+    let effects = open_editor(
+        app,
+        issue_id,
+        compose,
+        team_id,
+        Some(&draft.body),
+        draft.seen,
+    );
+    if let Some(editor) = app.editor_mut() {
+        editor.set_original(seed.unwrap_or_default());
+    }
+
+    Report::with_status(effects, Status::DraftRestored)
+}
+
+/// Keeps the open editor's text as a draft while it differs from what the
+/// editor opened with. Emptying it or changing it back discards the draft.
+pub(super) fn keep_draft(app: &mut App) {
+    let Overlay::Editor(editor) = app.overlay() else {
+        return;
+    };
+
+    if !editor.modified() {
+        let (issue_id, compose) = (editor.issue_id.clone(), editor.compose.clone());
+        app.workspace.drafts.discard(&issue_id, &compose);
+        return;
+    }
+
+    let draft = Draft {
+        identifier: draft_identifier(app, &editor.issue_id),
+        issue_id: editor.issue_id.clone(),
+        team_id: editor.target_team.clone(),
+        compose: editor.compose.clone(),
+        body: editor.text(),
+        seen: editor.seen,
+        saved_at: app.now,
+    };
+
+    app.workspace.drafts.save(draft);
+}
+
+/// Keeps text that failed to send, in case it cannot be reopened.
+pub(super) fn stash_draft(app: &mut App, recovery: &ComposeRecovery) {
+    let draft = Draft {
+        identifier: draft_identifier(app, &recovery.issue_id),
+        issue_id: recovery.issue_id.clone(),
+        team_id: recovery.team_id.clone(),
+        compose: recovery.compose.clone(),
+        body: recovery.body.clone(),
+        seen: recovery.seen,
+        saved_at: app.now,
+    };
+
+    app.workspace.drafts.save(draft);
+}
+
+fn draft_identifier(app: &App, issue_id: &IssueId) -> String {
+    app.identifier_for(issue_id)
+        .or_else(|| {
+            app.workspace
+                .drafts
+                .list()
+                .iter()
+                .find(|draft| draft.issue_id == *issue_id)
+                .map(|draft| draft.identifier.clone())
+        })
+        .unwrap_or_default()
+}
+
+pub(super) fn open_drafts(app: &mut App) -> Report {
+    if app.workspace.drafts.is_empty() {
+        return Report::status(Status::NoDrafts);
+    }
+
+    app.set_overlay(Overlay::Drafts(DraftList::new(app.workspace.drafts.list())));
+
+    Effects::default().into()
+}
+
+/// Opens the draft's issue with the editor over it, as it was left.
+pub(super) fn resume_draft(app: &mut App, draft: Draft) -> Effects {
+    let origin = app.take_origin();
+    let mut effects = open_issue(app, IssueRef::Id(draft.issue_id.clone()), None, origin);
+
+    let fresh = matches!(draft.compose, Compose::Comment | Compose::Reply { .. });
+    effects.extend(open_editor(
+        app,
+        draft.issue_id,
+        draft.compose,
+        draft.team_id,
+        Some(&draft.body),
+        draft.seen,
+    ));
+    if let (true, Some(editor)) = (fresh, app.editor_mut()) {
+        editor.set_original("");
+    }
+
+    effects
+}
+
 pub(super) fn open_in_browser(app: &mut App) -> Report {
     let target = match require(app.open_target(), Status::NeedHighlightedIssue) {
         Ok(target) => target,
@@ -2,8 +2,8 @@ use super::feed::{
     access_feed, feed_keep_id, reconcile_feed, resolve, revalidate_focus, selected_view_key,
 };
 use super::issue::{
-    fill_picker, found_users, newest_comment_index, open_editor, place_editor, status_items,
-    stop_assign_picker,
+    fill_picker, found_users, keep_draft, newest_comment_index, open_editor, place_editor,
+    stash_draft, status_items, stop_assign_picker,
 };
 use super::nav::clamp_selection;
 use crate::api::{
@@ -13,14 +13,16 @@ use crate::api::{
 use crate::store::Account;
 use crate::tui::app::{App, AuthState};
 use crate::tui::cache::Stale;
+use crate::tui::drafts::Draft;
 use crate::tui::feed::{FeedKey, FeedRequest};
 use crate::tui::focus::{Cursor, DetailView, Focus, LeftPanel, Reveal, Scroll};
 use crate::tui::message::{
     ApiCommand, Commands, ComposeRecovery, Effect, Effects, FailureTarget, Message, RequestError,
     RuntimeCommand, StoreCommand,
 };
-use crate::tui::overlay::{LabelResults, Overlay, PickerKind};
+use crate::tui::overlay::{Conflict, LabelResults, Overlay, PickerKind};
 use crate::tui::status::Status;
+use crate::tui::templates::Template;
 use crate::tui::view::ViewKind;
 
 enum Transition {
@@ -45,10 +47,20 @@ enum Transition {
         focused: bool,
         settle: bool,
     },
+    DetailPrefetched(Box<IssueDetail>),
+    EditConflict {
+        detail: Box<IssueDetail>,
+        draft: Box<ComposeRecovery>,
+        focused: bool,
+        settle: bool,
+    },
     RecentLoaded(Vec<IssueSummary>),
     RecentCleared {
         leave_panel: bool,
     },
+    DraftsLoaded(Vec<Draft>),
+    RecentEmojiLoaded(Vec<String>),
+    TemplatesLoaded(Vec<Template>),
     StatesLoaded {
         team_id: TeamId,
         states: Vec<StateOption>,
@@ -65,6 +77,10 @@ enum Transition {
         query: String,
         labels: Vec<Label>,
     },
+    IssuesFound {
+        query: String,
+        issues: Vec<IssueSummary>,
+    },
     IssueUpdated {
         id: crate::api::IssueId,
         on_detail: bool,
@@ -74,6 +90,7 @@ enum Transition {
         reveal: Reveal,
         status: Option<Status>,
         on_detail: bool,
+        sent: Option<Box<ComposeRecovery>>,
     },
     AccountAdded(Box<Account>),
     LoginSucceeded(Credential),
@@ -84,6 +101,10 @@ enum Transition {
     RefreshFailed {
         workspace_key: String,
     },
+    EditedExternally {
+        issue_id: crate::api::IssueId,
+        body: String,
+    },
     Failed {
         target: FailureTarget,
         error: RequestError,
@@ -122,38 +143,45 @@ fn reduce(app: &App, msg: Message) -> Transition {
         Message::CustomViewsLoaded(views) => Transition::CustomViewsLoaded(views),
         Message::TeamsLoaded { teams } => Transition::TeamsLoaded(teams),
         Message::DetailLoaded { detail, reveal } => {
-            let focused = app
-                .focus()
-                .detail()
-                .is_some_and(|focus| focus.issue.matches_detail(&detail));
-            let revalidates = app
-                .workspace
-                .detail()
-                .value()
-                .is_some_and(|current| current.id == detail.id);
+            let (focused, settle) = placement(app, &detail);
 
             Transition::DetailLoaded {
                 detail,
                 reveal,
                 focused,
-                settle: focused || revalidates,
+                settle,
+            }
+        }
+        Message::DetailPrefetched(detail) => Transition::DetailPrefetched(detail),
+        Message::EditConflict { detail, draft } => {
+            let (focused, settle) = placement(app, &detail);
+
+            Transition::EditConflict {
+                detail,
+                draft,
+                focused,
+                settle,
             }
         }
         Message::RecentLoaded(issues) => Transition::RecentLoaded(issues),
         Message::RecentCleared => Transition::RecentCleared {
             leave_panel: app.focus().left() == LeftPanel::Recent,
         },
+        Message::DraftsLoaded(drafts) => Transition::DraftsLoaded(drafts),
+        Message::RecentEmojiLoaded(names) => Transition::RecentEmojiLoaded(names),
+        Message::TemplatesLoaded(templates) => Transition::TemplatesLoaded(templates),
         Message::StatesLoaded { team_id, states } => Transition::StatesLoaded { team_id, states },
         Message::MembersLoaded { team_id, members } => {
             Transition::MembersLoaded { team_id, members }
         }
         Message::UsersFound { query, users } => Transition::UsersFound { query, users },
         Message::LabelsFound { query, labels } => Transition::LabelsFound { query, labels },
+        Message::IssuesFound { query, issues } => Transition::IssuesFound { query, issues },
         Message::IssueUpdated { id } => Transition::IssueUpdated {
             on_detail: focused_on_issue(app, &id),
             id,
         },
-        Message::CommentPosted { id } => {
+        Message::CommentPosted { id, draft } => {
             let reveal = match app.focus() {
                 Focus::Detail(detail) if detail.view.is_comments() => Reveal::NewestComment,
                 Focus::Detail(_)
@@ -168,25 +196,36 @@ fn reduce(app: &App, msg: Message) -> Transition {
                 id,
                 reveal,
                 status: Some(Status::CommentPosted),
+                sent: Some(draft),
             }
         }
-        Message::CommentEdited { id } => Transition::ReloadDetail {
+        Message::CommentEdited { id, draft } => Transition::ReloadDetail {
             on_detail: focused_on_issue(app, &id),
             id,
             reveal: Reveal::Top,
             status: Some(Status::CommentEdited),
+            sent: Some(draft),
+        },
+        Message::DescriptionEdited { id, draft } => Transition::ReloadDetail {
+            on_detail: focused_on_issue(app, &id),
+            id,
+            reveal: Reveal::Keep,
+            status: Some(Status::DescriptionUpdated),
+            sent: Some(draft),
         },
         Message::CommentDeleted { id } => Transition::ReloadDetail {
             on_detail: focused_on_issue(app, &id),
             id,
             reveal: Reveal::Top,
             status: Some(Status::CommentDeleted),
+            sent: None,
         },
         Message::ReactionToggled { id } => Transition::ReloadDetail {
             on_detail: focused_on_issue(app, &id),
             id,
             reveal: Reveal::Keep,
             status: None,
+            sent: None,
         },
         Message::AccountAdded { account } => Transition::AccountAdded(account),
         Message::LoginSucceeded { credential } => Transition::LoginSucceeded(credential),
@@ -198,10 +237,41 @@ fn reduce(app: &App, msg: Message) -> Transition {
             credential,
         },
         Message::RefreshFailed { workspace_key } => Transition::RefreshFailed { workspace_key },
+        Message::EditedExternally { issue_id, body } => {
+            Transition::EditedExternally { issue_id, body }
+        }
         Message::Failed { target, error } => Transition::Failed { target, error },
     }
 }
 
+/// The draft stays until its text is safely posted. One changed since then
+/// is newer work and is kept.
+fn drop_sent_draft(app: &mut App, sent: &ComposeRecovery) {
+    let unchanged = app
+        .workspace
+        .drafts
+        .get(&sent.issue_id, &sent.compose)
+        .is_some_and(|draft| draft.body == sent.body);
+
+    if unchanged {
+        app.workspace.drafts.discard(&sent.issue_id, &sent.compose);
+    }
+}
+
+fn placement(app: &App, detail: &IssueDetail) -> (bool, bool) {
+    let focused = app
+        .focus()
+        .detail()
+        .is_some_and(|focus| focus.issue.matches_detail(detail));
+    let revalidates = app
+        .workspace
+        .detail()
+        .value()
+        .is_some_and(|current| current.id == detail.id);
+
+    (focused, focused || revalidates)
+}
+
 fn focused_on_issue(app: &App, id: &crate::api::IssueId) -> bool {
     app.focus()
         .detail()
@@ -289,6 +359,32 @@ fn commit(app: &mut App, transition: Transition) -> Commands {
             focused,
             settle,
         } => commit_detail(app, *detail, reveal, focused, settle),
+        Transition::DetailPrefetched(detail) => {
+            app.workspace.prefetch.settle(*detail, app.now);
+            Commands::default()
+        }
+        Transition::EditConflict {
+            detail,
+            draft,
+            focused,
+            settle,
+        } => {
+            let conflict = Conflict::new(*draft, &detail);
+
+            app.workspace.prefetch.forget(&detail.id);
+            let commands = commit_detail(app, *detail, Reveal::Keep, focused, settle);
+
+            // Something else is open, so the text waits in drafts instead.
+            if matches!(app.overlay(), Overlay::None) {
+                app.set_overlay(Overlay::Conflict(conflict));
+                app.ui.status = Some(Status::EditConflict);
+            } else {
+                stash_draft(app, &conflict.draft);
+                app.ui.status = Some(Status::EditConflictKept);
+            }
+
+            commands
+        }
         Transition::RecentLoaded(issues) => {
             app.merge_recent(issues);
 
@@ -307,6 +403,21 @@ fn commit(app: &mut App, transition: Transition) -> Commands {
             }
             Commands::default()
         }
+        Transition::DraftsLoaded(drafts) => {
+            app.workspace.drafts.merge(drafts);
+
+            Commands::default()
+        }
+        Transition::RecentEmojiLoaded(names) => {
+            app.ui.recent_emoji.merge(names);
+
+            Commands::default()
+        }
+        Transition::TemplatesLoaded(templates) => {
+            app.ui.templates = templates;
+
+            Commands::default()
+        }
         Transition::StatesLoaded { team_id, states } => {
             let items = status_items(&states);
 
@@ -355,8 +466,17 @@ fn commit(app: &mut App, transition: Transition) -> Commands {
             }
             Commands::default()
         }
+        Transition::IssuesFound { query, issues } => {
+            if let Some(editor) = app.editor_mut() {
+                if editor.mention().is_some_and(|menu| menu.query == query) {
+                    editor.add_issues(issues);
+                }
+            }
+            Commands::default()
+        }
         Transition::IssueUpdated { id, on_detail } => {
             app.ui.status = Some(Status::IssueUpdated);
+            app.workspace.prefetch.forget(&id);
             app.workspace.feeds.invalidate_all();
             app.workspace.inbox.mark_stale();
             let mut refresh = revalidate_focus(app);
@@ -377,11 +497,18 @@ fn commit(app: &mut App, transition: Transition) -> Commands {
             reveal,
             status,
             on_detail,
+            sent,
         } => {
             if let Some(status) = status {
                 app.ui.status = Some(status);
             }
 
+            if let Some(sent) = sent {
+                drop_sent_draft(app, &sent);
+            }
+
+            app.workspace.prefetch.forget(&id);
+
             if !on_detail {
                 return Commands::default();
             }
@@ -394,9 +521,8 @@ fn commit(app: &mut App, transition: Transition) -> Commands {
             }))
         }
         Transition::AccountAdded(account) => {
-            let account = *account;
-            app.session.upsert_account(account.clone());
-            Commands::runtime(RuntimeCommand::SwitchWorkspace(Box::new(account)))
+            let stored = app.session.upsert_account(*account).clone();
+            Commands::runtime(RuntimeCommand::SwitchWorkspace(Box::new(stored)))
         }
         Transition::LoginSucceeded(credential) => {
             app.ui.status = Some(Status::ConnectingWorkspace);
@@ -423,6 +549,15 @@ fn commit(app: &mut App, transition: Transition) -> Commands {
 
             Commands::default()
         }
+        Transition::EditedExternally { issue_id, body } => {
+            if let Some(editor) = app.editor_mut() {
+                if editor.issue_id == issue_id {
+                    editor.replace_text(&body);
+                }
+            }
+            keep_draft(app);
+            Commands::default()
+        }
         Transition::Failed { target, error } => commit_failure(app, target, error),
     }
 }
@@ -444,6 +579,7 @@ fn commit_detail(
 
     let id = detail.id.clone();
 
+    app.workspace.prefetch.supersede(&detail);
     app.workspace.set_detail(detail, app.now);
     app.clear_transient_status();
 
@@ -502,6 +638,10 @@ fn revealed_view(
 fn commit_failure(app: &mut App, target: FailureTarget, error: RequestError) -> Commands {
     let (error, command) = match error {
         RequestError::Unauthorised(message) => (message, reauthenticate(app)),
+        RequestError::RateLimited(message) => {
+            app.workspace.prefetch.back_off(app.now);
+            (message, Commands::default())
+        }
         RequestError::Other(message) => (message, Commands::default()),
     };
 
@@ -512,6 +652,10 @@ fn commit_failure(app: &mut App, target: FailureTarget, error: RequestError) ->
         FailureTarget::CustomViews => app.workspace.saved_views.views.fail(error.clone()),
         FailureTarget::Teams => app.workspace.teams.teams.fail(error.clone()),
         FailureTarget::Detail => app.workspace.fail_detail(error.clone()),
+        FailureTarget::Prefetch { id } => {
+            app.workspace.prefetch.fail(&id, error);
+            return command;
+        }
         FailureTarget::States { team_id } => {
             app.workspace
                 .states
@@ -525,6 +669,8 @@ fn commit_failure(app: &mut App, target: FailureTarget, error: RequestError) ->
                 .fail(error.clone());
         }
         FailureTarget::UserSearch => stop_assign_picker(app.picker_mut()),
+        // Completion still offers the issues already held.
+        FailureTarget::IssueSearch => return command,
         FailureTarget::LabelSearch => {
             if let Some(overlay) = app.labels_mut() {
                 overlay.results = LabelResults::Loaded(Vec::new());
@@ -532,6 +678,7 @@ fn commit_failure(app: &mut App, target: FailureTarget, error: RequestError) ->
         }
         FailureTarget::Compose(recovery) => {
             app.ui.status = Some(Status::Error(error));
+            stash_draft(app, &recovery);
 
             if command.is_empty() {
                 return recover_compose(app, *recovery).into();
@@ -563,9 +710,10 @@ fn recover_compose(app: &mut App, recovery: ComposeRecovery) -> Effects {
         team_id,
         compose,
         body,
+        seen,
     } = recovery;
 
-    open_editor(app, issue_id, compose, team_id, Some(&body))
+    open_editor(app, issue_id, compose, team_id, Some(&body), seen)
 }
 
 fn reopen_compose_cached(app: &mut App, recovery: ComposeRecovery) {
@@ -578,9 +726,10 @@ fn reopen_compose_cached(app: &mut App, recovery: ComposeRecovery) {
         team_id,
         compose,
         body,
+        seen,
     } = recovery;
 
-    place_editor(app, issue_id, compose, &team_id, Some(&body));
+    place_editor(app, issue_id, compose, &team_id, Some(&body), seen);
 }
 
 fn refreshable(app: &App) -> Option<String> {
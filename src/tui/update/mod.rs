@@ -1,11 +1,12 @@
-use crossterm::event::KeyEvent;
+use crossterm::event::{KeyCode, KeyEvent};
 
-use super::action::ConfirmInput;
-use super::app::App;
+use super::action::{ConfirmInput, ConflictInput};
+use super::app::{App, AuthState};
 use super::event::Redraw;
 use super::focus::Reveal;
-use super::message::{ApiCommand, Commands, Effect, Effects};
+use super::message::{ApiCommand, Commands, Effect, Effects, RuntimeCommand, StoreCommand};
 use super::overlay::{Overlay, Workspaces};
+use super::status::Status;
 use crate::api::Timestamp;
 
 mod feed;
@@ -18,10 +19,11 @@ pub use feed::{initial_commands, restore_feeds};
 pub use message::apply;
 
 use input::{
-    apply_action, apply_confirm, apply_editor, apply_find, apply_input, apply_labels, apply_menu,
-    apply_outcome, apply_picker, apply_prefix, apply_reactions, apply_search, apply_workspaces,
-    resolve_browse,
+    apply_action, apply_confirm, apply_conflict, apply_drafts, apply_editor, apply_emoji,
+    apply_find, apply_input, apply_labels, apply_links, apply_menu, apply_outcome, apply_picker,
+    apply_prefix, apply_reactions, apply_search, apply_workspaces, resolve_browse, search_labels,
 };
+use issue::keep_draft;
 
 pub fn open_workspaces(app: &mut App) {
     app.set_overlay(Overlay::Workspaces(Workspaces::new(
@@ -55,18 +57,30 @@ pub fn handle_key(app: &mut App, key: KeyEvent) -> Commands {
         return Commands::default();
     }
 
+    if super::action::is_suspend(&key) {
+        if cfg!(unix) {
+            return Commands::runtime(RuntimeCommand::Suspend);
+        }
+        app.ui.status = Some(Status::SuspendUnsupported);
+        return Commands::default();
+    }
+
     let outcome = match app.take_overlay() {
         Overlay::Confirm(confirm) => apply_confirm(confirm, ConfirmInput::from_key(key)),
         Overlay::Picker(picker) => apply_picker(picker, key),
         Overlay::Menu(menu) => apply_menu(menu, key),
         Overlay::Prefix(prefix) => apply_prefix(prefix, key),
         Overlay::Input(input) => apply_input(app, input, key),
-        Overlay::Editor(editor) => apply_editor(editor, key),
+        Overlay::Editor(editor) => apply_editor(app, editor, key),
         Overlay::Search(search) => apply_search(app, search, key),
         Overlay::Find(find) => apply_find(app, find, key),
         Overlay::Reactions(reactions) => apply_reactions(app, reactions, key),
+        Overlay::Emoji(picker) => apply_emoji(app, picker, key),
         Overlay::Labels(labels) => apply_labels(labels, key),
         Overlay::Workspaces(workspaces) => apply_workspaces(app, workspaces, key),
+        Overlay::Conflict(conflict) => apply_conflict(app, conflict, ConflictInput::from_key(key)),
+        Overlay::Drafts(drafts) => apply_drafts(app, drafts, key),
+        Overlay::Links(hints) => apply_links(app, hints, key),
         Overlay::None => {
             return resolve_browse(app, key)
                 .map(|action| apply_action(app, action))
@@ -75,7 +89,54 @@ pub fn handle_key(app: &mut App, key: KeyEvent) -> Commands {
         }
     };
 
-    apply_outcome(app, outcome)
+    let commands = apply_outcome(app, outcome);
+    keep_draft(app);
+
+    commands
+}
+
+/// A bracketed paste. The editor takes it as one edit; single-line fields
+/// take it flattened onto one line; the find bar takes it as typing and the
+/// labels query as one search. Where keys would act rather than type, it is
+/// dropped.
+pub fn handle_paste(app: &mut App, text: &str) -> Commands {
+    match app.take_overlay() {
+        Overlay::Editor(mut editor) => {
+            editor.paste(text);
+            app.set_overlay(Overlay::Editor(editor));
+            keep_draft(app);
+            Commands::default()
+        }
+        Overlay::Input(mut input) => {
+            for c in text.trim_end_matches(['\r', '\n']).chars() {
+                input.insert(if c.is_control() { ' ' } else { c });
+            }
+            app.set_overlay(Overlay::Input(input));
+            Commands::default()
+        }
+        Overlay::Labels(mut labels) => {
+            labels
+                .query
+                .extend(text.chars().filter(|c| !c.is_control()));
+            let outcome = search_labels(labels);
+            apply_outcome(app, outcome)
+        }
+        overlay @ Overlay::Find(_) => {
+            app.set_overlay(overlay);
+
+            let mut effects = Effects::default();
+            for c in text.chars().filter(|c| !c.is_control()) {
+                if let Commands::Effects(more) = handle_key(app, KeyEvent::from(KeyCode::Char(c))) {
+                    effects.extend(more);
+                }
+            }
+            effects.into()
+        }
+        overlay => {
+            app.set_overlay(overlay);
+            Commands::default()
+        }
+    }
 }
 
 pub fn tick(app: &mut App, now: Timestamp) -> Redraw {
@@ -97,6 +158,49 @@ pub fn tick(app: &mut App, now: Timestamp) -> Redraw {
     }
 }
 
+/// Writes drafts that changed since the last write, at most once every few
+/// seconds while typing goes on.
+pub fn autosave(app: &mut App) -> Effects {
+    app.workspace
+        .drafts
+        .due(app.now)
+        .map(|drafts| Effect::Store(StoreCommand::SaveDrafts(drafts)))
+        .into_iter()
+        .collect()
+}
+
+/// Searches for issue completion once typing pauses.
+pub fn search_completions(app: &mut App) -> Effects {
+    app.editor_mut()
+        .and_then(|editor| editor.due_issue_search())
+        .map(|query| Effect::Api(ApiCommand::SearchIssues { query }))
+        .into_iter()
+        .collect()
+}
+
+pub fn prefetch(app: &mut App) -> Effects {
+    let window: Vec<_> = app
+        .prefetch_window()
+        .into_iter()
+        .map(|issue| (issue.id.clone(), issue.updated_at))
+        .collect();
+
+    app.workspace
+        .prefetch
+        .observe(window.first().map(|(id, _)| id));
+
+    if app.session.auth() != AuthState::Authenticated {
+        return Effects::default();
+    }
+
+    app.workspace
+        .prefetch
+        .plan(&window, app.now)
+        .into_iter()
+        .map(|id| Effect::Api(ApiCommand::PrefetchDetail { id }))
+        .collect()
+}
+
 fn earliest_time_refresh(app: &App) -> Option<Timestamp> {
     let now = app.now;
 
@@ -264,8 +264,12 @@ pub(super) fn jump_edge(app: &mut App, edge: Edge) -> Effects {
         | Overlay::Editor(_)
         | Overlay::Find(_)
         | Overlay::Reactions(_)
+        | Overlay::Emoji(_)
         | Overlay::Workspaces(_)
-        | Overlay::Labels(_) => {
+        | Overlay::Labels(_)
+        | Overlay::Conflict(_)
+        | Overlay::Drafts(_)
+        | Overlay::Links(_) => {
             app.jump_to_edge(edge);
             load_more_for_focus(app)
         }
@@ -3,16 +3,18 @@ use ratatui::text::Line;
 use ratatui::widgets::ListState;
 
 use super::cache::{Cache, CacheStatus, Remote};
+use super::drafts::Drafts;
 use super::feed::{Feed, FeedKey, FeedStore};
 use super::markdown;
+use super::prefetch::Prefetcher;
 use super::saved_views::SavedViewsPanel;
 use super::view::{View, ViewKind};
 use crate::api::{
-    IssueDetail, IssueSummary, NotificationItem, Session, StateOption, Team, TeamId, Timestamp,
-    User,
+    IssueDetail, IssueId, IssueRef, IssueSummary, NotificationItem, Session, StateOption, Team,
+    TeamId, Timestamp, User,
 };
 
-#[derive(Default)]
+#[derive(Clone, Default)]
 pub struct RenderedDetail {
     pub description: Vec<Line<'static>>,
     pub comment_bodies: Vec<Vec<Line<'static>>>,
@@ -78,12 +80,14 @@ pub struct WorkspaceData {
     pub inbox: Feed<NotificationItem>,
     detail: Remote<IssueDetail>,
     detail_markdown: RenderedDetail,
+    pub prefetch: Prefetcher,
     pub states: Cache<TeamId, Remote<Vec<StateOption>>>,
     pub members: Cache<TeamId, Remote<Vec<User>>>,
     pub saved_views: SavedViewsPanel,
     pub recently_viewed: Vec<IssueSummary>,
     pub recent_state: ListState,
     pub teams: TeamsPanel,
+    pub drafts: Drafts,
 }
 
 impl WorkspaceData {
@@ -94,12 +98,14 @@ impl WorkspaceData {
             inbox: Feed::default(),
             detail: Remote::default(),
             detail_markdown: RenderedDetail::default(),
+            prefetch: Prefetcher::default(),
             states: Cache::default(),
             members: Cache::default(),
             saved_views: SavedViewsPanel::new(),
             recently_viewed: Vec::new(),
             recent_state: ListState::default().with_selected(Some(0)),
             teams: TeamsPanel::new(),
+            drafts: Drafts::default(),
         }
     }
 
@@ -108,6 +114,25 @@ impl WorkspaceData {
         self.detail.set(detail, now);
     }
 
+    pub fn adopt_prefetched(&mut self, target: &IssueRef, now: Timestamp) -> Option<bool> {
+        let (warm, fetched_at, fresh) = self.prefetch.lookup(target, now)?;
+
+        self.detail_markdown = warm.markdown.clone();
+        self.detail = Remote::ready(warm.detail.clone(), fetched_at);
+
+        Some(fresh)
+    }
+
+    pub fn ready_detail(&self, id: &IssueId) -> Option<(&IssueDetail, &RenderedDetail)> {
+        match self.detail.value().filter(|detail| &detail.id == id) {
+            Some(detail) => Some((detail, &self.detail_markdown)),
+            None => self
+                .prefetch
+                .warm(id)
+                .map(|warm| (&warm.detail, &warm.markdown)),
+        }
+    }
+
     pub fn bust_detail(&mut self) {
         self.detail.bust();
         self.detail_markdown = RenderedDetail::default();
@@ -132,16 +157,19 @@ impl WorkspaceData {
             inbox,
             detail,
             detail_markdown: _,
+            prefetch,
             states,
             members,
             saved_views,
             recently_viewed: _,
             recent_state: _,
             teams,
+            drafts: _,
         } = self;
 
         session.cancel();
         detail.cancel();
+        prefetch.cancel();
         inbox.cancel();
         saved_views.views.cancel();
         teams.teams.cancel();
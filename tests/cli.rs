@@ -1,5 +1,7 @@
 use std::process::Command;
 
+use linear_tui::tui::script::FRAME_SEPARATOR;
+
 #[test]
 fn headless_detail_render_includes_the_cached_markdown_bodies() {
     let output = Command::new(env!("CARGO_BIN_EXE_linear-tui"))
@@ -38,3 +40,274 @@ fn headless_detail_render_includes_the_cached_markdown_bodies() {
         "detail render should include the issue's reaction chips, got:\n{stdout}"
     );
 }
+
+fn render(args: &[&str]) -> String {
+    let output = Command::new(env!("CARGO_BIN_EXE_linear-tui"))
+        .arg("render")
+        .args(args)
+        .output()
+        .expect("run the headless render subcommand");
+
+    assert!(
+        output.status.success(),
+        "render exited with failure: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+
+    String::from_utf8_lossy(&output.stdout).into_owned()
+}
+
+#[test]
+fn a_key_script_reaches_pickers_and_applies_their_edits() {
+    let stdout = render(&[
+        "--detail",
+        "DAN2-7",
+        "--height",
+        "24",
+        "--keys",
+        "e s snap j j j enter enter",
+    ]);
+
+    let separator = format!("{FRAME_SEPARATOR}\n");
+    let frames: Vec<&str> = stdout.split(&separator).collect();
+    assert_eq!(
+        frames.len(),
+        2,
+        "snap should print an extra frame:\n{stdout}"
+    );
+    assert!(frames[0].contains("Set status"), "{}", frames[0]);
+    assert!(
+        frames[1].contains("DAN2-7  Done"),
+        "the status edit should land in the final frame:\n{}",
+        frames[1]
+    );
+}
+
+#[test]
+fn a_key_script_can_come_from_a_file() {
+    let dir = tempfile::tempdir().expect("a temp dir");
+    let script = dir.path().join("open-teams.keys");
+    std::fs::write(&script, "// jump to the teams panel\n4\n").expect("write the script");
+
+    let stdout = render(&["--script", script.to_str().expect("a utf-8 path")]);
+
+    assert!(stdout.contains("DAN  Donuts"), "{stdout}");
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn record_captures_workspace_metadata_and_requested_details() {
+    use linear_tui::api::fixture::FIXTURE_VERSION;
+    use linear_tui::api::mock::MockServer;
+    use linear_tui::api::{FixtureClient, LinearApi, TeamId};
+
+    let server = MockServer::start(FixtureClient::sample())
+        .await
+        .expect("a local server");
+    // Small pages, so the labels only arrive whole, and the third detail
+    // only at all, if later pages are read.
+    server.set_page_size(2);
+    let dir = tempfile::tempdir().expect("a temp dir");
+    let out = dir.path().join("recorded.json");
+
+    let mut command = Command::new(env!("CARGO_BIN_EXE_linear-tui"));
+    command
+        .args(["record", "--details", "3", "--out"])
+        .arg(&out)
+        .env("LINEAR_API_KEY", "lin_api_test")
+        .env("LINEAR_TUI_ENDPOINT", server.endpoint());
+    let output = tokio::task::spawn_blocking(move || command.output())
+        .await
+        .expect("the record process")
+        .expect("run the record subcommand");
+
+    assert!(
+        output.status.success(),
+        "record exited with failure: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+
+    let raw = std::fs::read_to_string(&out).expect("the recorded fixture");
+    assert!(raw.contains(&format!("\"version\": {FIXTURE_VERSION}")));
+
+    let recorded = FixtureClient::from_path(&out).expect("a loadable fixture");
+    let original = FixtureClient::sample();
+    let pizza = TeamId::from_raw("t_pizza");
+
+    assert_eq!(
+        recorded.teams().await.unwrap(),
+        original.teams().await.unwrap()
+    );
+    assert_eq!(
+        recorded.workflow_states(&pizza).await.unwrap(),
+        original.workflow_states(&pizza).await.unwrap()
+    );
+    assert_eq!(
+        recorded.team_members(&pizza).await.unwrap(),
+        original.team_members(&pizza).await.unwrap()
+    );
+    let labels = original.search_labels("", None).await.unwrap().items;
+    assert!(labels.len() > 2, "the sample spans several label pages");
+    assert_eq!(
+        recorded.search_labels("", None).await.unwrap().items,
+        labels
+    );
+
+    let looked_up: usize = server
+        .requests()
+        .iter()
+        .filter(|request| matches!(request.operation.as_str(), "IssueQuery" | "IssueBatch"))
+        .map(|request| {
+            request
+                .variables
+                .as_object()
+                .map_or(0, |variables| variables.len())
+        })
+        .sum();
+    assert_eq!(looked_up, 3);
+}
+
+#[test]
+fn scrub_rewrites_a_fixture_that_still_renders() {
+    let dir = tempfile::tempdir().expect("a temp dir");
+    let out = dir.path().join("scrubbed.json");
+
+    let scrubbed = Command::new(env!("CARGO_BIN_EXE_linear-tui"))
+        .args(["scrub", "fixtures/dans-donuts.json", "--out"])
+        .arg(&out)
+        .output()
+        .expect("run the scrub subcommand");
+    assert!(
+        scrubbed.status.success(),
+        "scrub exited with failure: {}",
+        String::from_utf8_lossy(&scrubbed.stderr)
+    );
+
+    let raw = std::fs::read_to_string(&out).expect("the scrubbed fixture");
+    assert!(!raw.contains("Dan's Donuts"));
+
+    let rendered = Command::new(env!("CARGO_BIN_EXE_linear-tui"))
+        .args(["render", "--detail", "BRAVO-7", "--fixture"])
+        .arg(&out)
+        .output()
+        .expect("render the scrubbed fixture");
+    let stdout = String::from_utf8_lossy(&rendered.stdout);
+
+    assert!(rendered.status.success());
+    assert!(stdout.contains("BRAVO-7"), "got:\n{stdout}");
+    assert!(stdout.contains("Example Org"), "got:\n{stdout}");
+}
+
+#[test]
+fn scrub_writes_beside_the_input_rather_than_over_it() {
+    let dir = tempfile::tempdir().expect("a temp dir");
+    let input = dir.path().join("recorded.json");
+    std::fs::copy("fixtures/dans-donuts.json", &input).expect("copy the fixture");
+
+    let scrubbed = Command::new(env!("CARGO_BIN_EXE_linear-tui"))
+        .arg("scrub")
+        .arg(&input)
+        .output()
+        .expect("run the scrub subcommand");
+    assert!(scrubbed.status.success());
+
+    let original = std::fs::read_to_string(&input).expect("the input");
+    let out = std::fs::read_to_string(dir.path().join("recorded.scrubbed.json"))
+        .expect("the scrubbed copy");
+    assert!(
+        original.contains("Dan's Donuts"),
+        "the input was overwritten"
+    );
+    assert!(!out.contains("Dan's Donuts"));
+
+    let over_itself = Command::new(env!("CARGO_BIN_EXE_linear-tui"))
+        .arg("scrub")
+        .arg(&input)
+        .arg("--out")
+        .arg(dir.path().join(".").join("recorded.json"))
+        .output()
+        .expect("run the scrub subcommand");
+    assert!(!over_itself.status.success());
+    assert!(std::fs::read_to_string(&input)
+        .expect("the input")
+        .contains("Dan's Donuts"));
+}
+
+#[tokio::test]
+async fn replay_rebuilds_a_journalled_session() {
+    use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+    use linear_tui::api::fixture::FixtureClient;
+    use linear_tui::api::{IssueFilter, LinearApi};
+    use linear_tui::tui::event::Lane;
+    use linear_tui::tui::feed::{FeedKey, FeedRequest};
+    use linear_tui::tui::journal::{Journal, Recorded, Startup};
+    use linear_tui::tui::message::Message;
+    use linear_tui::tui::overlay::EditorKeys;
+
+    let dir = tempfile::tempdir().expect("a temp dir");
+    let path = dir.path().join("session.journal");
+    let client = FixtureClient::sample();
+    let session = client.session().await.expect("a session");
+    let filter = IssueFilter::assigned_to_me();
+    let page = client.issues(&filter, None).await.expect("issues");
+
+    let mut journal = Journal::create(&path).expect("a journal");
+    journal.record::<Message>(Recorded::Start {
+        now: "2026-07-16T21:00:00Z".into(),
+        accounts: vec![linear_tui::tui::run::fixture_account(session.clone())],
+        startup: Startup::Connected {
+            workspace: session.org_url_key.clone(),
+            feeds: None,
+        },
+        fixture: true,
+        editor_keys: EditorKeys::Plain,
+        width: 100,
+        height: 20,
+    });
+    for message in [
+        Message::SessionLoaded(session),
+        Message::FeedLoaded {
+            key: FeedKey::Issues(filter),
+            request: FeedRequest::Refresh,
+            page,
+        },
+    ] {
+        journal.record(Recorded::Message {
+            lane: Lane::Host,
+            message,
+        });
+    }
+    journal.record::<Message>(Recorded::Input {
+        key: KeyEvent::new(KeyCode::Char('j'), KeyModifiers::NONE),
+    });
+    drop(journal);
+
+    let replay = |args: &[&str]| {
+        let output = Command::new(env!("CARGO_BIN_EXE_linear-tui"))
+            .arg("replay")
+            .arg(&path)
+            .args(args)
+            .output()
+            .expect("run the replay subcommand");
+        assert!(
+            output.status.success(),
+            "replay exited with failure: {}",
+            String::from_utf8_lossy(&output.stderr)
+        );
+        String::from_utf8_lossy(&output.stdout).into_owned()
+    };
+
+    let last = replay(&[]);
+    assert!(last.contains("DAN2-7"), "{last}");
+    assert!(
+        last.contains("2 of 7"),
+        "the replayed j should move down:\n{last}"
+    );
+    assert!(last.contains("fixture"), "{last}");
+
+    let stepped = replay(&["--step"]);
+    assert_eq!(stepped.matches("── ").count(), 4, "{stepped}");
+    assert!(stepped.contains("── 4 key Char('j')"), "{stepped}");
+
+    let early = replay(&["--until", "2"]);
+    assert!(!early.contains("DAN2-7"), "{early}");
+}
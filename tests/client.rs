@@ -0,0 +1,351 @@
+use linear_tui::api::mock::{Fault, MockServer};
+use linear_tui::api::{
+    ApiError, CassetteMode, Client, ConnectionSettings, Credential, Cursor, FixtureClient,
+    IssueFilter, IssueId, IssueRef, IssueSummary, IssueUpdate, LinearApi, StateType, TeamId,
+};
+use serde_json::json;
+
+const KEY: &str = "lin_api_test";
+
+fn fixture() -> FixtureClient {
+    FixtureClient::from_path("fixtures/dans-donuts.json").expect("the sample fixture")
+}
+
+async fn serve() -> (MockServer, Client) {
+    let server = MockServer::start(fixture()).await.expect("a local server");
+    let client = Client::configured(Credential::PersonalKey(KEY.into()), &server.settings())
+        .expect("a client for the mock endpoint");
+
+    (server, client)
+}
+
+async fn every_page(client: &Client, filter: &IssueFilter) -> Vec<IssueSummary> {
+    let mut issues = Vec::new();
+    let mut after: Option<Cursor> = None;
+
+    loop {
+        let page = client
+            .issues(filter, after.as_ref())
+            .await
+            .expect("a page of issues");
+        issues.extend(page.items);
+
+        match page.next {
+            Some(cursor) => after = Some(cursor),
+            None => return issues,
+        }
+    }
+}
+
+fn identifiers(issues: &[IssueSummary]) -> Vec<&str> {
+    issues
+        .iter()
+        .map(|issue| issue.identifier.as_str())
+        .collect()
+}
+
+#[tokio::test]
+async fn the_session_round_trips_through_the_viewer_query() {
+    let (server, client) = serve().await;
+
+    let session = client.session().await.expect("a session");
+
+    assert_eq!(session.org_url_key, "dans-donuts");
+    assert!(session.user.is_me);
+
+    let requests = server.requests();
+    assert_eq!(requests[0].operation, "ViewerQuery");
+    assert_eq!(requests[0].authorization.as_deref(), Some(KEY));
+}
+
+#[tokio::test]
+async fn issues_follow_cursors_across_pages() {
+    let (server, client) = serve().await;
+    server.set_page_size(5);
+
+    let issues = every_page(&client, &IssueFilter::default()).await;
+    let expected = fixture()
+        .issues(&IssueFilter::default(), None)
+        .await
+        .expect("fixture issues")
+        .items;
+
+    assert_eq!(identifiers(&issues), identifiers(&expected));
+    assert_eq!(issues[0].updated_at, expected[0].updated_at);
+    assert_eq!(issues[0].labels, expected[0].labels);
+
+    let cursors: Vec<_> = server
+        .requests()
+        .into_iter()
+        .map(|request| request.variables["after"].clone())
+        .collect();
+    assert_eq!(cursors, vec![json!(null), json!("5"), json!("10")]);
+}
+
+#[tokio::test]
+async fn the_issue_filter_reaches_the_wire_as_linear_expects() {
+    let (server, client) = serve().await;
+    let filter = IssueFilter {
+        assigned_to_me: true,
+        state_types_not_in: vec![StateType::Completed, StateType::Cancelled],
+        team: Some(TeamId::from_raw("t_donut")),
+        ..IssueFilter::default()
+    };
+
+    let issues = every_page(&client, &filter).await;
+    let expected = fixture()
+        .issues(&filter, None)
+        .await
+        .expect("fixture issues")
+        .items;
+
+    assert_eq!(
+        server.requests()[0].variables["filter"],
+        json!({
+            "assignee": { "isMe": { "eq": true } },
+            "state": { "type": { "nin": ["completed", "canceled"] } },
+            "team": { "id": { "eq": "t_donut" } },
+        })
+    );
+    assert_eq!(identifiers(&issues), identifiers(&expected));
+}
+
+#[tokio::test]
+async fn concurrent_lookups_share_one_batched_request() {
+    let (server, client) = serve().await;
+    let targets = ["DAN2-7", "DAN-15", "DAN-404"].map(IssueRef::parse);
+
+    let results =
+        futures::future::join_all(targets.iter().map(|target| client.issue_detail(target))).await;
+
+    let found: Vec<Option<String>> = results
+        .into_iter()
+        .map(|result| result.expect("a lookup").map(|detail| detail.identifier))
+        .collect();
+    assert_eq!(
+        found,
+        vec![Some("DAN2-7".into()), Some("DAN-15".into()), None]
+    );
+
+    let requests = server.requests();
+    assert_eq!(requests.len(), 1);
+    assert_eq!(requests[0].operation, "IssueBatch");
+}
+
+#[tokio::test]
+async fn lookups_by_id_and_by_identifier_batch_together() {
+    let (server, client) = serve().await;
+    let targets = [
+        IssueRef::Id(IssueId::from_raw("7b610dc5-8e7b-4729-be52-993df52d54e0")),
+        IssueRef::parse("DAN-15"),
+    ];
+
+    let results =
+        futures::future::join_all(targets.iter().map(|target| client.issue_detail(target))).await;
+
+    let found: Vec<Option<String>> = results
+        .into_iter()
+        .map(|result| result.expect("a lookup").map(|detail| detail.identifier))
+        .collect();
+    assert_eq!(found, vec![Some("DAN2-7".into()), Some("DAN-15".into())]);
+
+    let requests = server.requests();
+    assert_eq!(requests.len(), 1);
+    assert_eq!(requests[0].operation, "IssueBatch");
+}
+
+#[tokio::test]
+async fn a_lone_lookup_maps_the_whole_detail() {
+    let (_server, client) = serve().await;
+    let target = IssueRef::parse("DAN2-7");
+
+    let detail = client
+        .issue_detail(&target)
+        .await
+        .expect("a lookup")
+        .expect("the issue");
+    let expected = fixture()
+        .issue_detail(&target)
+        .await
+        .expect("a fixture lookup")
+        .expect("the fixture issue");
+
+    assert_eq!(detail.description, expected.description);
+    assert_eq!(detail.comments.len(), expected.comments.len());
+    assert_eq!(detail.comments[0].body, expected.comments[0].body);
+    assert_eq!(
+        detail.comments[0].created_at,
+        expected.comments[0].created_at
+    );
+    assert_eq!(detail.reactions, expected.reactions);
+}
+
+#[tokio::test]
+async fn workflow_states_come_back_in_position_order() {
+    let (_server, client) = serve().await;
+    let team = TeamId::from_raw("t_donut");
+
+    let states = client.workflow_states(&team).await.expect("states");
+    let expected = fixture().workflow_states(&team).await.expect("states");
+
+    assert_eq!(states, expected);
+}
+
+#[tokio::test]
+async fn unassigning_sends_an_explicit_null() {
+    let (server, client) = serve().await;
+
+    client
+        .update_issue(
+            &IssueId::from_raw("7b610dc5-8e7b-4729-be52-993df52d54e0"),
+            IssueUpdate::Assignee(None),
+        )
+        .await
+        .expect("the mutation succeeds");
+
+    let request = &server.requests()[0];
+    assert_eq!(request.operation, "AssigneeMutation");
+    assert_eq!(request.variables["input"], json!({ "assigneeId": null }));
+}
+
+#[tokio::test]
+async fn a_401_is_an_auth_error() {
+    let (server, client) = serve().await;
+    server.fail_next(Fault::unauthorized());
+
+    let error = client.session().await.expect_err("the request is refused");
+
+    assert!(error.is_auth(), "{error}");
+    assert!(!error.is_rate_limited());
+}
+
+#[tokio::test]
+async fn a_wrong_key_is_an_auth_error() {
+    let (server, client) = serve().await;
+    server.require_authorization("lin_api_other");
+
+    let error = client.teams().await.expect_err("the request is refused");
+
+    assert!(matches!(error, ApiError::Status { status: 401, .. }));
+    assert!(error.is_auth());
+}
+
+#[tokio::test]
+async fn an_authentication_graphql_error_is_an_auth_error() {
+    let (server, client) = serve().await;
+    server.fail_next(Fault::GraphQl(
+        "Authentication required, not authenticated".into(),
+    ));
+
+    let error = client.custom_views().await.expect_err("the query fails");
+
+    assert!(matches!(error, ApiError::GraphQl(_)));
+    assert!(error.is_auth());
+}
+
+#[tokio::test]
+async fn rate_limits_are_recognised_by_status_and_by_message() {
+    let (server, client) = serve().await;
+    server.fail_next(Fault::rate_limited());
+    server.fail_next(Fault::GraphQl("Rate limit exceeded".into()));
+
+    for _ in 0..2 {
+        let error = client
+            .notifications(None)
+            .await
+            .expect_err("the query is limited");
+
+        assert!(error.is_rate_limited(), "{error}");
+        assert!(!error.is_auth());
+    }
+
+    assert!(client.notifications(None).await.is_ok());
+}
+
+#[tokio::test]
+async fn a_server_error_is_neither_auth_nor_rate_limited() {
+    let (server, client) = serve().await;
+    server.fail_next(Fault::Status {
+        status: 500,
+        body: "upstream unavailable".into(),
+    });
+
+    let error = client.teams().await.expect_err("the query fails");
+
+    assert!(error.to_string().contains("upstream unavailable"));
+    assert!(!error.is_auth());
+    assert!(!error.is_rate_limited());
+}
+
+#[tokio::test]
+async fn a_recorded_cassette_replays_without_the_server() {
+    let dir = tempfile::tempdir().expect("a temp dir");
+    let path = dir.path().join("bug.cassette.json");
+    let credential = || Credential::PersonalKey(KEY.into());
+    let target = IssueRef::parse("DAN2-7");
+
+    let server = MockServer::start(fixture()).await.expect("a local server");
+    server.fail_next(Fault::rate_limited());
+    let recording = ConnectionSettings {
+        cassette: Some(CassetteMode::Record {
+            path: path.clone(),
+            redact: vec!["term".into(), "description".into()],
+        }),
+        ..server.settings()
+    };
+    let client = Client::configured(credential(), &recording).expect("a recording client");
+
+    let limited = client.teams().await.expect_err("the first call is limited");
+    let teams = client.teams().await.expect("teams");
+    let hits = client
+        .search_issues("oven", None)
+        .await
+        .expect("search hits");
+    let detail = client.issue_detail(&target).await.expect("a lookup");
+    drop(server);
+
+    let cassette = std::fs::read_to_string(&path).expect("the cassette was written");
+    assert!(!cassette.contains(KEY));
+    assert!(!cassette.contains("oven\""));
+    assert!(!cassette.contains("Suspect"));
+
+    let replaying = ConnectionSettings {
+        endpoint: Some("http://127.0.0.1:9/graphql".into()),
+        cassette: Some(CassetteMode::Replay(path)),
+        ..ConnectionSettings::default()
+    };
+    let replay = Client::configured(credential(), &replaying).expect("a replaying client");
+
+    assert!(replay
+        .teams()
+        .await
+        .expect_err("still limited")
+        .is_rate_limited());
+    assert!(limited.is_rate_limited());
+    assert_eq!(replay.teams().await.expect("teams"), teams);
+    assert_eq!(
+        identifiers(
+            &replay
+                .search_issues("pastry", None)
+                .await
+                .expect("hits")
+                .items
+        ),
+        identifiers(&hits.items)
+    );
+
+    let replayed = replay.issue_detail(&target).await.expect("a lookup");
+    assert_eq!(
+        replayed.as_ref().map(|detail| &detail.comments),
+        detail.as_ref().map(|detail| &detail.comments)
+    );
+    assert_eq!(
+        replayed.and_then(|detail| detail.description).as_deref(),
+        Some("[redacted]")
+    );
+
+    assert!(matches!(
+        replay.session().await,
+        Err(ApiError::Cassette(message)) if message.contains("ViewerQuery")
+    ));
+}
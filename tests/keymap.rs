@@ -157,6 +157,18 @@ fn react_is_bound_in_reading_and_comments() {
     );
 }
 
+#[test]
+fn links_are_bound_in_reading_and_comments() {
+    assert_eq!(
+        DETAIL_KEYS.resolve(key(KeyCode::Char('f'))),
+        Some(Action::Links)
+    );
+    assert_eq!(
+        COMMENTS_KEYS.resolve(key(KeyCode::Char('f'))),
+        Some(Action::Links)
+    );
+}
+
 #[test]
 fn reactions_overlay_keymap() {
     assert_eq!(
@@ -181,7 +193,7 @@ fn reactions_overlay_keymap() {
     );
     assert_eq!(
         ReactionInput::from_key(key(KeyCode::Char('c'))),
-        Some(ReactionInput::Custom)
+        Some(ReactionInput::More)
     );
     assert_eq!(
         ReactionInput::from_key(key(KeyCode::Esc)),
@@ -208,6 +220,10 @@ fn edit_group_resolves_field_edits() {
         EDIT_GROUP.resolve(key(KeyCode::Char('l'))),
         Some(Action::SetLabels)
     );
+    assert_eq!(
+        EDIT_GROUP.resolve(key(KeyCode::Char('d'))),
+        Some(Action::EditDescription)
+    );
     assert_eq!(Action::from_key(key(KeyCode::Char('s'))), None);
 }
 
@@ -1,5 +1,5 @@
 use linear_tui::api::fixture::FixtureClient;
-use linear_tui::api::{Credential, LinearApi, Timestamp};
+use linear_tui::api::{ConnectionSettings, Credential, LinearApi, Timestamp};
 use linear_tui::store::Account;
 use linear_tui::tui::app::App;
 use linear_tui::tui::cache::Remote;
@@ -16,6 +16,7 @@ async fn monochrome_app(client: &FixtureClient) -> App {
         workspace_key: "ws".into(),
         org_name: "Test".into(),
         credential: Credential::PersonalKey("k".into()),
+        connection: ConnectionSettings::default(),
     });
     assert!(app.session.activate("ws"));
 
@@ -59,3 +60,23 @@ async fn styled_monochrome_assigned_view() {
         insta::assert_snapshot!("render__styled_monochrome_assigned_view", frame);
     });
 }
+
+#[test]
+fn monochrome_code_highlighting_keeps_only_modifiers() {
+    theme::init(ColourMode::Monochrome);
+
+    let lines = linear_tui::tui::markdown::render(
+        "```rust\n// why\npub fn hot() -> u32 { 240 }\n```",
+        theme::TEXT,
+    );
+    let spans: Vec<_> = lines.iter().flat_map(|line| &line.spans).collect();
+
+    assert!(spans
+        .iter()
+        .all(|span| matches!(span.style.fg, None | Some(ratatui::style::Color::Reset))));
+    assert!(spans.iter().any(|span| span.content == "pub"
+        && span
+            .style
+            .add_modifier
+            .contains(ratatui::style::Modifier::BOLD)));
+}
@@ -1,14 +1,19 @@
 use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
 use linear_tui::api::fixture::FixtureClient;
-use linear_tui::api::{Credential, IssueRef, LinearApi, TeamId, Timestamp, ViewId};
-use linear_tui::api::{Label, LabelId, Rgb};
+use linear_tui::api::{
+    ConnectionSettings, Credential, IssueRef, LinearApi, TeamId, Timestamp, ViewId,
+};
+use linear_tui::api::{IssueId, Label, LabelId, Rgb};
 use linear_tui::store::Account;
 use linear_tui::tui::app::App;
 use linear_tui::tui::cache::Remote;
+use linear_tui::tui::drafts::Draft;
 use linear_tui::tui::feed::{Feed, FeedKey, FeedRequest};
 use linear_tui::tui::focus::{DetailFocus, LeftPanel, Origin};
 use linear_tui::tui::message::{FailureTarget, Message, RequestError};
-use linear_tui::tui::update::{apply, handle_key};
+use linear_tui::tui::overlay::Compose;
+use linear_tui::tui::templates::Template;
+use linear_tui::tui::update::{apply, handle_key, handle_paste};
 use linear_tui::tui::view::ViewKind;
 use linear_tui::tui::{render_styled_to_string, render_to_string};
 
@@ -22,6 +27,7 @@ fn sign_in(app: &mut App) {
         workspace_key: "ws".into(),
         org_name: "Test".into(),
         credential: Credential::PersonalKey("k".into()),
+        connection: ConnectionSettings::default(),
     });
     assert!(app.session.activate("ws"));
 }
@@ -86,6 +92,39 @@ async fn reactions_overlay_shows_current_and_add_sections() {
     insta::assert_snapshot!(render_to_string(&mut app, 90, 22));
 }
 
+#[tokio::test]
+async fn emoji_picker_lists_matching_shortcodes() {
+    let client = FixtureClient::sample();
+    let mut app = opened_detail_app(&client).await;
+    for c in "+crock".chars() {
+        handle_key(
+            &mut app,
+            KeyEvent::new(KeyCode::Char(c), KeyModifiers::NONE),
+        );
+    }
+    insta::assert_snapshot!(render_to_string(&mut app, 90, 22));
+}
+
+#[tokio::test]
+async fn link_hints_label_every_link_in_the_issue() {
+    let client = FixtureClient::sample();
+    let mut app = opened_detail_app(&client).await;
+    let mut detail = app.workspace.detail().value().cloned().unwrap();
+    if let Some(description) = detail.description.as_mut() {
+        description.push_str(
+            "\n\n![probe](https://cdn.example.com/probe.png) \
+             https://linear.app/dans-donuts/issue/DAN-9/fryer",
+        );
+    }
+    app.workspace.set_detail(detail, app.now);
+
+    handle_key(
+        &mut app,
+        KeyEvent::new(KeyCode::Char('f'), KeyModifiers::NONE),
+    );
+    insta::assert_snapshot!(render_to_string(&mut app, 100, 22));
+}
+
 async fn saved_views_app(client: &FixtureClient) -> App {
     let mut app = App::new();
     sign_in(&mut app);
@@ -124,6 +163,21 @@ async fn a_refreshing_session_still_shows_an_error_in_the_footer() {
     assert!(!output.contains("Refreshing your session"));
 }
 
+#[tokio::test]
+async fn a_fixture_workspace_carries_a_badge_in_the_footer() {
+    let client = FixtureClient::sample();
+    let mut app = home_app(&client, 0).await;
+    app.ui.fixture = true;
+
+    let output = render_to_string(&mut app, 110, 16);
+    let footer = output.lines().last().unwrap_or_default();
+
+    assert!(
+        footer.contains(" fixture  Dan's Donuts"),
+        "the footer must flag a fixture workspace:\n{output}"
+    );
+}
+
 #[tokio::test]
 async fn a_first_run_with_no_account_is_not_shown_as_connected() {
     let mut app = App::new();
@@ -510,6 +564,29 @@ async fn comment_editor_overlay() {
     insta::assert_snapshot!(render_to_string(&mut app, 90, 22));
 }
 
+#[tokio::test]
+async fn comment_editor_with_markdown_preview() {
+    let client = FixtureClient::sample();
+    let mut app = opened_detail_app(&client).await;
+
+    handle_key(
+        &mut app,
+        KeyEvent::new(KeyCode::Char('c'), KeyModifiers::NONE),
+    );
+    handle_key(
+        &mut app,
+        KeyEvent::new(KeyCode::Char('p'), KeyModifiers::CONTROL),
+    );
+    handle_paste(
+        &mut app,
+        "**Damper** check, cc https://linear.app/dans-donuts/profiles/dan\n\n\
+         - [x] spring tension\n- [ ] `reseat` the hinge\n\n\
+         | part | qty |\n|---|---|\n| spring | 2 |",
+    );
+
+    insta::assert_snapshot!(render_to_string(&mut app, 120, 26));
+}
+
 #[tokio::test]
 async fn mention_autocomplete_popup() {
     let client = FixtureClient::sample();
@@ -538,6 +615,59 @@ async fn mention_autocomplete_popup() {
     insta::assert_snapshot!(render_to_string(&mut app, 90, 24));
 }
 
+#[tokio::test]
+async fn issue_reference_popup() {
+    let client = FixtureClient::sample();
+    let mut app = opened_detail_app(&client).await;
+
+    for c in "c#oven".chars() {
+        handle_key(
+            &mut app,
+            KeyEvent::new(KeyCode::Char(c), KeyModifiers::NONE),
+        );
+    }
+    let page = client.search_issues("oven", None).await.unwrap();
+    apply(
+        &mut app,
+        Message::IssuesFound {
+            query: "oven".into(),
+            issues: page.items,
+        },
+    );
+
+    insta::assert_snapshot!(render_to_string(&mut app, 90, 24));
+}
+
+#[tokio::test]
+async fn template_popup() {
+    let client = FixtureClient::sample();
+    let mut app = opened_detail_app(&client).await;
+    apply(
+        &mut app,
+        Message::TemplatesLoaded(vec![
+            Template {
+                name: "needs repro".into(),
+                body: "Could you add steps to reproduce {{identifier}}?".into(),
+            },
+            Template {
+                name: "released".into(),
+                body: "\nShipped in {{date}}.".into(),
+            },
+        ]),
+    );
+
+    handle_key(
+        &mut app,
+        KeyEvent::new(KeyCode::Char('c'), KeyModifiers::NONE),
+    );
+    handle_key(
+        &mut app,
+        KeyEvent::new(KeyCode::Char('t'), KeyModifiers::CONTROL),
+    );
+
+    insta::assert_snapshot!(render_to_string(&mut app, 90, 24));
+}
+
 #[tokio::test]
 async fn go_prefix_overlay() {
     let mut app = App::new();
@@ -549,6 +679,50 @@ async fn go_prefix_overlay() {
     insta::assert_snapshot!(render_to_string(&mut app, 84, 16));
 }
 
+#[tokio::test]
+async fn drafts_overlay() {
+    let mut app = App::new();
+    sign_in(&mut app);
+    app.now = Timestamp::from("2026-07-16T21:00:00Z");
+    let now = app.now;
+    let draft = |issue: &str, identifier: &str, compose, body: &str, minutes: i64| Draft {
+        issue_id: IssueId::from_raw(issue),
+        identifier: identifier.into(),
+        team_id: TeamId::from_raw("t_pizza"),
+        compose,
+        body: body.into(),
+        seen: None,
+        saved_at: Timestamp::from_epoch(now.epoch() - minutes * 60),
+    };
+    apply(
+        &mut app,
+        Message::DraftsLoaded(vec![
+            draft(
+                "i1",
+                "DAN2-7",
+                Compose::Comment,
+                "Dough needs\nanother hour",
+                3,
+            ),
+            draft(
+                "i2",
+                "DAN2-12",
+                Compose::Description,
+                "\nRewrite the glaze recipe so it sets before the morning rush",
+                90,
+            ),
+        ]),
+    );
+
+    for c in ['g', 'd'] {
+        handle_key(
+            &mut app,
+            KeyEvent::new(KeyCode::Char(c), KeyModifiers::NONE),
+        );
+    }
+    insta::assert_snapshot!(render_to_string(&mut app, 84, 16));
+}
+
 #[tokio::test]
 async fn jump_input_overlay() {
     let mut app = App::new();
@@ -1,5 +1,5 @@
 use linear_tui::api::fixture::FixtureClient;
-use linear_tui::api::{Credential, LinearApi, Timestamp};
+use linear_tui::api::{ConnectionSettings, Credential, LinearApi, Timestamp};
 use linear_tui::store::Account;
 use linear_tui::tui::app::App;
 use linear_tui::tui::cache::Remote;
@@ -26,6 +26,7 @@ async fn themed_app(client: &FixtureClient) -> App {
         workspace_key: "ws".into(),
         org_name: "Test".into(),
         credential: Credential::PersonalKey("k".into()),
+        connection: ConnectionSettings::default(),
     });
     assert!(app.session.activate("ws"));
 
@@ -4,7 +4,9 @@ use linear_tui::api::{
     CommentId, Cursor, IssueId, IssueRef, IssueSummary, Label, LabelId, Page, Reaction, ReactionId,
     ReactionTarget, Rgb, StateId, Team, TeamId, Timestamp, UserId, ViewId,
 };
-use linear_tui::api::{Credential, IssueUpdate, LinearApi, OAuthToken, Priority};
+use linear_tui::api::{
+    ConnectionSettings, Credential, IssueUpdate, LinearApi, OAuthToken, Priority,
+};
 use linear_tui::store::Account;
 use linear_tui::tui::app::{App, AuthState, RECENT_CAP};
 use linear_tui::tui::cache::{CacheStatus, Remote};
@@ -12,13 +14,21 @@ use linear_tui::tui::event::Redraw;
 use linear_tui::tui::feed::{Feed, FeedKey, FeedRequest};
 use linear_tui::tui::focus::{DetailFocus, DetailView, Focus, LeftPanel, Origin, Reveal, Scroll};
 use linear_tui::tui::message::{
-    ApiCommand, Commands, Effect, Effects, FailureTarget, Message, PlatformCommand, RequestError,
-    RuntimeCommand, StoreCommand,
+    ApiCommand, Commands, ComposeRecovery, Effect, Effects, FailureTarget, Foreground, Message,
+    PlatformCommand, RequestError, RuntimeCommand, StoreCommand,
 };
-use linear_tui::tui::overlay::{Compose, InputPurpose, Overlay, PickerKind};
+use linear_tui::tui::overlay::{
+    Cell, Completion, Compose, EditorKeys, InputPurpose, Overlay, PickerKind, ViMode,
+    ISSUE_SEARCH_IDLE_TICKS,
+};
+use linear_tui::tui::prefetch::{PREFETCH_IDLE_TICKS, PREFETCH_REFRESH};
 use linear_tui::tui::render_to_string;
 use linear_tui::tui::status::Status;
-use linear_tui::tui::update::{apply as apply_all, handle_key as handle_key_all, tick};
+use linear_tui::tui::templates::Template;
+use linear_tui::tui::update::{
+    apply as apply_all, autosave, handle_key as handle_key_all, handle_paste, prefetch,
+    search_completions, tick,
+};
 use linear_tui::tui::view::ViewKind;
 
 fn press(code: KeyCode) -> KeyEvent {
@@ -71,6 +81,7 @@ fn signed_in() -> App {
         workspace_key: "ws".into(),
         org_name: "Test".into(),
         credential: Credential::PersonalKey("k".into()),
+        connection: ConnectionSettings::default(),
     });
     assert!(app.session.activate("ws"));
 
@@ -115,6 +126,40 @@ fn adding_a_key_requests_account_validation() {
     }
 }
 
+#[test]
+fn ctrl_z_suspends_without_disturbing_an_open_overlay() {
+    let mut app = App::new();
+    handle_key(&mut app, press(KeyCode::Char('w')));
+
+    let command = handle_key_all(&mut app, ctrl('z'));
+
+    assert!(matches!(
+        command,
+        Commands::Runtime(RuntimeCommand::Suspend)
+    ));
+    assert!(matches!(app.overlay(), Overlay::Workspaces(_)));
+}
+
+#[test]
+fn bang_runs_a_shell_command_in_the_foreground() {
+    let mut app = App::new();
+
+    handle_key(&mut app, press(KeyCode::Char('!')));
+    for c in "git log".chars() {
+        handle_key(&mut app, press(KeyCode::Char(c)));
+    }
+    let command = handle_key_all(&mut app, press(KeyCode::Enter));
+
+    match command {
+        Commands::Runtime(RuntimeCommand::Foreground(command)) => {
+            assert_eq!(command, Foreground::shell("git log"));
+            assert_eq!(command.describe(), "git log");
+        }
+        other => panic!("expected Foreground, got {other:?}"),
+    }
+    assert!(matches!(app.overlay(), Overlay::None));
+}
+
 #[test]
 fn account_added_is_stored_and_switched_to() {
     let mut app = App::new();
@@ -122,6 +167,7 @@ fn account_added_is_stored_and_switched_to() {
         workspace_key: "acme".into(),
         org_name: "Acme".into(),
         credential: Credential::PersonalKey("k".into()),
+        connection: ConnectionSettings::default(),
     };
 
     let command = apply_all(
@@ -144,6 +190,42 @@ fn account_added_is_stored_and_switched_to() {
     }
 }
 
+#[test]
+fn re_adding_an_account_keeps_its_connection_settings() {
+    let mut app = App::new();
+    let connection = ConnectionSettings {
+        proxy: Some("http://proxy.corp:3128".into()),
+        ..ConnectionSettings::default()
+    };
+    app.session.upsert_account(Account {
+        workspace_key: "acme".into(),
+        org_name: "Acme".into(),
+        credential: Credential::PersonalKey("old".into()),
+        connection: connection.clone(),
+    });
+
+    let command = apply_all(
+        &mut app,
+        Message::AccountAdded {
+            account: Box::new(Account {
+                workspace_key: "acme".into(),
+                org_name: "Acme".into(),
+                credential: Credential::PersonalKey("new".into()),
+                connection: ConnectionSettings::default(),
+            }),
+        },
+    );
+
+    match command {
+        Commands::Runtime(RuntimeCommand::SwitchWorkspace(account)) => {
+            assert_eq!(account.credential, Credential::PersonalKey("new".into()));
+            assert_eq!(account.connection, connection);
+        }
+        other => panic!("expected SwitchWorkspace, got {other:?}"),
+    }
+    assert_eq!(app.session.accounts().len(), 1);
+}
+
 #[test]
 fn tick_is_idle_when_nothing_loads_or_expires() {
     let mut app = App::new();
@@ -180,6 +262,183 @@ fn tick_redraws_when_a_timestamp_comes_due() {
     assert_eq!(tick(&mut app, Timestamp::from_epoch(60)), Redraw::Needed);
 }
 
+fn signed_in_list_app() -> App {
+    let mut app = signed_in();
+    app.focus_my_work();
+    seed_active(
+        &mut app,
+        vec![
+            sample_issue("i1", "DAN-1"),
+            sample_issue("i2", "DAN-2"),
+            sample_issue("i3", "DAN-3"),
+        ],
+    );
+    app.ui.list_state.select(Some(0));
+    app
+}
+
+fn prefetched_ids(effects: Effects) -> Vec<String> {
+    effects
+        .into_iter()
+        .map(|effect| match effect {
+            Effect::Api(ApiCommand::PrefetchDetail { id }) => id.as_str().to_string(),
+            other => panic!("expected only prefetches, got {other:?}"),
+        })
+        .collect()
+}
+
+fn rest(app: &mut App) -> Vec<String> {
+    let mut ids = Vec::new();
+
+    for _ in 0..=PREFETCH_IDLE_TICKS {
+        ids.extend(prefetched_ids(prefetch(app)));
+    }
+
+    ids
+}
+
+#[test]
+fn resting_on_a_row_prefetches_it_and_then_its_neighbours() {
+    let mut app = signed_in_list_app();
+
+    assert_eq!(rest(&mut app), vec!["i1", "i2"]);
+
+    apply(
+        &mut app,
+        Message::DetailPrefetched(Box::new(sample_detail("i1", "DAN-1"))),
+    );
+
+    assert_eq!(prefetched_ids(prefetch(&mut app)), vec!["i3"]);
+}
+
+#[test]
+fn moving_the_cursor_defers_prefetching() {
+    let mut app = signed_in_list_app();
+
+    for _ in 0..PREFETCH_IDLE_TICKS {
+        assert!(prefetch(&mut app).is_empty());
+        handle_key(&mut app, press(KeyCode::Char('j')));
+    }
+
+    assert!(prefetch(&mut app).is_empty());
+}
+
+#[test]
+fn a_signed_out_session_does_not_prefetch() {
+    let mut app = list_app_with_issues();
+
+    assert!(rest(&mut app).is_empty());
+}
+
+#[test]
+fn a_prefetched_detail_previews_the_selected_row() {
+    let mut app = signed_in_list_app();
+
+    apply(
+        &mut app,
+        Message::DetailPrefetched(Box::new(sample_detail("i1", "DAN-1"))),
+    );
+
+    assert!(app
+        .workspace
+        .ready_detail(&IssueId::from_raw("i1"))
+        .is_some());
+    assert!(app.workspace.detail().value().is_none());
+}
+
+#[test]
+fn opening_a_freshly_prefetched_issue_skips_the_load() {
+    let mut app = signed_in_list_app();
+    apply(
+        &mut app,
+        Message::DetailPrefetched(Box::new(sample_detail("i1", "DAN-1"))),
+    );
+
+    let command = handle_key(&mut app, press(KeyCode::Enter));
+
+    assert!(matches!(
+        command,
+        Some(Effect::Store(StoreCommand::SaveRecent(_)))
+    ));
+    assert!(app.open_detail().is_some());
+    assert!(!app.workspace.detail().in_flight());
+    assert_eq!(app.workspace.recently_viewed[0].id.as_str(), "i1");
+}
+
+#[test]
+fn opening_a_stale_prefetched_issue_shows_it_while_revalidating() {
+    let mut app = signed_in_list_app();
+    apply(
+        &mut app,
+        Message::DetailPrefetched(Box::new(sample_detail("i1", "DAN-1"))),
+    );
+    app.now = Timestamp::from_epoch(app.now.epoch() + PREFETCH_REFRESH.fresh_for);
+
+    let command = handle_key(&mut app, press(KeyCode::Enter));
+
+    assert!(matches!(
+        command,
+        Some(Effect::Api(ApiCommand::LoadDetail { .. }))
+    ));
+    assert!(app.open_detail().is_some());
+    assert_eq!(app.workspace.detail().status(), CacheStatus::Revalidating);
+}
+
+#[test]
+fn a_failed_prefetch_is_silent() {
+    let mut app = signed_in_list_app();
+    rest(&mut app);
+
+    let command = apply(
+        &mut app,
+        Message::Failed {
+            target: FailureTarget::Prefetch {
+                id: IssueId::from_raw("i1"),
+            },
+            error: RequestError::Other("boom".into()),
+        },
+    );
+
+    assert!(command.is_none());
+    assert!(app.ui.status.is_none());
+}
+
+#[test]
+fn a_rate_limit_anywhere_pauses_prefetching() {
+    let mut app = signed_in_list_app();
+
+    apply(
+        &mut app,
+        Message::Failed {
+            target: FailureTarget::Ephemeral,
+            error: RequestError::RateLimited("Rate limit exceeded".into()),
+        },
+    );
+
+    assert!(rest(&mut app).is_empty());
+}
+
+#[test]
+fn updating_an_issue_forgets_its_prefetched_detail() {
+    let mut app = signed_in_list_app();
+    apply(
+        &mut app,
+        Message::DetailPrefetched(Box::new(sample_detail("i2", "DAN-2"))),
+    );
+
+    apply_all(
+        &mut app,
+        Message::IssueUpdated {
+            id: IssueId::from_raw("i2"),
+        },
+    );
+
+    assert!(app
+        .workspace
+        .ready_detail(&IssueId::from_raw("i2"))
+        .is_none());
+}
+
 #[tokio::test]
 async fn in_progress_filter_returns_only_started() {
     let client = FixtureClient::sample();
@@ -1202,19 +1461,20 @@ fn toggling_reactions_deletes_your_own_and_creates_a_new_one() {
 
     // Reopen: the highlight starts on the first Add item (heart), toggle it on.
     handle_key(&mut app, press(KeyCode::Char('+')));
-    let created = handle_key(&mut app, press(KeyCode::Enter));
-    match created {
+    let created = effects(handle_key_all(&mut app, press(KeyCode::Enter)));
+    match created.iter().next() {
         Some(Effect::Api(ApiCommand::CreateReaction {
             target,
             emoji,
             issue_id,
         })) => {
-            assert_eq!(target, ReactionTarget::Issue(IssueId::from_raw("i1")));
+            assert_eq!(*target, ReactionTarget::Issue(IssueId::from_raw("i1")));
             assert_eq!(emoji, "heart");
             assert_eq!(issue_id.as_str(), "i1");
         }
         other => panic!("expected CreateReaction, got {other:?}"),
     }
+    assert_eq!(app.ui.recent_emoji.list(), ["heart"]);
 }
 
 #[test]
@@ -1247,28 +1507,61 @@ fn a_custom_reaction_you_made_is_removable_from_the_current_section() {
 }
 
 #[test]
-fn custom_reaction_routes_through_the_input_overlay() {
+fn more_reactions_searches_every_emoji_and_remembers_the_pick() {
     let mut app = detail_app();
 
     handle_key(&mut app, press(KeyCode::Char('+')));
     handle_key(&mut app, press(KeyCode::Char('c')));
+    assert!(matches!(app.overlay(), Overlay::Emoji(_)));
 
-    match app.overlay() {
-        Overlay::Input(input) => assert_eq!(
-            input.purpose,
-            InputPurpose::CustomReaction {
-                issue_id: IssueId::from_raw("i1"),
-                target: ReactionTarget::Issue(IssueId::from_raw("i1"))
-            }
-        ),
-        _ => panic!("expected the input overlay"),
-    }
+    type_text(&mut app, "rock");
+    let effects = effects(handle_key_all(&mut app, press(KeyCode::Enter)));
 
-    handle_key(&mut app, press(KeyCode::Char('🚀')));
-    let command = handle_key(&mut app, press(KeyCode::Enter));
-    match command {
-        Some(Effect::Api(ApiCommand::CreateReaction { emoji, .. })) => assert_eq!(emoji, "🚀"),
-        other => panic!("expected CreateReaction, got {other:?}"),
+    assert!(matches!(app.overlay(), Overlay::None));
+    assert!(effects.iter().any(|effect| matches!(
+        effect,
+        Effect::Api(ApiCommand::CreateReaction { emoji, .. }) if emoji == "rocket"
+    )));
+    assert!(effects.iter().any(|effect| matches!(
+        effect,
+        Effect::Store(StoreCommand::SaveRecentEmoji(recent)) if recent == &["rocket"]
+    )));
+}
+
+#[test]
+fn an_unknown_emoji_name_reacts_as_typed() {
+    let mut app = detail_app();
+
+    handle_key(&mut app, press(KeyCode::Char('+')));
+    handle_key(&mut app, press(KeyCode::Char('c')));
+    type_text(&mut app, ":partyparrotzz:");
+    let effects = effects(handle_key_all(&mut app, press(KeyCode::Enter)));
+
+    assert!(effects.iter().any(|effect| matches!(
+        effect,
+        Effect::Api(ApiCommand::CreateReaction { emoji, .. }) if emoji == "partyparrotzz"
+    )));
+}
+
+#[test]
+fn loaded_recent_emoji_lead_the_picker() {
+    let mut app = detail_app();
+    app.ui.recent_emoji.record("tada");
+    apply(
+        &mut app,
+        Message::RecentEmojiLoaded(vec!["taco".into(), "tada".into()]),
+    );
+
+    assert_eq!(app.ui.recent_emoji.list(), ["tada", "taco"]);
+
+    handle_key(&mut app, press(KeyCode::Char('+')));
+    handle_key(&mut app, press(KeyCode::Char('c')));
+    match app.overlay() {
+        Overlay::Emoji(picker) => {
+            let names: Vec<&str> = picker.results.iter().take(2).map(|e| e.name).collect();
+            assert_eq!(names, ["tada", "taco"]);
+        }
+        _ => panic!("expected the emoji picker"),
     }
 }
 
@@ -1692,6 +1985,7 @@ fn switching_the_workspace_clears_the_ui_pointing_at_the_old_one() {
         workspace_key: "acme".into(),
         org_name: "Acme".into(),
         credential: Credential::PersonalKey("k".into()),
+        connection: ConnectionSettings::default(),
     });
     assert!(app.session.activate("acme"));
     app.reset_workspace();
@@ -1946,6 +2240,7 @@ fn comment_edited_refetches_the_thread_from_the_top() {
         &mut app,
         Message::CommentEdited {
             id: IssueId::from_raw("i1"),
+            draft: sent("i1", Compose::Description, "edited"),
         },
     );
 
@@ -1960,66 +2255,281 @@ fn comment_edited_refetches_the_thread_from_the_top() {
 }
 
 #[test]
-fn d_confirms_before_deleting_my_comment() {
-    let mut app = detail_app_with_comments();
-    handle_key(&mut app, press(KeyCode::Char('m')));
-
-    let no_command = handle_key(&mut app, press(KeyCode::Char('d')));
-    assert!(no_command.is_none());
+fn ed_opens_the_description_editor_stamped_with_the_issue_version() {
+    let mut app = detail_app();
 
-    let confirm = app.confirm().expect("delete confirm open");
-    match &confirm.command {
-        Effect::Api(ApiCommand::DeleteComment {
-            issue_id,
-            comment_id,
-        }) => {
-            assert_eq!(issue_id.as_str(), "i1");
-            assert_eq!(comment_id.as_str(), "c1");
-        }
-        other => panic!("expected DeleteComment, got {other:?}"),
-    }
+    handle_key(&mut app, press(KeyCode::Char('e')));
+    handle_key(&mut app, press(KeyCode::Char('d')));
 
-    let command = handle_key(&mut app, press(KeyCode::Char('y')));
-    assert!(app.confirm().is_none());
-    match command {
-        Some(Effect::Api(ApiCommand::DeleteComment {
-            issue_id,
-            comment_id,
-        })) if issue_id.as_str() == "i1" && comment_id.as_str() == "c1" => {}
-        other => panic!("expected DeleteComment on confirm, got {other:?}"),
-    }
+    let editor = app.editor().expect("description editor open");
+    assert!(matches!(editor.compose, Compose::Description));
+    assert_eq!(editor.text(), "Body");
+    assert_eq!(editor.seen, Some(Timestamp::default()));
 }
 
 #[test]
-fn d_refuses_to_delete_someone_elses_comment() {
-    let mut app = detail_app_with_comments();
-    let mut detail = app.workspace.detail().value().cloned().expect("detail");
-    detail.comments[0].is_mine = false;
-    app.workspace.set_detail(detail, app.now);
-    handle_key(&mut app, press(KeyCode::Char('m')));
+fn submitting_a_description_carries_the_seen_stamp() {
+    let mut app = detail_app();
+    handle_key(&mut app, press(KeyCode::Char('e')));
+    handle_key(&mut app, press(KeyCode::Char('d')));
+    handle_key(&mut app, press(KeyCode::Char('!')));
 
-    let command = handle_key(&mut app, press(KeyCode::Char('d')));
+    let command = handle_key(&mut app, ctrl('s'));
 
-    assert!(app.confirm().is_none());
-    assert_eq!(app.ui.status, Some(Status::NotYourComment));
-    assert!(command.is_none());
+    match command {
+        Some(Effect::Api(ApiCommand::UpdateDescription { body, seen, .. })) => {
+            assert_eq!(body, "Body!");
+            assert_eq!(seen, Some(Timestamp::default()));
+        }
+        other => panic!("expected UpdateDescription, got {other:?}"),
+    }
+    assert_eq!(app.ui.status, Some(Status::SavingDescription));
 }
 
 #[test]
-fn ctrl_d_still_pages_in_comments_mode() {
+fn editing_a_comment_is_stamped_with_the_issue_version() {
     let mut app = detail_app_with_comments();
     handle_key(&mut app, press(KeyCode::Char('m')));
+    handle_key(&mut app, press(KeyCode::Char('e')));
 
-    let command = handle_key(&mut app, ctrl('d'));
+    let command = handle_key(&mut app, ctrl('s'));
 
-    assert!(app.confirm().is_none());
-    assert!(command.is_none());
-    assert_ne!(app.comment_cursor(), Some(0));
+    assert!(matches!(
+        command,
+        Some(Effect::Api(ApiCommand::UpdateComment { seen: Some(_), .. }))
+    ));
 }
 
 #[test]
-fn comment_deleted_stays_in_comments_and_refetches() {
-    let mut app = detail_app_with_comments();
+fn description_edited_reloads_the_detail() {
+    let mut app = detail_app();
+
+    let command = apply(
+        &mut app,
+        Message::DescriptionEdited {
+            id: IssueId::from_raw("i1"),
+            draft: sent("i1", Compose::Description, "edited"),
+        },
+    );
+
+    assert_eq!(app.ui.status, Some(Status::DescriptionUpdated));
+    assert!(matches!(
+        command,
+        Some(Effect::Api(ApiCommand::LoadDetail { target, .. })) if target.as_str() == "i1"
+    ));
+}
+
+fn conflicted_app() -> App {
+    let mut app = detail_app();
+    let mut theirs = sample_detail("i1", "DAN2-7");
+    theirs.description = Some("Theirs".into());
+    theirs.updated_at = Timestamp::from_epoch(5);
+
+    apply_all(
+        &mut app,
+        Message::EditConflict {
+            detail: Box::new(theirs),
+            draft: Box::new(ComposeRecovery {
+                issue_id: IssueId::from_raw("i1"),
+                team_id: TeamId::from_raw("t_pizza"),
+                compose: Compose::Description,
+                body: "Mine".into(),
+                seen: Some(Timestamp::default()),
+            }),
+        },
+    );
+
+    app
+}
+
+#[test]
+fn the_conflict_overlay_lists_the_keys_it_is_bound_to() {
+    let mut app = conflicted_app();
+
+    let screen = render_to_string(&mut app, 120, 30);
+
+    for hint in ["o overwrite", "r re-edit theirs", "esc discard"] {
+        assert!(screen.contains(hint), "{hint} missing:\n{screen}");
+    }
+}
+
+#[test]
+fn an_edit_conflict_shows_both_versions_and_adopts_theirs() {
+    let app = conflicted_app();
+
+    match app.overlay() {
+        Overlay::Conflict(conflict) => {
+            assert_eq!(conflict.theirs, "Theirs");
+            assert_eq!(conflict.draft.body, "Mine");
+        }
+        _ => panic!("expected the conflict overlay"),
+    }
+    assert_eq!(app.ui.status, Some(Status::EditConflict));
+    assert_eq!(
+        app.open_detail()
+            .and_then(|detail| detail.description.clone()),
+        Some("Theirs".into())
+    );
+}
+
+#[test]
+fn overwriting_a_conflict_resends_without_the_check() {
+    let mut app = conflicted_app();
+
+    let command = handle_key(&mut app, press(KeyCode::Char('o')));
+
+    match command {
+        Some(Effect::Api(ApiCommand::UpdateDescription { body, seen, .. })) => {
+            assert_eq!(body, "Mine");
+            assert_eq!(seen, None);
+        }
+        other => panic!("expected an unchecked UpdateDescription, got {other:?}"),
+    }
+    assert!(matches!(app.overlay(), Overlay::None));
+}
+
+#[test]
+fn re_editing_a_conflict_starts_from_theirs_and_keeps_mine_below() {
+    let mut app = conflicted_app();
+
+    handle_key(&mut app, press(KeyCode::Char('r')));
+
+    let editor = app.editor().expect("editor reopened");
+    assert!(matches!(editor.compose, Compose::Description));
+    assert_eq!(editor.text(), "Theirs\n\nMine");
+    assert_eq!(editor.seen, Some(Timestamp::from_epoch(5)));
+}
+
+#[test]
+fn a_conflict_behind_another_overlay_keeps_the_text_as_a_draft() {
+    let mut app = detail_app();
+    handle_key(&mut app, press(KeyCode::Char('?')));
+    assert!(!matches!(app.overlay(), Overlay::None));
+
+    let mut theirs = sample_detail("i1", "DAN2-7");
+    theirs.updated_at = Timestamp::from_epoch(5);
+    apply_all(
+        &mut app,
+        Message::EditConflict {
+            detail: Box::new(theirs),
+            draft: Box::new(ComposeRecovery {
+                issue_id: IssueId::from_raw("i1"),
+                team_id: TeamId::from_raw("t_pizza"),
+                compose: Compose::Description,
+                body: "Mine".into(),
+                seen: Some(Timestamp::default()),
+            }),
+        },
+    );
+
+    assert!(!matches!(app.overlay(), Overlay::Conflict(_)));
+    assert_eq!(app.ui.status, Some(Status::EditConflictKept));
+    let drafts = app.workspace.drafts.list();
+    assert_eq!(drafts.len(), 1);
+    assert_eq!(drafts[0].body, "Mine");
+}
+
+#[test]
+fn discarding_a_conflict_drops_the_draft() {
+    let mut app = conflicted_app();
+
+    let command = handle_key(&mut app, press(KeyCode::Esc));
+
+    assert!(command.is_none());
+    assert!(matches!(app.overlay(), Overlay::None));
+    assert_eq!(app.ui.status, Some(Status::EditDiscarded));
+}
+
+#[test]
+fn a_comment_conflict_shows_their_comment_body() {
+    let mut app = detail_app_with_comments();
+    let mut theirs = app.workspace.detail().value().cloned().expect("detail");
+    theirs.comments[0].body = "their rewrite".into();
+    theirs.updated_at = Timestamp::from_epoch(5);
+
+    apply_all(
+        &mut app,
+        Message::EditConflict {
+            detail: Box::new(theirs),
+            draft: Box::new(ComposeRecovery {
+                issue_id: IssueId::from_raw("i1"),
+                team_id: TeamId::from_raw("t_pizza"),
+                compose: Compose::Edit {
+                    comment_id: CommentId::from_raw("c1"),
+                },
+                body: "my rewrite".into(),
+                seen: Some(Timestamp::default()),
+            }),
+        },
+    );
+
+    match app.overlay() {
+        Overlay::Conflict(conflict) => assert_eq!(conflict.theirs, "their rewrite"),
+        _ => panic!("expected the conflict overlay"),
+    }
+}
+
+#[test]
+fn d_confirms_before_deleting_my_comment() {
+    let mut app = detail_app_with_comments();
+    handle_key(&mut app, press(KeyCode::Char('m')));
+
+    let no_command = handle_key(&mut app, press(KeyCode::Char('d')));
+    assert!(no_command.is_none());
+
+    let confirm = app.confirm().expect("delete confirm open");
+    match &confirm.command {
+        Effect::Api(ApiCommand::DeleteComment {
+            issue_id,
+            comment_id,
+        }) => {
+            assert_eq!(issue_id.as_str(), "i1");
+            assert_eq!(comment_id.as_str(), "c1");
+        }
+        other => panic!("expected DeleteComment, got {other:?}"),
+    }
+
+    let command = handle_key(&mut app, press(KeyCode::Char('y')));
+    assert!(app.confirm().is_none());
+    match command {
+        Some(Effect::Api(ApiCommand::DeleteComment {
+            issue_id,
+            comment_id,
+        })) if issue_id.as_str() == "i1" && comment_id.as_str() == "c1" => {}
+        other => panic!("expected DeleteComment on confirm, got {other:?}"),
+    }
+}
+
+#[test]
+fn d_refuses_to_delete_someone_elses_comment() {
+    let mut app = detail_app_with_comments();
+    let mut detail = app.workspace.detail().value().cloned().expect("detail");
+    detail.comments[0].is_mine = false;
+    app.workspace.set_detail(detail, app.now);
+    handle_key(&mut app, press(KeyCode::Char('m')));
+
+    let command = handle_key(&mut app, press(KeyCode::Char('d')));
+
+    assert!(app.confirm().is_none());
+    assert_eq!(app.ui.status, Some(Status::NotYourComment));
+    assert!(command.is_none());
+}
+
+#[test]
+fn ctrl_d_still_pages_in_comments_mode() {
+    let mut app = detail_app_with_comments();
+    handle_key(&mut app, press(KeyCode::Char('m')));
+
+    let command = handle_key(&mut app, ctrl('d'));
+
+    assert!(app.confirm().is_none());
+    assert!(command.is_none());
+    assert_ne!(app.comment_cursor(), Some(0));
+}
+
+#[test]
+fn comment_deleted_stays_in_comments_and_refetches() {
+    let mut app = detail_app_with_comments();
     handle_key(&mut app, press(KeyCode::Char('m')));
 
     let command = apply(
@@ -2029,163 +2539,763 @@ fn comment_deleted_stays_in_comments_and_refetches() {
         },
     );
 
-    assert!(app.workspace.detail().in_flight());
-    assert!(matches!(
-        app.focus(),
-        Focus::Detail(DetailFocus {
-            origin: Origin::Panel(LeftPanel::MyWork),
-            view: DetailView::Comments { .. },
-            ..
-        })
-    ));
+    assert!(app.workspace.detail().in_flight());
+    assert!(matches!(
+        app.focus(),
+        Focus::Detail(DetailFocus {
+            origin: Origin::Panel(LeftPanel::MyWork),
+            view: DetailView::Comments { .. },
+            ..
+        })
+    ));
+    match command {
+        Some(Effect::Api(ApiCommand::LoadDetail {
+            target,
+            reveal: Reveal::Top,
+        })) if target.as_str() == "i1" => {}
+        other => panic!("expected LoadDetail for i1 revealing the top, got {other:?}"),
+    }
+}
+
+#[test]
+fn a_shrunk_thread_clamps_the_comment_selection() {
+    let mut app = detail_app_with_comments();
+    handle_key(&mut app, press(KeyCode::Char('m')));
+    app.reveal_focused(Some(2));
+
+    let mut detail = app.workspace.detail().value().cloned().expect("detail");
+    detail.comments.pop();
+    apply(
+        &mut app,
+        Message::DetailLoaded {
+            detail: Box::new(detail),
+            reveal: Reveal::Top,
+        },
+    );
+
+    assert_eq!(app.comment_cursor(), Some(1));
+    assert!(matches!(
+        app.focus(),
+        Focus::Detail(DetailFocus {
+            origin: Origin::Panel(LeftPanel::MyWork),
+            view: DetailView::Comments { .. },
+            ..
+        })
+    ));
+}
+
+#[test]
+fn deleting_the_last_comment_falls_back_to_reading() {
+    let mut app = detail_app_with_comments();
+    handle_key(&mut app, press(KeyCode::Char('m')));
+
+    let mut detail = app.workspace.detail().value().cloned().expect("detail");
+    detail.comments.clear();
+    apply(
+        &mut app,
+        Message::DetailLoaded {
+            detail: Box::new(detail),
+            reveal: Reveal::Top,
+        },
+    );
+
+    assert!(matches!(
+        app.focus(),
+        Focus::Detail(DetailFocus {
+            origin: Origin::Panel(LeftPanel::MyWork),
+            view: DetailView::Reading { .. },
+            ..
+        })
+    ));
+}
+
+#[test]
+fn comment_action_requires_an_opened_issue() {
+    let mut app = list_app_with_issue();
+
+    let commands = handle_key(&mut app, press(KeyCode::Char('c')));
+
+    assert!(app.editor().is_none());
+    assert!(commands.is_none());
+}
+
+#[test]
+fn c_opens_the_comment_editor_once_issue_is_loaded() {
+    let mut app = detail_app();
+
+    let command = handle_key(&mut app, press(KeyCode::Char('c')));
+
+    assert!(app.editor().is_some());
+    match command {
+        Some(Effect::Api(ApiCommand::LoadMembers { team_id })) if team_id.as_str() == "t_pizza" => {
+        }
+        other => panic!("expected LoadMembers for the mention popup, got {other:?}"),
+    }
+}
+
+#[test]
+fn enter_inserts_a_newline_and_does_not_submit() {
+    let mut app = detail_app();
+    handle_key(&mut app, press(KeyCode::Char('c')));
+
+    handle_key(&mut app, press(KeyCode::Char('a')));
+    let command = handle_key(&mut app, press(KeyCode::Enter));
+    handle_key(&mut app, press(KeyCode::Char('b')));
+
+    assert!(command.is_none());
+    assert_eq!(app.editor().map(|e| e.text()), Some("a\nb".to_string()));
+}
+
+#[test]
+fn ctrl_s_posts_the_multiline_comment_for_the_open_issue() {
+    let mut app = detail_app();
+    handle_key(&mut app, press(KeyCode::Char('c')));
+    handle_key(&mut app, press(KeyCode::Char('a')));
+    handle_key(&mut app, press(KeyCode::Enter));
+    handle_key(&mut app, press(KeyCode::Char('b')));
+
+    let command = handle_key(&mut app, ctrl('s'));
+
+    match command {
+        Some(Effect::Api(ApiCommand::CreateComment {
+            issue_id,
+            body,
+            parent_id,
+            ..
+        })) => {
+            assert_eq!(issue_id.as_str(), "i1");
+            assert_eq!(body, "a\nb");
+            assert_eq!(parent_id, None);
+        }
+        other => panic!("expected CreateComment, got {other:?}"),
+    }
+    assert!(app.editor().is_none());
+}
+
+fn type_text(app: &mut App, text: &str) {
+    for c in text.chars() {
+        handle_key(app, press(KeyCode::Char(c)));
+    }
+}
+
+#[test]
+fn a_cancelled_comment_is_kept_as_a_draft_and_restored() {
+    let mut app = detail_app();
+    handle_key(&mut app, press(KeyCode::Char('c')));
+    type_text(&mut app, "half a thought");
+
+    handle_key(&mut app, press(KeyCode::Esc));
+
+    assert!(app.editor().is_none());
+    assert_eq!(app.ui.status, Some(Status::DraftSaved));
+    assert!(app.workspace.drafts.has(&IssueId::from_raw("i1")));
+
+    handle_key(&mut app, press(KeyCode::Char('c')));
+
+    assert_eq!(
+        app.editor().map(|e| e.text()),
+        Some("half a thought".to_string())
+    );
+    assert_eq!(app.ui.status, Some(Status::DraftRestored));
+}
+
+#[test]
+fn posting_or_emptying_a_comment_discards_its_draft() {
+    let mut app = detail_app();
+    handle_key(&mut app, press(KeyCode::Char('c')));
+    type_text(&mut app, "ok");
+    handle_key(&mut app, press(KeyCode::Backspace));
+    handle_key(&mut app, press(KeyCode::Backspace));
+    handle_key(&mut app, press(KeyCode::Esc));
+
+    assert!(app.workspace.drafts.is_empty());
+    assert_eq!(app.ui.status, Some(Status::Cancelled));
+
+    handle_key(&mut app, press(KeyCode::Char('c')));
+    type_text(&mut app, "sent");
+    assert!(!app.workspace.drafts.is_empty());
+
+    let draft = match handle_key(&mut app, ctrl('s')) {
+        Some(Effect::Api(ApiCommand::CreateComment { body, .. })) => {
+            sent("i1", Compose::Comment, &body)
+        }
+        other => panic!("expected CreateComment, got {other:?}"),
+    };
+    assert!(
+        !app.workspace.drafts.is_empty(),
+        "the draft outlives the request until it succeeds"
+    );
+
+    apply(
+        &mut app,
+        Message::CommentPosted {
+            id: IssueId::from_raw("i1"),
+            draft,
+        },
+    );
+
+    assert!(app.workspace.drafts.is_empty());
+}
+
+#[test]
+fn a_draft_changed_while_posting_survives_the_post() {
+    let mut app = detail_app();
+    handle_key(&mut app, press(KeyCode::Char('c')));
+    type_text(&mut app, "first");
+    handle_key(&mut app, ctrl('s'));
+    handle_key(&mut app, press(KeyCode::Char('c')));
+    type_text(&mut app, " and more");
+    handle_key(&mut app, press(KeyCode::Esc));
+
+    apply(
+        &mut app,
+        Message::CommentPosted {
+            id: IssueId::from_raw("i1"),
+            draft: sent("i1", Compose::Comment, "first"),
+        },
+    );
+
+    let drafts = app.workspace.drafts.list();
+    assert_eq!(drafts.len(), 1);
+    assert_eq!(drafts[0].body, "first and more");
+}
+
+#[test]
+fn drafts_are_written_once_typing_pauses() {
+    let mut app = detail_app();
+    handle_key(&mut app, press(KeyCode::Char('c')));
+    type_text(&mut app, "later");
+
+    app.now = Timestamp::from_epoch(app.now.epoch() + 5);
+    let effects = autosave(&mut app);
+
+    match effects.iter().collect::<Vec<_>>().as_slice() {
+        [Effect::Store(StoreCommand::SaveDrafts(drafts))] => {
+            assert_eq!(drafts[0].body, "later");
+            assert_eq!(drafts[0].identifier, "DAN2-7");
+        }
+        other => panic!("expected SaveDrafts, got {other:?}"),
+    }
+    assert!(autosave(&mut app).is_empty(), "nothing new to write");
+}
+
+#[test]
+fn go_d_lists_drafts_and_enter_resumes_one() {
+    let mut app = detail_app();
+    handle_key(&mut app, press(KeyCode::Char('c')));
+    type_text(&mut app, "resume me");
+    handle_key(&mut app, press(KeyCode::Esc));
+
+    handle_key(&mut app, press(KeyCode::Char('g')));
+    handle_key(&mut app, press(KeyCode::Char('d')));
+
+    match app.overlay() {
+        Overlay::Drafts(list) => assert_eq!(list.drafts.len(), 1),
+        _ => panic!("expected the drafts overlay"),
+    }
+    assert!(render_to_string(&mut app, 100, 30).contains("DAN2-7"));
+
+    handle_key(&mut app, press(KeyCode::Enter));
+
+    let editor = app.editor().expect("draft reopened");
+    assert!(matches!(editor.compose, Compose::Comment));
+    assert_eq!(editor.text(), "resume me");
+}
+
+#[test]
+fn discarding_from_the_drafts_list_forgets_the_draft() {
+    let mut app = detail_app();
+    handle_key(&mut app, press(KeyCode::Char('c')));
+    type_text(&mut app, "never mind");
+    handle_key(&mut app, press(KeyCode::Esc));
+    handle_key(&mut app, press(KeyCode::Char('g')));
+    handle_key(&mut app, press(KeyCode::Char('d')));
+
+    handle_key(&mut app, press(KeyCode::Char('d')));
+
+    assert!(app.workspace.drafts.is_empty());
+    assert_eq!(app.ui.status, Some(Status::DraftDiscarded));
+}
+
+#[test]
+fn go_d_without_drafts_says_so() {
+    let mut app = detail_app();
+
+    handle_key(&mut app, press(KeyCode::Char('g')));
+    handle_key(&mut app, press(KeyCode::Char('d')));
+
+    assert!(matches!(app.overlay(), Overlay::None));
+    assert_eq!(app.ui.status, Some(Status::NoDrafts));
+}
+
+#[test]
+fn colon_completes_an_emoji_shortcode() {
+    let mut app = detail_app();
+    handle_key(&mut app, press(KeyCode::Char('c')));
+    type_text(&mut app, "ship it :ro");
+
+    let editor = app.editor().expect("editor open");
+    assert_eq!(
+        editor.mention().map(|menu| menu.kind),
+        Some(Completion::Emoji)
+    );
+
+    let effects = effects(handle_key_all(&mut app, press(KeyCode::Enter)));
+
+    assert_eq!(
+        app.editor().map(|e| e.text()),
+        Some("ship it 🚀".to_string())
+    );
+    assert_eq!(app.ui.recent_emoji.list(), ["rocket"]);
+    assert!(matches!(
+        effects.iter().next(),
+        Some(Effect::Store(StoreCommand::SaveRecentEmoji(_)))
+    ));
+}
+
+#[test]
+fn a_colon_that_matches_nothing_stays_text() {
+    let mut app = detail_app();
+    handle_key(&mut app, press(KeyCode::Char('c')));
+    type_text(&mut app, "steps :");
+    handle_key(&mut app, press(KeyCode::Enter));
+    type_text(&mut app, "time 10:30");
+
+    let editor = app.editor().expect("editor open");
+    assert_eq!(editor.text(), "steps :\ntime 10:30");
+    assert!(editor.mention().is_none());
+}
+
+fn templates() -> Vec<Template> {
+    vec![
+        Template {
+            name: "needs repro".into(),
+            body: "Could you add steps to reproduce {{identifier}}?".into(),
+        },
+        Template {
+            name: "released".into(),
+            body: "{{identifier}} ({{title}}) shipped {{date}}.\n\nThanks {{assignee}}".into(),
+        },
+    ]
+}
+
+#[test]
+fn ctrl_t_inserts_a_template_filled_in_for_the_issue() {
+    let mut app = detail_app();
+    apply(&mut app, Message::TemplatesLoaded(templates()));
+    handle_key(&mut app, press(KeyCode::Char('c')));
+    type_text(&mut app, "Hi. ");
+
+    handle_key(&mut app, ctrl('t'));
+    type_text(&mut app, "rel");
+    let editor = app.editor().expect("editor open");
+    assert_eq!(
+        editor.mention().map(|menu| menu.kind),
+        Some(Completion::Templates)
+    );
+    assert_eq!(editor.completion_count(), 1);
+
+    handle_key(&mut app, press(KeyCode::Enter));
+
+    assert_eq!(
+        app.editor().map(|e| e.text()),
+        Some(format!(
+            "Hi. DAN2-7 (Title) shipped {}.\n\nThanks Unassigned",
+            app.now.date()
+        ))
+    );
+}
+
+#[test]
+fn backing_out_of_templates_leaves_the_text_alone() {
+    let mut app = detail_app();
+    apply(&mut app, Message::TemplatesLoaded(templates()));
+    handle_key(&mut app, press(KeyCode::Char('c')));
+    type_text(&mut app, "ok");
+
+    handle_key(&mut app, ctrl('t'));
+    handle_key(&mut app, press(KeyCode::Backspace));
+
+    let editor = app.editor().expect("editor open");
+    assert!(editor.mention().is_none());
+    assert_eq!(editor.text(), "ok");
+}
+
+#[test]
+fn ctrl_t_without_templates_says_where_they_go() {
+    let mut app = detail_app();
+    handle_key(&mut app, press(KeyCode::Char('c')));
+    handle_key(&mut app, ctrl('t'));
+
+    assert!(app.editor().is_some_and(|e| e.mention().is_none()));
+    assert_eq!(app.ui.status, Some(Status::NoTemplates));
+}
+
+#[test]
+fn hash_completes_an_issue_reference_from_search() {
+    let mut app = detail_app();
+    handle_key(&mut app, press(KeyCode::Char('c')));
+    handle_key(&mut app, press(KeyCode::Char('#')));
+
+    for c in "fry".chars() {
+        handle_key(&mut app, press(KeyCode::Char(c)));
+    }
+    assert!(matches!(
+        settle_search(&mut app),
+        Some(Effect::Api(ApiCommand::SearchIssues { query })) if query == "fry"
+    ));
+
+    let fryer = linear_tui::api::IssueSummary {
+        title: Some("Fryer overheats".into()),
+        ..sample_issue("i40", "DAN2-40")
+    };
+    apply(
+        &mut app,
+        Message::IssuesFound {
+            query: "fr".into(),
+            issues: vec![fryer.clone()],
+        },
+    );
+    let editor = app.editor().expect("editor open");
+    assert!(editor.issue_candidates("fry").is_empty(), "a stale answer");
+
+    apply(
+        &mut app,
+        Message::IssuesFound {
+            query: "fry".into(),
+            issues: vec![fryer],
+        },
+    );
+    handle_key(&mut app, press(KeyCode::Enter));
+
+    assert_eq!(
+        app.editor().map(|e| e.text()),
+        Some("https://linear.app/dans-donuts/issue/DAN2-40".to_string())
+    );
+}
+
+#[test]
+fn issue_search_waits_for_typing_to_pause() {
+    let mut app = detail_app();
+    handle_key(&mut app, press(KeyCode::Char('c')));
+    handle_key(&mut app, press(KeyCode::Char('#')));
+
+    for c in "oven".chars() {
+        assert!(handle_key(&mut app, press(KeyCode::Char(c))).is_none());
+        assert!(search_completions(&mut app).is_empty());
+    }
+
+    assert!(matches!(
+        settle_search(&mut app),
+        Some(Effect::Api(ApiCommand::SearchIssues { query })) if query == "oven"
+    ));
+    for _ in 0..=ISSUE_SEARCH_IDLE_TICKS {
+        assert!(search_completions(&mut app).is_empty(), "searched once");
+    }
+}
+
+#[test]
+fn issue_completion_holds_a_bounded_number_of_answers() {
+    let mut app = detail_app();
+    handle_key(&mut app, press(KeyCode::Char('c')));
+    handle_key(&mut app, press(KeyCode::Char('#')));
+    handle_key(&mut app, press(KeyCode::Char('d')));
+
+    for round in 0..5 {
+        let issues = (0..100)
+            .map(|n| sample_issue(&format!("i{round}-{n}"), &format!("DAN2-{round}{n:03}")))
+            .collect();
+        apply(
+            &mut app,
+            Message::IssuesFound {
+                query: "d".into(),
+                issues,
+            },
+        );
+    }
+
+    let editor = app.editor().expect("editor open");
+    let held = editor.issue_candidates("dan2");
+    assert!(held.len() <= 200, "held {}", held.len());
+    assert_eq!(held[0].identifier, "DAN2-4000", "the newest answers lead");
+}
+
+#[test]
+fn typing_a_team_key_offers_the_issues_already_held() {
+    let mut app = detail_app();
+    handle_key(&mut app, press(KeyCode::Char('c')));
+
+    for c in "see DAN2-".chars() {
+        handle_key(&mut app, press(KeyCode::Char(c)));
+    }
+
+    assert!(matches!(
+        settle_search(&mut app),
+        Some(Effect::Api(ApiCommand::SearchIssues { query })) if query == "DAN2-"
+    ));
+    let editor = app.editor().expect("editor open");
+    let menu = editor.mention().expect("issue completion open");
+    assert_eq!(menu.kind, Completion::Issues);
+    assert!(!editor.issue_candidates(&menu.query).is_empty());
+    assert!(render_to_string(&mut app, 90, 30).contains("DAN2-7"));
+}
+
+#[test]
+fn a_failed_issue_search_keeps_completion_quiet() {
+    let mut app = detail_app();
+    handle_key(&mut app, press(KeyCode::Char('c')));
+    handle_key(&mut app, press(KeyCode::Char('#')));
+    handle_key(&mut app, press(KeyCode::Char('x')));
+    app.ui.status = None;
+
+    apply(
+        &mut app,
+        Message::Failed {
+            target: FailureTarget::IssueSearch,
+            error: RequestError::Other("offline".into()),
+        },
+    );
+
+    assert_eq!(app.ui.status, None);
+    assert!(app.editor().and_then(|e| e.mention()).is_some());
+}
+
+#[test]
+fn mention_autocomplete_inserts_the_profile_url() {
+    let mut app = detail_app();
+    handle_key(&mut app, press(KeyCode::Char('c')));
+    apply(
+        &mut app,
+        Message::MembersLoaded {
+            team_id: TeamId::from_raw("t_pizza"),
+            members: vec![member("danniieelg"), member("sam")],
+        },
+    );
+
+    handle_key(&mut app, press(KeyCode::Char('@')));
+    handle_key(&mut app, press(KeyCode::Char('d')));
+    assert!(app.editor().is_some_and(|e| e.mention().is_some()));
+
+    handle_key(&mut app, press(KeyCode::Enter));
+
+    let editor = app.editor().expect("editor open");
+    assert!(editor.mention().is_none());
+    assert_eq!(
+        editor.text(),
+        "https://linear.app/dans-donuts/profiles/danniieelg"
+    );
+}
+
+#[test]
+fn ctrl_e_hands_the_draft_to_an_external_editor_and_keeps_the_overlay() {
+    let mut app = detail_app();
+    handle_key(&mut app, press(KeyCode::Char('c')));
+    handle_key(&mut app, press(KeyCode::Char('a')));
+
+    let command = handle_key_all(&mut app, ctrl('e'));
+
     match command {
-        Some(Effect::Api(ApiCommand::LoadDetail {
-            target,
-            reveal: Reveal::Top,
-        })) if target.as_str() == "i1" => {}
-        other => panic!("expected LoadDetail for i1 revealing the top, got {other:?}"),
+        Commands::Runtime(RuntimeCommand::EditExternally { issue_id, body }) => {
+            assert_eq!(issue_id.as_str(), "i1");
+            assert_eq!(body, "a");
+        }
+        other => panic!("expected EditExternally, got {other:?}"),
     }
+    assert!(app.editor().is_some());
 }
 
 #[test]
-fn a_shrunk_thread_clamps_the_comment_selection() {
-    let mut app = detail_app_with_comments();
-    handle_key(&mut app, press(KeyCode::Char('m')));
-    app.reveal_focused(Some(2));
+fn an_externally_edited_draft_replaces_the_buffer_and_keeps_mentions() {
+    let mut app = detail_app();
+    handle_key(&mut app, press(KeyCode::Char('c')));
+    apply(
+        &mut app,
+        Message::MembersLoaded {
+            team_id: TeamId::from_raw("t_pizza"),
+            members: vec![member("sam")],
+        },
+    );
 
-    let mut detail = app.workspace.detail().value().cloned().expect("detail");
-    detail.comments.pop();
     apply(
         &mut app,
-        Message::DetailLoaded {
-            detail: Box::new(detail),
-            reveal: Reveal::Top,
+        Message::EditedExternally {
+            issue_id: IssueId::from_raw("i1"),
+            body: "thanks https://linear.app/dans-donuts/profiles/sam\nshipping".into(),
         },
     );
 
-    assert_eq!(app.comment_cursor(), Some(1));
-    assert!(matches!(
-        app.focus(),
-        Focus::Detail(DetailFocus {
-            origin: Origin::Panel(LeftPanel::MyWork),
-            view: DetailView::Comments { .. },
-            ..
-        })
-    ));
+    let editor = app.editor().expect("editor open");
+    assert_eq!(
+        editor.text(),
+        "thanks https://linear.app/dans-donuts/profiles/sam\nshipping"
+    );
+    assert!(matches!(editor.lines()[0].last(), Some(Cell::Mention(_))));
+    assert_eq!((editor.row(), editor.col()), (1, "shipping".len()));
 }
 
 #[test]
-fn deleting_the_last_comment_falls_back_to_reading() {
-    let mut app = detail_app_with_comments();
-    handle_key(&mut app, press(KeyCode::Char('m')));
+fn an_externally_edited_draft_for_another_issue_is_ignored() {
+    let mut app = detail_app();
+    handle_key(&mut app, press(KeyCode::Char('c')));
+    handle_key(&mut app, press(KeyCode::Char('a')));
 
-    let mut detail = app.workspace.detail().value().cloned().expect("detail");
-    detail.comments.clear();
     apply(
         &mut app,
-        Message::DetailLoaded {
-            detail: Box::new(detail),
-            reveal: Reveal::Top,
+        Message::EditedExternally {
+            issue_id: IssueId::from_raw("i2"),
+            body: "elsewhere".into(),
         },
     );
 
-    assert!(matches!(
-        app.focus(),
-        Focus::Detail(DetailFocus {
-            origin: Origin::Panel(LeftPanel::MyWork),
-            view: DetailView::Reading { .. },
-            ..
-        })
-    ));
+    assert_eq!(app.editor().map(|e| e.text()), Some("a".to_string()));
 }
 
 #[test]
-fn comment_action_requires_an_opened_issue() {
-    let mut app = list_app_with_issue();
+fn a_paste_lands_in_the_editor_as_one_undoable_edit() {
+    let mut app = detail_app();
+    handle_key(&mut app, press(KeyCode::Char('c')));
 
-    let commands = handle_key(&mut app, press(KeyCode::Char('c')));
+    let commands = handle_paste(&mut app, "first line\nsecond line");
+    assert!(commands.is_empty());
+    assert_eq!(
+        app.editor().map(|e| e.text()),
+        Some("first line\nsecond line".to_string())
+    );
 
-    assert!(app.editor().is_none());
-    assert!(commands.is_none());
+    handle_key(&mut app, ctrl('/'));
+    assert_eq!(app.editor().map(|e| e.text()), Some(String::new()));
+
+    handle_key(&mut app, ctrl('y'));
+    assert_eq!(
+        app.editor().map(|e| e.text()),
+        Some("first line\nsecond line".to_string())
+    );
 }
 
 #[test]
-fn c_opens_the_comment_editor_once_issue_is_loaded() {
-    let mut app = detail_app();
+fn a_paste_into_a_single_line_input_is_flattened() {
+    let mut app = App::new();
+    handle_key(&mut app, press(KeyCode::Char('!')));
 
-    let command = handle_key(&mut app, press(KeyCode::Char('c')));
+    handle_paste(&mut app, "git log\n--oneline\n");
 
-    assert!(app.editor().is_some());
-    match command {
-        Some(Effect::Api(ApiCommand::LoadMembers { team_id })) if team_id.as_str() == "t_pizza" => {
-        }
-        other => panic!("expected LoadMembers for the mention popup, got {other:?}"),
-    }
+    assert_eq!(
+        app.input().map(|input| input.buffer.as_str()),
+        Some("git log --oneline")
+    );
 }
 
 #[test]
-fn enter_inserts_a_newline_and_does_not_submit() {
+fn a_paste_while_browsing_is_dropped_rather_than_run_as_keys() {
+    let mut app = App::new();
+
+    let commands = handle_paste(&mut app, "q");
+
+    assert!(commands.is_empty());
+    assert!(!app.should_quit);
+    assert!(matches!(app.overlay(), Overlay::None));
+}
+
+#[test]
+fn ctrl_w_and_alt_backspace_erase_words_in_the_editor() {
     let mut app = detail_app();
     handle_key(&mut app, press(KeyCode::Char('c')));
+    handle_paste(&mut app, "ship the fix");
 
-    handle_key(&mut app, press(KeyCode::Char('a')));
-    let command = handle_key(&mut app, press(KeyCode::Enter));
-    handle_key(&mut app, press(KeyCode::Char('b')));
+    handle_key(&mut app, ctrl('w'));
+    assert_eq!(
+        app.editor().map(|e| e.text()),
+        Some("ship the ".to_string())
+    );
 
-    assert!(command.is_none());
-    assert_eq!(app.editor().map(|e| e.text()), Some("a\nb".to_string()));
+    handle_key(
+        &mut app,
+        KeyEvent::new(KeyCode::Backspace, KeyModifiers::ALT),
+    );
+    assert_eq!(app.editor().map(|e| e.text()), Some("ship ".to_string()));
+
+    handle_key(
+        &mut app,
+        KeyEvent::new(KeyCode::Left, KeyModifiers::CONTROL),
+    );
+    assert_eq!(app.editor().map(|e| e.col()), Some(0));
 }
 
 #[test]
-fn ctrl_s_posts_the_multiline_comment_for_the_open_issue() {
+fn the_preview_toggle_sticks_for_the_next_editor() {
     let mut app = detail_app();
     handle_key(&mut app, press(KeyCode::Char('c')));
-    handle_key(&mut app, press(KeyCode::Char('a')));
-    handle_key(&mut app, press(KeyCode::Enter));
-    handle_key(&mut app, press(KeyCode::Char('b')));
 
-    let command = handle_key(&mut app, ctrl('s'));
+    handle_key(&mut app, ctrl('p'));
+    assert!(app.editor().is_some_and(|e| e.previewing()));
 
-    match command {
-        Some(Effect::Api(ApiCommand::CreateComment {
-            issue_id,
-            body,
-            parent_id,
-            ..
-        })) => {
-            assert_eq!(issue_id.as_str(), "i1");
-            assert_eq!(body, "a\nb");
-            assert_eq!(parent_id, None);
-        }
-        other => panic!("expected CreateComment, got {other:?}"),
-    }
-    assert!(app.editor().is_none());
+    handle_key(&mut app, press(KeyCode::Esc));
+    handle_key(&mut app, press(KeyCode::Char('c')));
+    assert!(app.editor().is_some_and(|e| e.previewing()));
+
+    handle_key(&mut app, ctrl('p'));
+    assert!(app.editor().is_some_and(|e| !e.previewing()));
+    assert!(!app.ui.editor_preview);
+}
+
+fn vi_comment(app: &mut App, keys: &str) -> Vec<Option<Effect>> {
+    app.ui.editor_keys = EditorKeys::Vi;
+    handle_key(app, press(KeyCode::Char('c')));
+
+    keys.chars()
+        .map(|c| match c {
+            '⎋' => handle_key(app, press(KeyCode::Esc)),
+            c => handle_key(app, press(KeyCode::Char(c))),
+        })
+        .collect()
 }
 
 #[test]
-fn mention_autocomplete_inserts_the_profile_url() {
+fn a_vi_editor_opens_in_normal_mode_and_esc_only_leaves_insert() {
     let mut app = detail_app();
-    handle_key(&mut app, press(KeyCode::Char('c')));
-    apply(
-        &mut app,
-        Message::MembersLoaded {
-            team_id: TeamId::from_raw("t_pizza"),
-            members: vec![member("danniieelg"), member("sam")],
-        },
-    );
 
-    handle_key(&mut app, press(KeyCode::Char('@')));
-    handle_key(&mut app, press(KeyCode::Char('d')));
-    assert!(app.editor().is_some_and(|e| e.mention().is_some()));
+    vi_comment(&mut app, "ihello world⎋⎋bciwthere⎋");
 
-    handle_key(&mut app, press(KeyCode::Enter));
+    let editor = app.editor().expect("esc never closes a vi editor");
+    assert_eq!(editor.text(), "hello there");
+    assert_eq!(editor.vi().map(|vi| vi.mode()), Some(ViMode::Normal));
+    assert!(render_to_string(&mut app, 80, 24).contains("Comment · normal"));
+}
 
-    let editor = app.editor().expect("editor open");
-    assert!(editor.mention().is_none());
-    assert_eq!(
-        editor.text(),
-        "https://linear.app/dans-donuts/profiles/danniieelg"
-    );
+#[test]
+fn yanking_to_the_plus_register_copies_to_the_clipboard() {
+    let mut app = detail_app();
+
+    let effects = vi_comment(&mut app, "iship it⎋0\"+yiw");
+
+    assert!(matches!(
+        effects.last(),
+        Some(Some(Effect::Platform(PlatformCommand::CopyToClipboard(text)))) if text == "ship"
+    ));
+}
+
+#[test]
+fn registers_outlive_the_editor_that_filled_them() {
+    let mut app = detail_app();
+    vi_comment(&mut app, "ishared⎋yiw\"_ddZQ");
+    assert!(app.editor().is_none());
+
+    vi_comment(&mut app, "p");
+
+    assert_eq!(app.editor().map(|e| e.text()), Some("shared".to_string()));
+}
+
+#[test]
+fn zz_posts_a_vi_comment() {
+    let mut app = detail_app();
+
+    let effects = vi_comment(&mut app, "idone⎋ZZ");
+
+    assert!(matches!(
+        effects.last(),
+        Some(Some(Effect::Api(ApiCommand::CreateComment { body, .. }))) if body == "done"
+    ));
+    assert!(app.editor().is_none());
 }
 
 #[test]
@@ -2206,6 +3316,7 @@ fn comment_posted_refetches_the_thread_and_reveals_the_bottom() {
         &mut app,
         Message::CommentPosted {
             id: IssueId::from_raw("i1"),
+            draft: sent("i1", Compose::Comment, "posted"),
         },
     );
 
@@ -2228,6 +3339,7 @@ fn posting_from_comments_mode_stays_in_comments_and_reveals_the_new_comment() {
         &mut app,
         Message::CommentPosted {
             id: IssueId::from_raw("i1"),
+            draft: sent("i1", Compose::Comment, "posted"),
         },
     );
 
@@ -2504,6 +3616,37 @@ fn labels_typing_reissues_the_search() {
     assert!(app.labels().is_some_and(|l| l.results().is_empty()));
 }
 
+#[test]
+fn a_paste_into_the_labels_query_searches_once() {
+    let mut app = detail_app();
+    edit(&mut app, 'l');
+
+    let commands = handle_paste(&mut app, "bug\n");
+
+    let effects: Vec<_> = effects(commands).into_iter().collect();
+    assert_eq!(effects.len(), 1);
+    assert!(matches!(
+        effects.first(),
+        Some(Effect::Api(ApiCommand::SearchLabels { query })) if query == "bug"
+    ));
+    assert_eq!(app.labels().map(|l| l.query.as_str()), Some("bug"));
+}
+
+#[test]
+fn a_paste_into_search_results_is_dropped() {
+    let mut app = App::new();
+    handle_key(&mut app, press(KeyCode::Char('g')));
+    handle_key(&mut app, press(KeyCode::Char('s')));
+    type_text(&mut app, "oven");
+    handle_key(&mut app, press(KeyCode::Enter));
+    assert!(app.search().is_some());
+
+    let commands = handle_paste(&mut app, "q");
+
+    assert!(commands.is_empty());
+    assert!(app.search().is_some());
+}
+
 #[test]
 fn assign_picker_can_unassign() {
     let mut app = detail_app();
@@ -2802,6 +3945,99 @@ fn go_prefix_cancels_on_an_unbound_key() {
     assert!(commands.is_none());
 }
 
+fn linked_detail_app() -> App {
+    let mut app = detail_app();
+    let mut detail = app.workspace.detail().value().cloned().expect("detail");
+    detail.description = Some(
+        "See [the spec](https://example.com/spec), blocked on \
+         https://linear.app/dans-donuts/issue/DAN-9/fryer."
+            .into(),
+    );
+    detail.comments = vec![comment(
+        "c1",
+        None,
+        "![shot](https://cdn.example.com/a.png) and [the spec](https://example.com/spec)",
+    )];
+    app.workspace.set_detail(detail, app.now);
+    app
+}
+
+#[test]
+fn f_labels_each_link_once_and_a_label_opens_it() {
+    let mut app = linked_detail_app();
+
+    handle_key(&mut app, press(KeyCode::Char('f')));
+    match app.overlay() {
+        Overlay::Links(hints) => {
+            let labelled: Vec<_> = hints
+                .hints
+                .iter()
+                .map(|hint| (hint.label.as_str(), hint.link.url.as_str()))
+                .collect();
+            assert_eq!(
+                labelled,
+                vec![
+                    ("a", "https://example.com/spec"),
+                    ("s", "https://linear.app/dans-donuts/issue/DAN-9/fryer"),
+                    ("d", "https://cdn.example.com/a.png"),
+                ]
+            );
+        }
+        _ => panic!("expected link hints"),
+    }
+
+    let commands = handle_key(&mut app, press(KeyCode::Char('d')));
+
+    match commands {
+        Some(Effect::Platform(PlatformCommand::OpenUrl(url)))
+            if url == "https://cdn.example.com/a.png" => {}
+        other => panic!("expected OpenUrl, got {other:?}"),
+    }
+    assert!(matches!(app.overlay(), Overlay::None));
+}
+
+#[test]
+fn a_capital_label_yanks_the_link_instead() {
+    let mut app = linked_detail_app();
+
+    handle_key(&mut app, press(KeyCode::Char('f')));
+    let commands = handle_key(
+        &mut app,
+        KeyEvent::new(KeyCode::Char('A'), KeyModifiers::SHIFT),
+    );
+
+    match commands {
+        Some(Effect::Platform(PlatformCommand::CopyToClipboard(url)))
+            if url == "https://example.com/spec" => {}
+        other => panic!("expected CopyToClipboard, got {other:?}"),
+    }
+    assert_eq!(app.ui.status, Some(Status::CopiedLink));
+}
+
+#[test]
+fn a_linear_issue_link_opens_inside_the_app() {
+    let mut app = linked_detail_app();
+
+    handle_key(&mut app, press(KeyCode::Char('f')));
+    let commands = handle_key(&mut app, press(KeyCode::Char('s')));
+
+    match commands {
+        Some(Effect::Api(ApiCommand::LoadDetail { target, .. })) if target.as_str() == "DAN-9" => {}
+        other => panic!("expected LoadDetail(DAN-9), got {other:?}"),
+    }
+    assert!(matches!(app.overlay(), Overlay::None));
+}
+
+#[test]
+fn f_without_links_says_so() {
+    let mut app = detail_app();
+
+    handle_key(&mut app, press(KeyCode::Char('f')));
+
+    assert!(matches!(app.overlay(), Overlay::None));
+    assert_eq!(app.ui.status, Some(Status::NoLinks));
+}
+
 #[test]
 fn gi_opens_a_jump_input_that_loads_the_referenced_issue() {
     let mut app = App::new();
@@ -3610,6 +4846,7 @@ fn a_comment_rejected_with_a_401_reopens_the_editor_without_wedging_members() {
             Some("refresh".into()),
             None,
         )),
+        connection: ConnectionSettings::default(),
     });
     assert!(app.session.activate("ws"));
 
@@ -3846,6 +5083,7 @@ fn a_comment_result_for_another_issue_does_not_reload_the_open_detail() {
         &mut app,
         Message::CommentPosted {
             id: IssueId::from_raw("i2"),
+            draft: sent("i2", Compose::Comment, "posted"),
         },
     ));
 
@@ -4004,6 +5242,21 @@ fn sample_detail(id: &str, identifier: &str) -> linear_tui::api::IssueDetail {
     }
 }
 
+/// Ticks until issue completion's search goes out.
+fn settle_search(app: &mut App) -> Option<Effect> {
+    (0..=ISSUE_SEARCH_IDLE_TICKS).find_map(|_| search_completions(app).into_iter().next())
+}
+
+fn sent(issue: &str, compose: Compose, body: &str) -> Box<ComposeRecovery> {
+    Box::new(ComposeRecovery {
+        issue_id: IssueId::from_raw(issue),
+        team_id: TeamId::from_raw("t1"),
+        compose,
+        body: body.into(),
+        seen: None,
+    })
+}
+
 fn comment(id: &str, parent: Option<&str>, body: &str) -> linear_tui::api::Comment {
     linear_tui::api::Comment {
         id: CommentId::from_raw(id),
@@ -4100,6 +5353,7 @@ fn oauth_app(refresh_token: Option<&str>, expires_at: Option<i64>) -> App {
             refresh_token.map(String::from),
             expires_at,
         )),
+        connection: ConnectionSettings::default(),
     });
     assert!(app.session.activate("ws"));
     app
@@ -4132,6 +5386,7 @@ fn a_token_refreshed_after_a_workspace_switch_updates_the_old_account_without_re
         workspace_key: "other".into(),
         org_name: "Other".into(),
         credential: Credential::PersonalKey("k".into()),
+        connection: ConnectionSettings::default(),
     });
     app.session.begin_refresh(app.now);
     assert!(app.session.activate("other"));
@@ -4178,6 +5433,7 @@ fn a_refresh_failure_for_an_inactive_workspace_does_not_expire_the_active_one()
         workspace_key: "other".into(),
         org_name: "Other".into(),
         credential: Credential::PersonalKey("k".into()),
+        connection: ConnectionSettings::default(),
     });
     assert!(app.session.activate("other"));
 